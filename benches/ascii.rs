@@ -0,0 +1,59 @@
+//! 纯 ASCII 文本计数的 SWAR 快速路径与逐字符统计的对比
+//!
+//! ```sh
+//! cargo bench --bench ascii
+//! ```
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use markdown_readtime::{ReadSpeed, estimate_plain_text_with_speed};
+
+/// 约 4 MB 的英文文本，包含换行、制表符和连续空格
+fn english() -> String {
+    "The quick brown fox jumps over the lazy dog.\tIt was  the best of times,\n\
+     it was the worst of times; 1234 words-per-minute (approx.)\r\n"
+        .repeat(40_000)
+}
+
+/// 快速路径之前英文计数方式的实现
+fn split_whitespace(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// 快速路径之前中文计数方式的实现
+fn filter_chars(text: &str) -> usize {
+    text.chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .count()
+}
+
+fn bench_ascii(c: &mut Criterion) {
+    let text = english();
+    let english_speed = ReadSpeed::default().chinese(false);
+    let chinese_speed = ReadSpeed::default();
+    assert_eq!(
+        estimate_plain_text_with_speed(&text, &english_speed).word_count,
+        split_whitespace(&text)
+    );
+    assert_eq!(
+        estimate_plain_text_with_speed(&text, &chinese_speed).word_count,
+        filter_chars(&text)
+    );
+
+    let mut group = c.benchmark_group("ascii");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("english/split_whitespace", |b| {
+        b.iter(|| split_whitespace(black_box(&text)))
+    });
+    group.bench_function("english/swar", |b| {
+        b.iter(|| estimate_plain_text_with_speed(black_box(&text), &english_speed))
+    });
+    group.bench_function("chinese/filter_chars", |b| {
+        b.iter(|| filter_chars(black_box(&text)))
+    });
+    group.bench_function("chinese/swar", |b| {
+        b.iter(|| estimate_plain_text_with_speed(black_box(&text), &chinese_speed))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_ascii);
+criterion_main!(benches);
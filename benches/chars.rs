@@ -0,0 +1,90 @@
+//! 查表的字符分类与逐字符判断空白、控制字符和 emoji 范围的旧实现的对比
+//!
+//! ```sh
+//! cargo bench --bench chars
+//! ```
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use markdown_readtime::{ReadSpeed, estimate_plain_text_with_speed};
+
+/// 约 12 MB 的中英文混排文本，包含 emoji、全角空格和不换行空格
+fn mixed() -> String {
+    "阅读时间估算 reading time 😀 中文与 English 混排，\u{3000}全角空格\u{a0}和 emoji 🚀🎉。\n\
+     第二段：tokens like café, naïve 以及 1234 个数字 words-per-minute 😀\n"
+        .repeat(80_000)
+}
+
+/// 查表之前的 emoji 范围判断
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F600..=0x1F64F |
+        0x1F300..=0x1F5FF |
+        0x1F680..=0x1F6FF |
+        0x1F700..=0x1F77F |
+        0x1F780..=0x1F7FF |
+        0x1F800..=0x1F8FF |
+        0x1F900..=0x1F9FF |
+        0x1FA00..=0x1FA6F |
+        0x1FA70..=0x1FAFF |
+        0x2600..=0x26FF   |
+        0x2700..=0x27BF   |
+        0x2B50            |
+        0x2B55
+    )
+}
+
+/// 查表之前中文计数方式的实现
+fn chinese_words(text: &str) -> usize {
+    text.chars()
+        .filter(|&c| !c.is_whitespace() && (!c.is_control() || is_emoji(c)))
+        .count()
+}
+
+/// 查表之前英文计数方式的实现
+fn english_words(text: &str) -> usize {
+    text.split_whitespace()
+        .map(|word| {
+            let emoji = word.chars().filter(|&c| is_emoji(c)).count();
+            if emoji > 0 {
+                word.chars()
+                    .filter(|&c| !is_emoji(c) && !c.is_whitespace())
+                    .count()
+                    + emoji
+            } else {
+                1
+            }
+        })
+        .sum()
+}
+
+fn bench_chars(c: &mut Criterion) {
+    let text = mixed();
+    let chinese_speed = ReadSpeed::default();
+    let english_speed = ReadSpeed::default().chinese(false);
+    assert_eq!(
+        estimate_plain_text_with_speed(&text, &chinese_speed).word_count,
+        chinese_words(&text)
+    );
+    assert_eq!(
+        estimate_plain_text_with_speed(&text, &english_speed).word_count,
+        english_words(&text)
+    );
+
+    let mut group = c.benchmark_group("chars");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("chinese/ranges", |b| {
+        b.iter(|| chinese_words(black_box(&text)))
+    });
+    group.bench_function("chinese/table", |b| {
+        b.iter(|| estimate_plain_text_with_speed(black_box(&text), &chinese_speed))
+    });
+    group.bench_function("english/ranges", |b| {
+        b.iter(|| english_words(black_box(&text)))
+    });
+    group.bench_function("english/table", |b| {
+        b.iter(|| estimate_plain_text_with_speed(black_box(&text), &english_speed))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_chars);
+criterion_main!(benches);
@@ -0,0 +1,7 @@
+fn main() {
+    // 构建 Node.js 原生模块时需要设置平台相关的链接参数
+    #[cfg(feature = "node")]
+    napi_build::setup();
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,17 @@
+use tokio::net::TcpListener;
+
+fn main() -> std::io::Result<()> {
+    // 监听地址可以通过第一个命令行参数指定
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(async {
+            let listener = TcpListener::bind(&addr).await?;
+            println!("监听 http://{}/estimate", listener.local_addr()?);
+            markdown_readtime::serve::serve(listener).await
+        })
+}
@@ -0,0 +1,85 @@
+#ifndef MARKDOWN_READTIME_H
+#define MARKDOWN_READTIME_H
+
+/* 此文件由 cbindgen 自动生成，请勿手动修改 */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// 调用成功
+#define MDR_OK 0
+
+// 传入了空指针
+#define MDR_ERR_NULL -1
+
+// C 语言中的阅读速度配置
+typedef struct MdrReadSpeed {
+  double words_per_minute;
+  double seconds_per_image;
+  double seconds_per_code_block;
+  bool count_emoji;
+  bool chinese;
+} MdrReadSpeed;
+
+// C 语言中的阅读时间估算结果
+//
+// `formatted` 由本库分配，需要调用 `mdr_read_time_free` 释放。
+typedef struct MdrReadTime {
+  uint64_t total_seconds;
+  char *formatted;
+  size_t word_count;
+  size_t image_count;
+  size_t code_block_count;
+} MdrReadTime;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// 返回默认的阅读速度配置
+struct MdrReadSpeed mdr_read_speed_default(void);
+
+// 估算阅读时间并写入 `out`
+//
+// `speed` 为空指针时使用默认配置。成功返回 `MDR_OK`，`markdown` 或 `out` 为空指针时返回 `MDR_ERR_NULL`。
+//
+// # Safety
+//
+// `markdown` 必须指向以 NUL 结尾的字符串，`speed` 和 `out` 必须为空指针或指向有效内存。
+int mdr_estimate(const char *markdown,
+                 const struct MdrReadSpeed *speed,
+                 struct MdrReadTime *out);
+
+// 释放 `MdrReadTime` 中由本库分配的内存
+//
+// # Safety
+//
+// `read_time` 必须为空指针或由 `mdr_estimate` 填充的结果，且不能重复释放。
+void mdr_read_time_free(struct MdrReadTime *read_time);
+
+// 估算阅读时间并返回 JSON 字符串
+//
+// `speed_json` 为空指针时使用默认配置，否则按 serde 格式解析，缺失的字段使用默认值。
+// 出错时返回空指针。返回的字符串需要调用 `mdr_string_free` 释放。
+//
+// # Safety
+//
+// `markdown` 和 `speed_json` 必须为空指针或指向以 NUL 结尾的字符串。
+char *mdr_estimate_json(const char *markdown,
+                        const char *speed_json);
+
+// 释放由本库返回的字符串
+//
+// # Safety
+//
+// `text` 必须为空指针或由本库返回的字符串，且不能重复释放。
+void mdr_string_free(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MARKDOWN_READTIME_H */
@@ -0,0 +1,222 @@
+//! C FFI 绑定
+//!
+//! 构建后得到 `libmarkdown_readtime_ffi` 动态库和静态库。对应的 C 头文件位于
+//! `include/markdown_readtime.h`，在本目录中由 cbindgen 根据 `cbindgen.toml` 生成：
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/markdown_readtime.h
+//! ```
+//!
+//! 所有字符串参数均为以 NUL 结尾的 UTF-8 字符串，无效的字节序列不计入字数。
+//! 由本库分配的内存必须使用对应的释放函数释放。
+use markdown_readtime::{ReadSpeed, ReadTime, estimate_bytes_with_speed};
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::ptr;
+
+/// 调用成功
+pub const MDR_OK: c_int = 0;
+
+/// 传入了空指针
+pub const MDR_ERR_NULL: c_int = -1;
+
+/// C 语言中的阅读速度配置
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct MdrReadSpeed {
+    pub words_per_minute: f64,
+    pub seconds_per_image: f64,
+    pub seconds_per_code_block: f64,
+    pub count_emoji: bool,
+    pub chinese: bool,
+}
+
+impl From<MdrReadSpeed> for ReadSpeed {
+    fn from(speed: MdrReadSpeed) -> Self {
+        ReadSpeed::new(
+            speed.words_per_minute,
+            speed.seconds_per_image,
+            speed.seconds_per_code_block,
+            speed.count_emoji,
+            speed.chinese,
+        )
+    }
+}
+
+/// C 语言中的阅读时间估算结果
+///
+/// `formatted` 由本库分配，需要调用 `mdr_read_time_free` 释放。
+#[repr(C)]
+#[derive(Debug)]
+pub struct MdrReadTime {
+    pub total_seconds: u64,
+    pub formatted: *mut c_char,
+    pub word_count: usize,
+    pub image_count: usize,
+    pub code_block_count: usize,
+}
+
+impl From<ReadTime> for MdrReadTime {
+    fn from(read_time: ReadTime) -> Self {
+        Self {
+            total_seconds: read_time.total_seconds,
+            formatted: into_c_string(read_time.formatted),
+            word_count: read_time.word_count,
+            image_count: read_time.image_count,
+            code_block_count: read_time.code_block_count,
+        }
+    }
+}
+
+/// 转换为 C 字符串，内部的 NUL 字符会被移除
+fn into_c_string(text: String) -> *mut c_char {
+    let text: String = text.chars().filter(|&c| c != '\0').collect();
+    CString::new(text).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// 返回默认的阅读速度配置
+#[unsafe(no_mangle)]
+pub extern "C" fn mdr_read_speed_default() -> MdrReadSpeed {
+    let speed = ReadSpeed::default();
+    MdrReadSpeed {
+        words_per_minute: speed.words_per_minute,
+        seconds_per_image: speed.seconds_per_image,
+        seconds_per_code_block: speed.seconds_per_code_block,
+        count_emoji: speed.count_emoji,
+        chinese: speed.chinese,
+    }
+}
+
+/// 估算阅读时间并写入 `out`
+///
+/// `speed` 为空指针时使用默认配置。成功返回 `MDR_OK`，`markdown` 或 `out` 为空指针时返回 `MDR_ERR_NULL`。
+///
+/// # Safety
+///
+/// `markdown` 必须指向以 NUL 结尾的字符串，`speed` 和 `out` 必须为空指针或指向有效内存。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdr_estimate(
+    markdown: *const c_char,
+    speed: *const MdrReadSpeed,
+    out: *mut MdrReadTime,
+) -> c_int {
+    if markdown.is_null() || out.is_null() {
+        return MDR_ERR_NULL;
+    }
+    // SAFETY: 调用方保证指针有效
+    let (markdown, speed, out) = unsafe { (CStr::from_ptr(markdown), speed.as_ref(), &mut *out) };
+    let speed = speed.map(|&speed| speed.into()).unwrap_or_default();
+    let read_time = estimate_bytes_with_speed(markdown.to_bytes(), &speed);
+    *out = read_time.into();
+    MDR_OK
+}
+
+/// 释放 `MdrReadTime` 中由本库分配的内存
+///
+/// # Safety
+///
+/// `read_time` 必须为空指针或由 `mdr_estimate` 填充的结果，且不能重复释放。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdr_read_time_free(read_time: *mut MdrReadTime) {
+    // SAFETY: 调用方保证指针有效
+    if let Some(read_time) = unsafe { read_time.as_mut() } {
+        unsafe { mdr_string_free(read_time.formatted) };
+        read_time.formatted = ptr::null_mut();
+    }
+}
+
+/// 估算阅读时间并返回 JSON 字符串
+///
+/// `speed_json` 为空指针时使用默认配置，否则按 serde 格式解析，缺失的字段使用默认值。
+/// 出错时返回空指针。返回的字符串需要调用 `mdr_string_free` 释放。
+///
+/// # Safety
+///
+/// `markdown` 和 `speed_json` 必须为空指针或指向以 NUL 结尾的字符串。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdr_estimate_json(
+    markdown: *const c_char,
+    speed_json: *const c_char,
+) -> *mut c_char {
+    if markdown.is_null() {
+        return ptr::null_mut();
+    }
+    // SAFETY: 调用方保证指针有效
+    let markdown = unsafe { CStr::from_ptr(markdown) };
+    let speed = if speed_json.is_null() {
+        ReadSpeed::default()
+    } else {
+        // SAFETY: 调用方保证指针有效
+        let speed_json = unsafe { CStr::from_ptr(speed_json) };
+        match serde_json::from_slice(speed_json.to_bytes()) {
+            Ok(speed) => speed,
+            Err(_) => return ptr::null_mut(),
+        }
+    };
+    let read_time = estimate_bytes_with_speed(markdown.to_bytes(), &speed);
+    match serde_json::to_string(&read_time) {
+        Ok(json) => into_c_string(json),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// 释放由本库返回的字符串
+///
+/// # Safety
+///
+/// `text` 必须为空指针或由本库返回的字符串，且不能重复释放。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mdr_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: 字符串由 CString::into_raw 分配
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use markdown_readtime::estimate;
+
+    #[test]
+    fn test_mdr_estimate() {
+        let markdown = CString::new("# 标题\n\n这是内容").unwrap();
+        let mut out = MdrReadTime {
+            total_seconds: 0,
+            formatted: ptr::null_mut(),
+            word_count: 0,
+            image_count: 0,
+            code_block_count: 0,
+        };
+        let status = unsafe { mdr_estimate(markdown.as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(status, MDR_OK);
+
+        let expected = estimate("# 标题\n\n这是内容");
+        assert_eq!(out.word_count, expected.word_count);
+        let formatted = unsafe { CStr::from_ptr(out.formatted) };
+        assert_eq!(formatted.to_str().unwrap(), expected.formatted);
+
+        unsafe { mdr_read_time_free(&mut out) };
+        assert!(out.formatted.is_null());
+        assert_eq!(
+            unsafe { mdr_estimate(ptr::null(), ptr::null(), &mut out) },
+            MDR_ERR_NULL
+        );
+    }
+
+    #[test]
+    fn test_mdr_estimate_json() {
+        let markdown = CString::new("hello world").unwrap();
+        let speed = CString::new(r#"{"chinese": false}"#).unwrap();
+        let json = unsafe { mdr_estimate_json(markdown.as_ptr(), speed.as_ptr()) };
+        assert!(!json.is_null());
+
+        let read_time: ReadTime =
+            serde_json::from_str(unsafe { CStr::from_ptr(json) }.to_str().unwrap()).unwrap();
+        assert_eq!(read_time.word_count, 2);
+        unsafe { mdr_string_free(json) };
+
+        let invalid = CString::new("{").unwrap();
+        let json = unsafe { mdr_estimate_json(markdown.as_ptr(), invalid.as_ptr()) };
+        assert!(json.is_null());
+    }
+}
@@ -0,0 +1,45 @@
+//! EPUB、DOCX 等基于 ZIP 的文档格式共用的读取函数
+
+use crate::Error;
+use crate::utils::decode_text;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+/// 转换压缩包错误，格式错误使用 `invalid` 构造对应格式的错误
+pub(crate) fn zip_error(err: ZipError, invalid: fn(String) -> Error) -> Error {
+    match err {
+        ZipError::Io(err) => Error::Io(err),
+        err => invalid(err.to_string()),
+    }
+}
+
+/// 读取压缩包中的文本文件
+pub(crate) fn read_entry<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+    invalid: fn(String) -> Error,
+) -> Result<String, Error> {
+    let mut entry = archive.by_name(name).map_err(|err| match err {
+        ZipError::FileNotFound => invalid(format!("缺少文件 {}", name)),
+        err => zip_error(err, invalid),
+    })?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(String::from(decode_text(&bytes)))
+}
+
+/// XML 中各开始标签的元素名（去掉命名空间前缀）和属性部分
+pub(crate) fn elements(xml: &str) -> impl Iterator<Item = (&str, &str)> {
+    xml.split('<').skip(1).filter_map(|tag| {
+        let tag = &tag[..tag.find('>')?];
+        if tag.starts_with(['/', '!', '?']) {
+            return None;
+        }
+        let name_len = tag
+            .find(|c: char| c.is_ascii_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let name = &tag[..name_len];
+        Some((name.rsplit(':').next().unwrap_or(name), &tag[name_len..]))
+    })
+}
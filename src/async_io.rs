@@ -0,0 +1,94 @@
+use crate::{ReadSpeed, ReadTime, estimate_bytes_with_speed, estimate_with_speed};
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::task;
+
+/// 从 [`AsyncRead`] 中读取内容并估算阅读时间
+///
+/// 读取在当前任务中异步完成，计数工作通过 [`task::spawn_blocking`] 转移到阻塞线程池，
+/// 不会阻塞异步运行时。需要启用 `tokio` 特性。
+///
+/// # Arguments
+///
+/// * `reader` - 异步内容来源，例如上传的文件流
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，读取失败时返回对应的 I/O 错误。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate_async, ReadSpeed};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let input = "# 标题\n\n这是内容".as_bytes();
+/// let read_time = estimate_async(input, &ReadSpeed::default()).await.unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// # });
+/// ```
+pub async fn estimate_async(
+    mut reader: impl AsyncRead + Unpin,
+    speed: &ReadSpeed,
+) -> io::Result<ReadTime> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).await?;
+    let speed = *speed;
+    task::spawn_blocking(move || estimate_bytes_with_speed(&buffer, &speed))
+        .await
+        .map_err(io::Error::other)
+}
+
+/// 异步批量估算多篇文档的阅读时间
+///
+/// 所有文档在同一个阻塞任务中依次估算，结果顺序与输入顺序一致。需要启用 `tokio` 特性。
+///
+/// # Arguments
+///
+/// * `docs` - 需要估算的 Markdown 文档
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 与输入顺序一致的 [`ReadTime`] 列表，阻塞任务失败时返回错误。
+pub async fn estimate_many_async<I, S>(docs: I, speed: &ReadSpeed) -> io::Result<Vec<ReadTime>>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let docs: Vec<String> = docs.into_iter().map(Into::into).collect();
+    let speed = *speed;
+    task::spawn_blocking(move || {
+        docs.iter()
+            .map(|doc| estimate_with_speed(doc, &speed))
+            .collect()
+    })
+    .await
+    .map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_estimate_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let markdown = "# 标题\n\n这是内容";
+            let speed = ReadSpeed::default();
+            let read_time = estimate_async(markdown.as_bytes(), &speed).await.unwrap();
+            assert_eq!(read_time, estimate(markdown));
+
+            let results = estimate_many_async(["第一篇", "第二篇文章"], &speed)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 2);
+            assert_eq!(results[1].word_count, 5);
+        });
+    }
+}
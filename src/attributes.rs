@@ -0,0 +1,139 @@
+//! Pandoc 风格的属性块
+//!
+//! Pandoc 和不少静态站点生成器允许在标题末尾、图片之后和代码块的信息字符串中写
+//! `{#id .class key=val}` 形式的属性块。严格的 CommonMark 不认识这种语法，属性块会作为正文文本出现，
+//! 中文计数方式下其中的每个字符都会计入字数。
+//!
+//! 启用 [`Estimator::strip_attributes`](crate::Estimator::strip_attributes) 后，
+//! 标题末尾和紧跟在图片之后的属性块在计数前去掉。代码块的信息字符串本来就不计入字数，无需处理。
+use pulldown_cmark::{Event, Tag, TagEnd};
+
+/// `block` 是否为完整的属性块，包括首尾的花括号
+///
+/// 属性之间以空白分隔，每个属性为 `#id`、`.class`、`key=val`、`key="带空格的值"` 或表示不编号的 `-`。
+fn is_attribute_block(block: &str) -> bool {
+    let Some(inner) = block
+        .strip_prefix('{')
+        .and_then(|rest| rest.strip_suffix('}'))
+    else {
+        return false;
+    };
+    let mut rest = inner.trim_start();
+    if rest.is_empty() {
+        return false;
+    }
+    while !rest.is_empty() {
+        let after = if let Some(name) = rest.strip_prefix(['#', '.']) {
+            let end = name.find(char::is_whitespace).unwrap_or(name.len());
+            if end == 0 {
+                return false;
+            }
+            &name[end..]
+        } else if let Some(after) = rest.strip_prefix('-') {
+            after
+        } else {
+            let Some(eq) = rest.find('=') else {
+                return false;
+            };
+            let key = &rest[..eq];
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | ':'))
+            {
+                return false;
+            }
+            let value = &rest[eq + 1..];
+            if let Some(quoted) = value.strip_prefix('"') {
+                let Some(close) = quoted.find('"') else {
+                    return false;
+                };
+                &quoted[close + 1..]
+            } else {
+                let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                if end == 0 || value[..end].contains('"') {
+                    return false;
+                }
+                &value[end..]
+            }
+        };
+        if !after.is_empty() && !after.starts_with(char::is_whitespace) {
+            return false;
+        }
+        rest = after.trim_start();
+    }
+    !inner.contains(['{', '}'])
+}
+
+/// 去掉文本末尾的属性块
+fn strip_trailing(text: &str) -> Option<&str> {
+    let trimmed = text.trim_end();
+    let start = trimmed.rfind('{')?;
+    is_attribute_block(&trimmed[start..]).then(|| trimmed[..start].trim_end())
+}
+
+/// 去掉文本开头的属性块
+fn strip_leading(text: &str) -> Option<&str> {
+    let end = text.find('}')? + 1;
+    is_attribute_block(&text[..end]).then(|| &text[end..])
+}
+
+/// 在遍历事件时找出需要去掉属性块的文本
+#[derive(Debug, Default)]
+pub(crate) struct AttributeFilter {
+    /// 当前是否位于标题内
+    in_heading: bool,
+    /// 上一个事件是否为图片的结束
+    after_image: bool,
+}
+
+impl AttributeFilter {
+    /// 处理一个事件，文本需要去掉属性块时返回剩余的部分
+    pub(crate) fn strip<'e>(&mut self, event: &'e Event<'_>) -> Option<&'e str> {
+        let after_image = std::mem::take(&mut self.after_image);
+        match event {
+            Event::Start(Tag::Heading { .. }) => self.in_heading = true,
+            Event::End(TagEnd::Heading(_)) => self.in_heading = false,
+            Event::End(TagEnd::Image) => self.after_image = true,
+            Event::Text(text) if after_image => return strip_leading(text),
+            Event::Text(text) if self.in_heading => return strip_trailing(text),
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed};
+
+    #[test]
+    fn test_is_attribute_block() {
+        assert!(is_attribute_block("{#intro}"));
+        assert!(is_attribute_block("{#id .class key=val}"));
+        assert!(is_attribute_block("{ .wide width=\"50 %\" }"));
+        assert!(is_attribute_block("{-}"));
+        assert!(!is_attribute_block("{}"));
+        assert!(!is_attribute_block("{注释}"));
+        assert!(!is_attribute_block("{a, b}"));
+        assert!(!is_attribute_block("{#}"));
+        assert!(!is_attribute_block("{key=}"));
+    }
+
+    #[test]
+    fn test_strip_attributes() {
+        let markdown = "# 标题 {#intro .unnumbered}\n\n\
+                        ![图](a.png){width=50%} 说明\n\n\
+                        ```{.python .numberLines}\nprint()\n```\n\n\
+                        集合 {a, b}\n";
+        let speed = ReadSpeed::default();
+        let read_time = Estimator::new(speed).estimate(markdown);
+        let stripped = Estimator::new(speed)
+            .strip_attributes(true)
+            .estimate(markdown);
+        // 不是属性块的花括号按原样计数
+        assert_eq!(stripped.word_count, 2 + 2 + 7);
+        assert_eq!(read_time.word_count, stripped.word_count + 19 + 11);
+    }
+}
@@ -0,0 +1,267 @@
+use pulldown_cmark::{Event, Options, Parser};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+#[cfg(feature = "asciidoc")]
+mod asciidoc;
+#[cfg(feature = "comrak")]
+mod comrak;
+#[cfg(feature = "docx")]
+mod docx;
+#[cfg(feature = "gemtext")]
+mod gemtext;
+#[cfg(feature = "handlebars")]
+mod handlebars;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(feature = "hugo")]
+mod hugo;
+#[cfg(feature = "latex")]
+mod latex;
+#[cfg(feature = "liquid")]
+mod liquid;
+#[cfg(any(
+    feature = "asciidoc",
+    feature = "docx",
+    feature = "gemtext",
+    feature = "handlebars",
+    feature = "html",
+    feature = "hugo",
+    feature = "latex",
+    feature = "liquid",
+    feature = "mdx",
+    feature = "mediawiki",
+    feature = "org",
+    feature = "pandoc",
+    feature = "rst",
+    feature = "textile",
+    feature = "zola"
+))]
+mod markup;
+#[cfg(feature = "mdx")]
+mod mdx;
+#[cfg(feature = "mediawiki")]
+mod mediawiki;
+#[cfg(feature = "org")]
+mod org;
+#[cfg(feature = "pandoc")]
+mod pandoc;
+#[cfg(feature = "rst")]
+mod rst;
+#[cfg(any(
+    feature = "handlebars",
+    feature = "hugo",
+    feature = "liquid",
+    feature = "mdx",
+    feature = "zola"
+))]
+mod template;
+#[cfg(feature = "textile")]
+mod textile;
+#[cfg(feature = "tree-sitter")]
+mod tree_sitter;
+#[cfg(feature = "zola")]
+mod zola;
+
+#[cfg(feature = "asciidoc")]
+pub use self::asciidoc::{AsciiDoc, IncludeResolver};
+#[cfg(feature = "comrak")]
+pub use self::comrak::Comrak;
+#[cfg(feature = "docx")]
+pub(crate) use self::docx::WordDocument;
+#[cfg(feature = "gemtext")]
+pub use self::gemtext::Gemtext;
+#[cfg(feature = "handlebars")]
+pub use self::handlebars::Handlebars;
+#[cfg(feature = "html")]
+pub use self::html::Html;
+#[cfg(feature = "hugo")]
+pub use self::hugo::Hugo;
+#[cfg(feature = "latex")]
+pub use self::latex::Latex;
+#[cfg(feature = "liquid")]
+pub use self::liquid::Liquid;
+#[cfg(any(feature = "docx", feature = "epub"))]
+pub(crate) use self::markup::attribute;
+#[cfg(feature = "mdx")]
+pub use self::mdx::{Mdx, MdxComponents};
+#[cfg(feature = "mediawiki")]
+pub use self::mediawiki::MediaWiki;
+#[cfg(feature = "org")]
+pub use self::org::Org;
+#[cfg(feature = "pandoc")]
+pub use self::pandoc::Pandoc;
+#[cfg(feature = "rst")]
+pub use self::rst::Rst;
+#[cfg(any(
+    feature = "handlebars",
+    feature = "hugo",
+    feature = "liquid",
+    feature = "zola"
+))]
+pub use self::template::TemplateTags;
+#[cfg(feature = "textile")]
+pub use self::textile::Textile;
+#[cfg(feature = "tree-sitter")]
+pub use self::tree_sitter::TreeSitter;
+#[cfg(feature = "zola")]
+pub use self::zola::Zola;
+
+/// Markdown 解析后端
+///
+/// 把 Markdown 文本解析为 pulldown-cmark 的 [`Event`] 流，[`Estimator`](crate::Estimator)
+/// 和 [`EventVisitor`](crate::EventVisitor) 在事件流上计数，因此不同后端共享同一套计数规则。
+/// 选择与站点渲染器一致的后端，可以让字数统计与最终页面保持一致。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, PulldownCmark, ReadSpeed};
+/// use markdown_readtime::pulldown_cmark::Options;
+///
+/// let mut estimator = Estimator::new(ReadSpeed::default())
+///     .backend(PulldownCmark::new(Options::ENABLE_STRIKETHROUGH));
+/// let read_time = estimator.estimate("~~删除~~内容");
+/// assert_eq!(read_time.word_count, 4);
+/// ```
+pub trait MarkdownBackend {
+    /// 解析 `markdown`，按文档顺序把事件传给 `sink`
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>));
+
+    /// 解析 `markdown`，同时传出每个事件对应的源文本字节范围
+    ///
+    /// `sink` 返回 [`ControlFlow::Break`] 后不再传出事件，能够逐步解析的后端应立即停止解析，
+    /// [`Estimator::try_estimate`](crate::Estimator::try_estimate) 超出资源限制时依赖这一点。
+    /// 默认实现不提供位置信息，所有事件的范围均为 `0..0`。
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let mut sink = until_break(sink, &stopped);
+        self.parse(markdown, &mut |event| sink(event, 0..0));
+    }
+
+    /// 输入开头是否可能带有 front matter，默认为 `true`
+    ///
+    /// 返回 `false` 时 [`Estimator`](crate::Estimator) 不会跳过开头的 front matter，
+    /// 适合以 JSON 等结构化数据为输入的后端。
+    fn front_matter(&self) -> bool {
+        true
+    }
+}
+
+/// 基于 pulldown-cmark 的默认后端
+///
+/// 默认不启用任何扩展，与 [`estimate`](crate::estimate) 的行为一致。
+#[derive(Debug, Clone, Copy)]
+pub struct PulldownCmark {
+    options: Options,
+}
+
+impl Default for PulldownCmark {
+    fn default() -> Self {
+        Self::new(Options::empty())
+    }
+}
+
+impl PulldownCmark {
+    /// 使用指定的 pulldown-cmark 扩展选项创建后端
+    pub fn new(options: Options) -> Self {
+        Self { options }
+    }
+
+    /// 按 Markdown 方言创建后端，参见 [`Dialect`]
+    pub fn dialect(dialect: Dialect) -> Self {
+        Self::new(dialect.options())
+    }
+}
+
+/// Markdown 方言
+///
+/// 决定启用哪些扩展语法，以及有歧义的写法按哪种规则解析。例如 `| a | b |` 只有 GFM
+/// 会渲染为表格；`[^1]: 说明` 在 CommonMark 中是链接引用定义，在 GFM 中是脚注定义。
+/// 选择与站点渲染器一致的方言，字数才与读者看到的页面一致。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Dialect, Estimator, ReadSpeed};
+///
+/// let markdown = "| 名称 | 说明 |\n| --- | --- |\n| 甲 | 乙 |\n";
+/// let commonmark = Estimator::new(ReadSpeed::default()).estimate(markdown);
+/// let gfm = Estimator::new(ReadSpeed::default())
+///     .dialect(Dialect::Gfm)
+///     .estimate(markdown);
+/// // CommonMark 不认识表格，竖线和分隔行都作为正文计数
+/// assert_eq!(commonmark.word_count, 21);
+/// assert_eq!(gfm.word_count, 6);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Dialect {
+    /// 严格的 CommonMark，不启用任何扩展
+    #[default]
+    CommonMark,
+
+    /// GitHub Flavored Markdown：表格、删除线、任务列表和 GitHub 风格的脚注
+    Gfm,
+
+    /// 自定义的 pulldown-cmark 扩展选项
+    Custom(Options),
+}
+
+impl Dialect {
+    /// 对应的 pulldown-cmark 扩展选项
+    pub fn options(&self) -> Options {
+        match self {
+            Dialect::CommonMark => Options::empty(),
+            Dialect::Gfm => {
+                Options::ENABLE_TABLES
+                    | Options::ENABLE_STRIKETHROUGH
+                    | Options::ENABLE_TASKLISTS
+                    | Options::ENABLE_FOOTNOTES
+            }
+            Dialect::Custom(options) => *options,
+        }
+    }
+}
+
+impl From<Dialect> for PulldownCmark {
+    fn from(dialect: Dialect) -> Self {
+        Self::dialect(dialect)
+    }
+}
+
+impl MarkdownBackend for PulldownCmark {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        Parser::new_ext(markdown, self.options).for_each(sink);
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        for (event, range) in Parser::new_ext(markdown, self.options).into_offset_iter() {
+            if sink(event, range).is_break() {
+                break;
+            }
+        }
+    }
+}
+
+/// 把返回 [`ControlFlow`] 的 `sink` 包装为普通回调，供一次生成全部事件的后端使用
+///
+/// `sink` 返回 `Break` 后 `stopped` 变为 `true`，之后的事件直接丢弃。
+pub(crate) fn until_break<'s, 'a>(
+    sink: &'s mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    stopped: &'s Cell<bool>,
+) -> impl FnMut(Event<'a>, Range<usize>) + 's {
+    move |event, range| {
+        if !stopped.get() && sink(event, range).is_break() {
+            stopped.set(true);
+        }
+    }
+}
@@ -0,0 +1,606 @@
+use super::markup::{
+    Line, Sink, code_block, end_tag, image, into_static, is_boundary, lines, offset_in, span,
+    start_tag, text,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Tag};
+use std::cell::Cell;
+use std::fmt;
+use std::ops::{ControlFlow, Range};
+
+/// `include::` 的最大嵌套层数，防止循环包含
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// 提示框的类型，出现在段落开头或块属性中时内容按引用统计
+const ADMONITIONS: &[&str] = &["NOTE", "TIP", "IMPORTANT", "CAUTION", "WARNING"];
+
+/// 解析 `include::` 目标的回调，返回被包含文件的内容
+pub type IncludeResolver<'r> = &'r dyn Fn(&str) -> Option<String>;
+
+/// AsciiDoc 后端，需要启用 `asciidoc` 特性
+///
+/// 把章节标题、段落、列表和分隔块转换为对应的事件，计数规则与 Markdown 相同：
+/// `image::` 和行内 `image:` 计为图片，`----` 和 `....` 分隔块计为代码块，
+/// `NOTE:` 等提示框以及 `____`、`****` 块按引用统计。注释、属性定义和 `++++` 透传块不计入字数。
+///
+/// `include::` 默认忽略，通过 [`AsciiDoc::with_includes`] 提供回调后会解析被包含的内容，
+/// 其中事件的字节范围为 `include::` 所在的行。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{AsciiDoc, Estimator, ReadSpeed};
+///
+/// let adoc = "= 标题\n\n这是 *正文* 。\n\nimage::a.png[图片]\n\n[source,rust]\n----\nfn main() {}\n----\n";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(AsciiDoc::new()).estimate(adoc);
+/// assert_eq!(read_time.word_count, 7);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.code_block_count, 1);
+///
+/// let resolve = |target: &str| (target == "intro.adoc").then(|| "引言".to_string());
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(AsciiDoc::with_includes(&resolve))
+///     .estimate("include::intro.adoc[]\n");
+/// assert_eq!(read_time.word_count, 2);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct AsciiDoc<'r> {
+    includes: Option<IncludeResolver<'r>>,
+}
+
+impl fmt::Debug for AsciiDoc<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsciiDoc")
+            .field("includes", &self.includes.is_some())
+            .finish()
+    }
+}
+
+impl<'r> AsciiDoc<'r> {
+    /// 忽略 `include::` 指令
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 通过 `resolve` 读取 `include::` 的目标，返回 `None` 时忽略该指令
+    pub fn with_includes(resolve: IncludeResolver<'r>) -> Self {
+        Self {
+            includes: Some(resolve),
+        }
+    }
+}
+
+impl MarkdownBackend for AsciiDoc<'_> {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let mut parser = Parser {
+            sink,
+            includes: self.includes,
+            depth: 0,
+        };
+        parser.blocks(&lines(markdown));
+    }
+}
+
+/// 分隔块的起止行，例如 `----`、`====` 和 `|===`
+fn is_delimiter(text: &str) -> bool {
+    let Some(&first) = text.as_bytes().first() else {
+        return false;
+    };
+    text == "--"
+        || (text.starts_with("|==") && text[1..].bytes().all(|b| b == b'='))
+        || (text.len() >= 4 && b"-.=*_+/".contains(&first) && text.bytes().all(|b| b == first))
+}
+
+/// 属性定义，例如 `:toc:` 或 `:author: 某人`
+fn is_attribute_entry(text: &str) -> bool {
+    let Some(rest) = text.strip_prefix(':') else {
+        return false;
+    };
+    rest.split_once(':').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && name
+                .trim_end_matches('!')
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    })
+}
+
+/// 章节标题的级别，`=` 为文档标题
+fn heading_level(text: &str) -> Option<usize> {
+    let level = text.bytes().take_while(|&b| b == b'=').count();
+    (1..=6)
+        .contains(&level)
+        .then_some(level)
+        .filter(|&level| text[level..].starts_with(' '))
+}
+
+/// 列表标记的字节长度（包括后面的空格）以及是否为有序列表
+fn list_marker(text: &str) -> Option<(usize, bool)> {
+    let (marker, rest) = text.split_once(' ')?;
+    let ordered = if marker == "-" || (!marker.is_empty() && marker.bytes().all(|b| b == b'*')) {
+        false
+    } else if marker.bytes().all(|b| b == b'.')
+        || marker
+            .strip_suffix('.')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    {
+        true
+    } else {
+        return None;
+    };
+    Some((text.len() - rest.trim_start().len(), ordered))
+}
+
+/// 宏的方括号内容中第一个位置属性，去掉引号
+fn first_attribute(attributes: &str) -> &str {
+    let first = attributes.split(',').next().unwrap_or_default().trim();
+    first.trim_matches('"')
+}
+
+/// 块属性 `[source,rust]` 中的代码语言
+fn source_lang(attributes: Option<&str>) -> &str {
+    let Some(attributes) = attributes else {
+        return "";
+    };
+    let mut parts = attributes.split(',').map(str::trim);
+    match (parts.next(), parts.next()) {
+        (Some("source"), Some(lang)) => lang,
+        _ => "",
+    }
+}
+
+/// 块属性是否表示引用、侧栏或提示框
+fn is_quote(attributes: Option<&str>) -> bool {
+    let style = attributes.map(first_attribute).unwrap_or_default();
+    ["quote", "verse", "sidebar"].contains(&style) || ADMONITIONS.contains(&style)
+}
+
+struct Parser<'a, 's, 'r> {
+    sink: Sink<'s, 'a>,
+    includes: Option<IncludeResolver<'r>>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a, '_, '_> {
+    fn blocks(&mut self, lines: &[Line<'a>]) {
+        // 紧接在块之前的属性行，例如 `[source,rust]`
+        let mut attributes = None;
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let text = line.text.trim_end();
+            let block_attributes = attributes.take();
+            if line.is_blank() || text == "<<<" || is_attribute_entry(text) {
+                i += 1;
+            } else if is_delimiter(text) {
+                let close = (i + 1..lines.len())
+                    .find(|&j| lines[j].text.trim_end() == text)
+                    .unwrap_or(lines.len());
+                let range = span(&lines[i..(close + 1).min(lines.len())]);
+                self.delimited(text, block_attributes, &lines[i + 1..close], range);
+                i = close + 1;
+            } else if text.starts_with("//") {
+                i += 1;
+            } else if text.starts_with('[') && text.ends_with(']') {
+                attributes = Some(&text[1..text.len() - 1]);
+                i += 1;
+            } else if let Some(level) = heading_level(text) {
+                self.heading(line.skip(level), level);
+                i += 1;
+            } else if text == "'''" {
+                (self.sink)(Event::Rule, line.range());
+                i += 1;
+            } else if let Some(rest) = text.strip_prefix("image::")
+                && let Some((target, alt)) = rest.strip_suffix(']').and_then(|r| r.split_once('['))
+            {
+                start_tag(self.sink, &Tag::Paragraph, line.range());
+                image(self.sink, target, first_attribute(alt), line.range());
+                end_tag(self.sink, &Tag::Paragraph, line.range());
+                i += 1;
+            } else if let Some(rest) = text.strip_prefix("include::") {
+                let target = rest.split_once('[').map_or(rest, |(target, _)| target);
+                self.include(target, line.range());
+                i += 1;
+            } else if text.len() > 1 && text.starts_with('.') && !text[1..].starts_with(['.', ' '])
+            {
+                // 块标题
+                let range = line.range();
+                start_tag(self.sink, &Tag::Paragraph, range.clone());
+                self.inline(&text[1..], line.start + 1);
+                end_tag(self.sink, &Tag::Paragraph, range);
+                attributes = block_attributes;
+                i += 1;
+            } else if let Some((_, ordered)) = list_marker(text) {
+                i = self.list(lines, i, ordered);
+            } else if line.indent() > 0 {
+                let end = paragraph_end(lines, i);
+                code_block(self.sink, "", &lines[i..end]);
+                i = end;
+            } else {
+                i = self.paragraph(lines, i, block_attributes);
+            }
+        }
+    }
+
+    fn heading(&mut self, title: Line<'a>, level: usize) {
+        let tag = Tag::Heading {
+            level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        };
+        let range = title.range();
+        start_tag(self.sink, &tag, range.clone());
+        let (title, offset) = title.trimmed();
+        self.inline(title, offset);
+        end_tag(self.sink, &tag, range);
+    }
+
+    fn delimited(
+        &mut self,
+        delimiter: &str,
+        attributes: Option<&'a str>,
+        lines: &[Line<'a>],
+        range: Range<usize>,
+    ) {
+        match delimiter.as_bytes()[0] {
+            b'-' if delimiter != "--" => code_block(self.sink, source_lang(attributes), lines),
+            b'.' => code_block(self.sink, "", lines),
+            b'/' => {}
+            b'+' => {
+                start_tag(self.sink, &Tag::HtmlBlock, range.clone());
+                for line in lines {
+                    (self.sink)(
+                        Event::Html(CowStr::Borrowed(line.raw)),
+                        line.start..line.start + line.raw.len(),
+                    );
+                }
+                end_tag(self.sink, &Tag::HtmlBlock, range);
+            }
+            b'|' => {
+                for line in lines.iter().filter(|line| !line.is_blank()) {
+                    start_tag(self.sink, &Tag::Paragraph, line.range());
+                    let mut offset = line.start;
+                    for cell in line.text.split('|') {
+                        let trimmed = cell.trim_start();
+                        self.inline(trimmed.trim_end(), offset + cell.len() - trimmed.len());
+                        offset += cell.len() + 1;
+                    }
+                    end_tag(self.sink, &Tag::Paragraph, line.range());
+                }
+            }
+            first if first == b'_' || first == b'*' || is_quote(attributes) => {
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                self.blocks(lines);
+                end_tag(self.sink, &Tag::BlockQuote, range);
+            }
+            _ => self.blocks(lines),
+        }
+    }
+
+    /// 段落，到空行或分隔块为止
+    fn paragraph(&mut self, lines: &[Line<'a>], i: usize, attributes: Option<&'a str>) -> usize {
+        let end = paragraph_end(lines, i);
+        let body = &lines[i..end];
+        let range = span(body);
+        if attributes.is_some_and(|attributes| first_attribute(attributes) == "source") {
+            code_block(self.sink, source_lang(attributes), body);
+            return end;
+        }
+
+        let mut first = body[0];
+        let admonition = ADMONITIONS.iter().find_map(|name| {
+            let rest = first.text.strip_prefix(name)?.strip_prefix(": ")?;
+            Some(first.text.len() - rest.len())
+        });
+        if let Some(prefix) = admonition {
+            first = first.skip(prefix);
+        }
+        let quote = admonition.is_some() || is_quote(attributes);
+        if quote {
+            start_tag(self.sink, &Tag::BlockQuote, range.clone());
+        }
+        start_tag(self.sink, &Tag::Paragraph, range.clone());
+        for (index, line) in std::iter::once(&first).chain(&body[1..]).enumerate() {
+            if index > 0 {
+                (self.sink)(Event::SoftBreak, line.start..line.start);
+            }
+            let (text, offset) = line.trimmed();
+            match text.strip_suffix(" +") {
+                Some(text) => {
+                    self.inline(text, offset);
+                    (self.sink)(
+                        Event::HardBreak,
+                        offset + text.len()..offset + text.len() + 2,
+                    );
+                }
+                None => self.inline(text, offset),
+            }
+        }
+        end_tag(self.sink, &Tag::Paragraph, range.clone());
+        if quote {
+            end_tag(self.sink, &Tag::BlockQuote, range);
+        }
+        end
+    }
+
+    /// 连续的列表项，列表项的后续行和 `+` 连接的续行属于同一项
+    fn list(&mut self, lines: &[Line<'a>], mut i: usize, ordered: bool) -> usize {
+        let list_start = i;
+        let tag = Tag::List(ordered.then_some(1));
+        start_tag(self.sink, &tag, span(&lines[i..i + 1]));
+        while let Some(line) = lines.get(i) {
+            let Some((width, item_ordered)) = list_marker(line.text) else {
+                break;
+            };
+            if item_ordered != ordered {
+                break;
+            }
+            let end = (i + 1..lines.len())
+                .find(|&j| {
+                    let text = lines[j].text.trim_end();
+                    lines[j].is_blank()
+                        || text == "+"
+                        || is_delimiter(text)
+                        || list_marker(text).is_some()
+                })
+                .unwrap_or(lines.len());
+            let range = span(&lines[i..end]);
+            start_tag(self.sink, &Tag::Item, range.clone());
+            let mut body = Vec::with_capacity(end - i);
+            body.push(line.skip(width));
+            body.extend_from_slice(&lines[i + 1..end]);
+            let checked = match body[0].text.get(..4) {
+                Some("[x] ") => Some(true),
+                Some("[ ] ") => Some(false),
+                _ => None,
+            };
+            if let Some(checked) = checked {
+                (self.sink)(Event::TaskListMarker(checked), body[0].range());
+                body[0] = body[0].skip(4);
+            }
+            self.paragraph(&body, 0, None);
+            end_tag(self.sink, &Tag::Item, range);
+            i = end;
+            while lines.get(i).is_some_and(|line| line.is_blank()) {
+                i += 1;
+            }
+            if lines.get(i).is_some_and(|line| line.text.trim_end() == "+") {
+                i += 1;
+            }
+        }
+        end_tag(self.sink, &tag, span(&lines[list_start..i]));
+        i
+    }
+
+    fn include(&mut self, target: &str, range: Range<usize>) {
+        let Some(resolve) = self.includes else {
+            return;
+        };
+        if self.depth >= MAX_INCLUDE_DEPTH {
+            return;
+        }
+        let Some(content) = resolve(target) else {
+            return;
+        };
+        let sink = &mut *self.sink;
+        let mut forward =
+            |event: Event<'_>, _: Range<usize>| sink(into_static(event), range.clone());
+        let mut parser = Parser {
+            sink: &mut forward,
+            includes: self.includes,
+            depth: self.depth + 1,
+        };
+        parser.blocks(&lines(&content));
+    }
+
+    /// 行内文本，去掉强调标记和宏语法，`` `等宽` `` 作为行内代码
+    fn inline(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let rest = &source[i..];
+            let at_word = i == 0 || is_boundary(Some(bytes[i - 1]));
+            if bytes[i] == b'`' {
+                let Some(len) = source[i + 1..].find('`') else {
+                    i += 1;
+                    continue;
+                };
+                text(self.sink, &source[plain..i], offset + plain);
+                let code = &source[i + 1..i + 1 + len];
+                (self.sink)(
+                    Event::Code(CowStr::Borrowed(code)),
+                    offset + i + 1..offset + i + 1 + len,
+                );
+                i += len + 2;
+                plain = i;
+            } else if matches!(bytes[i], b'*' | b'_' | b'#') {
+                let run = bytes[i..].iter().take_while(|&&b| b == bytes[i]).count();
+                let before = i.checked_sub(1).map(|j| bytes[j]);
+                let after = bytes.get(i + run).copied();
+                let opens = is_boundary(before) && !is_boundary(after);
+                let closes = !is_boundary(before) && is_boundary(after);
+                if run == 2 || opens || closes {
+                    text(self.sink, &source[plain..i], offset + plain);
+                    plain = i + run;
+                }
+                i += run;
+            } else if let Some(macro_len) = at_word.then(|| macro_prefix(rest)).flatten() {
+                let target_end = rest[macro_len..]
+                    .find(|c: char| c == '[' || c.is_whitespace())
+                    .map_or(rest.len(), |end| macro_len + end);
+                let attributes = rest[target_end..]
+                    .strip_prefix('[')
+                    .and_then(|attributes| Some(&attributes[..attributes.find(']')?]));
+                text(self.sink, &source[plain..i], offset + plain);
+                let target = &rest[macro_len..target_end];
+                let label = attributes.map(first_attribute).unwrap_or_default();
+                if rest.starts_with("image:") {
+                    let range = offset + i..offset + i + target_end;
+                    image(self.sink, target, label, range);
+                } else if label.is_empty() {
+                    let url = if rest.starts_with("http") {
+                        &rest[..target_end]
+                    } else {
+                        target
+                    };
+                    text(self.sink, url, offset + i);
+                } else {
+                    text(self.sink, label, offset + offset_in(source, label));
+                }
+                i += target_end + attributes.map_or(0, |a| a.len() + 2);
+                plain = i;
+            } else if let Some(reference) = rest.strip_prefix("<<")
+                && let Some(len) = reference.find(">>")
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                let reference = &reference[..len];
+                let label = reference
+                    .split_once(',')
+                    .map_or(reference, |(_, label)| label.trim());
+                text(self.sink, label, offset + offset_in(source, label));
+                i += len + 4;
+                plain = i;
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        text(self.sink, &source[plain..], offset + plain);
+    }
+}
+
+/// 行内宏的前缀长度，例如 `link:`、`xref:`、`image:` 和 `footnote:`；网址的前缀长度为 0
+fn macro_prefix(text: &str) -> Option<usize> {
+    [
+        "link:",
+        "xref:",
+        "image:",
+        "footnote:",
+        "kbd:",
+        "btn:",
+        "menu:",
+    ]
+    .iter()
+    .find(|prefix| text.starts_with(**prefix) && !text[prefix.len()..].starts_with(':'))
+    .map(|prefix| prefix.len())
+    .or_else(|| {
+        ["https://", "http://", "mailto:"]
+            .iter()
+            .any(|prefix| text.starts_with(prefix))
+            .then_some(0)
+    })
+}
+
+/// 段落的结束位置：下一个空行或分隔块
+fn paragraph_end(lines: &[Line<'_>], i: usize) -> usize {
+    (i + 1..lines.len())
+        .find(|&j| lines[j].is_blank() || is_delimiter(lines[j].text.trim_end()))
+        .unwrap_or(lines.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_adoc(adoc: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(AsciiDoc::new())
+            .estimate(adoc)
+    }
+
+    #[test]
+    fn test_asciidoc_matches_markdown() {
+        let adoc = "\
+= 文档
+:toc:
+
+== 简介
+
+这是 *重要* 的 _内容_ ，参见 https://example.com[链接] 和 <<intro,章节>>。
+
+* 第一项
+* 第二项
+
+// 注释
+
+.示例代码
+[source,rust]
+----
+fn main() {}
+----
+
+NOTE: 注意事项
+
+image::a.png[图片]
+
+////
+注释块
+////
+";
+        let markdown = "\
+# 文档
+
+## 简介
+
+这是 **重要** 的 *内容* ，参见 [链接](https://example.com) 和 章节。
+
+- 第一项
+- 第二项
+
+示例代码
+
+```rust
+fn main() {}
+```
+
+> 注意事项
+
+![图片](a.png)
+";
+        assert_eq!(estimate_adoc(adoc), estimate(markdown));
+    }
+
+    #[test]
+    fn test_asciidoc_includes() {
+        let resolve = |target: &str| match target {
+            "a.adoc" => Some("第一章\n\ninclude::b.adoc[]\n".to_string()),
+            "b.adoc" => Some("第二章 `code`\n\nimage:b.png[]\n".to_string()),
+            "loop.adoc" => Some("循环\n\ninclude::loop.adoc[]\n".to_string()),
+            _ => None,
+        };
+        let adoc = "前言\n\ninclude::a.adoc[]\n\ninclude::missing.adoc[]\n";
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(AsciiDoc::with_includes(&resolve))
+            .estimate(adoc);
+        assert_eq!(read_time.word_count, 12);
+        assert_eq!(read_time.image_count, 1);
+        assert_eq!(estimate_adoc(adoc).word_count, 2);
+
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(AsciiDoc::with_includes(&resolve))
+            .estimate("include::loop.adoc[]");
+        assert_eq!(read_time.word_count, 2 * MAX_INCLUDE_DEPTH);
+
+        let mut ranges = Vec::new();
+        AsciiDoc::with_includes(&resolve).parse_with_offsets(adoc, &mut |_, range| {
+            ranges.push(range);
+            ControlFlow::Continue(())
+        });
+        assert!(ranges.contains(&(8..25)));
+    }
+}
@@ -0,0 +1,224 @@
+use super::{MarkdownBackend, until_break};
+use comrak::nodes::{AstNode, ListType, NodeValue, Sourcepos, TableAlignment};
+use comrak::{Arena, Options, parse_document};
+use pulldown_cmark::{
+    Alignment, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd,
+};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 基于 comrak 的后端，需要启用 `comrak` 特性
+///
+/// comrak 支持完整的 GFM 以及 front matter 等扩展，通过 [`comrak::Options`] 配置。
+/// 启用 front matter 扩展后，front matter 不计入字数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Comrak, Estimator, ReadSpeed};
+///
+/// let mut options = comrak::Options::default();
+/// options.extension.front_matter_delimiter = Some("---".into());
+///
+/// let mut estimator = Estimator::new(ReadSpeed::default()).backend(Comrak::new(options));
+/// let read_time = estimator.estimate("---\ntitle: 标题\n---\n\n正文");
+/// assert_eq!(read_time.word_count, 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Comrak {
+    options: Options<'static>,
+}
+
+impl Comrak {
+    pub fn new(options: Options<'static>) -> Self {
+        Self { options }
+    }
+}
+
+impl MarkdownBackend for Comrak {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &self.options);
+        emit(root, &LineIndex::new(markdown), sink);
+    }
+}
+
+/// 把 comrak 节点转换为对应的 pulldown-cmark 事件
+fn emit<'a, 'n>(
+    node: &'n AstNode<'n>,
+    lines: &LineIndex,
+    sink: &mut dyn FnMut(Event<'a>, Range<usize>),
+) {
+    let range = lines.range(&node.data.borrow().sourcepos);
+    let tag = match &node.data.borrow().value {
+        NodeValue::FrontMatter(_) => return,
+        NodeValue::Text(text) => return sink(Event::Text(owned(text)), range.clone()),
+        NodeValue::Code(code) => return sink(Event::Code(owned(&code.literal)), range.clone()),
+        NodeValue::Math(math) => return sink(Event::Code(owned(&math.literal)), range.clone()),
+        NodeValue::HtmlInline(html) => return sink(Event::InlineHtml(owned(html)), range.clone()),
+        NodeValue::Raw(raw) => return sink(Event::Html(owned(raw)), range.clone()),
+        NodeValue::SoftBreak => return sink(Event::SoftBreak, range.clone()),
+        NodeValue::LineBreak => return sink(Event::HardBreak, range.clone()),
+        NodeValue::ThematicBreak => return sink(Event::Rule, range.clone()),
+        NodeValue::FootnoteReference(reference) => {
+            return sink(
+                Event::FootnoteReference(owned(&reference.name)),
+                range.clone(),
+            );
+        }
+        NodeValue::CodeBlock(block) => {
+            let kind = if block.fenced {
+                CodeBlockKind::Fenced(owned(&block.info))
+            } else {
+                CodeBlockKind::Indented
+            };
+            sink(Event::Start(Tag::CodeBlock(kind)), range.clone());
+            sink(Event::Text(owned(&block.literal)), range.clone());
+            return sink(Event::End(TagEnd::CodeBlock), range.clone());
+        }
+        NodeValue::HtmlBlock(block) => {
+            sink(Event::Start(Tag::HtmlBlock), range.clone());
+            sink(Event::Html(owned(&block.literal)), range.clone());
+            return sink(Event::End(TagEnd::HtmlBlock), range.clone());
+        }
+        NodeValue::Paragraph => Some(Tag::Paragraph),
+        NodeValue::Heading(heading) => Some(Tag::Heading {
+            level: HeadingLevel::try_from(heading.level as usize).unwrap_or(HeadingLevel::H6),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        }),
+        NodeValue::BlockQuote | NodeValue::MultilineBlockQuote(_) | NodeValue::Alert(_) => {
+            Some(Tag::BlockQuote)
+        }
+        NodeValue::List(list) => Some(Tag::List(match list.list_type {
+            ListType::Ordered => Some(list.start as u64),
+            ListType::Bullet => None,
+        })),
+        NodeValue::Item(_) => Some(Tag::Item),
+        NodeValue::TaskItem(task) => {
+            sink(Event::Start(Tag::Item), range.clone());
+            sink(Event::TaskListMarker(task.symbol.is_some()), range.clone());
+            emit_children(node, lines, sink);
+            return sink(Event::End(TagEnd::Item), range.clone());
+        }
+        NodeValue::FootnoteDefinition(definition) => {
+            Some(Tag::FootnoteDefinition(owned(&definition.name)))
+        }
+        NodeValue::Table(table) => Some(Tag::Table(
+            table.alignments.iter().map(|&a| alignment(a)).collect(),
+        )),
+        NodeValue::TableRow(true) => Some(Tag::TableHead),
+        NodeValue::TableRow(false) => Some(Tag::TableRow),
+        NodeValue::TableCell => Some(Tag::TableCell),
+        NodeValue::Emph => Some(Tag::Emphasis),
+        NodeValue::Strong => Some(Tag::Strong),
+        NodeValue::Strikethrough => Some(Tag::Strikethrough),
+        NodeValue::Link(link) => Some(Tag::Link {
+            link_type: LinkType::Inline,
+            dest_url: owned(&link.url),
+            title: owned(&link.title),
+            id: CowStr::Borrowed(""),
+        }),
+        NodeValue::Image(link) => Some(Tag::Image {
+            link_type: LinkType::Inline,
+            dest_url: owned(&link.url),
+            title: owned(&link.title),
+            id: CowStr::Borrowed(""),
+        }),
+        _ => None,
+    };
+
+    match tag {
+        Some(tag) => {
+            let end = tag.to_end();
+            sink(Event::Start(tag), range.clone());
+            emit_children(node, lines, sink);
+            sink(Event::End(end), range.clone());
+        }
+        None => emit_children(node, lines, sink),
+    }
+}
+
+fn emit_children<'a, 'n>(
+    node: &'n AstNode<'n>,
+    lines: &LineIndex,
+    sink: &mut dyn FnMut(Event<'a>, Range<usize>),
+) {
+    for child in node.children() {
+        emit(child, lines, sink);
+    }
+}
+
+/// 每行起始位置的字节偏移，用于把 comrak 的行列位置转换为字节范围
+struct LineIndex {
+    starts: Vec<usize>,
+    len: usize,
+}
+
+impl LineIndex {
+    fn new(text: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            starts,
+            len: text.len(),
+        }
+    }
+
+    fn offset(&self, line: usize, column: usize) -> usize {
+        match self.starts.get(line.saturating_sub(1)) {
+            Some(start) => (start + column.saturating_sub(1)).min(self.len),
+            None => self.len,
+        }
+    }
+
+    /// 行列位置为闭区间，转换为半开的字节范围
+    fn range(&self, sourcepos: &Sourcepos) -> Range<usize> {
+        let start = self.offset(sourcepos.start.line, sourcepos.start.column);
+        let end = self.offset(sourcepos.end.line, sourcepos.end.column + 1);
+        start..end.max(start)
+    }
+}
+
+fn owned<'a>(text: &str) -> CowStr<'a> {
+    CowStr::from(String::from(text))
+}
+
+fn alignment(alignment: TableAlignment) -> Alignment {
+    match alignment {
+        TableAlignment::None => Alignment::None,
+        TableAlignment::Left => Alignment::Left,
+        TableAlignment::Center => Alignment::Center,
+        TableAlignment::Right => Alignment::Right,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, estimate};
+
+    #[test]
+    fn test_comrak_matches_pulldown() {
+        let markdown = "# 标题\n\n这是**内容**，包含`代码`。\n\n![图片](a.png)\n\n```rust\nfn main() {}\n```\n";
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(Comrak::default())
+            .estimate(markdown);
+        assert_eq!(read_time, estimate(markdown));
+    }
+}
@@ -0,0 +1,276 @@
+use super::markup::{Sink, attribute, closing_tag, element, end_tag, entity, image, start_tag};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Tag};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 内容不计入正文的元素：`mc:Fallback` 与 `mc:Choice` 内容重复，`w:instrText` 是域代码
+const SKIPPED_ELEMENTS: &[&str] = &["Fallback", "instrText"];
+
+/// DOCX 正文 `word/document.xml` 的解析后端
+///
+/// Word 会在单词中间拆分文本段（`w:r`），因此同一段落的文本合并为一个文本事件。
+/// `Title` 和 `Heading1` 到 `Heading9` 样式的段落计为标题，`w:drawing` 和 `w:pict` 计为图片，
+/// 表格中的段落计入字数。
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct WordDocument;
+
+impl MarkdownBackend for WordDocument {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let xml = markdown;
+        let mut paragraphs: Vec<Paragraph> = Vec::new();
+        let mut i = 0;
+        while let Some(found) = xml[i..].find('<') {
+            let at = i + found;
+            let Some((element, len)) = element(&xml[at..]) else {
+                i = at + 1;
+                continue;
+            };
+            let end = at + len;
+            i = end;
+            let skip_to_closing = |name| {
+                closing_tag(&xml[end..], name).map_or(xml.len(), |(close, len)| end + close + len)
+            };
+            let name = element.name;
+            if element.closing {
+                match name {
+                    "p" => {
+                        if let Some(mut paragraph) = paragraphs.pop() {
+                            paragraph.finish(sink, end);
+                        }
+                    }
+                    "tbl" | "tr" | "tc" => {
+                        if let Some(paragraph) = paragraphs.last_mut() {
+                            paragraph.flush(sink, at);
+                        }
+                        end_tag(sink, &table_tag(name), at..end);
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            if element.self_closing && matches!(name, "p" | "tbl" | "tr" | "tc") {
+                continue;
+            }
+            match name {
+                "p" => {
+                    // 文本框中的段落嵌套在外层段落中
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.flush(sink, at);
+                    }
+                    paragraphs.push(Paragraph::new(at));
+                }
+                "pStyle" => {
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.level =
+                            attribute(element.attributes, "val").and_then(heading_level);
+                    }
+                }
+                "t" if !element.self_closing => {
+                    let (close, len) =
+                        closing_tag(&xml[end..], "t").unwrap_or((xml.len() - end, 0));
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.push_text(&xml[end..end + close], end);
+                    }
+                    i = end + close + len;
+                }
+                "tab" | "br" | "cr" | "noBreakHyphen" => {
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        let c = match name {
+                            "tab" => '\t',
+                            "noBreakHyphen" => '-',
+                            _ => '\n',
+                        };
+                        paragraph.push(c, at);
+                    }
+                }
+                "drawing" | "pict" => {
+                    i = if element.self_closing {
+                        end
+                    } else {
+                        skip_to_closing(name)
+                    };
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.flush(sink, at);
+                        paragraph.open(sink);
+                    }
+                    image(sink, "", "", at..i);
+                }
+                "tbl" | "tr" | "tc" => {
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.flush(sink, at);
+                    }
+                    start_tag(sink, &table_tag(name), at..end);
+                }
+                _ if SKIPPED_ELEMENTS.contains(&name) && !element.self_closing => {
+                    i = skip_to_closing(name);
+                }
+                _ => {}
+            }
+        }
+        while let Some(mut paragraph) = paragraphs.pop() {
+            paragraph.finish(sink, xml.len());
+        }
+    }
+}
+
+fn table_tag(name: &str) -> Tag<'static> {
+    match name {
+        "tbl" => Tag::Table(Vec::new()),
+        "tr" => Tag::TableRow,
+        _ => Tag::TableCell,
+    }
+}
+
+/// 段落样式对应的标题级别
+fn heading_level(style: &str) -> Option<HeadingLevel> {
+    if style == "Title" {
+        return Some(HeadingLevel::H1);
+    }
+    let level: usize = style.strip_prefix("Heading")?.parse().ok()?;
+    HeadingLevel::try_from(level.min(6)).ok()
+}
+
+/// 正在解析的段落，开始事件在遇到第一段内容时输出
+struct Paragraph {
+    start: usize,
+    level: Option<HeadingLevel>,
+    started: bool,
+    text: String,
+    text_start: usize,
+}
+
+impl Paragraph {
+    fn new(start: usize) -> Self {
+        Self {
+            start,
+            level: None,
+            started: false,
+            text: String::new(),
+            text_start: start,
+        }
+    }
+
+    fn tag(&self) -> Tag<'static> {
+        match self.level {
+            Some(level) => Tag::Heading {
+                level,
+                id: None,
+                classes: Vec::new(),
+                attrs: Vec::new(),
+            },
+            None => Tag::Paragraph,
+        }
+    }
+
+    fn open<'a>(&mut self, sink: Sink<'_, 'a>) {
+        if !self.started {
+            self.started = true;
+            sink(Event::Start(self.tag()), self.start..self.start);
+        }
+    }
+
+    fn push(&mut self, c: char, at: usize) {
+        if self.text.is_empty() {
+            self.text_start = at;
+        }
+        self.text.push(c);
+    }
+
+    /// 追加 `w:t` 的内容并解码字符引用
+    fn push_text(&mut self, text: &str, offset: usize) {
+        if self.text.is_empty() {
+            self.text_start = offset;
+        }
+        let mut rest = text;
+        while let Some(at) = rest.find('&') {
+            self.text.push_str(&rest[..at]);
+            rest = &rest[at..];
+            match entity(rest) {
+                Some((c, len)) => {
+                    self.text.push(c);
+                    rest = &rest[len..];
+                }
+                None => {
+                    self.text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        self.text.push_str(rest);
+    }
+
+    /// 输出已合并的文本
+    fn flush<'a>(&mut self, sink: Sink<'_, 'a>, at: usize) {
+        if self.text.is_empty() {
+            return;
+        }
+        self.open(sink);
+        let text = std::mem::take(&mut self.text);
+        sink(Event::Text(CowStr::from(text)), self.text_start..at);
+    }
+
+    fn finish<'a>(&mut self, sink: Sink<'_, 'a>, end: usize) {
+        self.flush(sink, end);
+        if self.started {
+            end_tag(sink, &self.tag(), self.start..end);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, estimate_with_speed};
+
+    #[test]
+    fn test_word_document_matches_markdown() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:body>
+  <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Intro</w:t></w:r><w:r><w:t>duction</w:t></w:r></w:p>
+  <w:p><w:r><w:t xml:space="preserve">Tom &amp; Jerry </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:t>run</w:t></w:r><w:r><w:tab/><w:t>fast</w:t></w:r></w:p>
+  <w:p/>
+  <w:p><w:r><w:drawing><wp:inline><wp:docPr descr="alt text"/></wp:inline></w:drawing></w:r></w:p>
+  <w:tbl><w:tr><w:tc><w:p><w:r><w:t>cell one</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>two</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+  <w:p><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText> PAGE </w:instrText></w:r><w:del><w:r><w:delText>gone</w:delText></w:r></w:del><w:r><w:t>end</w:t></w:r></w:p>
+  <w:sectPr/>
+</w:body>
+</w:document>"#;
+        let speed = ReadSpeed::default().chinese(false);
+        let markdown =
+            "# Introduction\n\nTom & Jerry run fast\n\n![](a.png)\n\ncell one\n\ntwo\n\nend\n";
+        assert_eq!(
+            Estimator::new(speed).backend(WordDocument).estimate(xml),
+            estimate_with_speed(markdown, &speed)
+        );
+        assert_eq!(
+            Estimator::new(ReadSpeed::default())
+                .backend(WordDocument)
+                .estimate_sections(xml)[0]
+                .heading,
+            "Introduction"
+        );
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(heading_level("Title"), Some(HeadingLevel::H1));
+        assert_eq!(heading_level("Heading2"), Some(HeadingLevel::H2));
+        assert_eq!(heading_level("Heading9"), Some(HeadingLevel::H6));
+        assert_eq!(heading_level("Normal"), None);
+    }
+}
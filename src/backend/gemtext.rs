@@ -0,0 +1,224 @@
+use super::markup::{Line, Sink, code_block, end_tag, image, lines, span, start_tag, text};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CowStr, Event, HeadingLevel, LinkType, Tag};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 链接目标为图片时按图片统计的扩展名
+const IMAGE_EXTENSIONS: &[&str] = &[".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp"];
+
+/// Gemini 协议的 Gemtext 后端，需要启用 `gemtext` 特性
+///
+/// Gemtext 按行区分类型：`#` 到 `###` 为标题，`* ` 为列表项，`>` 为引用，
+/// ```` ``` ```` 切换预格式化文本并计为代码块，其他每个非空行是一个段落。
+/// `=> URL 文字` 链接行只统计显示的文字，没有文字时统计 URL，指向图片的链接计为图片。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Gemtext, ReadSpeed};
+///
+/// let gemtext = "# 标题\n\n正文内容\n=> gemini://example.org/ 示例站点\n=> /a.png\n```rust\nfn main() {}\n```";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Gemtext).estimate(gemtext);
+/// assert_eq!(read_time.word_count, 10);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.code_block_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gemtext;
+
+impl MarkdownBackend for Gemtext {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let lines = lines(markdown);
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            if let Some(alt) = line.text.strip_prefix("```") {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].text.starts_with("```"))
+                    .unwrap_or(lines.len());
+                let lang = alt.split_whitespace().next().unwrap_or_default();
+                code_block(sink, lang, &lines[i + 1..end]);
+                i = end + 1;
+            } else if line.text.starts_with("* ") {
+                let end = (i..lines.len())
+                    .find(|&j| !lines[j].text.starts_with("* "))
+                    .unwrap_or(lines.len());
+                let tag = Tag::List(None);
+                let range = span(&lines[i..end]);
+                start_tag(sink, &tag, range.clone());
+                for item in &lines[i..end] {
+                    start_tag(sink, &Tag::Item, item.range());
+                    inline(sink, item.skip(2));
+                    end_tag(sink, &Tag::Item, item.range());
+                }
+                end_tag(sink, &tag, range);
+                i = end;
+            } else if line.text.starts_with('>') {
+                let end = (i..lines.len())
+                    .find(|&j| !lines[j].text.starts_with('>'))
+                    .unwrap_or(lines.len());
+                let range = span(&lines[i..end]);
+                start_tag(sink, &Tag::BlockQuote, range.clone());
+                for quote in &lines[i..end] {
+                    paragraph(sink, quote.skip(1));
+                }
+                end_tag(sink, &Tag::BlockQuote, range);
+                i = end;
+            } else {
+                block(sink, line);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// 标题、链接行或普通段落
+fn block<'a>(sink: Sink<'_, 'a>, line: Line<'a>) {
+    if line.is_blank() {
+        return;
+    }
+    let level = line.text.bytes().take_while(|&b| b == b'#').count();
+    if (1..=3).contains(&level) {
+        let tag = Tag::Heading {
+            level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H3),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        };
+        start_tag(sink, &tag, line.range());
+        inline(sink, line.skip(level));
+        end_tag(sink, &tag, line.range());
+    } else if let Some(link) = line.text.strip_prefix("=>") {
+        let link = link.trim_start();
+        let (url, label) = match link.split_once(char::is_whitespace) {
+            Some((url, label)) => (url, label.trim()),
+            None => (link, ""),
+        };
+        let is_image = IMAGE_EXTENSIONS
+            .iter()
+            .any(|extension| url.to_ascii_lowercase().ends_with(extension));
+        start_tag(sink, &Tag::Paragraph, line.range());
+        if is_image {
+            image(sink, url, "", line.range());
+        } else {
+            let tag = Tag::Link {
+                link_type: LinkType::Inline,
+                dest_url: CowStr::Borrowed(url),
+                title: CowStr::Borrowed(""),
+                id: CowStr::Borrowed(""),
+            };
+            start_tag(sink, &tag, line.range());
+            let shown = if label.is_empty() { url } else { label };
+            text(sink, shown, line.offset_of(shown));
+            end_tag(sink, &tag, line.range());
+        }
+        end_tag(sink, &Tag::Paragraph, line.range());
+    } else {
+        paragraph(sink, line);
+    }
+}
+
+fn paragraph<'a>(sink: Sink<'_, 'a>, line: Line<'a>) {
+    if line.is_blank() {
+        return;
+    }
+    start_tag(sink, &Tag::Paragraph, line.range());
+    inline(sink, line);
+    end_tag(sink, &Tag::Paragraph, line.range());
+}
+
+/// Gemtext 没有行内标记，整行内容都是文本
+fn inline<'a>(sink: Sink<'_, 'a>, line: Line<'a>) {
+    let (content, offset) = line.trimmed();
+    text(sink, content, offset);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_gemtext(gemtext: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Gemtext)
+            .estimate(gemtext)
+    }
+
+    #[test]
+    fn test_gemtext_matches_markdown() {
+        let gemtext = "\
+# 我的胶囊
+## 最新文章
+
+欢迎来到我的 Gemini 胶囊。
+这里的每一行都是一个段落。
+
+=> gemini://example.org/post.gmi 第一篇文章
+=> https://example.com
+=> images/cat.JPG 一只猫
+
+* 第一项
+* 第二项
+
+> 引用的内容
+
+```python 示例代码
+print(\"# 不是标题\")
+```
+";
+        let markdown = "\
+# 我的胶囊
+
+## 最新文章
+
+欢迎来到我的 Gemini 胶囊。
+
+这里的每一行都是一个段落。
+
+[第一篇文章](gemini://example.org/post.gmi)
+
+<https://example.com>
+
+![](images/cat.JPG)
+
+- 第一项
+- 第二项
+
+> 引用的内容
+
+```python
+print(\"# 不是标题\")
+```
+";
+        assert_eq!(estimate_gemtext(gemtext), estimate(markdown));
+    }
+
+    #[test]
+    fn test_gemtext_sections() {
+        let gemtext =
+            "# 第一章\n内容\n=> /next.gmi 下一章\n# 第二章\n更多内容\n```\n未关闭的预格式化";
+        let sections = Estimator::new(ReadSpeed::default())
+            .backend(Gemtext)
+            .estimate_sections(gemtext);
+        let headings: Vec<_> = sections
+            .iter()
+            .map(|section| section.heading.as_str())
+            .collect();
+        assert_eq!(headings, ["第一章", "第二章"]);
+        assert_eq!(sections[1].read_time.code_block_count, 1);
+    }
+}
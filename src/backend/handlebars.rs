@@ -0,0 +1,279 @@
+use super::MarkdownBackend;
+use super::template::{Removals, find_close, scan};
+use pulldown_cmark::Event;
+use std::ops::{ControlFlow, Range};
+
+/// 带 Handlebars/Mustache 模板语法的 Markdown 后端，需要启用 `handlebars` 特性
+///
+/// 去掉 `{{…}}`、`{{{…}}}`、`{{> partial}}` 和 `{{#name}}…{{/name}}` 等模板标签之后按 Markdown 统计，
+/// `{{! … }}` 注释不计入字数。块之间的内容默认正常计入字数，
+/// 通过 [`count_block_bodies`](Self::count_block_bodies) 可以把整个块当作一个标签跳过。
+/// 标签以 HTML 事件输出，可以配合 [`TemplateTags`](crate::TemplateTags) 按标签名设置固定时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Handlebars, ReadSpeed};
+///
+/// let markdown = "你好 {{name}}\n\n{{#each items}}\n- {{this}} 项目\n{{/each}}";
+/// let mut estimator = Estimator::new(ReadSpeed::default()).backend(Handlebars::default());
+/// assert_eq!(estimator.estimate(markdown).word_count, 4);
+///
+/// let mut estimator = Estimator::new(ReadSpeed::default())
+///     .backend(Handlebars::default().count_block_bodies(false));
+/// assert_eq!(estimator.estimate(markdown).word_count, 2);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Handlebars {
+    block_bodies: bool,
+}
+
+impl Default for Handlebars {
+    fn default() -> Self {
+        Self { block_bodies: true }
+    }
+}
+
+impl Handlebars {
+    /// 是否统计 `{{#name}}…{{/name}}` 块中的内容，默认为 `true`
+    ///
+    /// 为 `false` 时整个块以一个 HTML 事件输出，适合循环和条件等只在渲染时展开的内容。
+    pub fn count_block_bodies(mut self, count: bool) -> Self {
+        self.block_bodies = count;
+        self
+    }
+}
+
+impl MarkdownBackend for Handlebars {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let source = markdown;
+        let mut removals = Removals::default();
+        scan(
+            source,
+            &['{'],
+            &mut removals,
+            |_, _| false,
+            |removals, at| {
+                let tag = Mustache::parse(source, at)?;
+                match tag.kind {
+                    Kind::Comment => removals.remove(at..tag.end),
+                    Kind::Raw => {
+                        // 原始块中的模板语法原样显示，只去掉原始块标签本身
+                        removals.remove(at..tag.end);
+                        let (close, end) = block_end(source, &tag)?;
+                        removals.remove(close..end);
+                        return Some(end);
+                    }
+                    Kind::Block if !self.block_bodies => {
+                        let end = block_end(source, &tag).map_or(tag.end, |(_, end)| end);
+                        removals.tag(source, at..end);
+                        return Some(end);
+                    }
+                    _ => removals.tag(source, at..tag.end),
+                }
+                Some(tag.end)
+            },
+        );
+        removals.parse(markdown, sink);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// `{{! … }}` 和 `{{!-- … --}}`
+    Comment,
+
+    /// `{{{{raw}}}}…{{{{/raw}}}}`
+    Raw,
+
+    /// `{{#name}}` 和 `{{^name}}`
+    Block,
+
+    /// `{{/name}}`
+    Close,
+
+    /// 变量、局部模板和 `{{else}}` 等其他标签
+    Other,
+}
+
+/// 源文本中的一个模板标签
+struct Mustache<'a> {
+    kind: Kind,
+    name: &'a str,
+    end: usize,
+}
+
+impl<'a> Mustache<'a> {
+    /// 解析 `at` 处的模板标签
+    fn parse(source: &'a str, at: usize) -> Option<Self> {
+        let rest = &source[at..];
+        if !rest.starts_with("{{") {
+            return None;
+        }
+        let (kind, end) = if rest.starts_with("{{!--") {
+            let end = at + rest.find("--}}")? + 4;
+            (Kind::Comment, end)
+        } else if rest.starts_with("{{!") {
+            (Kind::Comment, at + rest.find("}}")? + 2)
+        } else if rest.starts_with("{{{{") {
+            let end = find_close(source, at + 4, "}}}}")?;
+            let kind = if rest.as_bytes().get(4) == Some(&b'/') {
+                Kind::Close
+            } else {
+                Kind::Raw
+            };
+            (kind, end)
+        } else if rest.starts_with("{{{") {
+            (Kind::Other, find_close(source, at + 3, "}}}")?)
+        } else {
+            let end = find_close(source, at + 2, "}}")?;
+            let inner = source[at + 2..end - 2].trim_start_matches('~').trim_start();
+            let kind = match inner.bytes().next() {
+                Some(b'#' | b'^') if inner.len() > 1 => Kind::Block,
+                Some(b'/') => Kind::Close,
+                _ => Kind::Other,
+            };
+            (kind, end)
+        };
+        let inner = source[at..end]
+            .trim_matches(['{', '}', '~'])
+            .trim_start_matches(['#', '^', '/', '*'])
+            .trim_start();
+        let name_len = inner
+            .find(|c: char| c.is_whitespace() || matches!(c, '}' | '~'))
+            .unwrap_or(inner.len());
+        Some(Self {
+            kind,
+            name: &inner[..name_len],
+            end,
+        })
+    }
+}
+
+/// 块 `open` 对应的结束标签的起止位置，跳过同名的嵌套块
+fn block_end(source: &str, open: &Mustache<'_>) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    let mut i = open.end;
+    while let Some(found) = source[i..].find("{{") {
+        let at = i + found;
+        let Some(tag) = Mustache::parse(source, at) else {
+            i = at + 2;
+            continue;
+        };
+        i = tag.end;
+        if tag.name != open.name {
+            continue;
+        }
+        match tag.kind {
+            _ if open.kind == Kind::Raw && tag.kind != Kind::Close => {}
+            Kind::Block => depth += 1,
+            Kind::Close if depth == 0 => return Some((at, tag.end)),
+            Kind::Close => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, TemplateTags, estimate};
+
+    fn estimate_handlebars(markdown: &str, handlebars: Handlebars) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(handlebars)
+            .estimate(markdown)
+    }
+
+    const TEMPLATE: &str = r#"# {{title}}
+
+{{!-- 注释 {{不计入}} --}}
+亲爱的 {{{user.name}}}，{{! 称呼 }}欢迎回来。
+
+{{#if items}}
+{{#each items}}
+- {{name}} 已发货
+{{/each}}
+{{else}}
+暂无订单
+{{/if}}
+
+{{> footer}}
+
+{{{{raw}}}}
+模板写作 {{变量}}
+{{{{/raw}}}}
+
+```
+{{#each items}}
+```
+"#;
+
+    #[test]
+    fn test_handlebars_matches_markdown() {
+        let markdown = r#"#
+
+亲爱的 ，欢迎回来。
+
+- 已发货
+
+暂无订单
+
+模板写作 {{变量}}
+
+```
+{{#each items}}
+```
+"#;
+        assert_eq!(
+            estimate_handlebars(TEMPLATE, Handlebars::default()),
+            estimate(markdown)
+        );
+    }
+
+    #[test]
+    fn test_handlebars_skip_block_bodies() {
+        let markdown =
+            "#\n\n亲爱的 ，欢迎回来。\n\n模板写作 {{变量}}\n\n```\n{{#each items}}\n```\n";
+        let handlebars = Handlebars::default().count_block_bodies(false);
+        assert_eq!(
+            estimate_handlebars(TEMPLATE, handlebars),
+            estimate(markdown)
+        );
+
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(handlebars)
+            .visitor(TemplateTags::new().tag("each", 20.0))
+            .estimate("{{#each posts}}\n{{#each tags}}{{this}}{{/each}}\n{{/each}}\n");
+        assert_eq!(read_time.custom_elements["each"], 1);
+        assert_eq!(read_time.extra_seconds, 20.0);
+    }
+
+    #[test]
+    fn test_mustache_parse() {
+        let tag = Mustache::parse("{{~#each items~}}", 0).unwrap();
+        assert_eq!(tag.kind, Kind::Block);
+        assert_eq!(tag.name, "each");
+        let tag = Mustache::parse("{{/each}}", 0).unwrap();
+        assert_eq!(tag.kind, Kind::Close);
+        assert_eq!(tag.name, "each");
+        let tag = Mustache::parse("{{{{raw}}}}", 0).unwrap();
+        assert_eq!(tag.kind, Kind::Raw);
+        assert_eq!(tag.name, "raw");
+        let tag = Mustache::parse("{{{{/raw}}}}", 0).unwrap();
+        assert_eq!(tag.kind, Kind::Close);
+        assert!(Mustache::parse("{ {x}}", 0).is_none());
+    }
+}
@@ -0,0 +1,566 @@
+use super::markup::{
+    Element, Sink, attribute, closing_tag, element, end_tag, entity, image, start_tag,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Tag, TagEnd};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 内容不显示的元素，连同内容一起忽略
+const HIDDEN_ELEMENTS: &[&str] = &[
+    "head", "math", "noscript", "script", "style", "svg", "template", "title",
+];
+
+/// 只作为块边界、本身不产生事件的容器元素
+const CONTAINER_ELEMENTS: &[&str] = &[
+    "article",
+    "aside",
+    "body",
+    "caption",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "header",
+    "html",
+    "main",
+    "nav",
+    "section",
+];
+
+/// HTML 后端，需要启用 `html` 特性
+///
+/// 按块级元素统计正文，计数规则与 Markdown 相同：`<img>` 计为图片，`<pre>` 计为代码块，
+/// `<code>` 按行内代码统计，`<head>`、`<script>`、`<style>` 等不显示的内容不计入字数。
+/// 也可以处理 EPUB 中的 XHTML 文档。
+///
+/// 响应式图片按读者看到的一张图片计数：`<figure>` 中的图片最多计为一张，`<figcaption>` 按正文计数；
+/// `<picture>` 中的 `<source>` 和 `<img>` 合计一张，只有 `<source>` 时也计为一张；
+/// `srcset` 中的多个候选地址不会重复计数，没有 `src` 时使用第一个候选地址。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Html, ReadSpeed};
+///
+/// let html = "<h1>标题</h1><p>这是<em>内容</em></p><img src=\"a.png\" alt=\"图片\">";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Html).estimate(html);
+/// assert_eq!(read_time.word_count, 6);
+/// assert_eq!(read_time.image_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Html;
+
+impl MarkdownBackend for Html {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let mut parser = Parser {
+            sink,
+            source: markdown,
+            open: Vec::new(),
+            paragraph: None,
+            inline: 0,
+            figures: Vec::new(),
+            picture: None,
+        };
+        parser.run();
+    }
+}
+
+/// 去掉标签并解码字符引用，用于代码内容
+fn strip_tags(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(['<', '&']) {
+        stripped.push_str(&rest[..at]);
+        rest = &rest[at..];
+        if let Some((_, len)) = element(rest) {
+            rest = &rest[len..];
+        } else if let Some((c, len)) = entity(rest) {
+            stripped.push(c);
+            rest = &rest[len..];
+        } else {
+            stripped.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// `srcset` 中的第一个候选地址
+fn first_candidate(srcset: &str) -> Option<&str> {
+    srcset
+        .split(',')
+        .find_map(|candidate| candidate.split_whitespace().next())
+}
+
+/// 正在处理的 `<picture>` 元素
+struct Picture<'a> {
+    start: usize,
+    /// 是否已输出图片
+    shown: bool,
+    /// 第一个 `<source>` 的地址，没有 `<img>` 时使用
+    fallback: Option<&'a str>,
+}
+
+fn heading_level(name: &str) -> Option<HeadingLevel> {
+    match name.as_bytes() {
+        [b'h' | b'H', level @ b'1'..=b'6'] => {
+            HeadingLevel::try_from(usize::from(level - b'0')).ok()
+        }
+        _ => None,
+    }
+}
+
+struct Parser<'a, 's> {
+    sink: Sink<'s, 'a>,
+    source: &'a str,
+    /// 已输出开始事件、尚未结束的元素
+    open: Vec<(&'a str, Tag<'a>, usize)>,
+    /// 当前段落的起始位置，`<p>` 之外的文本会自动开始段落
+    paragraph: Option<usize>,
+    /// 大于 0 时文本位于标题或表格单元格中，不自动开始段落
+    inline: usize,
+    /// 尚未结束的 `<figure>` 元素中是否已输出图片
+    figures: Vec<bool>,
+    picture: Option<Picture<'a>>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn open_paragraph(&mut self, at: usize) {
+        if self.inline == 0 && self.paragraph.is_none() {
+            self.paragraph = Some(at);
+            start_tag(self.sink, &Tag::Paragraph, at..at);
+        }
+    }
+
+    fn close_paragraph(&mut self, at: usize) {
+        if let Some(start) = self.paragraph.take() {
+            end_tag(self.sink, &Tag::Paragraph, start..at);
+        }
+    }
+
+    /// 输出图片，同一个 `<figure>` 或 `<picture>` 中只输出第一张
+    fn image(&mut self, src: &'a str, alt: &'a str, range: Range<usize>) {
+        if let Some(shown) = self.figures.last_mut() {
+            if *shown {
+                return;
+            }
+            *shown = true;
+        }
+        if let Some(picture) = self.picture.as_mut() {
+            if picture.shown {
+                return;
+            }
+            picture.shown = true;
+        }
+        self.open_paragraph(range.start);
+        image(self.sink, src, alt, range);
+    }
+
+    fn text(&mut self, text: &'a str, offset: usize) {
+        if text.is_empty() {
+            return;
+        }
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() && self.paragraph.is_none() && self.inline == 0 {
+            return;
+        }
+        self.open_paragraph(offset + text.len() - trimmed.len());
+        (self.sink)(
+            Event::Text(CowStr::Borrowed(text)),
+            offset..offset + text.len(),
+        );
+    }
+
+    fn run(&mut self) {
+        let source = self.source;
+        let mut plain = 0;
+        let mut i = 0;
+        while let Some(found) = source[i..].find(['<', '&']) {
+            let at = i + found;
+            let rest = &source[at..];
+            if rest.starts_with('&') {
+                match entity(rest) {
+                    Some((c, len)) => {
+                        self.text(&source[plain..at], plain);
+                        if !c.is_whitespace() || self.paragraph.is_some() {
+                            self.open_paragraph(at);
+                            (self.sink)(Event::Text(CowStr::from(c)), at..at + len);
+                        }
+                        i = at + len;
+                        plain = i;
+                    }
+                    None => i = at + 1,
+                }
+                continue;
+            }
+            let skip = [
+                ("<!--", "-->"),
+                ("<![CDATA[", "]]>"),
+                ("<!", ">"),
+                ("<?", ">"),
+            ]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open));
+            if let Some((open, close)) = skip {
+                self.text(&source[plain..at], plain);
+                let content = &rest[open.len()..];
+                let end = content
+                    .find(close)
+                    .map_or(rest.len(), |end| open.len() + end + close.len());
+                if *open == "<![CDATA[" {
+                    self.text(&content[..end - open.len() - close.len()], at + open.len());
+                }
+                i = at + end;
+                plain = i;
+                continue;
+            }
+            let Some((element, len)) = element(rest) else {
+                i = at + 1;
+                continue;
+            };
+            self.text(&source[plain..at], plain);
+            i = self.element(&element, at, at + len);
+            plain = i;
+        }
+        self.text(&source[plain..], plain);
+        self.close_paragraph(source.len());
+        while let Some((_, tag, start)) = self.open.pop() {
+            self.end(&tag, start..source.len());
+        }
+    }
+
+    /// 处理 `start..end` 处的标签，返回继续解析的位置
+    fn element(&mut self, element: &Element<'a>, start: usize, end: usize) -> usize {
+        let name = element.name;
+        let is = |names: &[&str]| names.iter().any(|n| n.eq_ignore_ascii_case(name));
+
+        if element.closing {
+            if name.eq_ignore_ascii_case("figure") {
+                self.figures.pop();
+            } else if name.eq_ignore_ascii_case("picture")
+                && let Some(picture) = self.picture.take()
+                && !picture.shown
+                && let Some(src) = picture.fallback
+            {
+                self.image(src, "", picture.start..end);
+            }
+            if let Some(index) = self
+                .open
+                .iter()
+                .rposition(|(open, ..)| open.eq_ignore_ascii_case(name))
+            {
+                self.close_paragraph(start);
+                while self.open.len() > index {
+                    if let Some((_, tag, open_start)) = self.open.pop() {
+                        self.end(&tag, open_start..end);
+                    }
+                }
+            } else if name.eq_ignore_ascii_case("p") || is(CONTAINER_ELEMENTS) {
+                self.close_paragraph(start);
+            }
+            return end;
+        }
+
+        if is(HIDDEN_ELEMENTS) {
+            if element.self_closing {
+                return end;
+            }
+            return closing_tag(&self.source[end..], name)
+                .map_or(self.source.len(), |(at, len)| end + at + len);
+        }
+
+        if is(&["pre"]) {
+            return self.code_block(element, start, end);
+        }
+        if is(&["code", "kbd", "samp"]) && !element.self_closing {
+            let (content_end, after) = closing_tag(&self.source[end..], name)
+                .map_or((self.source.len(), self.source.len()), |(at, len)| {
+                    (end + at, end + at + len)
+                });
+            let code = &self.source[end..content_end];
+            let code = if code.contains(['<', '&']) {
+                CowStr::from(strip_tags(code))
+            } else {
+                CowStr::Borrowed(code)
+            };
+            self.open_paragraph(start);
+            (self.sink)(Event::Code(code), start..after);
+            return after;
+        }
+        if is(&["img", "image"]) {
+            let src = attribute(element.attributes, "src")
+                .or_else(|| attribute(element.attributes, "href"))
+                .or_else(|| attribute(element.attributes, "srcset").and_then(first_candidate))
+                .unwrap_or_default();
+            let alt = attribute(element.attributes, "alt").unwrap_or_default();
+            self.image(src, alt, start..end);
+            return end;
+        }
+        if is(&["picture"]) {
+            if !element.self_closing {
+                self.picture = Some(Picture {
+                    start,
+                    shown: false,
+                    fallback: None,
+                });
+            }
+            return end;
+        }
+        if is(&["source"]) {
+            if let Some(picture) = self.picture.as_mut()
+                && picture.fallback.is_none()
+            {
+                picture.fallback =
+                    attribute(element.attributes, "srcset").and_then(first_candidate);
+            }
+            return end;
+        }
+        if is(&["br"]) {
+            (self.sink)(Event::HardBreak, start..end);
+            return end;
+        }
+        if is(&["hr"]) {
+            self.close_paragraph(start);
+            (self.sink)(Event::Rule, start..end);
+            return end;
+        }
+
+        let tag = if name.eq_ignore_ascii_case("p") {
+            self.close_paragraph(start);
+            if !element.self_closing {
+                self.paragraph = Some(start);
+                start_tag(self.sink, &Tag::Paragraph, start..end);
+            }
+            return end;
+        } else if let Some(level) = heading_level(name) {
+            Tag::Heading {
+                level,
+                id: attribute(element.attributes, "id").map(CowStr::Borrowed),
+                classes: Vec::new(),
+                attrs: Vec::new(),
+            }
+        } else if is(&["blockquote"]) {
+            Tag::BlockQuote
+        } else if is(&["ul"]) {
+            Tag::List(None)
+        } else if is(&["ol"]) {
+            Tag::List(Some(
+                attribute(element.attributes, "start")
+                    .and_then(|start| start.parse().ok())
+                    .unwrap_or(1),
+            ))
+        } else if is(&["li"]) {
+            Tag::Item
+        } else if is(&["table"]) {
+            Tag::Table(Vec::new())
+        } else if is(&["tr"]) {
+            Tag::TableRow
+        } else if is(&["td", "th"]) {
+            Tag::TableCell
+        } else {
+            if is(CONTAINER_ELEMENTS) {
+                self.close_paragraph(start);
+            }
+            if name.eq_ignore_ascii_case("figure") && !element.self_closing {
+                self.figures.push(false);
+            }
+            return end;
+        };
+
+        if !matches!(tag, Tag::TableCell) {
+            self.close_paragraph(start);
+        }
+        if matches!(tag, Tag::Heading { .. } | Tag::TableCell) {
+            self.inline += 1;
+        }
+        start_tag(self.sink, &tag, start..end);
+        if element.self_closing {
+            self.end(&tag, start..end);
+        } else {
+            self.open.push((name, tag, start));
+        }
+        end
+    }
+
+    fn end(&mut self, tag: &Tag<'a>, range: Range<usize>) {
+        if matches!(tag, Tag::Heading { .. } | Tag::TableCell) {
+            self.inline -= 1;
+        }
+        self.close_paragraph(range.end);
+        end_tag(self.sink, tag, range);
+    }
+
+    /// `<pre>` 元素，语言取自内部 `<code>` 的 `language-` 类名
+    fn code_block(&mut self, element: &Element<'a>, start: usize, end: usize) -> usize {
+        let (content_end, after) = if element.self_closing {
+            (end, end)
+        } else {
+            closing_tag(&self.source[end..], "pre")
+                .map_or((self.source.len(), self.source.len()), |(at, len)| {
+                    (end + at, end + at + len)
+                })
+        };
+        let content = &self.source[end..content_end];
+        let lang = element_at_start(content)
+            .and_then(|code| attribute(code.attributes, "class"))
+            .and_then(|class| {
+                class
+                    .split_ascii_whitespace()
+                    .find_map(|class| class.strip_prefix("language-"))
+            })
+            .unwrap_or_default();
+        self.close_paragraph(start);
+        let range = start..after;
+        (self.sink)(
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::Borrowed(
+                lang,
+            )))),
+            range.clone(),
+        );
+        let code = strip_tags(content);
+        let code = code.trim_matches('\n');
+        if !code.is_empty() {
+            (self.sink)(
+                Event::Text(CowStr::from(String::from(code))),
+                end..content_end,
+            );
+        }
+        (self.sink)(Event::End(TagEnd::CodeBlock), range);
+        after
+    }
+}
+
+/// 去掉开头空白后位于开头的开始标签
+fn element_at_start(text: &str) -> Option<Element<'_>> {
+    element(text.trim_start()).map(|(element, _)| element)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_html(html: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Html)
+            .estimate(html)
+    }
+
+    #[test]
+    fn test_html_matches_markdown() {
+        let html = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head><title>不计入</title><style>p { color: red; }</style></head>
+<body>
+  <!-- 注释 -->
+  <section>
+    <h2 id="intro">简介</h2>
+    <p>这是<strong>重要</strong>的<a href="https://example.com">链接</a>&amp;说明。</p>
+    <p>使用 <code>cargo &lt;cmd&gt;</code> 命令</p>
+    <ul>
+      <li>第一项</li>
+      <li>第二项<ol><li>子项</li></ol></li>
+    </ul>
+    <figure><img src="a.png" alt="替代文本"/><figcaption>图片说明</figcaption></figure>
+    <pre><code class="language-rust">fn main() {}</code></pre>
+    <blockquote><p>引用</p></blockquote>
+    <script>let ignored = "不计入";</script>
+    直接位于容器中的文本
+  </section>
+</body>
+</html>"#;
+        let markdown = "\
+## 简介
+
+这是**重要**的[链接](https://example.com)&说明。
+
+使用 `cargo <cmd>` 命令
+
+- 第一项
+- 第二项
+  1. 子项
+
+![替代文本](a.png)
+
+图片说明
+
+```rust
+fn main() {}
+```
+
+> 引用
+
+直接位于容器中的文本
+";
+        assert_eq!(estimate_html(html), estimate(markdown));
+    }
+
+    #[test]
+    fn test_responsive_images() {
+        let html = r#"
+<figure>
+  <picture>
+    <source srcset="a.avif 1x, a@2x.avif 2x" type="image/avif">
+    <source srcset="a.webp">
+    <img src="a.png" srcset="a.png 1x, a@2x.png 2x" alt="架构图">
+  </picture>
+  <img src="a-dark.png" alt="深色">
+  <figcaption>系统架构</figcaption>
+</figure>
+<picture><source srcset="b.webp 480w, b-large.webp 1080w"></picture>
+<p><img srcset="c.png 1x, c@2x.png 2x"></p>
+<img src="d.png"><img src="e.png">
+"#;
+        let read_time = estimate_html(html);
+        assert_eq!(read_time.image_count, 5);
+        assert_eq!(read_time.word_count, 4);
+
+        let mut urls = Vec::new();
+        Html.parse(html, &mut |event| {
+            if let Event::Start(Tag::Image { dest_url, .. }) = event {
+                urls.push(dest_url.into_string());
+            }
+        });
+        assert_eq!(urls, ["a.png", "b.webp", "c.png", "d.png", "e.png"]);
+    }
+
+    #[test]
+    fn test_html_sections() {
+        let sections = Estimator::new(ReadSpeed::default())
+            .backend(Html)
+            .estimate_sections("<p>前言</p><h1>第一章</h1><p>内容</p><h2>第一节</h2>内容");
+        let headings: Vec<_> = sections.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(headings, ["", "第一章", "第一节"]);
+        assert_eq!(sections[2].read_time.word_count, 3 + 2);
+    }
+
+    #[test]
+    fn test_attribute() {
+        let attributes = r#" idref='ch1' id="x" linear=no xlink:href="a b.png" hidden"#;
+        assert_eq!(attribute(attributes, "id"), Some("x"));
+        assert_eq!(attribute(attributes, "idref"), Some("ch1"));
+        assert_eq!(attribute(attributes, "linear"), Some("no"));
+        assert_eq!(attribute(attributes, "href"), Some("a b.png"));
+        assert_eq!(attribute(attributes, "hidden"), Some(""));
+        assert_eq!(attribute(attributes, "src"), None);
+    }
+}
@@ -0,0 +1,252 @@
+use super::MarkdownBackend;
+use super::markup::attribute;
+use super::template::{Removals, Replacement, find_close, scan};
+use pulldown_cmark::Event;
+use std::ops::{ControlFlow, Range};
+
+/// 带 Hugo 短代码的 Markdown 后端，需要启用 `hugo` 特性
+///
+/// 去掉 `{{< … >}}` 和 `{{% … %}}` 短代码标签之后按 Markdown 统计，成对短代码之间的内容正常计入字数。
+/// 图片短代码（默认为 `figure`）按 `src` 参数计为图片，代码短代码（默认为 `highlight`）
+/// 连同内容计为代码块，其他短代码以 HTML 事件输出，可以配合 [`TemplateTags`](crate::TemplateTags)
+/// 按短代码名设置固定时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Hugo, ReadSpeed, TemplateTags};
+///
+/// let markdown = "{{< figure src=\"a.png\" >}}\n\n{{< youtube w7Ft2ymGmfc >}}\n\n{{% note %}}\n注意事项\n{{% /note %}}";
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(Hugo::default())
+///     .visitor(TemplateTags::new().tag("youtube", 120.0))
+///     .estimate(markdown);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.custom_elements["youtube"], 1);
+/// assert_eq!(read_time.word_count, 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Hugo {
+    images: Vec<String>,
+    code: Vec<String>,
+}
+
+impl Default for Hugo {
+    fn default() -> Self {
+        Self {
+            images: vec![String::from("figure")],
+            code: vec![String::from("highlight")],
+        }
+    }
+}
+
+impl Hugo {
+    /// 把短代码 `name` 计为图片，图片地址取自 `src` 参数
+    pub fn image_shortcode(mut self, name: impl Into<String>) -> Self {
+        self.images.push(name.into());
+        self
+    }
+
+    /// 把成对的短代码 `name` 连同内容计为代码块，第一个位置参数为语言
+    pub fn code_shortcode(mut self, name: impl Into<String>) -> Self {
+        self.code.push(name.into());
+        self
+    }
+}
+
+impl MarkdownBackend for Hugo {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let source = markdown;
+        let mut removals = Removals::default();
+        scan(
+            source,
+            &['{'],
+            &mut removals,
+            |_, _| false,
+            |removals, at| {
+                let shortcode = Shortcode::parse(source, at)?;
+                let name = shortcode.name;
+                if shortcode.closing || shortcode.comment {
+                    removals.tag(source, at..shortcode.end);
+                } else if self.images.iter().any(|image| image == name) {
+                    let url = attribute(shortcode.params, "src").unwrap_or_default();
+                    removals.replace(at..shortcode.end, Replacement::Image { url });
+                } else if self.code.iter().any(|code| code == name) && !shortcode.self_closing {
+                    let (close, end) = closing(source, shortcode.end, name)?;
+                    let lang = shortcode
+                        .params
+                        .split_whitespace()
+                        .next()
+                        .filter(|lang| !lang.contains('='))
+                        .unwrap_or_default();
+                    let body = shortcode.end..close;
+                    removals.replace(at..end, Replacement::CodeBlock { lang, body });
+                    return Some(end);
+                } else {
+                    removals.tag(source, at..shortcode.end);
+                }
+                Some(shortcode.end)
+            },
+        );
+        removals.parse(markdown, sink);
+    }
+}
+
+/// 源文本中的一个短代码标签
+struct Shortcode<'a> {
+    name: &'a str,
+    params: &'a str,
+    end: usize,
+    closing: bool,
+    self_closing: bool,
+
+    /// `{{</* … */>}}` 形式的注释，原样输出短代码而不执行
+    comment: bool,
+}
+
+impl<'a> Shortcode<'a> {
+    /// 解析 `at` 处的短代码标签
+    fn parse(source: &'a str, at: usize) -> Option<Self> {
+        let rest = &source[at..];
+        let (open, close) = if rest.starts_with("{{</*") {
+            ("{{</*", "*/>}}")
+        } else if rest.starts_with("{{%/*") {
+            ("{{%/*", "*/%}}")
+        } else if rest.starts_with("{{<") {
+            ("{{<", ">}}")
+        } else if rest.starts_with("{{%") {
+            ("{{%", "%}}")
+        } else {
+            return None;
+        };
+        let end = find_close(source, at + open.len(), close)?;
+        let inner = source[at + open.len()..end - close.len()].trim();
+        let (inner, closing) = match inner.strip_prefix('/') {
+            Some(inner) => (inner.trim_start(), true),
+            None => (inner, false),
+        };
+        let (inner, self_closing) = match inner.strip_suffix('/') {
+            Some(inner) => (inner.trim_end(), true),
+            None => (inner, false),
+        };
+        let name_len = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        Some(Self {
+            name: &inner[..name_len],
+            params: &inner[name_len..],
+            end,
+            closing,
+            self_closing,
+            comment: open.ends_with('*'),
+        })
+    }
+}
+
+/// 从 `from` 开始查找短代码 `name` 的结束标签，返回其起止位置
+fn closing(source: &str, from: usize, name: &str) -> Option<(usize, usize)> {
+    let mut i = from;
+    while let Some(found) = source[i..].find("{{") {
+        let at = i + found;
+        match Shortcode::parse(source, at) {
+            Some(shortcode) if shortcode.closing && shortcode.name == name => {
+                return Some((at, shortcode.end));
+            }
+            Some(shortcode) => i = shortcode.end,
+            None => i = at + 2,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, TemplateTags, estimate};
+
+    fn estimate_hugo(markdown: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Hugo::default())
+            .estimate(markdown)
+    }
+
+    #[test]
+    fn test_hugo_matches_markdown() {
+        let hugo = r#"# 标题
+
+{{< figure src="/images/a.png" title="标题 >}} 中的引号" >}}
+
+{{% notice warning %}}
+这是**重要**内容。
+{{% /notice %}}
+
+段落中的 {{< ref "other.md" >}} 链接。
+
+{{< highlight go "linenos=table" >}}
+fmt.Println("hello")
+{{< /highlight >}}
+
+```
+{{< figure src="b.png" >}}
+```
+
+使用 `{{< youtube id >}}` 嵌入视频。
+"#;
+        let markdown = r#"# 标题
+
+![](/images/a.png)
+
+这是**重要**内容。
+
+段落中的  链接。
+
+```go
+fmt.Println("hello")
+```
+
+```
+{{< figure src="b.png" >}}
+```
+
+使用 `{{< youtube id >}}` 嵌入视频。
+"#;
+        assert_eq!(estimate_hugo(hugo), estimate(markdown));
+    }
+
+    #[test]
+    fn test_hugo_shortcode_weights() {
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(Hugo::default().image_shortcode("img"))
+            .visitor(TemplateTags::new().tag("youtube", 90.0).tag("vimeo", 60.0))
+            .estimate("{{< youtube a >}}\n\n文本 {{< img src=\"a.png\" >}} {{< vimeo b />}}\n");
+        assert_eq!(read_time.custom_elements["youtube"], 1);
+        assert_eq!(read_time.custom_elements["vimeo"], 1);
+        assert_eq!(read_time.extra_seconds, 150.0);
+        assert_eq!(read_time.image_count, 1);
+        assert_eq!(read_time.word_count, 2);
+    }
+
+    #[test]
+    fn test_shortcode_parse() {
+        let source = "{{< /note >}}{{</* figure src=\"a\" */>}}";
+        let closing = Shortcode::parse(source, 0).unwrap();
+        assert!(closing.closing);
+        assert_eq!(closing.name, "note");
+        let comment = Shortcode::parse(source, closing.end).unwrap();
+        assert!(comment.comment);
+        assert_eq!(comment.name, "figure");
+        assert_eq!(comment.end, source.len());
+        assert!(Shortcode::parse("{{ .Title }}", 0).is_none());
+    }
+}
@@ -0,0 +1,652 @@
+use super::markup::{Sink, end_tag, image, start_tag};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Tag, TagEnd};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 内容为公式的环境，计为语言为 `math` 的代码块
+const MATH_ENVIRONMENTS: &[&str] = &[
+    "align",
+    "align*",
+    "displaymath",
+    "eqnarray",
+    "eqnarray*",
+    "equation",
+    "equation*",
+    "gather",
+    "gather*",
+    "math",
+    "multline",
+    "multline*",
+];
+
+/// 内容原样输出的代码环境
+const CODE_ENVIRONMENTS: &[&str] = &["lstlisting", "minted", "verbatim", "Verbatim"];
+
+/// 按引用统计的环境
+const QUOTE_ENVIRONMENTS: &[&str] = &["abstract", "quotation", "quote", "verse"];
+
+/// 参数不属于正文的命令，连同参数一起忽略
+const IGNORED_COMMANDS: &[&str] = &[
+    "addtolength",
+    "autoref",
+    "bibliography",
+    "bibliographystyle",
+    "cite",
+    "citep",
+    "citet",
+    "Cref",
+    "cref",
+    "documentclass",
+    "eqref",
+    "graphicspath",
+    "hspace",
+    "hypersetup",
+    "include",
+    "input",
+    "label",
+    "newcommand",
+    "pageref",
+    "pagestyle",
+    "providecommand",
+    "ref",
+    "renewcommand",
+    "setcounter",
+    "setlength",
+    "thispagestyle",
+    "usepackage",
+    "vspace",
+];
+
+/// 章节命令及其标题级别
+const SECTIONS: &[(&str, usize)] = &[
+    ("part", 1),
+    ("chapter", 1),
+    ("section", 2),
+    ("subsection", 3),
+    ("subsubsection", 4),
+    ("paragraph", 5),
+    ("subparagraph", 6),
+];
+
+/// LaTeX 后端，需要启用 `latex` 特性
+///
+/// 去掉命令和导言区，只统计 `document` 环境中的正文，计数规则与 Markdown 相同：
+/// `\includegraphics` 计为图片，`figure` 的 `\caption` 计入字数，
+/// `lstlisting`、`verbatim`、`minted` 计为代码块，行内公式 `$…$` 按行内代码统计。
+/// `equation`、`align` 等公式环境以及 `$$…$$`、`\[…\]` 计为语言为 `math` 的代码块，
+/// 可以通过 [`EventVisitor`](crate::EventVisitor) 为公式设置单独的权重。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::pulldown_cmark::{CodeBlockKind, Event, Tag};
+/// use markdown_readtime::{Estimator, Latex, ReadSpeed, Visit};
+///
+/// let tex = r"\documentclass{article}
+/// \begin{document}
+/// \section{引言}
+/// 这是\textbf{正文}\cite{knuth}。
+/// \begin{equation}
+///   E = mc^2
+/// \end{equation}
+/// \end{document}";
+///
+/// // 每个公式按 15 秒计算，不计入代码块
+/// let mut estimator = Estimator::new(ReadSpeed::default())
+///     .backend(Latex)
+///     .visitor(|event: &Event<'_>, _: &_| match event {
+///         Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) if &**lang == "math" => {
+///             Visit::skip().extra_seconds(15.0).tag("equation")
+///         }
+///         _ => Visit::count(),
+///     });
+/// let read_time = estimator.estimate(tex);
+/// assert_eq!(read_time.word_count, 7);
+/// assert_eq!(read_time.code_block_count, 0);
+/// assert_eq!(read_time.custom_elements["equation"], 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Latex;
+
+impl MarkdownBackend for Latex {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        const BEGIN: &str = "\\begin{document}";
+        let (start, end) = match markdown.find(BEGIN) {
+            Some(begin) => {
+                let start = begin + BEGIN.len();
+                let end = markdown[start..]
+                    .find("\\end{document}")
+                    .map_or(markdown.len(), |end| start + end);
+                (start, end)
+            }
+            None => (0, markdown.len()),
+        };
+        let mut parser = Parser {
+            sink,
+            paragraph: None,
+            inline: 0,
+        };
+        parser.run(&markdown[start..end], start);
+        parser.close_paragraph(end);
+    }
+}
+
+/// 命令名的字节长度：字母序列（可带 `*`），或单个非字母字符
+fn command_len(text: &str) -> usize {
+    let letters = text.bytes().take_while(|b| b.is_ascii_alphabetic()).count();
+    match letters {
+        0 => text.chars().next().map_or(0, char::len_utf8),
+        _ if text[letters..].starts_with('*') => letters + 1,
+        _ => letters,
+    }
+}
+
+/// 以 `open` 开头的平衡括号组，返回内容和整个组的字节长度
+fn group(text: &str, open: u8, close: u8) -> Option<(&str, usize)> {
+    if text.as_bytes().first() != Some(&open) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, b) in text.bytes().enumerate() {
+        match b {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            _ if b == open => depth += 1,
+            _ if b == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&text[1..i], i + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 开头连续的可选参数 `[…]` 的字节长度
+fn skip_options(text: &str) -> usize {
+    let mut len = 0;
+    while let Some((_, options)) = group(&text[len..], b'[', b']') {
+        len += options;
+    }
+    len
+}
+
+/// 环境 `name` 的内容和 `\end{name}` 之后的位置，`nested` 为真时同名环境可以嵌套
+fn environment<'t>(text: &'t str, name: &str, nested: bool) -> (&'t str, usize) {
+    let mut depth = 1;
+    let mut i = 0;
+    while let Some(found) = text[i..].find('\\') {
+        let at = i + found;
+        let rest = &text[at + 1..];
+        let matches = |keyword: &str| {
+            rest.strip_prefix(keyword)
+                .and_then(|rest| group(rest, b'{', b'}'))
+                .filter(|(env, _)| *env == name)
+                .map(|(_, len)| keyword.len() + len + 1)
+        };
+        if let Some(len) = matches("end") {
+            depth -= 1;
+            if depth == 0 || !nested {
+                return (&text[..at], at + len);
+            }
+            i = at + len;
+        } else if let Some(len) = nested.then(|| matches("begin")).flatten() {
+            depth += 1;
+            i = at + len;
+        } else {
+            i = at + 1;
+        }
+    }
+    (text, text.len())
+}
+
+/// 列表中各 `\item` 的起始位置，不包括嵌套环境中的项
+fn items(text: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while let Some(found) = text[i..].find('\\') {
+        let at = i + found;
+        let name_len = command_len(&text[at + 1..]);
+        let name = &text[at + 1..at + 1 + name_len];
+        match name {
+            "begin" => depth += 1,
+            "end" => depth = depth.saturating_sub(1),
+            "item" if depth == 0 => positions.push(at),
+            _ => {}
+        }
+        i = at + 1 + name_len.max(1);
+    }
+    positions
+}
+
+struct Parser<'a, 's> {
+    sink: Sink<'s, 'a>,
+    /// 当前段落的起始位置
+    paragraph: Option<usize>,
+    /// 大于 0 时文本位于标题等行内上下文中，不自动开始段落
+    inline: usize,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn open_paragraph(&mut self, at: usize) {
+        if self.inline == 0 && self.paragraph.is_none() {
+            self.paragraph = Some(at);
+            start_tag(self.sink, &Tag::Paragraph, at..at);
+        }
+    }
+
+    fn close_paragraph(&mut self, at: usize) {
+        if let Some(start) = self.paragraph.take() {
+            end_tag(self.sink, &Tag::Paragraph, start..at);
+        }
+    }
+
+    fn text(&mut self, text: &'a str, offset: usize) {
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() {
+            if self.paragraph.is_some() || self.inline > 0 {
+                (self.sink)(
+                    Event::Text(CowStr::Borrowed(text)),
+                    offset..offset + text.len(),
+                );
+            }
+            return;
+        }
+        self.open_paragraph(offset + text.len() - trimmed.len());
+        (self.sink)(
+            Event::Text(CowStr::Borrowed(text)),
+            offset..offset + text.len(),
+        );
+    }
+
+    fn code_block(&mut self, lang: &'a str, content: &'a str, offset: usize) {
+        let range = offset..offset + content.len();
+        self.close_paragraph(offset);
+        (self.sink)(
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::Borrowed(
+                lang,
+            )))),
+            range.clone(),
+        );
+        let trimmed = content.trim_matches('\n');
+        if !trimmed.is_empty() {
+            let start = offset + content.len() - content.trim_start_matches('\n').len();
+            (self.sink)(
+                Event::Text(CowStr::Borrowed(trimmed)),
+                start..start + trimmed.len(),
+            );
+        }
+        (self.sink)(Event::End(TagEnd::CodeBlock), range);
+    }
+
+    /// 在行内上下文中处理 `text`，用于标题和图注
+    fn inline_run(&mut self, text: &'a str, offset: usize) {
+        self.inline += 1;
+        self.run(text, offset);
+        self.inline -= 1;
+    }
+
+    fn run(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let special = match bytes[i] {
+                b'\\' | b'{' | b'}' | b'$' | b'%' | b'~' | b'&' => true,
+                b'\n' => self.inline == 0 && is_blank_line(&source[i + 1..]),
+                _ => false,
+            };
+            if !special {
+                i += 1;
+                continue;
+            }
+            self.text(&source[plain..i], offset + plain);
+            i = match bytes[i] {
+                b'\\' => self.command(source, i, offset),
+                b'$' => self.math(source, i, offset),
+                b'%' => source[i..].find('\n').map_or(source.len(), |end| i + end),
+                b'\n' => {
+                    self.close_paragraph(offset + i);
+                    i + 1
+                }
+                _ => i + 1,
+            };
+            plain = i;
+        }
+        self.text(&source[plain..], offset + plain);
+    }
+
+    /// `$…$` 行内公式和 `$$…$$` 公式块
+    fn math(&mut self, source: &'a str, i: usize, offset: usize) -> usize {
+        let display = source[i..].starts_with("$$");
+        let delimiter = if display { "$$" } else { "$" };
+        let start = i + delimiter.len();
+        let Some(len) = source[start..].find(delimiter) else {
+            return start;
+        };
+        let formula = &source[start..start + len];
+        if display {
+            self.code_block("math", formula, offset + start);
+        } else {
+            self.open_paragraph(offset + i);
+            (self.sink)(
+                Event::Code(CowStr::Borrowed(formula)),
+                offset + start..offset + start + len,
+            );
+        }
+        start + len + delimiter.len()
+    }
+
+    /// 处理位于 `i` 的命令，返回命令及其被消耗的参数之后的位置
+    fn command(&mut self, source: &'a str, i: usize, offset: usize) -> usize {
+        let name_start = i + 1;
+        let name = &source[name_start..name_start + command_len(&source[name_start..])];
+        let after = name_start + name.len();
+        match name {
+            "" => after,
+            "\\" => {
+                (self.sink)(Event::HardBreak, offset + i..offset + after);
+                after
+            }
+            "%" | "&" | "_" | "#" | "$" | "{" | "}" => {
+                self.text(name, offset + name_start);
+                after
+            }
+            "[" => {
+                let (formula, len) = environment_by(&source[after..], "\\]");
+                self.code_block("math", formula, offset + after);
+                after + len
+            }
+            "(" => {
+                let (formula, len) = environment_by(&source[after..], "\\)");
+                self.open_paragraph(offset + i);
+                (self.sink)(
+                    Event::Code(CowStr::Borrowed(formula)),
+                    offset + after..offset + after + formula.len(),
+                );
+                after + len
+            }
+            "begin" => self.begin(source, after, offset),
+            "end" => after + group(&source[after..], b'{', b'}').map_or(0, |(_, len)| len),
+            "includegraphics" => {
+                let options = skip_options(&source[after..]);
+                let Some((target, len)) = group(&source[after + options..], b'{', b'}') else {
+                    return after;
+                };
+                let end = after + options + len;
+                self.open_paragraph(offset + i);
+                image(self.sink, target, "", offset + i..offset + end);
+                end
+            }
+            "href" => {
+                let Some((_, url)) = group(&source[after..], b'{', b'}') else {
+                    return after;
+                };
+                after + url
+            }
+            _ if IGNORED_COMMANDS.contains(&name.trim_end_matches('*')) => {
+                let mut end = after;
+                loop {
+                    let rest = &source[end..];
+                    match group(rest, b'{', b'}').or_else(|| group(rest, b'[', b']')) {
+                        Some((_, len)) => end += len,
+                        None => break end,
+                    }
+                }
+            }
+            _ => {
+                let section = SECTIONS
+                    .iter()
+                    .find(|(section, _)| *section == name.trim_end_matches('*'));
+                match section {
+                    Some(&(_, level)) => self.heading(source, i, after, level, offset),
+                    None => after + skip_options(&source[after..]),
+                }
+            }
+        }
+    }
+
+    fn heading(
+        &mut self,
+        source: &'a str,
+        i: usize,
+        after: usize,
+        level: usize,
+        offset: usize,
+    ) -> usize {
+        let options = skip_options(&source[after..]);
+        let Some((title, len)) = group(&source[after + options..], b'{', b'}') else {
+            return after;
+        };
+        let end = after + options + len;
+        let range = offset + i..offset + end;
+        let tag = Tag::Heading {
+            level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        };
+        self.close_paragraph(offset + i);
+        start_tag(self.sink, &tag, range.clone());
+        self.inline_run(title, offset + after + options + 1);
+        end_tag(self.sink, &tag, range);
+        end
+    }
+
+    /// `\begin{name}`，返回环境结束之后的位置
+    fn begin(&mut self, source: &'a str, after: usize, offset: usize) -> usize {
+        let Some((name, len)) = group(&source[after..], b'{', b'}') else {
+            return after;
+        };
+        let body_start = after + len;
+        let rest = &source[body_start..];
+        let base = name.trim_end_matches('*');
+
+        if CODE_ENVIRONMENTS.contains(&name) || MATH_ENVIRONMENTS.contains(&name) {
+            let (content, end) = environment(rest, name, false);
+            let lang = if MATH_ENVIRONMENTS.contains(&name) {
+                "math"
+            } else if name == "minted" {
+                group(&rest[skip_options(rest)..], b'{', b'}').map_or("", |(lang, _)| lang)
+            } else {
+                ""
+            };
+            // 去掉 `[language=…]` 和 minted 的语言参数
+            let mut skip = skip_options(content);
+            if name == "minted" {
+                skip += group(&content[skip..], b'{', b'}').map_or(0, |(_, len)| len);
+            }
+            self.code_block(lang, &content[skip..], offset + body_start + skip);
+            return body_start + end;
+        }
+
+        let (content, end) = environment(rest, name, true);
+        let content_offset = offset + body_start;
+        let range = offset + after..content_offset + end;
+        match base {
+            "figure" | "wrapfigure" => {
+                self.close_paragraph(range.start);
+                self.figure(content, content_offset);
+            }
+            "itemize" | "enumerate" | "description" => {
+                self.close_paragraph(range.start);
+                let tag = Tag::List((base == "enumerate").then_some(1));
+                start_tag(self.sink, &tag, range.clone());
+                let positions = items(content);
+                for (index, &start) in positions.iter().enumerate() {
+                    let item_end = positions.get(index + 1).copied().unwrap_or(content.len());
+                    let item_range = content_offset + start..content_offset + item_end;
+                    start_tag(self.sink, &Tag::Item, item_range.clone());
+                    let mut body = start + "\\item".len();
+                    // description 列表的条目名计入正文
+                    if let Some((term, len)) = group(&content[body..], b'[', b']') {
+                        self.run(term, content_offset + body + 1);
+                        body += len;
+                    }
+                    self.run(&content[body..item_end], content_offset + body);
+                    self.close_paragraph(item_range.end);
+                    end_tag(self.sink, &Tag::Item, item_range);
+                }
+                end_tag(self.sink, &tag, range);
+            }
+            _ if QUOTE_ENVIRONMENTS.contains(&base) => {
+                self.close_paragraph(range.start);
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                self.run(content, content_offset);
+                self.close_paragraph(range.end);
+                end_tag(self.sink, &Tag::BlockQuote, range);
+            }
+            "comment" => {}
+            _ => {
+                self.run(content, content_offset);
+            }
+        }
+        body_start + end
+    }
+
+    /// 图片环境：图片计为图片，图注作为段落，其余内容忽略
+    fn figure(&mut self, content: &'a str, offset: usize) {
+        let mut i = 0;
+        while let Some(found) = content[i..].find('\\') {
+            let at = i + found;
+            let name_len = command_len(&content[at + 1..]);
+            let name = &content[at + 1..at + 1 + name_len];
+            let after = at + 1 + name_len;
+            i = match name {
+                "includegraphics" => {
+                    let end = self.command(content, at, offset);
+                    self.close_paragraph(offset + end);
+                    end
+                }
+                "caption" => {
+                    let options = skip_options(&content[after..]);
+                    match group(&content[after + options..], b'{', b'}') {
+                        Some((caption, len)) => {
+                            let caption_offset = offset + after + options + 1;
+                            self.run(caption, caption_offset);
+                            self.close_paragraph(caption_offset + caption.len());
+                            after + options + len
+                        }
+                        None => after,
+                    }
+                }
+                _ => after,
+            };
+        }
+    }
+}
+
+/// 查找结束标记 `close`，返回之前的内容和包括标记在内的长度
+fn environment_by<'t>(text: &'t str, close: &str) -> (&'t str, usize) {
+    match text.find(close) {
+        Some(end) => (&text[..end], end + close.len()),
+        None => (text, text.len()),
+    }
+}
+
+/// 从行首开始到下一个换行符之前是否只有空白
+fn is_blank_line(text: &str) -> bool {
+    let line = text.split('\n').next().unwrap_or_default();
+    line.trim().is_empty() && text.contains('\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_tex(tex: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Latex)
+            .estimate(tex)
+    }
+
+    #[test]
+    fn test_latex_matches_markdown() {
+        let tex = r"\documentclass{article}
+\usepackage{graphicx}
+\title{不计入}
+\begin{document}
+\maketitle
+\section*{简介}\label{sec:intro}
+这是\textbf{重要}的\emph{内容}，参见\href{https://example.com}{链接}和图~\ref{fig:a}。
+% 注释
+
+公式 $x^2$ 很简单\footnote{脚注}。
+
+\begin{itemize}
+  \item 第一项
+  \item 第二项
+  \begin{enumerate}
+    \item 子项
+  \end{enumerate}
+\end{itemize}
+
+\begin{figure}[h]
+  \centering
+  \includegraphics[width=0.5\textwidth]{a.png}
+  \caption{图片说明}
+  \label{fig:a}
+\end{figure}
+
+\begin{lstlisting}[language=Rust]
+fn main() {}
+\end{lstlisting}
+
+\begin{quote}
+引用
+\end{quote}
+\end{document}
+之后的内容不计入";
+        let markdown = "\
+## 简介
+
+这是**重要**的*内容*，参见[链接](https://example.com)和图 。
+
+公式 `x^2` 很简单脚注。
+
+- 第一项
+- 第二项
+  1. 子项
+
+![](a.png)
+
+图片说明
+
+```
+fn main() {}
+```
+
+> 引用
+";
+        assert_eq!(estimate_tex(tex), estimate(markdown));
+    }
+
+    #[test]
+    fn test_latex_math() {
+        let read_time = estimate_tex(
+            "前文\n\\[ a + b \\]\n$$c$$\n\\begin{align*}\nx &= 1\n\\end{align*}\n后文 50\\%",
+        );
+        assert_eq!(read_time.code_block_count, 3);
+        assert_eq!(read_time.word_count, 7);
+        assert_eq!(estimate_tex("\\(x\\) 与 \\verb").word_count, 2);
+    }
+}
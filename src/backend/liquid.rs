@@ -0,0 +1,243 @@
+use super::MarkdownBackend;
+use super::template::{Removals, Replacement, find_close, scan};
+use pulldown_cmark::Event;
+use std::ops::{ControlFlow, Range};
+
+/// 带 Liquid 模板语法的 Markdown 后端（Jekyll），需要启用 `liquid` 特性
+///
+/// 去掉 `{% … %}` 标签和 `{{ … }}` 输出之后按 Markdown 统计，成对标签之间的内容正常计入字数，
+/// `{% comment %}` 中的内容不计入字数，`{% raw %}` 中的内容原样计入。
+/// 图片标签（默认为 `img`）计为图片，代码标签（默认为 `highlight`）连同内容计为代码块，
+/// 其他标签和输出以 HTML 事件输出，可以配合 [`TemplateTags`](crate::TemplateTags) 按标签名设置固定时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Liquid, ReadSpeed, TemplateTags};
+///
+/// let markdown = "欢迎 {{ page.author }}\n\n{% include video.html id=\"a\" %}\n\n{% highlight ruby %}\nputs 1\n{% endhighlight %}";
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(Liquid::default())
+///     .visitor(TemplateTags::new().tag("include", 30.0))
+///     .estimate(markdown);
+/// assert_eq!(read_time.word_count, 2);
+/// assert_eq!(read_time.code_block_count, 1);
+/// assert_eq!(read_time.extra_seconds, 30.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Liquid {
+    images: Vec<String>,
+    code: Vec<String>,
+}
+
+impl Default for Liquid {
+    fn default() -> Self {
+        Self {
+            images: vec![String::from("img")],
+            code: vec![String::from("highlight")],
+        }
+    }
+}
+
+impl Liquid {
+    /// 把标签 `name` 计为图片，图片地址取自第一个参数
+    pub fn image_tag(mut self, name: impl Into<String>) -> Self {
+        self.images.push(name.into());
+        self
+    }
+
+    /// 把成对的标签 `name` 连同内容计为代码块，第一个参数为语言
+    pub fn code_tag(mut self, name: impl Into<String>) -> Self {
+        self.code.push(name.into());
+        self
+    }
+}
+
+impl MarkdownBackend for Liquid {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let source = markdown;
+        let mut removals = Removals::default();
+        scan(
+            source,
+            &['{'],
+            &mut removals,
+            |_, _| false,
+            |removals, at| {
+                if source[at..].starts_with("{{") {
+                    let end = find_close(source, at + 2, "}}")?;
+                    removals.tag(source, at..end);
+                    return Some(end);
+                }
+                let tag = LiquidTag::parse(source, at)?;
+                let name = tag.name;
+                let argument = tag.params.split_whitespace().next().unwrap_or_default();
+                match name {
+                    "comment" => {
+                        let (_, end) = end_tag(source, tag.end, name).unwrap_or((0, source.len()));
+                        removals.remove(at..end);
+                        Some(end)
+                    }
+                    "raw" => {
+                        // raw 中的模板语法原样显示，只去掉 raw 标签本身
+                        removals.remove(at..tag.end);
+                        let (close, end) = end_tag(source, tag.end, name)?;
+                        removals.remove(close..end);
+                        Some(end)
+                    }
+                    _ if self.images.iter().any(|image| image == name) => {
+                        let url = argument.trim_matches(['"', '\'']);
+                        removals.replace(at..tag.end, Replacement::Image { url });
+                        Some(tag.end)
+                    }
+                    _ if self.code.iter().any(|code| code == name) => {
+                        let (close, end) = end_tag(source, tag.end, name)?;
+                        let body = tag.end..close;
+                        let lang = argument;
+                        removals.replace(at..end, Replacement::CodeBlock { lang, body });
+                        Some(end)
+                    }
+                    _ => {
+                        removals.tag(source, at..tag.end);
+                        Some(tag.end)
+                    }
+                }
+            },
+        );
+        removals.parse(markdown, sink);
+    }
+}
+
+/// 源文本中的一个 `{% … %}` 标签
+struct LiquidTag<'a> {
+    name: &'a str,
+    params: &'a str,
+    end: usize,
+}
+
+impl<'a> LiquidTag<'a> {
+    /// 解析 `at` 处的标签，`-` 空白控制符不属于标签名和参数
+    fn parse(source: &'a str, at: usize) -> Option<Self> {
+        if !source[at..].starts_with("{%") {
+            return None;
+        }
+        let end = find_close(source, at + 2, "%}")?;
+        let inner = source[at + 2..end - 2].trim_matches('-').trim();
+        let name_len = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        Some(Self {
+            name: &inner[..name_len],
+            params: &inner[name_len..],
+            end,
+        })
+    }
+}
+
+/// 从 `from` 开始查找标签 `name` 对应的 `end<name>` 标签，返回其起止位置
+fn end_tag(source: &str, from: usize, name: &str) -> Option<(usize, usize)> {
+    let close = format!("end{}", name);
+    let mut i = from;
+    while let Some(found) = source[i..].find("{%") {
+        let at = i + found;
+        match LiquidTag::parse(source, at) {
+            Some(tag) if tag.name == close => return Some((at, tag.end)),
+            Some(tag) => i = tag.end,
+            None => i = at + 2,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, TemplateTags, estimate};
+
+    fn estimate_liquid(markdown: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Liquid::default())
+            .estimate(markdown)
+    }
+
+    #[test]
+    fn test_liquid_matches_markdown() {
+        let liquid = r#"---
+title: 文章
+---
+
+# {{ page.title }}
+
+{% comment %}
+这段注释不计入
+{% endcomment %}
+
+{%- if page.draft -%}
+这是**草稿**内容。
+{%- endif -%}
+
+{% for post in site.posts %}
+- [{{ post.title }}]({{ post.url }})
+{% endfor %}
+
+{% img /images/a.png 300 200 %}
+
+{% highlight ruby linenos %}
+def foo
+  puts 'foo'
+end
+{% endhighlight %}
+
+{% raw %}
+模板写作 {{ 变量 }}
+{% endraw %}
+
+使用 `{{ page.title }}` 输出标题。
+"#;
+        let markdown = r#"---
+title: 文章
+---
+
+#
+
+这是**草稿**内容。
+
+- []()
+
+![](/images/a.png)
+
+```ruby
+def foo
+  puts 'foo'
+end
+```
+
+模板写作 {{ 变量 }}
+
+使用 `{{ page.title }}` 输出标题。
+"#;
+        assert_eq!(estimate_liquid(liquid), estimate(markdown));
+    }
+
+    #[test]
+    fn test_liquid_tag_weights() {
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(Liquid::default().image_tag("picture"))
+            .visitor(TemplateTags::new().tag("include", 45.0))
+            .estimate("{% include figure.html %}\n\n文本 {% picture 'a.jpg' %}\n");
+        assert_eq!(read_time.custom_elements["include"], 1);
+        assert_eq!(read_time.extra_seconds, 45.0);
+        assert_eq!(read_time.image_count, 1);
+        assert_eq!(read_time.word_count, 2);
+    }
+}
@@ -0,0 +1,419 @@
+// 各后端只用到其中一部分辅助函数
+#![cfg_attr(
+    not(all(
+        feature = "asciidoc",
+        feature = "docx",
+        feature = "gemtext",
+        feature = "handlebars",
+        feature = "html",
+        feature = "hugo",
+        feature = "latex",
+        feature = "liquid",
+        feature = "mdx",
+        feature = "mediawiki",
+        feature = "org",
+        feature = "pandoc",
+        feature = "rst",
+        feature = "textile",
+        feature = "zola"
+    )),
+    allow(dead_code)
+)]
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, LinkType, Tag, TagEnd};
+use std::ops::Range;
+
+pub(super) type Sink<'s, 'a> = &'s mut dyn FnMut(Event<'a>, Range<usize>);
+
+/// 源文本中的一行
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Line<'a> {
+    /// 行首在源文本中的字节偏移
+    pub(super) start: usize,
+
+    /// 不包含换行符的内容
+    pub(super) text: &'a str,
+
+    /// 包含换行符的内容
+    pub(super) raw: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub(super) fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// 行首空白的字节数
+    pub(super) fn indent(&self) -> usize {
+        self.text.len() - self.text.trim_start_matches([' ', '\t']).len()
+    }
+
+    /// 去掉开头的 `n` 个字节
+    pub(super) fn skip(&self, n: usize) -> Self {
+        let n = n.min(self.text.len());
+        Self {
+            start: self.start + n,
+            text: &self.text[n..],
+            raw: &self.raw[n..],
+        }
+    }
+
+    /// 去掉首尾空白后的内容及其在源文本中的字节偏移
+    pub(super) fn trimmed(&self) -> (&'a str, usize) {
+        let text = self.text.trim_start();
+        (text.trim_end(), self.start + self.text.len() - text.len())
+    }
+
+    /// `part` 在源文本中的字节偏移，`part` 必须是本行内容的一部分
+    pub(super) fn offset_of(&self, part: &str) -> usize {
+        self.start + offset_in(self.text, part)
+    }
+
+    pub(super) fn range(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+}
+
+/// `part` 相对于 `text` 开头的字节偏移，`part` 必须是 `text` 的一部分
+pub(super) fn offset_in(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+/// 按行拆分文本，保留每行的字节偏移
+pub(super) fn lines(text: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    text.split_inclusive('\n')
+        .map(|raw| {
+            let line = Line {
+                start,
+                text: raw.trim_end_matches(['\n', '\r']),
+                raw,
+            };
+            start += raw.len();
+            line
+        })
+        .collect()
+}
+
+/// 去掉各行共同的缩进，空行不参与计算
+pub(super) fn dedent<'a>(lines: &[Line<'a>]) -> Vec<Line<'a>> {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_blank())
+        .map(Line::indent)
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.skip(indent.min(line.indent())))
+        .collect()
+}
+
+/// 从 `start` 开始，连续的缩进行和空行的结束位置，不包含末尾的空行
+pub(super) fn indented_end(lines: &[Line<'_>], start: usize) -> usize {
+    let mut end = start;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        if line.is_blank() {
+            continue;
+        }
+        if line.indent() == 0 {
+            break;
+        }
+        end = i + 1;
+    }
+    end
+}
+
+/// 多行内容覆盖的字节范围
+pub(super) fn span(lines: &[Line<'_>]) -> Range<usize> {
+    match (lines.first(), lines.last()) {
+        (Some(first), Some(last)) => first.start..last.range().end,
+        _ => 0..0,
+    }
+}
+
+pub(super) fn start_tag<'a>(sink: Sink<'_, 'a>, tag: &Tag<'a>, range: Range<usize>) {
+    sink(Event::Start(tag.clone()), range);
+}
+
+pub(super) fn end_tag<'a>(sink: Sink<'_, 'a>, tag: &Tag<'a>, range: Range<usize>) {
+    sink(Event::End(tag.to_end()), range);
+}
+
+/// 输出代码块，去掉首尾的空行
+pub(super) fn code_block<'a>(sink: Sink<'_, 'a>, lang: &'a str, lines: &[Line<'a>]) {
+    let first = lines.iter().position(|line| !line.is_blank());
+    let last = lines.iter().rposition(|line| !line.is_blank());
+    let lines = match (first, last) {
+        (Some(first), Some(last)) => &lines[first..=last],
+        _ => &[],
+    };
+    let range = span(lines);
+    sink(
+        Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::Borrowed(
+            lang,
+        )))),
+        range.clone(),
+    );
+    for line in lines {
+        sink(
+            Event::Text(CowStr::Borrowed(line.raw)),
+            line.start..line.start + line.raw.len(),
+        );
+    }
+    sink(Event::End(TagEnd::CodeBlock), range);
+}
+
+/// 输出图片，替代文本不计入字数
+pub(super) fn image<'a>(sink: Sink<'_, 'a>, url: &'a str, alt: &'a str, range: Range<usize>) {
+    let tag = Tag::Image {
+        link_type: LinkType::Inline,
+        dest_url: CowStr::Borrowed(url),
+        title: CowStr::Borrowed(""),
+        id: CowStr::Borrowed(""),
+    };
+    start_tag(sink, &tag, range.clone());
+    if !alt.is_empty() {
+        sink(Event::Text(CowStr::Borrowed(alt)), range.clone());
+    }
+    end_tag(sink, &tag, range);
+}
+
+/// 输出非空的文本
+pub(super) fn text<'a>(sink: Sink<'_, 'a>, text: &'a str, offset: usize) {
+    if !text.is_empty() {
+        sink(
+            Event::Text(CowStr::Borrowed(text)),
+            offset..offset + text.len(),
+        );
+    }
+}
+
+/// 行内标记前后的边界：开头、结尾、空白或 ASCII 标点
+pub(super) fn is_boundary(byte: Option<u8>) -> bool {
+    byte.is_none_or(|b| b.is_ascii_whitespace() || b.is_ascii_punctuation())
+}
+
+/// 结束标记之后的边界，中文等非 ASCII 字符后面通常不加空格
+pub(super) fn is_closing(byte: Option<u8>) -> bool {
+    is_boundary(byte) || byte.is_some_and(|b| !b.is_ascii())
+}
+
+fn owned(text: CowStr<'_>) -> CowStr<'static> {
+    CowStr::from(String::from(&*text))
+}
+
+/// 复制事件中借用的文本，用于输出解析被包含文件得到的事件
+pub(super) fn into_static(event: Event<'_>) -> Event<'static> {
+    map_text(event, &mut owned)
+}
+
+/// 用 `f` 转换事件中的所有文本
+pub(super) fn map_text<'a, 'b>(
+    event: Event<'a>,
+    f: &mut impl FnMut(CowStr<'a>) -> CowStr<'b>,
+) -> Event<'b> {
+    match event {
+        Event::Start(tag) => Event::Start(match tag {
+            Tag::Paragraph => Tag::Paragraph,
+            Tag::Heading {
+                level,
+                id,
+                classes,
+                attrs,
+            } => Tag::Heading {
+                level,
+                id: id.map(&mut *f),
+                classes: classes.into_iter().map(&mut *f).collect(),
+                attrs: attrs
+                    .into_iter()
+                    .map(|(key, value)| (f(key), value.map(&mut *f)))
+                    .collect(),
+            },
+            Tag::BlockQuote => Tag::BlockQuote,
+            Tag::CodeBlock(CodeBlockKind::Fenced(lang)) => {
+                Tag::CodeBlock(CodeBlockKind::Fenced(f(lang)))
+            }
+            Tag::CodeBlock(CodeBlockKind::Indented) => Tag::CodeBlock(CodeBlockKind::Indented),
+            Tag::HtmlBlock => Tag::HtmlBlock,
+            Tag::List(start) => Tag::List(start),
+            Tag::Item => Tag::Item,
+            Tag::FootnoteDefinition(label) => Tag::FootnoteDefinition(f(label)),
+            Tag::Table(alignments) => Tag::Table(alignments),
+            Tag::TableHead => Tag::TableHead,
+            Tag::TableRow => Tag::TableRow,
+            Tag::TableCell => Tag::TableCell,
+            Tag::Emphasis => Tag::Emphasis,
+            Tag::Strong => Tag::Strong,
+            Tag::Strikethrough => Tag::Strikethrough,
+            Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            } => Tag::Link {
+                link_type,
+                dest_url: f(dest_url),
+                title: f(title),
+                id: f(id),
+            },
+            Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            } => Tag::Image {
+                link_type,
+                dest_url: f(dest_url),
+                title: f(title),
+                id: f(id),
+            },
+            Tag::MetadataBlock(kind) => Tag::MetadataBlock(kind),
+        }),
+        Event::End(tag) => Event::End(tag),
+        Event::Text(text) => Event::Text(f(text)),
+        Event::Code(code) => Event::Code(f(code)),
+        Event::Html(html) => Event::Html(f(html)),
+        Event::InlineHtml(html) => Event::InlineHtml(f(html)),
+        Event::FootnoteReference(label) => Event::FootnoteReference(f(label)),
+        Event::SoftBreak => Event::SoftBreak,
+        Event::HardBreak => Event::HardBreak,
+        Event::Rule => Event::Rule,
+        Event::TaskListMarker(checked) => Event::TaskListMarker(checked),
+    }
+}
+
+/// 常见的命名字符引用
+const ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("apos", '\''),
+    ("gt", '>'),
+    ("hellip", '…'),
+    ("ldquo", '“'),
+    ("lsquo", '‘'),
+    ("lt", '<'),
+    ("mdash", '—'),
+    ("nbsp", ' '),
+    ("ndash", '–'),
+    ("quot", '"'),
+    ("rdquo", '”'),
+    ("rsquo", '’'),
+];
+
+/// 元素的开始或结束标签
+pub(super) struct Element<'a> {
+    /// 保留原始大小写、去掉命名空间前缀的元素名
+    pub(super) name: &'a str,
+
+    /// 元素名之后的属性部分
+    pub(super) attributes: &'a str,
+
+    pub(super) closing: bool,
+    pub(super) self_closing: bool,
+}
+
+/// 解析位于 `text` 开头的标签，返回标签和标签的字节长度
+pub(super) fn element(text: &str) -> Option<(Element<'_>, usize)> {
+    let body = text.strip_prefix('<')?;
+    let (closing, body) = match body.strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, body),
+    };
+    if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut quote = None;
+    let end = body.char_indices().find_map(|(i, c)| {
+        match (quote, c) {
+            (Some(q), _) if q == c => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+        None
+    })?;
+    let inner = &body[..end];
+    let name_len = inner
+        .find(|c: char| c.is_ascii_whitespace() || c == '/')
+        .unwrap_or(inner.len());
+    let name = &inner[..name_len];
+    let name = name.rsplit(':').next().unwrap_or(name);
+    Some((
+        Element {
+            name,
+            attributes: &inner[name_len..],
+            closing,
+            self_closing: inner.ends_with('/'),
+        },
+        text.len() - body.len() + end + 1,
+    ))
+}
+
+/// 标签属性部分中的属性值，属性名不区分大小写
+pub(crate) fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+        let key_len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let key = &rest[..key_len];
+        rest = rest[key_len..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(after) => {
+                let after = after.trim_start();
+                let (value, len) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..].find(quote).map_or(after.len(), |end| end + 1);
+                        (&after[1..end], (end + 1).min(after.len()))
+                    }
+                    _ => {
+                        let end = after
+                            .find(|c: char| c.is_ascii_whitespace())
+                            .unwrap_or(after.len());
+                        (&after[..end], end)
+                    }
+                };
+                rest = &after[len..];
+                value
+            }
+            None => "",
+        };
+        let key = key.rsplit(':').next().unwrap_or(key);
+        if key.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+/// `name` 的结束标签在 `text` 中的位置和长度
+pub(super) fn closing_tag(text: &str, name: &str) -> Option<(usize, usize)> {
+    let mut i = 0;
+    while let Some(found) = text[i..].find("</") {
+        let at = i + found;
+        if let Some((element, len)) = element(&text[at..])
+            && element.name.eq_ignore_ascii_case(name)
+        {
+            return Some((at, len));
+        }
+        i = at + 2;
+    }
+    None
+}
+
+/// 解析位于 `text` 开头的字符引用
+pub(super) fn entity(text: &str) -> Option<(char, usize)> {
+    let end = text.get(..12).unwrap_or(text).find(';')?;
+    let name = &text[1..end];
+    let c = match name.strip_prefix('#') {
+        Some(hex) if hex.starts_with(['x', 'X']) => {
+            char::from_u32(u32::from_str_radix(&hex[1..], 16).ok()?)?
+        }
+        Some(decimal) => char::from_u32(decimal.parse().ok()?)?,
+        None => ENTITIES.iter().find(|(entity, _)| *entity == name)?.1,
+    };
+    Some((c, end + 1))
+}
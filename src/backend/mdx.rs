@@ -0,0 +1,289 @@
+use super::MarkdownBackend;
+use super::template::{Removals, scan};
+use crate::{Counters, EventVisitor, Visit};
+use pulldown_cmark::Event;
+use std::collections::BTreeMap;
+use std::ops::{ControlFlow, Range};
+
+/// MDX 后端，需要启用 `mdx` 特性
+///
+/// 去掉 `import`/`export` 语句、JSX 标签和 `{…}` 表达式之后按 Markdown 统计，
+/// 组件之间的 Markdown 内容正常计入字数，代码块和行内代码中的内容保持不变。
+/// 去掉的 JSX 标签以 HTML 事件输出，不计入字数，可以配合 [`MdxComponents`] 为组件设置固定时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Mdx, ReadSpeed, estimate};
+///
+/// let mdx = "import Chart from './chart'\n\n# 标题\n\n<Chart data={[1, 2]} />\n\n这是{props.name}内容";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Mdx).estimate(mdx);
+/// assert_eq!(read_time, estimate("# 标题\n\n这是内容"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mdx;
+
+impl MarkdownBackend for Mdx {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let source = markdown;
+        let mut removals = Removals::default();
+        let mut esm = false;
+        scan(
+            source,
+            &['{', '<'],
+            &mut removals,
+            |removals, line| {
+                let text = &source[line.clone()];
+                if esm || text.starts_with("import ") || text.starts_with("export ") {
+                    // ESM 语句一直延续到空行
+                    esm = !text.trim().is_empty();
+                    if esm {
+                        removals.remove(line);
+                    }
+                    return true;
+                }
+                false
+            },
+            |removals, at| {
+                if source.as_bytes()[at] == b'{' {
+                    let end = expression_end(source, at)?;
+                    removals.remove(at..end);
+                    return Some(end);
+                }
+                let end = tag_end(source, at)?;
+                removals.tag(source, at..end);
+                Some(end)
+            },
+        );
+        removals.parse(markdown, sink);
+    }
+}
+
+/// 从 `at` 处的 `{` 开始的表达式的结束位置，跳过字符串中的括号
+fn expression_end(source: &str, at: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, b) in source[at..].bytes().enumerate() {
+        match (quote, b) {
+            _ if escaped => escaped = false,
+            (_, b'\\') => escaped = true,
+            (Some(q), _) if q == b => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'' | b'`') => quote = Some(b),
+            (None, b'{') => depth += 1,
+            (None, b'}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(at + i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 从 `at` 处的 `<` 开始的 JSX 标签的结束位置，自动链接和普通的小于号返回 `None`
+fn tag_end(source: &str, at: usize) -> Option<usize> {
+    let rest = &source[at + 1..];
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    let name_len = rest
+        .bytes()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+        .count();
+    let after_name = rest[name_len..].bytes().next();
+    let valid = match rest.bytes().next() {
+        Some(b'>') => true,
+        Some(b) if b.is_ascii_alphabetic() => {
+            after_name.is_some_and(|b| b.is_ascii_whitespace() || matches!(b, b'/' | b'>'))
+        }
+        _ => false,
+    };
+    if !valid {
+        return None;
+    }
+    let start = source.len() - rest.len() + name_len;
+    let mut i = start;
+    while i < source.len() {
+        match source.as_bytes()[i] {
+            b'>' => return Some(i + 1),
+            b'{' => i = expression_end(source, i)?,
+            quote @ (b'"' | b'\'') => {
+                i += 1 + source[i + 1..].find(quote as char)? + 1;
+            }
+            b'\n' if source[i + 1..].starts_with('\n') => return None,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// 按组件名为 MDX 组件设置固定的阅读时间，配合 [`Mdx`] 后端使用
+///
+/// 组件的开始标签计为以组件名标记的自定义元素，并添加对应的时间；结束标签和未配置的组件不计时。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Mdx, MdxComponents, ReadSpeed};
+///
+/// let components = MdxComponents::new().component("Chart", 20.0).component("Video", 60.0);
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(Mdx)
+///     .visitor(components)
+///     .estimate("<Chart data={data} />\n\n<Video src=\"a.mp4\">\n  视频说明\n</Video>");
+/// assert_eq!(read_time.custom_elements["Chart"], 1);
+/// assert_eq!(read_time.custom_elements["Video"], 1);
+/// assert_eq!(read_time.extra_seconds, 80.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MdxComponents {
+    seconds: BTreeMap<String, f64>,
+}
+
+impl MdxComponents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置组件 `name` 的阅读时间（秒）
+    pub fn component(mut self, name: impl Into<String>, seconds: f64) -> Self {
+        self.seconds.insert(name.into(), seconds);
+        self
+    }
+
+    /// 组件 `name` 的阅读时间（秒），未配置时返回 `None`
+    pub fn seconds(&self, name: &str) -> Option<f64> {
+        self.seconds.get(name).copied()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, f64)> for MdxComponents {
+    fn from_iter<I: IntoIterator<Item = (K, f64)>>(iter: I) -> Self {
+        Self {
+            seconds: iter
+                .into_iter()
+                .map(|(name, seconds)| (name.into(), seconds))
+                .collect(),
+        }
+    }
+}
+
+impl EventVisitor for MdxComponents {
+    fn visit(&mut self, event: &Event<'_>, _: &Counters) -> Visit {
+        let (Event::Html(html) | Event::InlineHtml(html)) = event else {
+            return Visit::count();
+        };
+        let Some(rest) = html.strip_prefix('<').filter(|rest| !rest.starts_with('/')) else {
+            return Visit::count();
+        };
+        let name_len = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        match self.seconds(name) {
+            Some(seconds) => Visit::count().extra_seconds(seconds).tag(name),
+            None => Visit::count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_mdx(mdx: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Mdx)
+            .estimate(mdx)
+    }
+
+    #[test]
+    fn test_mdx_matches_markdown() {
+        let mdx = r#"import { Callout, Tabs } from "../components"
+export const meta = {
+  title: "不计入",
+}
+
+# 标题 {/* 注释 */}
+
+<Callout type="warning" onClick={() => alert("<不计入>")}>
+  这是**重要**内容，值为 {props.value}。
+</Callout>
+
+段落中的 <Badge label="新" /> 组件和 <https://example.com> 链接，以及 a < b。
+
+```jsx
+import React from "react"
+<Component prop={1} />
+```
+
+使用 `<Tabs>` 和 `{value}` 作为行内代码。
+
+<Tabs items={["一", "二"]}>
+<Tab>第一项</Tab>
+</Tabs>
+"#;
+        let markdown = r#"# 标题
+
+这是**重要**内容，值为 。
+
+段落中的  组件和 <https://example.com> 链接，以及 a < b。
+
+```jsx
+import React from "react"
+<Component prop={1} />
+```
+
+使用 `<Tabs>` 和 `{value}` 作为行内代码。
+
+第一项
+"#;
+        assert_eq!(estimate_mdx(mdx), estimate(markdown));
+    }
+
+    #[test]
+    fn test_mdx_sections_offsets() {
+        let mdx = "import A from 'a'\n\n# 第一章\n\n<A />\n\n内容\n";
+        let mut estimator = Estimator::new(ReadSpeed::default()).backend(Mdx);
+        let sections = estimator.estimate_sections(mdx);
+        assert_eq!(sections[0].heading, "第一章");
+
+        let mut ranges = Vec::new();
+        Mdx.parse_with_offsets(mdx, &mut |event, range| {
+            if let Event::Text(text) = &event {
+                assert_eq!(&mdx[range.clone()], &**text);
+            }
+            ranges.push(range);
+            ControlFlow::Continue(())
+        });
+        assert!(ranges.iter().all(|range| range.end <= mdx.len()));
+    }
+
+    #[test]
+    fn test_mdx_components() {
+        let components: MdxComponents = [("Chart", 15.0)].into_iter().collect();
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(Mdx)
+            .visitor(components)
+            .estimate("<Chart />\n\n文本 <Chart type=\"bar\" /> 和 <Other />\n");
+        assert_eq!(read_time.custom_elements["Chart"], 2);
+        assert_eq!(read_time.extra_seconds, 30.0);
+        assert_eq!(read_time.word_count, 3);
+    }
+}
@@ -0,0 +1,647 @@
+use super::markup::{
+    Line, Sink, attribute, closing_tag, code_block, element, end_tag, image, lines, span,
+    start_tag, text,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Tag, TagEnd};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 链接目标为图片的命名空间
+const FILE_NAMESPACES: &[&str] = &["file:", "image:", "文件:", "图像:"];
+
+/// 不显示在正文中的命名空间，例如分类
+const HIDDEN_NAMESPACES: &[&str] = &["category:", "分类:"];
+
+/// 内容按代码块统计的元素
+const CODE_ELEMENTS: &[&str] = &["pre", "syntaxhighlight", "source"];
+
+/// MediaWiki 标记后端，需要启用 `mediawiki` 特性
+///
+/// 把 `= 标题 =`、列表、`{| … |}` 表格和以空格开头的预格式化文本转换为对应的事件，
+/// `'''粗体'''` 等强调标记、`{{模板}}`、分类链接和注释不计入字数，`[[目标|文字]]` 只统计显示的文字，
+/// `[[File:…]]` 计为图片，`<pre>` 和 `<syntaxhighlight>` 计为代码块。
+/// `<ref>` 的内容作为脚注输出在文末，与 `<references/>` 渲染的位置一致。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, MediaWiki, ReadSpeed};
+///
+/// let wiki = "== 简介 ==\n{{Infobox|name=不计入}}\n'''北京'''是[[中华人民共和国|中国]]的首都。<ref>来源</ref>\n\n[[File:Beijing.jpg|thumb|说明]]\n[[Category:城市]]";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(MediaWiki).estimate(wiki);
+/// assert_eq!(read_time.word_count, 13);
+/// assert_eq!(read_time.image_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MediaWiki;
+
+impl MarkdownBackend for MediaWiki {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let mut parser = Parser {
+            source: markdown,
+            sink,
+            references: Vec::new(),
+        };
+        parser.blocks(&lines(markdown));
+        parser.references();
+    }
+}
+
+/// 标题的级别和标题文字
+fn heading(text: &str) -> Option<(usize, &str)> {
+    let text = text.trim_end();
+    let leading = text.bytes().take_while(|&b| b == b'=').count();
+    let trailing = text.bytes().rev().take_while(|&b| b == b'=').count();
+    if leading == 0 || trailing == 0 || text.len() <= leading + 1 {
+        return None;
+    }
+    let level = leading.min(trailing).min(6);
+    Some((level, text[level..text.len() - level].trim()))
+}
+
+/// 列表行的前缀，例如 `**` 或 `#:`
+fn list_prefix(text: &str) -> &str {
+    let len = text
+        .bytes()
+        .take_while(|b| matches!(b, b'*' | b'#' | b':' | b';'))
+        .count();
+    &text[..len]
+}
+
+/// 段落是否在此行之前结束
+fn interrupts(text: &str) -> bool {
+    heading(text).is_some()
+        || !list_prefix(text).is_empty()
+        || text.starts_with("{|")
+        || text.starts_with("----")
+        || text.starts_with(' ')
+        || code_element(text).is_some()
+}
+
+/// 行首的 `<pre>` 等代码元素的名称、语言和开始标签的长度
+fn code_element(text: &str) -> Option<(&str, &str, usize)> {
+    let (element, len) = element(text)?;
+    let name = CODE_ELEMENTS
+        .iter()
+        .find(|name| name.eq_ignore_ascii_case(element.name))?;
+    if element.closing || element.self_closing {
+        return None;
+    }
+    let lang = attribute(element.attributes, "lang").unwrap_or_default();
+    Some((name, lang, len))
+}
+
+/// 从 `at` 处的 `open` 开始的嵌套结构的结束位置，例如 `{{…}}` 和 `[[…]]`
+fn nested_end(source: &str, at: usize, open: &str, close: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = at;
+    while i < source.len() {
+        let rest = &source.as_bytes()[i..];
+        if rest.starts_with(open.as_bytes()) {
+            depth += 1;
+            i += open.len();
+        } else if rest.starts_with(close.as_bytes()) {
+            depth -= 1;
+            i += close.len();
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// 按顶层的 `|` 拆分链接和表格单元格，嵌套的链接和模板中的 `|` 不拆分
+fn split_pipes(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match &bytes[i..] {
+            [b'[', b'[', ..] | [b'{', b'{', ..] => {
+                depth += 1;
+                i += 2;
+            }
+            [b']', b']', ..] | [b'}', b'}', ..] => {
+                depth = depth.saturating_sub(1);
+                i += 2;
+            }
+            [b'|', ..] if depth == 0 => {
+                parts.push(&text[start..i]);
+                i += 1;
+                start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// `text` 是否以 `namespaces` 之一开头（不区分大小写）
+fn has_namespace(text: &str, namespaces: &[&str]) -> bool {
+    namespaces.iter().any(|namespace| {
+        text.get(..namespace.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(namespace))
+    })
+}
+
+struct Parser<'a, 's> {
+    source: &'a str,
+    sink: Sink<'s, 'a>,
+
+    /// 正文中的 `<ref>`：脚注名和内容
+    references: Vec<(CowStr<'a>, &'a str)>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn blocks(&mut self, lines: &[Line<'a>]) {
+        // 从 `offset` 开始的第一行
+        let line_at = |offset: usize| {
+            (0..lines.len())
+                .find(|&j| lines[j].start >= offset)
+                .unwrap_or(lines.len())
+        };
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.text.trim();
+            if line.is_blank() {
+                i += 1;
+            } else if let Some(end) = self.standalone(line, "{{", "}}") {
+                i = line_at(end);
+            } else if let Some(end) = self.standalone(line, "<!--", "-->") {
+                i = line_at(end);
+            } else if let Some((level, title)) = heading(line.text) {
+                let tag = Tag::Heading {
+                    level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+                    id: None,
+                    classes: Vec::new(),
+                    attrs: Vec::new(),
+                };
+                start_tag(self.sink, &tag, line.range());
+                self.inline(title, line.offset_of(title));
+                end_tag(self.sink, &tag, line.range());
+                i += 1;
+            } else if trimmed.starts_with("----") && trimmed.bytes().all(|b| b == b'-') {
+                (self.sink)(Event::Rule, line.range());
+                i += 1;
+            } else if line.text.starts_with("{|") {
+                let end =
+                    nested_end(self.source, line.start, "{|", "|}").unwrap_or(self.source.len());
+                self.table(&lines[i + 1..line_at(end)]);
+                i = line_at(end);
+            } else if let Some((name, lang, len)) = code_element(line.text) {
+                let body_start = line.start + len;
+                let (close, close_len) = closing_tag(&self.source[body_start..], name)
+                    .unwrap_or((self.source.len() - body_start, 0));
+                let body = body_start..body_start + close;
+                self.code(lang, body.clone(), line.start..body.end + close_len);
+                i = line_at(body.end + close_len);
+            } else if line.text.starts_with(' ') {
+                let end = (i..lines.len())
+                    .find(|&j| !lines[j].text.starts_with(' ') || lines[j].is_blank())
+                    .unwrap_or(lines.len());
+                let body: Vec<_> = lines[i..end].iter().map(|line| line.skip(1)).collect();
+                code_block(self.sink, "", &body);
+                i = end;
+            } else if !list_prefix(line.text).is_empty() {
+                i = self.list(lines, i, 0);
+            } else {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].is_blank() || interrupts(lines[j].text))
+                    .unwrap_or(lines.len());
+                let range = span(&lines[i..end]);
+                let source = self.source;
+                start_tag(self.sink, &Tag::Paragraph, range.clone());
+                self.inline(&source[range.clone()], range.start);
+                end_tag(self.sink, &Tag::Paragraph, range);
+                i = end;
+            }
+        }
+    }
+
+    /// 独占一行或多行的模板、注释等，返回其结束位置
+    fn standalone(&self, line: Line<'a>, open: &str, close: &str) -> Option<usize> {
+        if !line.text.starts_with(open) {
+            return None;
+        }
+        let end = if open == "{{" {
+            nested_end(self.source, line.start, open, close)?
+        } else {
+            line.start + self.source[line.start..].find(close)? + close.len()
+        };
+        let rest = self.source[end..].split('\n').next().unwrap_or_default();
+        rest.trim().is_empty().then_some(end)
+    }
+
+    fn code(&mut self, lang: &'a str, body: Range<usize>, range: Range<usize>) {
+        let kind = CodeBlockKind::Fenced(CowStr::Borrowed(lang));
+        (self.sink)(Event::Start(Tag::CodeBlock(kind)), range.clone());
+        let code = self.source[body.clone()].trim_matches('\n');
+        if !code.is_empty() {
+            let offset = body.start + self.source[body].find(code).unwrap_or(0);
+            text(self.sink, code, offset);
+        }
+        (self.sink)(Event::End(TagEnd::CodeBlock), range);
+    }
+
+    /// `{| … |}` 表格中的行，单元格可以延续到下一行
+    fn table(&mut self, lines: &[Line<'a>]) {
+        let range = span(lines);
+        let table = Tag::Table(Vec::new());
+        start_tag(self.sink, &table, range.clone());
+        let mut row: Option<Range<usize>> = None;
+        let mut cell: Option<Range<usize>> = None;
+        let close_cell = |sink: Sink<'_, 'a>, cell: &mut Option<Range<usize>>| {
+            if let Some(range) = cell.take() {
+                end_tag(sink, &Tag::TableCell, range);
+            }
+        };
+        let close_row = |sink: Sink<'_, 'a>, row: &mut Option<Range<usize>>| {
+            if let Some(range) = row.take() {
+                end_tag(sink, &Tag::TableRow, range);
+            }
+        };
+        for line in lines {
+            let text = line.text.trim_start();
+            if text.starts_with("|}") {
+                continue;
+            } else if text.starts_with("|-") {
+                close_cell(self.sink, &mut cell);
+                close_row(self.sink, &mut row);
+            } else if let Some(caption) = text.strip_prefix("|+") {
+                close_cell(self.sink, &mut cell);
+                start_tag(self.sink, &Tag::Paragraph, line.range());
+                let caption = cell_content(caption);
+                self.inline(caption, line.offset_of(caption));
+                end_tag(self.sink, &Tag::Paragraph, line.range());
+            } else if let Some(cells) = text.strip_prefix(['|', '!']) {
+                close_cell(self.sink, &mut cell);
+                if row.is_none() {
+                    row = Some(line.range());
+                    start_tag(self.sink, &Tag::TableRow, line.range());
+                }
+                let separator = if text.starts_with('!') { "!!" } else { "||" };
+                for part in cells.split(separator) {
+                    close_cell(self.sink, &mut cell);
+                    cell = Some(line.range());
+                    start_tag(self.sink, &Tag::TableCell, line.range());
+                    let content = cell_content(part).trim();
+                    self.inline(content, line.offset_of(content));
+                }
+            } else if cell.is_some() {
+                (self.sink)(Event::SoftBreak, line.start..line.start);
+                self.inline(line.text, line.start);
+            }
+        }
+        close_cell(self.sink, &mut cell);
+        close_row(self.sink, &mut row);
+        end_tag(self.sink, &table, range);
+    }
+
+    /// 前缀长度大于 `depth` 的连续列表行
+    fn list(&mut self, lines: &[Line<'a>], mut i: usize, depth: usize) -> usize {
+        let ordered = list_prefix(lines[i].text).as_bytes().get(depth) == Some(&b'#');
+        let tag = Tag::List(ordered.then_some(1));
+        let list_start = i;
+        start_tag(self.sink, &tag, lines[i].range());
+        let mut item: Option<Range<usize>> = None;
+        while let Some(line) = lines.get(i) {
+            let prefix = list_prefix(line.text);
+            if prefix.len() <= depth {
+                break;
+            }
+            if prefix.len() > depth + 1 && item.is_some() {
+                i = self.list(lines, i, depth + 1);
+                continue;
+            }
+            if let Some(range) = item.take() {
+                end_tag(self.sink, &Tag::Item, range);
+            }
+            item = Some(line.range());
+            start_tag(self.sink, &Tag::Item, line.range());
+            let content = line.text[prefix.len()..].trim();
+            self.inline(content, line.offset_of(content));
+            i += 1;
+        }
+        if let Some(range) = item {
+            end_tag(self.sink, &Tag::Item, range);
+        }
+        end_tag(self.sink, &tag, span(&lines[list_start..i]));
+        i
+    }
+
+    /// 文末的 `<ref>` 脚注
+    fn references(&mut self) {
+        for (label, content) in std::mem::take(&mut self.references) {
+            let offset = content.as_ptr() as usize - self.source.as_ptr() as usize;
+            let range = offset..offset + content.len();
+            let tag = Tag::FootnoteDefinition(label);
+            start_tag(self.sink, &tag, range.clone());
+            start_tag(self.sink, &Tag::Paragraph, range.clone());
+            self.inline(content, offset);
+            end_tag(self.sink, &Tag::Paragraph, range.clone());
+            end_tag(self.sink, &tag, range);
+        }
+    }
+
+    /// 行内文本，去掉强调标记、模板和链接语法
+    fn inline(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let rest = &source[i..];
+            let skip = if rest.starts_with("''") {
+                let len = rest.bytes().take_while(|&b| b == b'\'').count();
+                Some(i + len)
+            } else if rest.starts_with("<!--") {
+                Some(rest.find("-->").map_or(source.len(), |end| i + end + 3))
+            } else if rest.starts_with("{{") {
+                nested_end(source, i, "{{", "}}")
+            } else if rest.starts_with("__")
+                && let Some(len) = rest[2..].find("__")
+                && len > 0
+                && rest[2..2 + len].bytes().all(|b| b.is_ascii_uppercase())
+            {
+                Some(i + len + 4)
+            } else if rest.starts_with("~~~") {
+                Some(i + rest.bytes().take_while(|&b| b == b'~').count())
+            } else {
+                None
+            };
+            if let Some(end) = skip {
+                text(self.sink, &source[plain..i], offset + plain);
+                i = end;
+                plain = i;
+                continue;
+            }
+
+            let end = if rest.starts_with("[[") {
+                nested_end(source, i, "[[", "]]").map(|end| (end, Inline::Link))
+            } else if rest.starts_with('[')
+                && ["http://", "https://", "//", "mailto:"]
+                    .iter()
+                    .any(|scheme| rest[1..].starts_with(scheme))
+            {
+                rest.find(']')
+                    .map(|end| (i + end + 1, Inline::ExternalLink))
+            } else if rest.starts_with('<')
+                && let Some((element, len)) = element(rest)
+            {
+                Some((
+                    i + len,
+                    Inline::Element(element.name, element.self_closing || element.closing),
+                ))
+            } else {
+                None
+            };
+            let Some((end, kind)) = end else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+            text(self.sink, &source[plain..i], offset + plain);
+            let range = offset + i..offset + end;
+            i = match kind {
+                Inline::Link => {
+                    self.link(&source[i + 2..end - 2], offset + i + 2, range);
+                    end
+                }
+                Inline::ExternalLink => {
+                    let inner = &source[i + 1..end - 1];
+                    if let Some((_, label)) = inner.split_once(' ') {
+                        let label = label.trim();
+                        self.inline(
+                            label,
+                            offset + (label.as_ptr() as usize - source.as_ptr() as usize),
+                        );
+                    }
+                    end
+                }
+                Inline::Element(name, self_closing) => {
+                    self.element(source, offset, i, end, name, self_closing)
+                }
+            };
+            plain = i;
+        }
+        text(self.sink, &source[plain..], offset + plain);
+    }
+
+    /// `[[目标|文字]]` 链接，`inner` 是方括号之间的内容
+    fn link(&mut self, inner: &'a str, offset: usize, range: Range<usize>) {
+        let parts = split_pipes(inner);
+        let target = parts[0].trim();
+        if has_namespace(target, FILE_NAMESPACES) {
+            let url = &target[target.find(':').map_or(0, |colon| colon + 1)..];
+            image(self.sink, url, "", range);
+        } else if !has_namespace(target, HIDDEN_NAMESPACES) {
+            let label = parts.last().copied().unwrap_or_default();
+            let label = match parts.len() {
+                1 => label.trim().trim_start_matches(':'),
+                _ => label.trim(),
+            };
+            self.inline(
+                label,
+                offset + (label.as_ptr() as usize - inner.as_ptr() as usize),
+            );
+        }
+    }
+
+    /// 行内的 HTML 或扩展标签，返回标签之后的位置
+    fn element(
+        &mut self,
+        source: &'a str,
+        offset: usize,
+        at: usize,
+        end: usize,
+        name: &str,
+        self_closing: bool,
+    ) -> usize {
+        let lower = name.to_ascii_lowercase();
+        if lower == "br" {
+            (self.sink)(Event::HardBreak, offset + at..offset + end);
+            return end;
+        }
+        let content_tags = ["ref", "nowiki", "math", "code", "tt"];
+        if self_closing || !content_tags.contains(&lower.as_str()) {
+            if lower == "ref"
+                && let Some((element, _)) = element(&source[at..])
+                && let Some(name) = attribute(element.attributes, "name")
+            {
+                (self.sink)(
+                    Event::FootnoteReference(CowStr::Borrowed(name)),
+                    offset + at..offset + end,
+                );
+            }
+            return end;
+        }
+        let (close, close_len) =
+            closing_tag(&source[end..], name).unwrap_or((source.len() - end, 0));
+        let content = &source[end..end + close];
+        let content_offset = offset + end;
+        match lower.as_str() {
+            "ref" => {
+                let label = element(&source[at..])
+                    .and_then(|(element, _)| attribute(element.attributes, "name"))
+                    .map_or_else(
+                        || CowStr::from((self.references.len() + 1).to_string()),
+                        CowStr::Borrowed,
+                    );
+                (self.sink)(
+                    Event::FootnoteReference(label.clone()),
+                    offset + at..content_offset + close + close_len,
+                );
+                self.references.push((label, content));
+            }
+            "nowiki" => text(self.sink, content, content_offset),
+            _ => {
+                if !content.is_empty() {
+                    (self.sink)(
+                        Event::Code(CowStr::Borrowed(content)),
+                        content_offset..content_offset + content.len(),
+                    );
+                }
+            }
+        }
+        end + close + close_len
+    }
+}
+
+/// 行内语法
+enum Inline<'a> {
+    Link,
+    ExternalLink,
+
+    /// 元素名，以及是否为自闭合或结束标签
+    Element(&'a str, bool),
+}
+
+/// 去掉表格单元格开头的属性，例如 `style="…" | 内容`
+fn cell_content(cell: &str) -> &str {
+    let parts = split_pipes(cell);
+    match parts.as_slice() {
+        [attributes, content] if attributes.contains('=') && !attributes.contains("[[") => content,
+        _ => cell,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_wiki(wiki: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(MediaWiki)
+            .estimate(wiki)
+    }
+
+    #[test]
+    fn test_mediawiki_matches_markdown() {
+        let wiki = r#"{{Infobox city
+| name = 北京
+| population = {{formatnum:21540000}}
+}}
+__NOTOC__
+'''北京市'''，简称'''京'''，是[[中华人民共和国]]的[[首都|首都城市]]。<!-- 注释 -->
+
+== 历史 ==
+北京有''三千多年''的建城史，参见 [https://example.com 官方网站] 和 [https://example.org]。
+
+* 第一项
+** 子项
+# 编号
+
+{| class="wikitable"
+|+ 表格标题
+|-
+! 区 !! 人口
+|-
+| style="color:red" | 东城 || 70万
+|}
+
+[[File:Beijing.jpg|thumb|[[天安门]]广场]]
+
+<syntaxhighlight lang="python">
+print("hello")
+</syntaxhighlight>
+
+ 预格式化文本
+
+使用 <code>wiki</code> 标记和 <nowiki>'''原样'''</nowiki>。
+
+[[Category:城市]]
+"#;
+        let markdown = r#"**北京市**，简称**京**，是中华人民共和国的首都城市。
+
+## 历史
+
+北京有*三千多年*的建城史，参见 官方网站 和 。
+
+- 第一项
+  - 子项
+
+1. 编号
+
+表格标题
+
+区 人口
+
+东城 70万
+
+![](Beijing.jpg)
+
+```python
+print("hello")
+```
+
+```
+预格式化文本
+```
+
+使用 `wiki` 标记和 '''原样'''。
+"#;
+        assert_eq!(estimate_wiki(wiki), estimate(markdown));
+    }
+
+    #[test]
+    fn test_mediawiki_references() {
+        let wiki = "== 标题 ==\n正文<ref name=\"a\">脚注内容</ref>，再次引用<ref name=\"a\" />。\n\n<references />\n";
+        let read_time = estimate_wiki(wiki);
+        assert_eq!(
+            read_time,
+            estimate("## 标题\n\n正文，再次引用。\n\n脚注内容\n")
+        );
+
+        let mut estimator = Estimator::new(ReadSpeed::default()).backend(MediaWiki);
+        let sections = estimator.estimate_sections(wiki);
+        assert_eq!(sections[0].heading, "标题");
+    }
+
+    #[test]
+    fn test_heading() {
+        assert_eq!(heading("== 标题 =="), Some((2, "标题")));
+        assert_eq!(heading("=== 不对称 =="), Some((2, "= 不对称")));
+        assert_eq!(heading("a = b"), None);
+        assert_eq!(heading("=="), None);
+    }
+}
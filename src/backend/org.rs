@@ -0,0 +1,519 @@
+use super::markup::{
+    Line, Sink, code_block, dedent, end_tag, image, is_boundary, is_closing, lines, offset_in,
+    span, start_tag, text,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Tag};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 链接目标为图片时按图片统计的扩展名
+const IMAGE_EXTENSIONS: &[&str] = &[".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp"];
+
+/// 不属于正文的规划行
+const PLANNING: &[&str] = &["SCHEDULED:", "DEADLINE:", "CLOSED:"];
+
+/// Org-mode 后端，需要启用 `org` 特性
+///
+/// 把标题、段落、列表、表格和 `#+BEGIN_` 块转换为对应的事件，计数规则与 Markdown 相同：
+/// `SRC` 和 `EXAMPLE` 块计为代码块，指向图片的链接计为图片，`QUOTE` 块按引用统计。
+/// 标题中的 TODO 关键字、优先级和标签，以及属性抽屉、规划行、注释和 `#+` 关键字不计入字数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Org, ReadSpeed};
+///
+/// let org = "* TODO 标题 :work:\n:PROPERTIES:\n:ID: 1\n:END:\n这是 *正文* 。\n\n[[./a.png]]\n\n#+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Org).estimate(org);
+/// assert_eq!(read_time.word_count, 7);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.code_block_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Org;
+
+impl MarkdownBackend for Org {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        Parser { sink }.blocks(&lines(markdown));
+    }
+}
+
+/// 标题的级别
+fn headline_level(text: &str) -> Option<usize> {
+    let level = text.bytes().take_while(|&b| b == b'*').count();
+    (level > 0 && text[level..].starts_with(' ')).then_some(level)
+}
+
+/// 去掉标题中的 TODO 关键字、优先级和结尾的标签
+fn headline_title(title: &str) -> &str {
+    let mut title = title.trim();
+    for keyword in ["TODO ", "DONE "] {
+        title = title.strip_prefix(keyword).unwrap_or(title);
+    }
+    if let Some(rest) = title.strip_prefix("[#")
+        && let Some((_, rest)) = rest.split_once("] ")
+    {
+        title = rest;
+    }
+    if title.ends_with(':')
+        && let Some((rest, tags)) = title.rsplit_once(' ')
+        && tags.starts_with(':')
+    {
+        title = rest.trim_end();
+    }
+    title
+}
+
+/// 抽屉的起始行，例如 `:PROPERTIES:`
+fn is_drawer(text: &str) -> bool {
+    let text = text.trim();
+    text.len() > 2
+        && text != ":END:"
+        && text.starts_with(':')
+        && text.ends_with(':')
+        && text[1..text.len() - 1]
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// `#+BEGIN_NAME 参数` 的名称（小写比较）和参数
+fn block_begin(text: &str) -> Option<(&str, &str)> {
+    let rest = text.trim_start();
+    let head = rest.get(..8)?;
+    if !head.eq_ignore_ascii_case("#+begin_") {
+        return None;
+    }
+    let rest = &rest[8..];
+    Some(rest.split_once(' ').unwrap_or((rest, "")))
+}
+
+/// 列表标记的字节长度（包括缩进和后面的空格）以及有序列表的起始序号
+fn list_marker(text: &str) -> Option<(usize, Option<u64>)> {
+    let trimmed = text.trim_start();
+    let indent = text.len() - trimmed.len();
+    let (marker, rest) = trimmed.split_once(' ')?;
+    let ordered = match marker {
+        "-" | "+" => None,
+        "*" if indent > 0 => None,
+        _ => Some(
+            marker
+                .strip_suffix('.')
+                .or_else(|| marker.strip_suffix(')'))?
+                .parse()
+                .ok()?,
+        ),
+    };
+    Some((text.len() - rest.trim_start().len(), ordered))
+}
+
+/// 段落是否在此行之前结束
+fn interrupts(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    headline_level(text).is_some()
+        || trimmed.starts_with("#+")
+        || trimmed.starts_with('|')
+        || list_marker(text).is_some()
+        || is_drawer(text)
+}
+
+struct Parser<'a, 's> {
+    sink: Sink<'s, 'a>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn blocks(&mut self, lines: &[Line<'a>]) {
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let trimmed = line.text.trim();
+            if line.is_blank()
+                || trimmed == "#"
+                || trimmed.starts_with("# ")
+                || PLANNING.iter().any(|keyword| trimmed.starts_with(keyword))
+            {
+                i += 1;
+            } else if let Some(level) = headline_level(line.text) {
+                let title = headline_title(&line.text[level..]);
+                self.heading(title, line.offset_of(title), level, line.range());
+                i += 1;
+            } else if let Some((name, args)) = block_begin(line.text) {
+                let end = (i + 1..lines.len())
+                    .find(|&j| {
+                        let text = lines[j].text.trim();
+                        text.get(..6)
+                            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("#+end_"))
+                            && text[6..].eq_ignore_ascii_case(name)
+                    })
+                    .unwrap_or(lines.len());
+                let range = span(&lines[i..(end + 1).min(lines.len())]);
+                self.block(name, args, &dedent(&lines[i + 1..end]), range);
+                i = end + 1;
+            } else if let Some(rest) = trimmed.strip_prefix("#+") {
+                if let Some(caption) = rest.get(..8).filter(|k| k.eq_ignore_ascii_case("caption:"))
+                {
+                    let caption = rest[caption.len()..].trim();
+                    let offset = line.offset_of(caption);
+                    start_tag(self.sink, &Tag::Paragraph, line.range());
+                    self.inline(caption, offset);
+                    end_tag(self.sink, &Tag::Paragraph, line.range());
+                } else if let Some(title) =
+                    rest.get(..6).filter(|k| k.eq_ignore_ascii_case("title:"))
+                {
+                    let title = rest[title.len()..].trim();
+                    self.heading(title, line.offset_of(title), 1, line.range());
+                }
+                i += 1;
+            } else if is_drawer(line.text) {
+                i = (i + 1..lines.len())
+                    .find(|&j| lines[j].text.trim().eq_ignore_ascii_case(":END:"))
+                    .map_or(i + 1, |end| end + 1);
+            } else if trimmed.chars().count() >= 5 && trimmed.bytes().all(|b| b == b'-') {
+                (self.sink)(Event::Rule, line.range());
+                i += 1;
+            } else if trimmed == ":" || trimmed.starts_with(": ") {
+                let end = (i..lines.len())
+                    .find(|&j| {
+                        let text = lines[j].text.trim();
+                        text != ":" && !text.starts_with(": ")
+                    })
+                    .unwrap_or(lines.len());
+                let body: Vec<_> = lines[i..end]
+                    .iter()
+                    .map(|line| line.skip(line.indent() + 2))
+                    .collect();
+                code_block(self.sink, "", &body);
+                i = end;
+            } else if trimmed.starts_with('|') {
+                i = self.table(lines, i);
+            } else if let Some((_, ordered)) = list_marker(line.text) {
+                i = self.list(lines, i, ordered);
+            } else if let Some(label) = line.text.strip_prefix("[fn:")
+                && let Some((label, _)) = label.split_once(']')
+            {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].is_blank() || interrupts(lines[j].text))
+                    .unwrap_or(lines.len());
+                let tag = Tag::FootnoteDefinition(CowStr::Borrowed(label));
+                let range = span(&lines[i..end]);
+                start_tag(self.sink, &tag, range.clone());
+                let mut body = vec![line.skip(label.len() + 5)];
+                body.extend_from_slice(&lines[i + 1..end]);
+                self.paragraph(&body);
+                end_tag(self.sink, &tag, range);
+                i = end;
+            } else {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].is_blank() || interrupts(lines[j].text))
+                    .unwrap_or(lines.len());
+                self.paragraph(&lines[i..end]);
+                i = end;
+            }
+        }
+    }
+
+    fn heading(&mut self, title: &'a str, offset: usize, level: usize, range: Range<usize>) {
+        let tag = Tag::Heading {
+            level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        };
+        start_tag(self.sink, &tag, range.clone());
+        self.inline(title, offset);
+        end_tag(self.sink, &tag, range);
+    }
+
+    /// `#+BEGIN_NAME` 块
+    fn block(&mut self, name: &str, args: &'a str, lines: &[Line<'a>], range: Range<usize>) {
+        let name = name.to_ascii_lowercase();
+        match name.as_str() {
+            "src" => {
+                let lang = args.split_whitespace().next().unwrap_or_default();
+                code_block(self.sink, lang, lines);
+            }
+            "example" => code_block(self.sink, "", lines),
+            "comment" => {}
+            "export" => {
+                start_tag(self.sink, &Tag::HtmlBlock, range.clone());
+                for line in lines {
+                    (self.sink)(
+                        Event::Html(CowStr::Borrowed(line.raw)),
+                        line.start..line.start + line.raw.len(),
+                    );
+                }
+                end_tag(self.sink, &Tag::HtmlBlock, range);
+            }
+            "quote" | "verse" => {
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                self.blocks(lines);
+                end_tag(self.sink, &Tag::BlockQuote, range);
+            }
+            _ => self.blocks(lines),
+        }
+    }
+
+    fn paragraph(&mut self, lines: &[Line<'a>]) {
+        let range = span(lines);
+        start_tag(self.sink, &Tag::Paragraph, range.clone());
+        for (index, line) in lines.iter().enumerate() {
+            let (text, offset) = line.trimmed();
+            if index > 0 {
+                (self.sink)(Event::SoftBreak, offset..offset);
+            }
+            match text.strip_suffix("\\\\") {
+                Some(text) => {
+                    self.inline(text, offset);
+                    let end = offset + text.len();
+                    (self.sink)(Event::HardBreak, end..end + 2);
+                }
+                None => self.inline(text, offset),
+            }
+        }
+        end_tag(self.sink, &Tag::Paragraph, range);
+    }
+
+    /// 表格，分隔行不计入字数
+    fn table(&mut self, lines: &[Line<'a>], i: usize) -> usize {
+        let end = (i..lines.len())
+            .find(|&j| !lines[j].text.trim_start().starts_with('|'))
+            .unwrap_or(lines.len());
+        for line in &lines[i..end] {
+            let (row, offset) = line.trimmed();
+            if row.starts_with("|-") {
+                continue;
+            }
+            start_tag(self.sink, &Tag::Paragraph, line.range());
+            let mut cell_offset = offset;
+            for cell in row.split('|') {
+                let trimmed = cell.trim_start();
+                self.inline(trimmed.trim_end(), cell_offset + cell.len() - trimmed.len());
+                cell_offset += cell.len() + 1;
+            }
+            end_tag(self.sink, &Tag::Paragraph, line.range());
+        }
+        end
+    }
+
+    /// 连续的同级列表项，缩进更深的行属于当前项
+    fn list(&mut self, lines: &[Line<'a>], mut i: usize, first: Option<u64>) -> usize {
+        let indent = lines[i].indent();
+        let list_start = i;
+        let tag = Tag::List(first);
+        start_tag(self.sink, &tag, lines[i].range());
+        while let Some(line) = lines.get(i) {
+            let Some((width, ordered)) = list_marker(line.text) else {
+                break;
+            };
+            if line.indent() != indent || ordered.is_some() != first.is_some() {
+                break;
+            }
+            let mut end = i + 1;
+            for (j, next) in lines.iter().enumerate().skip(i + 1) {
+                if next.is_blank() {
+                    continue;
+                }
+                if next.indent() <= indent {
+                    break;
+                }
+                end = j + 1;
+            }
+            let range = span(&lines[i..end]);
+            start_tag(self.sink, &Tag::Item, range.clone());
+            let mut first_line = line.skip(width);
+            let checked = match first_line.text.get(..4) {
+                Some("[X] " | "[x] ") => Some(true),
+                Some("[ ] " | "[-] ") => Some(false),
+                _ => None,
+            };
+            if let Some(checked) = checked {
+                (self.sink)(Event::TaskListMarker(checked), first_line.range());
+                first_line = first_line.skip(4);
+            }
+            let mut body = vec![first_line];
+            body.extend(dedent(&lines[i + 1..end]));
+            self.blocks(&body);
+            end_tag(self.sink, &Tag::Item, range);
+            i = (end..lines.len())
+                .find(|&j| !lines[j].is_blank())
+                .unwrap_or(lines.len());
+        }
+        end_tag(self.sink, &tag, span(&lines[list_start..i]));
+        i
+    }
+
+    /// 行内文本，去掉强调标记和链接语法，`=verbatim=` 和 `~code~` 作为行内代码
+    fn inline(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let rest = &source[i..];
+            let before = i.checked_sub(1).map(|j| bytes[j]);
+            if let Some(link) = rest.strip_prefix("[[")
+                && let Some(len) = link.find("]]")
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                let link = &link[..len];
+                let (target, description) = match link.split_once("][") {
+                    Some((target, description)) => (target, Some(description)),
+                    None => (link, None),
+                };
+                let range = offset + i..offset + i + len + 4;
+                let is_image = IMAGE_EXTENSIONS
+                    .iter()
+                    .any(|extension| target.to_ascii_lowercase().ends_with(extension));
+                match description {
+                    None if is_image => {
+                        let target = target.strip_prefix("file:").unwrap_or(target);
+                        image(self.sink, target, "", range);
+                    }
+                    Some(description) => {
+                        self.inline(description, offset + offset_in(source, description))
+                    }
+                    None => text(self.sink, target, offset + offset_in(source, target)),
+                }
+                i += len + 4;
+                plain = i;
+            } else if let Some(label) = rest.strip_prefix("[fn:")
+                && let Some(len) = label.find(']')
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                (self.sink)(
+                    Event::FootnoteReference(CowStr::Borrowed(&label[..len])),
+                    offset + i..offset + i + len + 5,
+                );
+                i += len + 5;
+                plain = i;
+            } else if matches!(bytes[i], b'=' | b'~')
+                && is_boundary(before)
+                && let Some(len) = rest[1..].find(bytes[i] as char)
+                && len > 0
+                && is_closing(bytes.get(i + len + 2).copied())
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                (self.sink)(
+                    Event::Code(CowStr::Borrowed(&rest[1..len + 1])),
+                    offset + i + 1..offset + i + 1 + len,
+                );
+                i += len + 2;
+                plain = i;
+            } else if matches!(bytes[i], b'*' | b'/' | b'_' | b'+') {
+                let after = bytes.get(i + 1).copied();
+                let opens = is_boundary(before) && !is_boundary(after);
+                let closes = !is_boundary(before) && is_closing(after);
+                if opens || closes {
+                    text(self.sink, &source[plain..i], offset + plain);
+                    plain = i + 1;
+                }
+                i += 1;
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        text(self.sink, &source[plain..], offset + plain);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_org(org: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Org)
+            .estimate(org)
+    }
+
+    #[test]
+    fn test_org_matches_markdown() {
+        let org = "\
+#+TITLE: 文档
+#+AUTHOR: 某人
+
+* DONE [#A] 简介 :intro:
+  SCHEDULED: <2024-01-01 Mon>
+  :LOGBOOK:
+  CLOCK: [2024-01-01 Mon 10:00]
+  :END:
+
+这是 *重要* 的 /内容/ ，参见 [[https://example.com][链接]] 和 ~代码~。
+
+- 第一项
+- 第二项
+  1. 子项
+
+# 注释
+
+#+CAPTION: 图片说明
+[[file:a.png]]
+
+#+begin_src rust
+fn main() {}
+#+end_src
+
+#+BEGIN_QUOTE
+引用
+#+END_QUOTE
+
+| 表头 | 列 |
+|------+----|
+| 单元 | 格 |
+";
+        let markdown = "\
+# 文档
+
+# 简介
+
+这是 **重要** 的 *内容* ，参见 [链接](https://example.com) 和 `代码`。
+
+- 第一项
+- 第二项
+  1. 子项
+
+图片说明
+
+![](a.png)
+
+```rust
+fn main() {}
+```
+
+> 引用
+
+表头 列
+
+单元 格
+";
+        assert_eq!(estimate_org(org), estimate(markdown));
+    }
+
+    #[test]
+    fn test_org_headlines() {
+        assert_eq!(headline_title(" TODO 标题 :a:b:"), "标题");
+        assert_eq!(headline_title(" 比例 1:2"), "比例 1:2");
+        assert_eq!(headline_title(" [#B] 时间 10:30 :x:"), "时间 10:30");
+        assert_eq!(estimate_org("*粗体* 不是标题").word_count, 6);
+    }
+
+    #[test]
+    fn test_org_multibyte_block_lines() {
+        // 块内以多字节字符开头的行不能按字节切分
+        let org = "#+BEGIN_QUOTE\n引用 中文\nhello中文\n#+END_QUOTE\n";
+        assert_eq!(estimate_org(org), estimate("> 引用 中文\n> hello中文\n"));
+    }
+}
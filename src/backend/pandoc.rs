@@ -0,0 +1,320 @@
+use super::markup::{Sink, end_tag, start_tag};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+use serde_json::Value;
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// Pandoc JSON AST 后端，需要启用 `pandoc` 特性
+///
+/// 解析 `pandoc -t json` 输出的文档（pandoc-types 1.22 及以上），把 `Para`、`Header`、`CodeBlock`、
+/// `Image`、`Table` 等节点转换为对应的事件，计数规则与 Markdown 相同：`Math` 计为语言为 `math` 的代码，
+/// `RawBlock` 和 `RawInline` 计为 HTML，`Note` 按脚注统计，`meta` 中的元数据不计入字数。
+/// JSON 中没有源文本位置，所有事件的字节范围都为空；无法解析的输入不输出任何事件。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Pandoc, ReadSpeed, estimate};
+///
+/// let json = r#"{"pandoc-api-version":[1,23],"meta":{},"blocks":[
+///     {"t":"Header","c":[1,["",[],[]],[{"t":"Str","c":"标题"}]]},
+///     {"t":"Para","c":[{"t":"Str","c":"Hello"},{"t":"Space"},{"t":"Strong","c":[{"t":"Str","c":"world"}]}]},
+///     {"t":"CodeBlock","c":[["",["rust"],[]],"fn main() {}"]}
+/// ]}"#;
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Pandoc).estimate(json);
+/// assert_eq!(read_time, estimate("# 标题\n\nHello **world**\n\n```rust\nfn main() {}\n```"));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pandoc;
+
+impl MarkdownBackend for Pandoc {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let Ok(document) = serde_json::from_str::<Value>(markdown) else {
+            return;
+        };
+        let mut walker = Walker { sink };
+        walker.blocks(&document["blocks"]);
+    }
+
+    fn front_matter(&self) -> bool {
+        false
+    }
+}
+
+/// 节点的类型和内容
+fn node(value: &Value) -> (&str, &Value) {
+    (
+        value["t"].as_str().unwrap_or_default(),
+        value.get("c").unwrap_or(&Value::Null),
+    )
+}
+
+fn items(value: &Value) -> &[Value] {
+    value.as_array().map_or(&[], Vec::as_slice)
+}
+
+fn string(value: &Value) -> String {
+    String::from(value.as_str().unwrap_or_default())
+}
+
+/// `Attr` 中的第一个类名，用作代码块的语言
+fn first_class(attr: &Value) -> String {
+    string(&attr[1][0])
+}
+
+struct Walker<'a, 's> {
+    sink: Sink<'s, 'a>,
+}
+
+impl<'a> Walker<'a, '_> {
+    fn emit(&mut self, event: Event<'a>) {
+        (self.sink)(event, 0..0);
+    }
+
+    fn wrap(&mut self, tag: Tag<'a>, f: impl FnOnce(&mut Self)) {
+        start_tag(self.sink, &tag, 0..0);
+        f(self);
+        end_tag(self.sink, &tag, 0..0);
+    }
+
+    fn text(&mut self, text: String) {
+        if !text.is_empty() {
+            self.emit(Event::Text(CowStr::from(text)));
+        }
+    }
+
+    fn code_block(&mut self, lang: String, code: String) {
+        let kind = CodeBlockKind::Fenced(CowStr::from(lang));
+        self.emit(Event::Start(Tag::CodeBlock(kind)));
+        self.text(code);
+        self.emit(Event::End(TagEnd::CodeBlock));
+    }
+
+    fn blocks(&mut self, blocks: &Value) {
+        for block in items(blocks) {
+            self.block(block);
+        }
+    }
+
+    fn block(&mut self, block: &Value) {
+        let (kind, content) = node(block);
+        match kind {
+            "Plain" | "Para" => self.wrap(Tag::Paragraph, |w| w.inlines(content)),
+            "LineBlock" => self.wrap(Tag::Paragraph, |w| {
+                for (index, line) in items(content).iter().enumerate() {
+                    if index > 0 {
+                        w.emit(Event::HardBreak);
+                    }
+                    w.inlines(line);
+                }
+            }),
+            "CodeBlock" => self.code_block(first_class(&content[0]), string(&content[1])),
+            "RawBlock" => self.wrap(Tag::HtmlBlock, |w| {
+                w.emit(Event::Html(CowStr::from(string(&content[1]))));
+            }),
+            "BlockQuote" => self.wrap(Tag::BlockQuote, |w| w.blocks(content)),
+            "OrderedList" => {
+                let start = content[0][0].as_u64().unwrap_or(1);
+                self.list(Some(start), &content[1]);
+            }
+            "BulletList" => self.list(None, content),
+            "DefinitionList" => self.wrap(Tag::List(None), |w| {
+                for entry in items(content) {
+                    w.wrap(Tag::Item, |w| {
+                        w.wrap(Tag::Paragraph, |w| w.inlines(&entry[0]));
+                        for definition in items(&entry[1]) {
+                            w.blocks(definition);
+                        }
+                    });
+                }
+            }),
+            "Header" => {
+                let level = content[0].as_u64().unwrap_or(1).clamp(1, 6) as usize;
+                let tag = Tag::Heading {
+                    level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+                    id: None,
+                    classes: Vec::new(),
+                    attrs: Vec::new(),
+                };
+                self.wrap(tag, |w| w.inlines(&content[2]));
+            }
+            "HorizontalRule" => self.emit(Event::Rule),
+            "Table" => self.table(content),
+            "Figure" => {
+                self.blocks(&content[2]);
+                self.blocks(&content[1][1]);
+            }
+            "Div" => self.blocks(&content[1]),
+            _ => {}
+        }
+    }
+
+    fn list(&mut self, start: Option<u64>, entries: &Value) {
+        self.wrap(Tag::List(start), |w| {
+            for item in items(entries) {
+                w.wrap(Tag::Item, |w| w.blocks(item));
+            }
+        });
+    }
+
+    /// `Table`：标题、表头、表体和表尾中的单元格
+    fn table(&mut self, content: &Value) {
+        self.blocks(&content[1][1]);
+        let mut rows: Vec<&Value> = items(&content[3][1]).iter().collect();
+        for body in items(&content[4]) {
+            rows.extend(items(&body[2]));
+            rows.extend(items(&body[3]));
+        }
+        rows.extend(items(&content[5][1]));
+        self.wrap(Tag::Table(Vec::new()), |w| {
+            for row in rows {
+                w.wrap(Tag::TableRow, |w| {
+                    for cell in items(&row[1]) {
+                        w.wrap(Tag::TableCell, |w| w.blocks(&cell[4]));
+                    }
+                });
+            }
+        });
+    }
+
+    fn inlines(&mut self, inlines: &Value) {
+        for inline in items(inlines) {
+            self.inline(inline);
+        }
+    }
+
+    fn inline(&mut self, inline: &Value) {
+        let (kind, content) = node(inline);
+        match kind {
+            "Str" => self.text(string(content)),
+            "Space" => self.text(String::from(" ")),
+            "SoftBreak" => self.emit(Event::SoftBreak),
+            "LineBreak" => self.emit(Event::HardBreak),
+            "Emph" | "Underline" | "Strong" | "Strikeout" | "Superscript" | "Subscript"
+            | "SmallCaps" => self.inlines(content),
+            "Quoted" | "Cite" | "Span" => self.inlines(&content[1]),
+            "Code" => {
+                let code = string(&content[1]);
+                if !code.is_empty() {
+                    self.emit(Event::Code(CowStr::from(code)));
+                }
+            }
+            "Math" => {
+                let math = string(&content[1]);
+                match node(&content[0]).0 {
+                    "DisplayMath" => self.code_block(String::from("math"), math),
+                    _ if !math.is_empty() => self.emit(Event::Code(CowStr::from(math))),
+                    _ => {}
+                }
+            }
+            "RawInline" => self.emit(Event::InlineHtml(CowStr::from(string(&content[1])))),
+            "Link" => {
+                let tag = Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: CowStr::from(string(&content[2][0])),
+                    title: CowStr::from(string(&content[2][1])),
+                    id: CowStr::Borrowed(""),
+                };
+                self.wrap(tag, |w| w.inlines(&content[1]));
+            }
+            "Image" => {
+                let tag = Tag::Image {
+                    link_type: LinkType::Inline,
+                    dest_url: CowStr::from(string(&content[2][0])),
+                    title: CowStr::from(string(&content[2][1])),
+                    id: CowStr::Borrowed(""),
+                };
+                self.wrap(tag, |w| w.inlines(&content[1]));
+            }
+            "Note" => self.wrap(Tag::FootnoteDefinition(CowStr::Borrowed("")), |w| {
+                w.blocks(content)
+            }),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_pandoc(json: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Pandoc)
+            .estimate(json)
+    }
+
+    #[test]
+    fn test_pandoc_matches_markdown() {
+        // pandoc -f markdown -t json 的输出
+        let json = r#"{"pandoc-api-version":[1,23,1],"meta":{"title":{"t":"MetaInlines","c":[{"t":"Str","c":"不计入"}]}},"blocks":[
+{"t":"Header","c":[1,["intro",[],[]],[{"t":"Str","c":"简介"}]]},
+{"t":"Para","c":[{"t":"Str","c":"这是"},{"t":"Emph","c":[{"t":"Str","c":"重要"}]},{"t":"Str","c":"内容，参见"},{"t":"Space"},{"t":"Link","c":[["",[],[]],[{"t":"Str","c":"链接"}],["https://example.com",""]]},{"t":"Space"},{"t":"Str","c":"和"},{"t":"Space"},{"t":"Code","c":[["",[],[]],"code"]},{"t":"Str","c":"。"}]},
+{"t":"BulletList","c":[[{"t":"Plain","c":[{"t":"Str","c":"第一项"}]}],[{"t":"Plain","c":[{"t":"Str","c":"第二项"}]}]]},
+{"t":"OrderedList","c":[[3,{"t":"Decimal"},{"t":"Period"}],[[{"t":"Plain","c":[{"t":"Str","c":"编号"}]}]]]},
+{"t":"Figure","c":[["",[],[]],[null,[{"t":"Plain","c":[{"t":"Str","c":"图片说明"}]}]],[{"t":"Plain","c":[{"t":"Image","c":[["",[],[]],[{"t":"Str","c":"图片说明"}],["a.png",""]]}]}]]},
+{"t":"CodeBlock","c":[["",["python"],[]],"print(\"hello\")"]},
+{"t":"BlockQuote","c":[{"t":"Para","c":[{"t":"Str","c":"引用"}]}]},
+{"t":"Para","c":[{"t":"Str","c":"公式"},{"t":"Space"},{"t":"Math","c":[{"t":"InlineMath"},"x^2"]}]},
+{"t":"Table","c":[["",[],[]],[null,[]],[[{"t":"AlignDefault"},{"t":"ColWidthDefault"}],[{"t":"AlignDefault"},{"t":"ColWidthDefault"}]],
+ [["",[],[]],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"表头"}]}]],[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"列"}]}]]]]]],
+ [[["",[],[]],0,[],[[["",[],[]],[[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"单元"}]}]],[["",[],[]],{"t":"AlignDefault"},1,1,[{"t":"Plain","c":[{"t":"Str","c":"格"}]}]]]]]]],
+ [["",[],[]],[]]]},
+{"t":"HorizontalRule"}
+]}"#;
+        let markdown = r#"# 简介
+
+这是*重要*内容，参见 [链接](https://example.com) 和 `code`。
+
+- 第一项
+- 第二项
+
+3. 编号
+
+![图片说明](a.png)
+
+图片说明
+
+```python
+print("hello")
+```
+
+> 引用
+
+公式 `x^2`
+
+表头 列
+
+单元 格
+
+---
+"#;
+        assert_eq!(estimate_pandoc(json), estimate(markdown));
+    }
+
+    #[test]
+    fn test_pandoc_invalid() {
+        assert_eq!(estimate_pandoc("not json"), estimate(""));
+        let sections = Estimator::new(ReadSpeed::default())
+            .backend(Pandoc)
+            .estimate_sections(
+                r#"{"blocks":[{"t":"Header","c":[2,["",[],[]],[{"t":"Str","c":"第一章"}]]},{"t":"Para","c":[{"t":"Str","c":"内容"}]}]}"#,
+            );
+        assert_eq!(sections[0].heading, "第一章");
+    }
+}
@@ -0,0 +1,588 @@
+use super::markup::{
+    Line, Sink, code_block, dedent, end_tag, image, indented_end, is_boundary, lines, span,
+    start_tag, text,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CowStr, Event, HeadingLevel, Tag};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// reStructuredText 后端，需要启用 `rst` 特性
+///
+/// 把标题、段落、列表、引用、字面块和常用指令转换为对应的事件，计数规则与 Markdown 相同：
+/// `image` 和 `figure` 计为图片，`code-block`、`literalinclude`、`math` 和 `::` 字面块计为代码块，
+/// `note`、`warning` 等提示框按引用统计。注释、超链接目标和 `toctree` 等不产生正文的指令不计入字数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, ReadSpeed, Rst};
+///
+/// let rst = "标题\n====\n\n这是 **正文** 。\n\n.. image:: a.png\n\n.. code-block:: rust\n\n   fn main() {}\n";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Rst).estimate(rst);
+/// assert_eq!(read_time.word_count, 7);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.code_block_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Rst;
+
+impl MarkdownBackend for Rst {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let mut parser = Parser {
+            sink,
+            styles: Vec::new(),
+        };
+        parser.blocks(&lines(markdown));
+    }
+}
+
+/// 内容按引用统计的提示框类指令
+const ADMONITIONS: &[&str] = &[
+    "admonition",
+    "attention",
+    "caution",
+    "danger",
+    "epigraph",
+    "error",
+    "highlights",
+    "hint",
+    "important",
+    "note",
+    "pull-quote",
+    "seealso",
+    "sidebar",
+    "tip",
+    "topic",
+    "warning",
+];
+
+/// 内容按正文统计的容器类指令
+const CONTAINERS: &[&str] = &["compound", "container", "only", "rubric", "versionadded"];
+
+/// 计为代码块的指令
+const CODE: &[&str] = &["code", "code-block", "literalinclude", "math", "sourcecode"];
+
+fn is_adornment(text: &str) -> bool {
+    let text = text.trim_end();
+    let Some(&first) = text.as_bytes().first() else {
+        return false;
+    };
+    text.len() >= 2 && first.is_ascii_punctuation() && text.bytes().all(|byte| byte == first)
+}
+
+/// 简单表格的边框，例如 `=====  =====`
+fn is_table_border(text: &str) -> bool {
+    let text = text.trim_end();
+    text.starts_with('=') && text.contains(' ') && text.bytes().all(|b| b == b'=' || b == b' ')
+}
+
+/// 列表标记的字节长度（包括后面的空格）以及有序列表的起始序号
+fn list_marker(text: &str) -> Option<(usize, Option<u64>)> {
+    let (marker, _) = text.split_once(' ').unwrap_or((text, ""));
+    let ordered = match marker {
+        "-" | "*" | "+" | "•" | "‣" | "⁃" => None,
+        _ => {
+            let body = marker
+                .strip_prefix('(')
+                .and_then(|body| body.strip_suffix(')'))
+                .or_else(|| marker.strip_suffix('.'))
+                .or_else(|| marker.strip_suffix(')'))?;
+            let letter = body.len() == 1 && body.bytes().all(|b| b.is_ascii_alphabetic());
+            if body == "#" || letter {
+                Some(1)
+            } else {
+                Some(body.parse().ok()?)
+            }
+        }
+    };
+    let width = text[marker.len()..].len() - text[marker.len()..].trim_start().len();
+    Some((marker.len() + width, ordered))
+}
+
+/// 去掉文本末尾的角色前缀，例如 `:ref:` 或 `:py:func:`
+fn strip_role(text: &str) -> &str {
+    let rest = text.trim_end_matches(|c: char| c.is_ascii_alphanumeric() || "-_+.:".contains(c));
+    let role = &text[rest.len()..];
+    if role.len() >= 3 && role.starts_with(':') && role.ends_with(':') {
+        rest
+    } else {
+        text
+    }
+}
+
+struct Parser<'a, 's> {
+    sink: Sink<'s, 'a>,
+    /// 已出现的标题装饰样式（字符，是否有上划线），按出现顺序对应标题级别
+    styles: Vec<(u8, bool)>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn blocks(&mut self, lines: &[Line<'a>]) {
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            let next = lines.get(i + 1).filter(|line| !line.is_blank());
+            if line.is_blank() {
+                i += 1;
+            } else if line.indent() > 0 {
+                let end = indented_end(lines, i);
+                let range = span(&lines[i..end]);
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                self.blocks(&dedent(&lines[i..end]));
+                end_tag(self.sink, &Tag::BlockQuote, range);
+                i = end;
+            } else if is_adornment(line.text)
+                && let (Some(title), Some(under)) = (next, lines.get(i + 2))
+                && under.text.trim_end() == line.text.trim_end()
+            {
+                self.heading(
+                    *title,
+                    (line.text.as_bytes()[0], true),
+                    span(&lines[i..i + 3]),
+                );
+                i += 3;
+            } else if let Some(under) = next
+                && !is_adornment(line.text)
+                && is_adornment(under.text)
+                && under.text.trim_end().len() >= line.text.trim().chars().count()
+            {
+                self.heading(
+                    line,
+                    (under.text.as_bytes()[0], false),
+                    span(&lines[i..i + 2]),
+                );
+                i += 2;
+            } else if is_adornment(line.text) && line.text.trim_end().len() >= 4 {
+                (self.sink)(Event::Rule, line.range());
+                i += 1;
+            } else if line.text == ".." || line.text.starts_with(".. ") {
+                i = self.explicit(lines, i);
+            } else if let Some((_, ordered)) = list_marker(line.text) {
+                i = self.list(lines, i, ordered);
+            } else if line.text.starts_with(">>>") {
+                let end = paragraph_end(lines, i);
+                code_block(self.sink, "pycon", &lines[i..end]);
+                i = end;
+            } else if line.text.starts_with('+') && line.text.trim_end().ends_with('+') {
+                let end = paragraph_end(lines, i);
+                self.table(&lines[i..end]);
+                i = end;
+            } else {
+                i = self.paragraph(lines, i);
+            }
+        }
+    }
+
+    fn heading(&mut self, title: Line<'a>, style: (u8, bool), range: Range<usize>) {
+        let level = match self.styles.iter().position(|&s| s == style) {
+            Some(index) => index + 1,
+            None => {
+                self.styles.push(style);
+                self.styles.len()
+            }
+        };
+        let tag = Tag::Heading {
+            level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+            id: None,
+            classes: Vec::new(),
+            attrs: Vec::new(),
+        };
+        start_tag(self.sink, &tag, range.clone());
+        let (title, offset) = title.trimmed();
+        self.inline(title, offset);
+        end_tag(self.sink, &tag, range);
+    }
+
+    /// 段落，以 `::` 结尾时后面的缩进块为字面块
+    fn paragraph(&mut self, lines: &[Line<'a>], i: usize) -> usize {
+        let end = paragraph_end(lines, i);
+        let body: Vec<_> = lines[i..end]
+            .iter()
+            .filter(|line| !is_table_border(line.text))
+            .map(|line| {
+                let line = line.text.strip_prefix("| ").map_or(*line, |_| line.skip(2));
+                line.trimmed()
+            })
+            .collect();
+        let literal = body.last().is_some_and(|(text, _)| text.ends_with("::"));
+        let range = span(&lines[i..end]);
+        if !matches!(body.as_slice(), [] | [("::", _)]) {
+            start_tag(self.sink, &Tag::Paragraph, range.clone());
+            for (index, &(text, offset)) in body.iter().enumerate() {
+                if index > 0 {
+                    (self.sink)(Event::SoftBreak, offset..offset);
+                }
+                let text = match text.strip_suffix("::") {
+                    Some(rest) if index == body.len() - 1 && rest.ends_with(' ') => rest.trim_end(),
+                    Some(_) if index == body.len() - 1 => &text[..text.len() - 1],
+                    _ => text,
+                };
+                self.inline(text, offset);
+            }
+            end_tag(self.sink, &Tag::Paragraph, range);
+        }
+
+        let next = (end..lines.len()).find(|&j| !lines[j].is_blank());
+        match next {
+            Some(next) if literal && lines[next].indent() > 0 => {
+                let block_end = indented_end(lines, next);
+                code_block(self.sink, "", &dedent(&lines[next..block_end]));
+                block_end
+            }
+            _ => end,
+        }
+    }
+
+    fn table(&mut self, lines: &[Line<'a>]) {
+        for line in lines.iter().filter(|line| line.text.starts_with('|')) {
+            let range = line.range();
+            start_tag(self.sink, &Tag::Paragraph, range.clone());
+            let mut offset = line.start;
+            for cell in line.text.split('|') {
+                let trimmed = cell.trim_start();
+                text(
+                    self.sink,
+                    trimmed.trim_end(),
+                    offset + cell.len() - trimmed.len(),
+                );
+                offset += cell.len() + 1;
+            }
+            end_tag(self.sink, &Tag::Paragraph, range);
+        }
+    }
+
+    /// 连续的列表项，`ordered` 相同的项属于同一个列表
+    fn list(&mut self, lines: &[Line<'a>], mut i: usize, first: Option<u64>) -> usize {
+        let list_start = i;
+        let ordered = first.is_some();
+        let mut items = Vec::new();
+        while let Some(line) = lines.get(i) {
+            let Some((width, number)) = list_marker(line.text) else {
+                break;
+            };
+            if line.indent() > 0 || number.is_some() != ordered {
+                break;
+            }
+            let end = indented_end(lines, i + 1).max(i + 1);
+            items.push((i, width, end));
+            i = end;
+            match (i..lines.len()).find(|&j| !lines[j].is_blank()) {
+                Some(next) if list_marker(lines[next].text).is_some() => i = next,
+                _ => break,
+            }
+        }
+
+        let tag = Tag::List(first);
+        let range = span(&lines[list_start..i]);
+        start_tag(self.sink, &tag, range.clone());
+        for (item, width, end) in items {
+            let item_range = span(&lines[item..end]);
+            start_tag(self.sink, &Tag::Item, item_range.clone());
+            let mut body = vec![lines[item].skip(width)];
+            body.extend(dedent(&lines[item + 1..end]));
+            self.blocks(&body);
+            end_tag(self.sink, &Tag::Item, item_range);
+        }
+        end_tag(self.sink, &tag, range);
+        i
+    }
+
+    /// 以 `..` 开头的显式标记：指令、脚注、超链接目标和注释
+    fn explicit(&mut self, lines: &[Line<'a>], i: usize) -> usize {
+        let end = indented_end(lines, i + 1).max(i + 1);
+        let range = span(&lines[i..end]);
+        let first = lines[i].skip(2);
+        let (mut rest, _) = first.trimmed();
+        let body = dedent(&lines[i + 1..end]);
+
+        if let Some(label) = rest.strip_prefix('[')
+            && let Some((label, _)) = label.split_once(']')
+        {
+            let tag = Tag::FootnoteDefinition(CowStr::Borrowed(label));
+            start_tag(self.sink, &tag, range.clone());
+            let text_start = first.offset_of(label) + label.len() + 1 - first.start;
+            let mut content = vec![first.skip(text_start)];
+            content.extend(body);
+            self.blocks(&content);
+            end_tag(self.sink, &tag, range);
+            return end;
+        }
+
+        // 替换定义 `.. |name| image:: a.png` 按其中的指令处理
+        if let Some(substitution) = rest.strip_prefix('|')
+            && let Some((_, directive)) = substitution.split_once('|')
+        {
+            rest = directive.trim_start();
+        }
+
+        let Some((name, args)) = rest.split_once("::") else {
+            return end;
+        };
+        let name = name.trim();
+        let args = args.trim();
+        let options = body
+            .iter()
+            .take_while(|line| line.text.starts_with(':'))
+            .count();
+        let option = |key: &str| {
+            body[..options].iter().find_map(|line| {
+                let value = line
+                    .text
+                    .strip_prefix(':')?
+                    .strip_prefix(key)?
+                    .strip_prefix(':')?;
+                Some(value.trim())
+            })
+        };
+        let content = &body[options..];
+
+        match name {
+            "image" | "figure" => {
+                start_tag(self.sink, &Tag::Paragraph, range.clone());
+                image(self.sink, args, option("alt").unwrap_or(""), range.clone());
+                end_tag(self.sink, &Tag::Paragraph, range);
+                if name == "figure" {
+                    self.blocks(content);
+                }
+            }
+            _ if CODE.contains(&name) => {
+                let lang = match name {
+                    "math" => "math",
+                    "literalinclude" => option("language").unwrap_or(""),
+                    _ => args,
+                };
+                code_block(self.sink, lang, content);
+            }
+            _ if ADMONITIONS.contains(&name) => {
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                if !args.is_empty() {
+                    let title = first.offset_of(args);
+                    start_tag(self.sink, &Tag::Paragraph, title..title + args.len());
+                    self.inline(args, title);
+                    end_tag(self.sink, &Tag::Paragraph, title..title + args.len());
+                }
+                self.blocks(content);
+                end_tag(self.sink, &Tag::BlockQuote, range);
+            }
+            _ if CONTAINERS.contains(&name) => self.blocks(content),
+            _ => {}
+        }
+        end
+    }
+
+    /// 行内文本，去掉强调、链接、角色等标记，``字面量`` 作为行内代码
+    fn inline(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'`' => {
+                    let delimiter = if source[i..].starts_with("``") {
+                        "``"
+                    } else {
+                        "`"
+                    };
+                    let open = i + delimiter.len();
+                    let Some(len) = source[open..].find(delimiter) else {
+                        i = open;
+                        continue;
+                    };
+                    let before = strip_role(&source[plain..i]);
+                    text(self.sink, before, offset + plain);
+                    let inner = &source[open..open + len];
+                    if delimiter == "``" {
+                        (self.sink)(
+                            Event::Code(CowStr::Borrowed(inner)),
+                            offset + open..offset + open + len,
+                        );
+                    } else {
+                        let (label, start) = reference_text(inner);
+                        text(self.sink, label, offset + open + start);
+                    }
+                    i = open + len + delimiter.len();
+                    while bytes.get(i) == Some(&b'_') {
+                        i += 1;
+                    }
+                    plain = i;
+                }
+                b'*' => {
+                    let run = bytes[i..].iter().take_while(|&&b| b == b'*').count();
+                    let before = i.checked_sub(1).map(|j| bytes[j]);
+                    let after = bytes.get(i + run).copied();
+                    let opens = is_boundary(before) && !is_boundary(after);
+                    let closes = !is_boundary(before) && is_boundary(after);
+                    if opens || closes {
+                        text(self.sink, &source[plain..i], offset + plain);
+                        plain = i + run;
+                    }
+                    i += run;
+                }
+                b'[' => {
+                    let footnote = source[i + 1..]
+                        .find("]_")
+                        .map(|len| &source[i + 1..i + 1 + len])
+                        .filter(|label| !label.is_empty() && !label.contains(' '));
+                    match footnote {
+                        Some(label) => {
+                            text(self.sink, &source[plain..i], offset + plain);
+                            let next = i + label.len() + 3;
+                            (self.sink)(
+                                Event::FootnoteReference(CowStr::Borrowed(label)),
+                                offset + i..offset + next,
+                            );
+                            i = next;
+                            plain = i;
+                        }
+                        None => i += 1,
+                    }
+                }
+                b'_' if i > 0 && bytes[i - 1].is_ascii_alphanumeric() => {
+                    let run = bytes[i..].iter().take_while(|&&b| b == b'_').count();
+                    if is_boundary(bytes.get(i + run).copied()) {
+                        text(self.sink, &source[plain..i], offset + plain);
+                        plain = i + run;
+                    }
+                    i += run;
+                }
+                _ => i += 1,
+            }
+        }
+        text(self.sink, &source[plain..], offset + plain);
+    }
+}
+
+/// 段落的结束位置：下一个空行
+fn paragraph_end(lines: &[Line<'_>], i: usize) -> usize {
+    (i..lines.len())
+        .find(|&j| lines[j].is_blank())
+        .unwrap_or(lines.len())
+}
+
+/// 链接或角色的显示文本：`标题 <目标>` 取标题，只有目标时取目标
+fn reference_text(inner: &str) -> (&str, usize) {
+    if let Some(target) = inner.strip_suffix('>')
+        && let Some(lt) = target.rfind('<')
+    {
+        let label = inner[..lt].trim_end();
+        if label.is_empty() {
+            return (&target[lt + 1..], lt + 1);
+        }
+        return (label, 0);
+    }
+    (inner, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, estimate};
+
+    fn estimate_rst(rst: &str) -> crate::ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Rst)
+            .estimate(rst)
+    }
+
+    #[test]
+    fn test_rst_matches_markdown() {
+        let rst = "\
+=====
+文档
+=====
+
+简介
+----
+
+这是 **重要** 的 *内容* ，参见 `链接 <https://example.com>`_ 和 :ref:`章节`。
+
+- 第一项
+- 第二项
+
+  继续第二项
+
+示例代码::
+
+    fn main() {}
+
+.. note:: 提示
+
+   注意事项
+
+.. figure:: a.png
+   :alt: 图片
+
+   图片说明
+
+.. toctree::
+   :maxdepth: 2
+
+   intro
+
+.. 这是注释
+";
+        let markdown = "\
+# 文档
+
+## 简介
+
+这是 **重要** 的 *内容* ，参见 [链接](https://example.com) 和 章节。
+
+- 第一项
+- 第二项
+
+  继续第二项
+
+示例代码:
+
+```
+fn main() {}
+```
+
+> 提示
+>
+> 注意事项
+
+![图片](a.png)
+
+图片说明
+";
+        assert_eq!(estimate_rst(rst), estimate(markdown));
+    }
+
+    #[test]
+    fn test_rst_headings() {
+        let mut levels = Vec::new();
+        Rst.parse(
+            "甲\n==\n\n乙\n--\n\n丙\n==\n\n----\n\n* x\n",
+            &mut |event| {
+                if let Event::Start(Tag::Heading { level, .. }) = event {
+                    levels.push(level);
+                }
+            },
+        );
+        assert_eq!(
+            levels,
+            [HeadingLevel::H1, HeadingLevel::H2, HeadingLevel::H1]
+        );
+
+        let read_time =
+            estimate_rst("| 第一行\n| 第二行\n\n>>> print(1)\n1\n\n+---+\n| 表格 |\n+---+\n");
+        assert_eq!(read_time.word_count, 8);
+        assert_eq!(read_time.code_block_count, 1);
+    }
+}
@@ -0,0 +1,417 @@
+//! 嵌入在 Markdown 中的模板语法（MDX、短代码、模板标签）的共用处理
+//!
+//! 先删除模板语法再按 Markdown 解析，事件的文本和字节范围映射回源文本，
+//! 删除的标签按源文本中的位置插入为 HTML、图片或代码块事件。
+
+// 各后端只用到其中一部分
+#![cfg_attr(
+    not(all(
+        feature = "handlebars",
+        feature = "hugo",
+        feature = "liquid",
+        feature = "mdx",
+        feature = "zola"
+    )),
+    allow(dead_code)
+)]
+
+use super::markup::{end_tag, image, map_text, start_tag};
+use super::{MarkdownBackend, PulldownCmark, until_break};
+use crate::{Counters, EventVisitor, Visit};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Tag, TagEnd};
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::ops::{ControlFlow, Range};
+
+/// 删除的标签输出为什么事件
+pub(super) enum Replacement<'a> {
+    /// HTML 事件，`flow` 表示标签独占一行
+    Html { flow: bool },
+
+    /// 图片
+    Image { url: &'a str },
+
+    /// 代码块，`body` 是代码在源文本中的范围
+    CodeBlock { lang: &'a str, body: Range<usize> },
+}
+
+/// 需要从源文本中删除的内容
+#[derive(Default)]
+pub(super) struct Removals<'a> {
+    ranges: Vec<Range<usize>>,
+    tags: Vec<(Range<usize>, Replacement<'a>)>,
+}
+
+impl<'a> Removals<'a> {
+    /// 删除 `range`，不输出任何事件
+    pub(super) fn remove(&mut self, range: Range<usize>) {
+        if !range.is_empty() {
+            self.ranges.push(range);
+        }
+    }
+
+    /// 删除 `range`，并在该位置输出 `replacement`
+    pub(super) fn replace(&mut self, range: Range<usize>, replacement: Replacement<'a>) {
+        self.remove(range.clone());
+        self.tags.push((range, replacement));
+    }
+
+    /// 删除 `range` 处的标签，并输出为 HTML 事件
+    pub(super) fn tag(&mut self, source: &str, range: Range<usize>) {
+        let flow = is_flow(source, &range);
+        self.replace(range, Replacement::Html { flow });
+    }
+
+    /// 解析删除之后的文本，输出映射回源文本的事件
+    ///
+    /// `sink` 返回 `Break` 后停止解析。
+    pub(super) fn parse(
+        mut self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        self.ranges.sort_by_key(|range| range.start);
+        self.tags.sort_by_key(|(range, _)| range.start);
+        let stripped = Stripped::new(markdown, &self.ranges);
+        let text = stripped.text.as_str();
+        let mut tags = self.tags.into_iter().peekable();
+        let mut emit_tags = |sink: &mut dyn FnMut(Event<'a>, Range<usize>), before: usize| {
+            while let Some((range, replacement)) = tags.next_if(|(range, _)| range.start < before) {
+                emit(markdown, sink, range, replacement);
+            }
+        };
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+
+        PulldownCmark::default().parse_with_offsets(text, &mut |event, range| {
+            let range = stripped.original_range(range);
+            emit_tags(sink, range.start);
+            let event = map_text(event, &mut |part| match part {
+                CowStr::Borrowed(part) => match stripped.original(text, part) {
+                    Some(original) => CowStr::Borrowed(&markdown[original]),
+                    None => CowStr::from(String::from(part)),
+                },
+                part => CowStr::from(String::from(&*part)),
+            });
+            sink(event, range);
+            match stopped.get() {
+                true => ControlFlow::Break(()),
+                false => ControlFlow::Continue(()),
+            }
+        });
+        emit_tags(sink, usize::MAX);
+    }
+}
+
+fn emit<'a>(
+    markdown: &'a str,
+    sink: &mut dyn FnMut(Event<'a>, Range<usize>),
+    range: Range<usize>,
+    replacement: Replacement<'a>,
+) {
+    match replacement {
+        Replacement::Html { flow: true } => {
+            start_tag(sink, &Tag::HtmlBlock, range.clone());
+            sink(
+                Event::Html(CowStr::Borrowed(&markdown[range.clone()])),
+                range.clone(),
+            );
+            end_tag(sink, &Tag::HtmlBlock, range);
+        }
+        Replacement::Html { flow: false } => {
+            sink(
+                Event::InlineHtml(CowStr::Borrowed(&markdown[range.clone()])),
+                range,
+            );
+        }
+        Replacement::Image { url } => image(sink, url, "", range),
+        Replacement::CodeBlock { lang, body } => {
+            sink(
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(CowStr::Borrowed(
+                    lang,
+                )))),
+                range.clone(),
+            );
+            let code = markdown[body.clone()].trim_matches('\n');
+            if !code.is_empty() {
+                sink(Event::Text(CowStr::Borrowed(code)), body);
+            }
+            sink(Event::End(TagEnd::CodeBlock), range);
+        }
+    }
+}
+
+/// `range` 处的标签是否独占一行，前后只有空白
+pub(super) fn is_flow(source: &str, range: &Range<usize>) -> bool {
+    let line_start = source[..range.start]
+        .rfind('\n')
+        .map_or(0, |start| start + 1);
+    let rest = source[range.end..].split('\n').next().unwrap_or_default();
+    source[line_start..range.start].trim().is_empty() && rest.trim().is_empty()
+}
+
+/// 删除模板语法之后的文本
+struct Stripped {
+    text: String,
+
+    /// 保留的各段在 `text` 和源文本中的起始位置
+    segments: Vec<(usize, usize)>,
+}
+
+impl Stripped {
+    fn new(source: &str, removed: &[Range<usize>]) -> Self {
+        let mut text = String::with_capacity(source.len());
+        let mut segments = Vec::new();
+        let mut kept = 0;
+        for range in removed
+            .iter()
+            .chain(std::iter::once(&(source.len()..source.len())))
+        {
+            if range.start > kept {
+                segments.push((text.len(), kept));
+                text.push_str(&source[kept..range.start]);
+            }
+            kept = kept.max(range.end);
+        }
+        Self { text, segments }
+    }
+
+    /// `text` 中的位置所在的段
+    fn segment(&self, at: usize) -> Option<&(usize, usize)> {
+        let index = self.segments.partition_point(|(start, _)| *start <= at);
+        self.segments.get(index.checked_sub(1)?)
+    }
+
+    fn original_offset(&self, at: usize) -> usize {
+        self.segment(at)
+            .map_or(at, |(start, original)| original + at - start)
+    }
+
+    fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.original_offset(range.start);
+        let end = match range.end.checked_sub(1) {
+            Some(last) if range.end > range.start => self.original_offset(last) + 1,
+            _ => start,
+        };
+        start..end
+    }
+
+    /// `part` 在源文本中的范围，`part` 不是 `text` 的一部分或跨越了删除的内容时返回 `None`
+    fn original(&self, text: &str, part: &str) -> Option<Range<usize>> {
+        let start = (part.as_ptr() as usize).checked_sub(text.as_ptr() as usize)?;
+        if start + part.len() > text.len() {
+            return None;
+        }
+        let first = self.segment(start);
+        let last = self.segment((start + part.len()).saturating_sub(1).max(start));
+        if part.is_empty() || first == last {
+            let start = self.original_offset(start);
+            return Some(start..start + part.len());
+        }
+        None
+    }
+}
+
+/// 扫描代码块和行内代码之外的内容
+///
+/// `line` 在代码块之外的每行行首以整行的范围（包含换行符）调用，返回真表示已处理该行；
+/// `inline` 在 `triggers` 中的字符处调用，返回 `Some(end)` 表示该处的语法到 `end` 结束，可以跨越多行。
+pub(super) fn scan<'a>(
+    source: &'a str,
+    triggers: &[char],
+    removals: &mut Removals<'a>,
+    mut line: impl FnMut(&mut Removals<'a>, Range<usize>) -> bool,
+    mut inline: impl FnMut(&mut Removals<'a>, usize) -> Option<usize>,
+) {
+    let mut fence: Option<(u8, usize)> = None;
+    let mut i = 0;
+    while i < source.len() {
+        let line_end = source[i..].find('\n').map_or(source.len(), |end| i + end);
+        let next = (line_end + 1).min(source.len());
+        let text = &source[i..line_end];
+        let trimmed = text.trim_start_matches(' ');
+        let fence_len = |c: u8| trimmed.bytes().take_while(|b| *b == c).count();
+
+        if let Some((c, len)) = fence {
+            if fence_len(c) >= len && trimmed[fence_len(c)..].trim().is_empty() {
+                fence = None;
+            }
+            i = next;
+            continue;
+        }
+        if line(removals, i..next) {
+            i = next;
+            continue;
+        }
+        if text.len() - trimmed.len() <= 3
+            && let Some(c) = trimmed.bytes().next().filter(|c| matches!(c, b'`' | b'~'))
+            && fence_len(c) >= 3
+        {
+            fence = Some((c, fence_len(c)));
+            i = next;
+            continue;
+        }
+
+        let mut j = i;
+        i = loop {
+            let line_end = source[j..].find('\n').map_or(source.len(), |end| j + end);
+            let found =
+                source[j..line_end].find(|c: char| c == '\\' || c == '`' || triggers.contains(&c));
+            let Some(found) = found else {
+                break (line_end + 1).min(source.len());
+            };
+            let at = j + found;
+            j = match source.as_bytes()[at] {
+                b'\\' => at + 1 + source[at + 1..].chars().next().map_or(0, char::len_utf8),
+                b'`' => code_span_end(source, at),
+                _ => inline(removals, at).unwrap_or(at + 1),
+            }
+            .min(source.len());
+        };
+    }
+}
+
+/// 从 `at` 开始的行内代码的结束位置，没有匹配的反引号时跳过开头的反引号
+fn code_span_end(source: &str, at: usize) -> usize {
+    let ticks = source[at..].bytes().take_while(|b| *b == b'`').count();
+    let mut i = at + ticks;
+    while let Some(found) = source[i..].find('`') {
+        let start = i + found;
+        let len = source[start..].bytes().take_while(|b| *b == b'`').count();
+        if len == ticks {
+            return start + len;
+        }
+        i = start + len;
+    }
+    at + ticks
+}
+
+/// 从 `at` 开始查找 `close`，跳过引号中的内容，返回 `close` 之后的位置
+pub(super) fn find_close(source: &str, at: usize, close: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut i = at;
+    while i < source.len() {
+        let b = source.as_bytes()[i];
+        match quote {
+            Some(q) if q == b => quote = None,
+            Some(_) => {}
+            None if matches!(b, b'"' | b'\'') => quote = Some(b),
+            None if source.as_bytes()[i..].starts_with(close.as_bytes()) => {
+                return Some(i + close.len());
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// 按名称为模板标签和短代码设置固定的阅读时间
+///
+/// 用于短代码和模板标签后端：标签以 HTML 事件输出，开始标签计为以标签名标记的自定义元素，
+/// 并添加对应的时间；结束标签和未配置的标签不计时。标签名是去掉 `{`、`<`、`%`、`#`
+/// 等定界符之后的第一个标识符，例如 `{{< youtube id >}}` 和 `{% include a.html %}`
+/// 的标签名分别是 `youtube` 和 `include`。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TemplateTags {
+    seconds: BTreeMap<String, f64>,
+}
+
+impl TemplateTags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置标签 `name` 的阅读时间（秒）
+    pub fn tag(mut self, name: impl Into<String>, seconds: f64) -> Self {
+        self.seconds.insert(name.into(), seconds);
+        self
+    }
+
+    /// 标签 `name` 的阅读时间（秒），未配置时返回 `None`
+    pub fn seconds(&self, name: &str) -> Option<f64> {
+        self.seconds.get(name).copied()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, f64)> for TemplateTags {
+    fn from_iter<I: IntoIterator<Item = (K, f64)>>(iter: I) -> Self {
+        Self {
+            seconds: iter
+                .into_iter()
+                .map(|(name, seconds)| (name.into(), seconds))
+                .collect(),
+        }
+    }
+}
+
+/// 模板标签的标签名，结束标签返回 `None`
+pub(super) fn tag_name(tag: &str) -> Option<&str> {
+    let rest = tag.trim_start_matches(|c: char| {
+        c.is_whitespace() || matches!(c, '{' | '<' | '%' | '#' | '-' | '~' | '>' | '!')
+    });
+    if rest.starts_with('/') {
+        return None;
+    }
+    let len = rest
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(rest.len());
+    Some(&rest[..len]).filter(|name| !name.is_empty())
+}
+
+impl EventVisitor for TemplateTags {
+    fn visit(&mut self, event: &Event<'_>, _: &Counters) -> Visit {
+        let (Event::Html(html) | Event::InlineHtml(html)) = event else {
+            return Visit::count();
+        };
+        match tag_name(html).and_then(|name| Some((name, self.seconds(name)?))) {
+            Some((name, seconds)) => Visit::count().extra_seconds(seconds).tag(name),
+            None => Visit::count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_name() {
+        assert_eq!(tag_name("{{< youtube id=\"x\" >}}"), Some("youtube"));
+        assert_eq!(tag_name("{{% /note %}}"), None);
+        assert_eq!(tag_name("{%- include a.html -%}"), Some("include"));
+        assert_eq!(tag_name("{{#each items}}"), Some("each"));
+        assert_eq!(tag_name("{{ youtube(id=\"x\") }}"), Some("youtube"));
+        assert_eq!(tag_name("{{/each}}"), None);
+    }
+
+    #[test]
+    fn test_removals_offsets() {
+        let source = "前言 {{x}} 正文\n\n```\n{{x}}\n```\n";
+        let mut removals = Removals::default();
+        scan(
+            source,
+            &['{'],
+            &mut removals,
+            |_, _| false,
+            |removals, at| {
+                let end = find_close(source, at, "}}")?;
+                removals.tag(source, at..end);
+                Some(end)
+            },
+        );
+        let mut events = Vec::new();
+        removals.parse(source, &mut |event, range| {
+            if let Event::Text(CowStr::Borrowed(text)) = &event {
+                assert_eq!(&source[range.clone()], *text);
+            }
+            events.push(event);
+            ControlFlow::Continue(())
+        });
+        assert!(events.contains(&Event::InlineHtml(CowStr::Borrowed("{{x}}"))));
+        assert!(events.contains(&Event::Text(CowStr::from("前言  正文"))));
+        assert!(events.contains(&Event::Text(CowStr::Borrowed("{{x}}\n"))));
+    }
+}
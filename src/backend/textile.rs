@@ -0,0 +1,676 @@
+use super::markup::{
+    Line, Sink, attribute, closing_tag, code_block, element, end_tag, image, is_boundary,
+    is_closing, lines, offset_in, span, start_tag, text,
+};
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+
+/// 块标记的名称，`fn` 脚注另外处理
+const BLOCKS: &[&str] = &[
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "p",
+    "bq",
+    "bc",
+    "pre",
+    "notextile",
+    "table",
+    "###",
+];
+
+/// Textile 后端，需要启用 `textile` 特性
+///
+/// 按 Textile（以及 Redmine 的扩展）的块标记转换为对应的事件，计数规则与 Markdown 相同：
+/// `bc.`、`pre.` 和 `<pre><code class="语言">` 计为代码块，`!图片!` 计为图片，
+/// `"文字":URL` 和 `[[页面|文字]]` 只统计链接文字，`@代码@` 计为行内代码。
+/// 块和单元格的样式修饰（例如 `p(class).`、`_.`）以及 `###.` 注释不计入字数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, ReadSpeed, Textile};
+///
+/// let textile = "h1. 标题\n\n参见 \"文档\":https://example.com 和 @code@。\n\n!a.png(示意图)!\n\nbc(ruby). puts 1\n";
+/// let read_time = Estimator::new(ReadSpeed::default()).backend(Textile).estimate(textile);
+/// assert_eq!(read_time.word_count, 12);
+/// assert_eq!(read_time.image_count, 1);
+/// assert_eq!(read_time.code_block_count, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Textile;
+
+impl MarkdownBackend for Textile {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        Parser {
+            source: markdown,
+            sink,
+        }
+        .blocks(&lines(markdown));
+    }
+}
+
+/// 开头的类名、样式、语言和对齐修饰的字节长度，例如 `(class#id){color:red}[zh]<>`
+fn modifiers(text: &str) -> usize {
+    let mut i = 0;
+    loop {
+        let rest = &text[i..];
+        let len = match rest.as_bytes().first() {
+            Some(b'{') => rest.find('}').map(|end| end + 1),
+            Some(b'[') => rest.find(']').map(|end| end + 1),
+            Some(b'(') => Some(rest.find(')').map_or(1, |end| end + 1)),
+            Some(b'<' | b'>' | b'=' | b')') => Some(1),
+            _ => None,
+        };
+        match len {
+            Some(len) => i += len,
+            None => return i,
+        }
+    }
+}
+
+/// 行首的块标记：名称、修饰、是否为扩展块（`..`）和标记的字节长度
+fn signature(text: &str) -> Option<(&str, &str, bool, usize)> {
+    let name_len = text
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+        .unwrap_or(text.len());
+    let name = &text[..name_len];
+    let is_footnote = name
+        .strip_prefix("fn")
+        .is_some_and(|label| !label.is_empty() && label.bytes().all(|b| b.is_ascii_digit()));
+    if !is_footnote && !BLOCKS.contains(&name) {
+        return None;
+    }
+    let modifiers = &text[name_len..name_len + modifiers(&text[name_len..])];
+    let rest = text[name_len + modifiers.len()..].strip_prefix('.')?;
+    let (extended, mut rest) = match rest.strip_prefix('.') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    if name == "bq"
+        && let Some(cite) = rest.strip_prefix(':')
+    {
+        rest = cite.trim_start_matches(|c: char| !c.is_whitespace());
+    }
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let len = text.len() - rest.len() + rest.len().min(1);
+    Some((name, modifiers, extended, len))
+}
+
+/// 修饰中的类名作为代码块的语言，例如 `(ruby)` 和 `(language-ruby)`
+fn language(class: &str) -> &str {
+    let class = class.split_whitespace().next().unwrap_or_default();
+    let class = class.split('#').next().unwrap_or_default();
+    class.strip_prefix("language-").unwrap_or(class)
+}
+
+fn modifier_class(modifiers: &str) -> &str {
+    modifiers
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map_or("", |(class, _)| language(class))
+}
+
+/// 列表项标记的层级和字节长度（包括后面的空格）
+fn list_marker(text: &str) -> Option<(usize, usize)> {
+    let depth = text
+        .bytes()
+        .take_while(|b| matches!(b, b'*' | b'#'))
+        .count();
+    let rest = &text[depth..];
+    let rest = &rest[modifiers(rest)..];
+    (depth > 0 && rest.starts_with(' ')).then(|| (depth, text.len() - rest.len() + 1))
+}
+
+/// 单元格开头的 `_.`、`\2.`、`/3.` 和样式修饰之后的内容
+fn cell_content(cell: &str) -> &str {
+    let mut i = 0;
+    loop {
+        let rest = &cell[i..];
+        let len = match rest.as_bytes().first() {
+            Some(b'_' | b'^' | b'~') => 1,
+            Some(b'\\' | b'/') => 1 + rest[1..].bytes().take_while(u8::is_ascii_digit).count(),
+            _ => modifiers(rest),
+        };
+        if len == 0 {
+            break;
+        }
+        i += len;
+    }
+    match cell[i..].strip_prefix('.') {
+        Some(rest) if i > 0 && (rest.is_empty() || rest.starts_with(' ')) => rest,
+        _ => cell,
+    }
+}
+
+/// 位于 `text` 开头的 `"文字(标题)":URL`：文字、URL 和字节长度，URL 结尾的标点不属于链接
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let close = text.strip_prefix('"')?.find("\":")? + 1;
+    let mut label = &text[1..close];
+    if label.is_empty() || label.contains('"') {
+        return None;
+    }
+    if label.ends_with(')')
+        && let Some(open) = label.rfind('(')
+    {
+        label = label[..open].trim_end();
+    }
+    let after = &text[close + 2..];
+    let url = &after[..after.find(char::is_whitespace).unwrap_or(after.len())];
+    let url = url.trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    let url = match url.strip_suffix(')') {
+        Some(stripped) if !url.contains('(') => stripped,
+        _ => url,
+    };
+    (!url.is_empty()).then(|| (label, url, close + 2 + url.len()))
+}
+
+/// 位于 `text` 开头的 `!(class)URL(替代文本)!:链接`：URL、替代文本和字节长度
+fn image_at(text: &str) -> Option<(&str, &str, usize)> {
+    let body = text.strip_prefix('!')?;
+    let start = modifiers(body);
+    let end = start + body[start..].find('!')?;
+    let inner = &body[start..end];
+    let (url, alt) = match inner.split_once('(') {
+        Some((url, alt)) if alt.ends_with(')') => (url, &alt[..alt.len() - 1]),
+        _ => (inner, ""),
+    };
+    if url.is_empty() || url.contains(char::is_whitespace) {
+        return None;
+    }
+    let mut len = end + 2;
+    if let Some(target) = text[len..].strip_prefix(':') {
+        len += 1 + target.find(char::is_whitespace).unwrap_or(target.len());
+    }
+    Some((url, alt, len))
+}
+
+struct Parser<'a, 's> {
+    source: &'a str,
+    sink: Sink<'s, 'a>,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn blocks(&mut self, lines: &[Line<'a>]) {
+        // 从 `offset` 开始的第一行
+        let line_at = |offset: usize| {
+            (0..lines.len())
+                .find(|&j| lines[j].start >= offset)
+                .unwrap_or(lines.len())
+        };
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i];
+            if line.is_blank() {
+                i += 1;
+            } else if let Some((name, modifiers, extended, len)) = signature(line.text) {
+                // 扩展块一直延续到下一个块标记
+                let end = (i + 1..lines.len())
+                    .find(|&j| {
+                        if extended {
+                            signature(lines[j].text).is_some()
+                        } else {
+                            lines[j].is_blank()
+                        }
+                    })
+                    .unwrap_or(lines.len());
+                let mut body = vec![line.skip(len)];
+                body.extend_from_slice(&lines[i + 1..end]);
+                self.block(name, modifiers, &body, span(&lines[i..end]));
+                i = end;
+            } else if let Some((pre, len)) = element(line.text)
+                && pre.name.eq_ignore_ascii_case("pre")
+                && !pre.closing
+            {
+                let end = self.pre(line.start, len);
+                i = line_at(end);
+            } else if list_marker(line.text).is_some() {
+                let end = (i..lines.len())
+                    .find(|&j| lines[j].is_blank())
+                    .unwrap_or(lines.len());
+                let mut j = i;
+                while j < end {
+                    j = self.list(&lines[..end], j, 0);
+                }
+                i = end;
+            } else if line.text.trim_start().starts_with('|') {
+                let end = (i..lines.len())
+                    .find(|&j| lines[j].is_blank())
+                    .unwrap_or(lines.len());
+                self.table(&lines[i..end]);
+                i = end;
+            } else {
+                let end = (i + 1..lines.len())
+                    .find(|&j| lines[j].is_blank() || signature(lines[j].text).is_some())
+                    .unwrap_or(lines.len());
+                self.paragraph(&lines[i..end]);
+                i = end;
+            }
+        }
+    }
+
+    /// 块标记之后的内容，`lines` 的第一行已经去掉块标记
+    fn block(
+        &mut self,
+        name: &'a str,
+        modifiers: &'a str,
+        lines: &[Line<'a>],
+        range: Range<usize>,
+    ) {
+        match name {
+            "p" => self.paragraphs(lines),
+            "bq" => {
+                start_tag(self.sink, &Tag::BlockQuote, range.clone());
+                self.paragraphs(lines);
+                end_tag(self.sink, &Tag::BlockQuote, range);
+            }
+            "bc" => code_block(self.sink, modifier_class(modifiers), lines),
+            "pre" => code_block(self.sink, "", lines),
+            "notextile" => {
+                start_tag(self.sink, &Tag::HtmlBlock, range.clone());
+                for line in lines.iter().filter(|line| !line.is_blank()) {
+                    (self.sink)(
+                        Event::Html(CowStr::Borrowed(line.raw)),
+                        line.start..line.start + line.raw.len(),
+                    );
+                }
+                end_tag(self.sink, &Tag::HtmlBlock, range);
+            }
+            "###" => {}
+            "table" => self.blocks(lines),
+            _ => {
+                if let Some(label) = name.strip_prefix("fn") {
+                    let tag = Tag::FootnoteDefinition(CowStr::Borrowed(label));
+                    start_tag(self.sink, &tag, range.clone());
+                    self.paragraphs(lines);
+                    end_tag(self.sink, &tag, range);
+                } else {
+                    let level = name[1..].parse().unwrap_or(1);
+                    let tag = Tag::Heading {
+                        level: HeadingLevel::try_from(level).unwrap_or(HeadingLevel::H6),
+                        id: None,
+                        classes: Vec::new(),
+                        attrs: Vec::new(),
+                    };
+                    let content = span(lines);
+                    start_tag(self.sink, &tag, range.clone());
+                    self.inline(&self.source[content.clone()], content.start);
+                    end_tag(self.sink, &tag, range);
+                }
+            }
+        }
+    }
+
+    /// 以空行分隔的多个段落
+    fn paragraphs(&mut self, lines: &[Line<'a>]) {
+        for paragraph in lines.split(Line::is_blank) {
+            if !paragraph.is_empty() {
+                self.paragraph(paragraph);
+            }
+        }
+    }
+
+    fn paragraph(&mut self, lines: &[Line<'a>]) {
+        let range = span(lines);
+        let source = self.source;
+        start_tag(self.sink, &Tag::Paragraph, range.clone());
+        self.inline(source[range.clone()].trim(), range.start);
+        end_tag(self.sink, &Tag::Paragraph, range);
+    }
+
+    /// 从 `start` 开始、开始标签长度为 `len` 的 `<pre>` 或 `<pre><code>`，返回其结束位置
+    fn pre(&mut self, start: usize, len: usize) -> usize {
+        let source = self.source;
+        let mut body_start = start + len;
+        let mut close_name = "pre";
+        let mut lang = "";
+        if let Some((code, code_len)) = element(&source[body_start..])
+            && code.name.eq_ignore_ascii_case("code")
+            && !code.closing
+        {
+            lang = attribute(code.attributes, "class").map_or("", language);
+            body_start += code_len;
+            close_name = "code";
+        }
+        let (close, close_len) = closing_tag(&source[body_start..], close_name)
+            .unwrap_or((source.len() - body_start, 0));
+        let body = body_start..body_start + close;
+        let mut end = body.end + close_len;
+        if close_name == "code"
+            && let Some((at, pre_len)) = closing_tag(&source[end..], "pre")
+            && source[end..end + at].trim().is_empty()
+        {
+            end += at + pre_len;
+        }
+        let kind = CodeBlockKind::Fenced(CowStr::Borrowed(lang));
+        (self.sink)(Event::Start(Tag::CodeBlock(kind)), start..end);
+        let code = source[body.clone()].trim_matches(['\r', '\n']);
+        if !code.is_empty() {
+            text(self.sink, code, body.start + offset_in(&source[body], code));
+        }
+        (self.sink)(Event::End(TagEnd::CodeBlock), start..end);
+        end
+    }
+
+    /// 层级大于 `depth` 的连续列表行，没有标记的行接在上一项之后
+    fn list(&mut self, lines: &[Line<'a>], mut i: usize, depth: usize) -> usize {
+        let ordered = lines[i].text.as_bytes().get(depth) == Some(&b'#');
+        let tag = Tag::List(ordered.then_some(1));
+        let list_start = i;
+        start_tag(self.sink, &tag, lines[i].range());
+        let mut item: Option<Range<usize>> = None;
+        while let Some(line) = lines.get(i) {
+            match list_marker(line.text) {
+                Some((level, _)) if level <= depth => break,
+                Some((level, _)) if level > depth + 1 && item.is_some() => {
+                    i = self.list(lines, i, depth + 1);
+                    continue;
+                }
+                Some((_, width)) => {
+                    if let Some(range) = item.take() {
+                        end_tag(self.sink, &Tag::Item, range);
+                    }
+                    item = Some(line.range());
+                    start_tag(self.sink, &Tag::Item, line.range());
+                    let content = line.text[width..].trim();
+                    self.inline(content, line.offset_of(content));
+                }
+                None => {
+                    let (content, offset) = line.trimmed();
+                    (self.sink)(Event::SoftBreak, offset..offset);
+                    self.inline(content, offset);
+                }
+            }
+            i += 1;
+        }
+        if let Some(range) = item {
+            end_tag(self.sink, &Tag::Item, range);
+        }
+        end_tag(self.sink, &tag, span(&lines[list_start..i]));
+        i
+    }
+
+    /// `|…|` 表格，行和单元格的修饰不计入字数
+    fn table(&mut self, lines: &[Line<'a>]) {
+        let range = span(lines);
+        let table = Tag::Table(Vec::new());
+        start_tag(self.sink, &table, range.clone());
+        for line in lines {
+            let mut row = line.text.trim();
+            if !row.starts_with('|') {
+                let Some(rest) = row[modifiers(row)..].strip_prefix('.') else {
+                    continue;
+                };
+                row = rest.trim_start();
+            }
+            let row = row.strip_prefix('|').unwrap_or(row);
+            let row = row.strip_suffix('|').unwrap_or(row);
+            start_tag(self.sink, &Tag::TableRow, line.range());
+            for cell in row.split('|') {
+                start_tag(self.sink, &Tag::TableCell, line.range());
+                let content = cell_content(cell).trim();
+                self.inline(content, line.offset_of(content));
+                end_tag(self.sink, &Tag::TableCell, line.range());
+            }
+            end_tag(self.sink, &Tag::TableRow, line.range());
+        }
+        end_tag(self.sink, &table, range);
+    }
+
+    fn link(&mut self, url: &'a str, label: &'a str, offset: usize, range: Range<usize>) {
+        let tag = Tag::Link {
+            link_type: LinkType::Inline,
+            dest_url: CowStr::Borrowed(url),
+            title: CowStr::Borrowed(""),
+            id: CowStr::Borrowed(""),
+        };
+        start_tag(self.sink, &tag, range.clone());
+        self.inline(label, offset);
+        end_tag(self.sink, &tag, range);
+    }
+
+    /// 行内文本，去掉强调标记、图片和链接语法
+    fn inline(&mut self, source: &'a str, offset: usize) {
+        let bytes = source.as_bytes();
+        let mut plain = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let rest = &source[i..];
+            let before = i.checked_sub(1).map(|j| bytes[j]);
+            if let Some(page) = rest.strip_prefix("[[")
+                && let Some(len) = page.find("]]")
+            {
+                // Redmine 的 wiki 链接
+                text(self.sink, &source[plain..i], offset + plain);
+                let page = &page[..len];
+                let (target, label) = page.split_once('|').unwrap_or((page, page));
+                let label = label.trim();
+                let range = offset + i..offset + i + len + 4;
+                self.link(target, label, offset + offset_in(source, label), range);
+                i += len + 4;
+                plain = i;
+            } else if rest.starts_with("[\"")
+                && let Some(close) = rest.find(']')
+                && let Some((label, url, len)) = link(&rest[1..close])
+                && len == close - 1
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                let range = offset + i..offset + i + close + 1;
+                self.link(url, label, offset + offset_in(source, label), range);
+                i += close + 1;
+                plain = i;
+            } else if let Some((label, url, len)) = link(rest) {
+                text(self.sink, &source[plain..i], offset + plain);
+                let range = offset + i..offset + i + len;
+                self.link(url, label, offset + offset_in(source, label), range);
+                i += len;
+                plain = i;
+            } else if bytes[i] == b'!'
+                && is_boundary(before)
+                && let Some((url, alt, len)) = image_at(rest)
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                image(self.sink, url, alt, offset + i..offset + i + len);
+                i += len;
+                plain = i;
+            } else if bytes[i] == b'@'
+                && is_boundary(before)
+                && let Some(len) = rest[1..].find('@')
+                && len > 0
+                && is_closing(bytes.get(i + len + 2).copied())
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                (self.sink)(
+                    Event::Code(CowStr::Borrowed(&rest[1..len + 1])),
+                    offset + i + 1..offset + i + 1 + len,
+                );
+                i += len + 2;
+                plain = i;
+            } else if let Some(raw) = rest.strip_prefix("==")
+                && let Some(len) = raw.find("==")
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                text(self.sink, &raw[..len], offset + i + 2);
+                i += len + 4;
+                plain = i;
+            } else if bytes[i] == b'['
+                && before.is_some_and(|b| !b.is_ascii_whitespace())
+                && let Some(len) = rest[1..].find(']')
+                && len > 0
+                && rest[1..len + 1].bytes().all(|b| b.is_ascii_digit())
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                (self.sink)(
+                    Event::FootnoteReference(CowStr::Borrowed(&rest[1..len + 1])),
+                    offset + i..offset + i + len + 2,
+                );
+                i += len + 2;
+                plain = i;
+            } else if bytes[i] == b'<'
+                && let Some((_, len)) = element(rest)
+            {
+                text(self.sink, &source[plain..i], offset + plain);
+                (self.sink)(
+                    Event::InlineHtml(CowStr::Borrowed(&rest[..len])),
+                    offset + i..offset + i + len,
+                );
+                i += len;
+                plain = i;
+            } else if matches!(bytes[i], b'*' | b'_' | b'+' | b'-' | b'^' | b'~' | b'%') {
+                // `**` 和 `__` 等连续的标记作为一个整体
+                let run = rest.bytes().take_while(|&b| b == bytes[i]).count();
+                let after = bytes.get(i + run).copied();
+                let opens = is_boundary(before) && !is_boundary(after);
+                let closes = !is_boundary(before) && is_closing(after);
+                if opens || closes {
+                    text(self.sink, &source[plain..i], offset + plain);
+                    plain = i + run;
+                    if opens && bytes[i] == b'%' {
+                        plain += modifiers(&source[plain..]);
+                    }
+                }
+                i += run;
+            } else {
+                i += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        text(self.sink, &source[plain..], offset + plain);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, estimate};
+
+    fn estimate_textile(textile: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Textile)
+            .estimate(textile)
+    }
+
+    #[test]
+    fn test_textile_matches_markdown() {
+        let textile = r#"h1. 项目说明
+
+h2(#intro){color:red}. 简介
+
+这是 *重要* 的 _内容_，参见 "官方文档(标题)":https://example.com/docs. 和 [[Wiki|维基页面]]，
+运行 @cargo build@ 即可。
+
+!{width:50%}images/arch.png(架构图)!:https://example.com
+
+* 第一项
+** 子项
+* 第二项
+
+# 编号
+
+bc(language-rust). fn main() {}
+
+<pre><code class="python">
+print("hello")
+</code></pre>
+
+bq. 引用
+
+|_. 表头 |_. 列 |
+|{background:#ddd}. 单元 | 格 |
+
+###. 注释不计入
+
+p>. 结束
+"#;
+        let markdown = r#"# 项目说明
+
+## 简介
+
+这是 **重要** 的 *内容*，参见 [官方文档](https://example.com/docs). 和 [维基页面](Wiki)，
+运行 `cargo build` 即可。
+
+![架构图](images/arch.png)
+
+- 第一项
+  - 子项
+- 第二项
+
+1. 编号
+
+```rust
+fn main() {}
+```
+
+```python
+print("hello")
+```
+
+> 引用
+
+表头 列
+
+单元 格
+
+结束
+"#;
+        assert_eq!(estimate_textile(textile), estimate(markdown));
+    }
+
+    #[test]
+    fn test_textile_extended_blocks() {
+        let textile =
+            "bc.. fn main() {\n\n    println!(\"hi\");\n}\n\np. 正文内容\n\nfn1. 脚注内容[2]\n";
+        let read_time = estimate_textile(textile);
+        assert_eq!(read_time.code_block_count, 1);
+        assert_eq!(
+            read_time.word_count,
+            estimate("正文内容\n\n脚注内容").word_count
+        );
+    }
+
+    #[test]
+    fn test_textile_syntax() {
+        assert_eq!(
+            signature("h2(class). 标题"),
+            Some(("h2", "(class)", false, 11))
+        );
+        assert_eq!(
+            signature("bq..:https://x.com 引用"),
+            Some(("bq", "", true, 19))
+        );
+        assert_eq!(signature("fn12. 脚注").map(|s| s.0), Some("fn12"));
+        assert!(signature("pretty. 不是块").is_none());
+        assert!(signature("p.s. 不是块").is_none());
+        assert_eq!(cell_content("_. 表头"), " 表头");
+        assert_eq!(cell_content("\\2. 合并"), " 合并");
+        assert_eq!(cell_content("2023. 年份"), "2023. 年份");
+        assert_eq!(
+            link("\"文字\":http://a.com/x)."),
+            Some(("文字", "http://a.com/x", 23))
+        );
+        assert!(image_at("! 感叹!").is_none());
+        assert_eq!(
+            estimate_textile("Wow! That's great!"),
+            estimate("Wow! That's great!")
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use super::{MarkdownBackend, until_break};
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Tag, TagEnd};
+use std::cell::Cell;
+use std::ops::{ControlFlow, Range};
+use tree_sitter::Node;
+use tree_sitter_md::{MarkdownParser, MarkdownTree};
+
+/// 基于 tree-sitter-markdown 的后端，需要启用 `tree-sitter` 特性
+///
+/// 编辑器通常已经维护了一棵 tree-sitter 语法树，通过 [`TreeSitter::with_tree`] 直接复用这棵树，
+/// 可以避免重复解析，树随编辑增量更新后重新估算即可。此时传给
+/// [`Estimator::estimate`](crate::Estimator::estimate) 的必须是生成这棵树的源文本。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, ReadSpeed, TreeSitter, estimate};
+/// use tree_sitter_md::MarkdownParser;
+///
+/// let source = "# 标题\n\n这是**内容**";
+/// let tree = MarkdownParser::default().parse(source.as_bytes(), None).unwrap();
+///
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(TreeSitter::with_tree(&tree))
+///     .estimate(source);
+/// assert_eq!(read_time, estimate(source));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreeSitter<'t> {
+    tree: Option<&'t MarkdownTree>,
+}
+
+impl<'t> TreeSitter<'t> {
+    /// 每次估算时重新解析源文本
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 复用已有的语法树
+    ///
+    /// 语法树中的位置对应完整的源文本，因此估算时不会切掉开头的 front matter，
+    /// 结果中的 `front_matter_bytes` 为 0，front matter 本身仍然不计入字数。
+    pub fn with_tree(tree: &'t MarkdownTree) -> Self {
+        Self { tree: Some(tree) }
+    }
+}
+
+impl MarkdownBackend for TreeSitter<'_> {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let stopped = Cell::new(false);
+        let sink = &mut until_break(sink, &stopped);
+        let parsed;
+        let tree = match self.tree {
+            Some(tree) => tree,
+            None => match MarkdownParser::default().parse(markdown.as_bytes(), None) {
+                Some(tree) => {
+                    parsed = tree;
+                    &parsed
+                }
+                None => return,
+            },
+        };
+        let mut walker = Walker {
+            tree,
+            source: markdown,
+            sink,
+        };
+        walker.block(tree.block_tree().root_node());
+    }
+
+    /// 复用的语法树按完整的源文本记录位置，不能只解析跳过 front matter 之后的部分；
+    /// 语法本身会识别 front matter，遍历时忽略即可
+    fn front_matter(&self) -> bool {
+        self.tree.is_none()
+    }
+}
+
+/// 遍历块级树和行内树，转换为对应的 pulldown-cmark 事件
+struct Walker<'a, 't, 's> {
+    tree: &'t MarkdownTree,
+    source: &'a str,
+    sink: &'s mut dyn FnMut(Event<'a>, Range<usize>),
+}
+
+impl<'a> Walker<'a, '_, '_> {
+    fn text(&self, node: Node<'_>) -> &'a str {
+        self.source.get(node.byte_range()).unwrap_or_default()
+    }
+
+    fn emit(&mut self, event: Event<'a>, range: Range<usize>) {
+        (self.sink)(event, range)
+    }
+
+    fn wrap(&mut self, tag: Tag<'a>, node: Node<'_>, children: fn(&mut Self, Node<'_>)) {
+        let end = tag.to_end();
+        self.emit(Event::Start(tag), node.byte_range());
+        children(self, node);
+        self.emit(Event::End(end), node.byte_range());
+    }
+
+    fn block_children(&mut self, node: Node<'_>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.block(child);
+        }
+    }
+
+    fn block(&mut self, node: Node<'_>) {
+        match node.kind() {
+            "minus_metadata" | "plus_metadata" | "link_reference_definition" => {}
+            "paragraph" => self.wrap(Tag::Paragraph, node, Self::block_children),
+            "atx_heading" | "setext_heading" => {
+                self.wrap(heading(node), node, Self::heading_content)
+            }
+            "block_quote" => self.wrap(Tag::BlockQuote, node, Self::block_children),
+            "list" => {
+                let tag = Tag::List(list_start(node, self.source));
+                self.wrap(tag, node, Self::block_children)
+            }
+            "list_item" => self.wrap(Tag::Item, node, Self::block_children),
+            "task_list_marker_checked" => self.emit(Event::TaskListMarker(true), node.byte_range()),
+            "task_list_marker_unchecked" => {
+                self.emit(Event::TaskListMarker(false), node.byte_range())
+            }
+            "fenced_code_block" | "indented_code_block" => {
+                let mut cursor = node.walk();
+                let info = node
+                    .named_children(&mut cursor)
+                    .find(|child| child.kind() == "info_string")
+                    .map(|child| self.text(child));
+                let content = if node.kind() == "fenced_code_block" {
+                    let mut cursor = node.walk();
+                    node.named_children(&mut cursor)
+                        .find(|child| child.kind() == "code_fence_content")
+                        .map(|child| self.text(child))
+                        .unwrap_or_default()
+                } else {
+                    self.text(node)
+                };
+                let kind = match node.kind() {
+                    "fenced_code_block" => CodeBlockKind::Fenced(info.unwrap_or_default().into()),
+                    _ => CodeBlockKind::Indented,
+                };
+                self.emit(Event::Start(Tag::CodeBlock(kind)), node.byte_range());
+                self.emit(Event::Text(content.into()), node.byte_range());
+                self.emit(Event::End(TagEnd::CodeBlock), node.byte_range());
+            }
+            "html_block" => {
+                self.emit(Event::Start(Tag::HtmlBlock), node.byte_range());
+                self.emit(Event::Html(self.text(node).into()), node.byte_range());
+                self.emit(Event::End(TagEnd::HtmlBlock), node.byte_range());
+            }
+            "thematic_break" => self.emit(Event::Rule, node.byte_range()),
+            "pipe_table" => self.wrap(Tag::Table(Vec::new()), node, Self::block_children),
+            "pipe_table_header" => self.wrap(Tag::TableHead, node, Self::block_children),
+            "pipe_table_row" => self.wrap(Tag::TableRow, node, Self::block_children),
+            "pipe_table_cell" => self.wrap(Tag::TableCell, node, Self::inline_root),
+            "inline" => self.inline_root(node),
+            "document" | "section" => self.block_children(node),
+            _ => {}
+        }
+    }
+
+    /// 标题的内容：ATX 标题的 `inline` 子节点或 Setext 标题的段落内容
+    fn heading_content(&mut self, node: Node<'_>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                "inline" => self.inline_root(child),
+                "paragraph" => self.block_children(child),
+                _ => {}
+            }
+        }
+    }
+
+    /// 切换到块级节点对应的行内树
+    fn inline_root(&mut self, node: Node<'_>) {
+        let tree = self.tree;
+        if let Some(inline) = tree.inline_tree(&node) {
+            self.inline_content(inline.root_node());
+        }
+    }
+
+    /// 行内容器的内容：具名子节点之间的文本，以及递归转换的子节点
+    fn inline_content(&mut self, node: Node<'_>) {
+        let mut offset = node.start_byte();
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            self.inline_text(offset, child.start_byte());
+            self.inline(child);
+            offset = child.end_byte();
+        }
+        self.inline_text(offset, node.end_byte());
+    }
+
+    fn inline_text(&mut self, start: usize, end: usize) {
+        match self.source.get(start..end) {
+            Some(text) if !text.is_empty() => self.emit(Event::Text(text.into()), start..end),
+            _ => {}
+        }
+    }
+
+    fn inline(&mut self, node: Node<'_>) {
+        match node.kind() {
+            "emphasis_delimiter"
+            | "code_span_delimiter"
+            | "link_destination"
+            | "link_title"
+            | "link_label" => {}
+            "emphasis" => self.wrap(Tag::Emphasis, node, Self::inline_content),
+            "strong_emphasis" => self.wrap(Tag::Strong, node, Self::inline_content),
+            "strikethrough" => self.wrap(Tag::Strikethrough, node, Self::inline_content),
+            "code_span" => {
+                let mut cursor = node.walk();
+                let delimiters: Vec<_> = node
+                    .named_children(&mut cursor)
+                    .filter(|child| child.kind() == "code_span_delimiter")
+                    .collect();
+                let (start, end) = match (delimiters.first(), delimiters.last()) {
+                    (Some(open), Some(close)) if delimiters.len() > 1 => {
+                        (open.end_byte(), close.start_byte())
+                    }
+                    _ => (node.start_byte(), node.end_byte()),
+                };
+                let code = self.source.get(start..end).unwrap_or_default();
+                self.emit(Event::Code(code.into()), node.byte_range());
+            }
+            "inline_link"
+            | "full_reference_link"
+            | "collapsed_reference_link"
+            | "shortcut_link"
+            | "uri_autolink"
+            | "email_autolink" => {
+                let tag = Tag::Link {
+                    link_type: LinkType::Inline,
+                    dest_url: self.destination(node),
+                    title: CowStr::Borrowed(""),
+                    id: CowStr::Borrowed(""),
+                };
+                self.wrap(tag, node, Self::link_text);
+            }
+            "image" => {
+                let tag = Tag::Image {
+                    link_type: LinkType::Inline,
+                    dest_url: self.destination(node),
+                    title: CowStr::Borrowed(""),
+                    id: CowStr::Borrowed(""),
+                };
+                self.wrap(tag, node, Self::link_text);
+            }
+            "html_tag" => self.emit(Event::InlineHtml(self.text(node).into()), node.byte_range()),
+            "hard_line_break" => self.emit(Event::HardBreak, node.byte_range()),
+            "backslash_escape" => {
+                let escaped = self.text(node).get(1..).unwrap_or_default();
+                self.emit(Event::Text(escaped.into()), node.byte_range());
+            }
+            _ => self.inline_content(node),
+        }
+    }
+
+    /// 链接和图片的文本内容
+    fn link_text(&mut self, node: Node<'_>) {
+        if node.kind().ends_with("_autolink") {
+            let text = self.text(node);
+            let inner = text.get(1..text.len().saturating_sub(1)).unwrap_or(text);
+            self.emit(Event::Text(inner.into()), node.byte_range());
+            return;
+        }
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if matches!(child.kind(), "link_text" | "image_description") {
+                self.inline_content(child);
+            }
+        }
+    }
+
+    fn destination(&self, node: Node<'_>) -> CowStr<'a> {
+        let mut cursor = node.walk();
+        let destination = node
+            .named_children(&mut cursor)
+            .find(|child| child.kind() == "link_destination")
+            .map(|child| self.text(child))
+            .unwrap_or_default();
+        CowStr::from(String::from(destination))
+    }
+}
+
+fn heading<'a>(node: Node<'_>) -> Tag<'a> {
+    let mut cursor = node.walk();
+    let level = node
+        .named_children(&mut cursor)
+        .find_map(|child| match child.kind() {
+            "setext_h1_underline" => Some(1),
+            "setext_h2_underline" => Some(2),
+            kind => kind
+                .strip_prefix("atx_h")
+                .and_then(|rest| rest.strip_suffix("_marker"))
+                .and_then(|level| level.parse().ok()),
+        })
+        .and_then(|level: usize| HeadingLevel::try_from(level).ok())
+        .unwrap_or(HeadingLevel::H1);
+    Tag::Heading {
+        level,
+        id: None,
+        classes: Vec::new(),
+        attrs: Vec::new(),
+    }
+}
+
+/// 有序列表的起始序号，无序列表返回 `None`
+fn list_start(node: Node<'_>, source: &str) -> Option<u64> {
+    let item = node.named_child(0)?;
+    let marker = item.named_child(0)?;
+    if !matches!(marker.kind(), "list_marker_dot" | "list_marker_parenthesis") {
+        return None;
+    }
+    let text = source.get(marker.byte_range())?;
+    text.trim_end_matches(|c: char| !c.is_ascii_digit())
+        .trim()
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, estimate};
+
+    #[test]
+    fn test_tree_sitter_matches_pulldown() {
+        let markdown = "# 标题 *强调*\n\n这是**内容**，包含`代码`和[链接](u)。\n\n![图片](a.png)\n\n> 引用\n\n- 列表\n\n```rust\nfn main() {}\n```\n";
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(TreeSitter::new())
+            .estimate(markdown);
+        assert_eq!(read_time, estimate(markdown));
+    }
+
+    #[test]
+    fn test_tree_sitter_reused_tree_with_front_matter() {
+        let source = "---\ntitle: 标题\ntags: [a, b]\n---\n# 正文\n\n这是**内容**，包含`代码`。\n";
+        let tree = MarkdownParser::default()
+            .parse(source.as_bytes(), None)
+            .unwrap();
+        let reused = Estimator::new(ReadSpeed::default())
+            .backend(TreeSitter::with_tree(&tree))
+            .estimate(source);
+        let expected = estimate(source);
+        assert_eq!(reused.word_count, expected.word_count);
+        assert_eq!(reused.total_seconds, expected.total_seconds);
+        // 复用语法树时不会切掉 front matter，也就不再统计其长度
+        assert_eq!(reused.front_matter_bytes, 0);
+    }
+}
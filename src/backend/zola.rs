@@ -0,0 +1,153 @@
+use super::MarkdownBackend;
+use super::template::{Removals, find_close, scan};
+use pulldown_cmark::Event;
+use std::ops::{ControlFlow, Range};
+
+/// 带 Zola 短代码的 Markdown 后端，需要启用 `zola` 特性
+///
+/// 去掉 `{{ name(…) }}` 和 `{% name(…) %}…{% end %}` 短代码调用之后按 Markdown 统计，
+/// 带内容的短代码之间的内容正常计入字数，`{# … #}` 注释不计入字数。
+/// 短代码以 HTML 事件输出，可以配合 [`TemplateTags`](crate::TemplateTags) 按短代码名设置固定时间。
+/// 与 Zola 一致，不是短代码调用的 `{{ … }}` 保持原样。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, ReadSpeed, TemplateTags, Zola};
+///
+/// let markdown = "{{ youtube(id=\"dQw4w9WgXcQ\") }}\n\n{% quote(author=\"佚名\") %}\n引用内容\n{% end %}";
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .backend(Zola)
+///     .visitor(TemplateTags::new().tag("youtube", 120.0))
+///     .estimate(markdown);
+/// assert_eq!(read_time.word_count, 4);
+/// assert_eq!(read_time.custom_elements["youtube"], 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Zola;
+
+impl MarkdownBackend for Zola {
+    fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+        self.parse_with_offsets(markdown, &mut |event, _| {
+            sink(event);
+            ControlFlow::Continue(())
+        });
+    }
+
+    fn parse_with_offsets<'a>(
+        &self,
+        markdown: &'a str,
+        sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+    ) {
+        let source = markdown;
+        let mut removals = Removals::default();
+        scan(
+            source,
+            &['{'],
+            &mut removals,
+            |_, _| false,
+            |removals, at| {
+                let rest = &source[at..];
+                if rest.starts_with("{#") {
+                    let end = at + 2 + source[at + 2..].find("#}")? + 2;
+                    removals.remove(at..end);
+                    return Some(end);
+                }
+                let (open, close) = if rest.starts_with("{{/*") {
+                    ("{{/*", "*/}}")
+                } else if rest.starts_with("{%/*") {
+                    ("{%/*", "*/%}")
+                } else if rest.starts_with("{{") {
+                    ("{{", "}}")
+                } else if rest.starts_with("{%") {
+                    ("{%", "%}")
+                } else {
+                    return None;
+                };
+                let end = find_close(source, at + open.len(), close)?;
+                let inner = source[at + open.len()..end - close.len()].trim();
+                if !(is_invocation(inner) || open == "{%" && inner == "end") {
+                    return None;
+                }
+                removals.tag(source, at..end);
+                Some(end)
+            },
+        );
+        removals.parse(markdown, sink);
+    }
+}
+
+/// `inner` 是否为 `name(…)` 形式的短代码调用
+fn is_invocation(inner: &str) -> bool {
+    let name_len = inner
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(inner.len());
+    name_len > 0 && inner[name_len..].trim_start().starts_with('(') && inner.ends_with(')')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, ReadTime, TemplateTags, estimate};
+
+    fn estimate_zola(markdown: &str) -> ReadTime {
+        Estimator::new(ReadSpeed::default())
+            .backend(Zola)
+            .estimate(markdown)
+    }
+
+    #[test]
+    fn test_zola_matches_markdown() {
+        let zola = r#"# 标题
+
+{{ youtube(id="dQw4w9WgXcQ", autoplay=true) }}
+
+{% note(kind="warning") %}
+这是**重要**内容，{# 注释 #}不要忽略。
+{% end %}
+
+段落中的 {{ icon(name="star") }} 图标，以及 {{ not_a_shortcode }}。
+
+```
+{{ youtube(id="a") }}
+```
+
+{{/* gist(url="b") */}}
+"#;
+        let markdown = r#"# 标题
+
+这是**重要**内容，不要忽略。
+
+段落中的  图标，以及 {{ not_a_shortcode }}。
+
+```
+{{ youtube(id="a") }}
+```
+"#;
+        assert_eq!(estimate_zola(zola), estimate(markdown));
+    }
+
+    #[test]
+    fn test_zola_shortcode_weights() {
+        let read_time = Estimator::new(ReadSpeed::default())
+            .backend(Zola)
+            .visitor(
+                TemplateTags::new()
+                    .tag("youtube", 90.0)
+                    .tag("gallery", 30.0),
+            )
+            .estimate("{{ youtube(id=\"a\") }}\n\n{% gallery() %}\n图集\n{% end %}\n");
+        assert_eq!(read_time.custom_elements["youtube"], 1);
+        assert_eq!(read_time.custom_elements["gallery"], 1);
+        assert_eq!(read_time.extra_seconds, 120.0);
+        assert_eq!(read_time.word_count, 2);
+    }
+
+    #[test]
+    fn test_is_invocation() {
+        assert!(is_invocation("youtube(id=\"a\")"));
+        assert!(is_invocation("gallery()"));
+        assert!(!is_invocation("page.title"));
+        assert!(!is_invocation("(1 + 2)"));
+    }
+}
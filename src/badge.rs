@@ -0,0 +1,129 @@
+use crate::ReadTime;
+use crate::toc::push_escaped;
+use std::fmt::Write;
+
+/// 徽章右侧的背景色
+const COLOR: &str = "#007ec6";
+
+impl ReadTime {
+    /// 渲染为 shields.io 风格的 SVG 徽章
+    ///
+    /// 左侧为 `label`，右侧为格式化的阅读时间。文本宽度按 11px Verdana 估算，
+    /// 中日韩等全角字符按接近两倍的宽度计算，生成的文件可以直接嵌入 README 或文档页面。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::ReadTime;
+    ///
+    /// let svg = ReadTime::builder().total_seconds(150).build().to_badge_svg("阅读时间");
+    /// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    /// assert!(svg.contains("<title>阅读时间: 2分30秒</title>"));
+    /// ```
+    pub fn to_badge_svg(&self, label: &str) -> String {
+        let label_width = text_width(label) + 10;
+        let value_width = text_width(&self.formatted) + 10;
+        let width = label_width + value_width;
+
+        let mut title = String::new();
+        push_escaped(&mut title, label);
+        title.push_str(": ");
+        push_escaped(&mut title, &self.formatted);
+
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" aria-label=\"{title}\">\
+             <title>{title}</title>\
+             <linearGradient id=\"s\" x2=\"0\" y2=\"100%\">\
+             <stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/>\
+             </linearGradient>\
+             <clipPath id=\"r\"><rect width=\"{width}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>\
+             <g clip-path=\"url(#r)\">\
+             <rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/>\
+             <rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" fill=\"{COLOR}\"/>\
+             <rect width=\"{width}\" height=\"20\" fill=\"url(#s)\"/>\
+             </g>\
+             <g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">",
+        );
+        for (x, text) in [
+            (label_width / 2, label),
+            (label_width + value_width / 2, self.formatted.as_str()),
+        ] {
+            let _ = write!(out, "<text x=\"{x}\" y=\"14\">");
+            push_escaped(&mut out, text);
+            out.push_str("</text>");
+        }
+        out.push_str("</g></svg>\n");
+        out
+    }
+
+    /// 渲染为 HTML 片段，例如 `<span class="readtime" data-seconds="150">2分30秒</span>`
+    ///
+    /// `data-seconds` 保存总秒数，便于脚本按需重新格式化。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::ReadTime;
+    ///
+    /// let read_time = ReadTime::builder().total_seconds(150).build();
+    /// assert_eq!(
+    ///     read_time.to_html(),
+    ///     "<span class=\"readtime\" data-seconds=\"150\">2分30秒</span>"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<span class=\"readtime\" data-seconds=\"{}\">",
+            self.total_seconds
+        );
+        push_escaped(&mut out, &self.formatted);
+        out.push_str("</span>");
+        out
+    }
+}
+
+/// 估算文本在 11px Verdana 下的显示宽度
+fn text_width(text: &str) -> u32 {
+    text.chars()
+        .map(|c| match c {
+            ' ' | 'i' | 'j' | 'l' | '.' | ',' | ':' => 4,
+            _ if c.is_ascii() => 7,
+            _ => 12,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_badge_svg() {
+        let read_time = ReadTime::builder().total_seconds(150).build();
+        let svg = read_time.to_badge_svg("reading time");
+        assert!(svg.contains("width=\"140\""));
+        assert!(svg.contains("<text x=\"42\" y=\"14\">reading time</text>"));
+        assert!(svg.contains("<text x=\"112\" y=\"14\">2分30秒</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+
+        let svg = read_time.to_badge_svg("<a & b>");
+        assert!(svg.contains("aria-label=\"&lt;a &amp; b&gt;: 2分30秒\""));
+        assert!(!svg.contains("<a & b>"));
+    }
+
+    #[test]
+    fn test_html() {
+        let read_time = ReadTime::builder()
+            .total_seconds(30)
+            .formatted("<1 min")
+            .build();
+        assert_eq!(
+            read_time.to_html(),
+            "<span class=\"readtime\" data-seconds=\"30\">&lt;1 min</span>"
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use crate::{Error, ReadSpeed, ReadTime, estimate_file_with_speed};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 目录批量估算选项
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{DirOptions, ReadSpeed};
+///
+/// let options = DirOptions::default()
+///     .speed(ReadSpeed::default().wpm(250.0))
+///     .recursive(false)
+///     .extensions(["md", "mdx"]);
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DirOptions {
+    /// 阅读速度配置（默认：[`ReadSpeed::default`]）
+    pub speed: ReadSpeed,
+
+    /// 是否递归子目录（默认：true）
+    pub recursive: bool,
+
+    /// 需要估算的文件扩展名，不区分大小写（默认：`md`、`markdown`）
+    pub extensions: Vec<String>,
+
+    /// 只估算相对路径匹配这些 glob 模式的文件，非空时代替扩展名过滤（默认：空）
+    ///
+    /// 只用于 [`estimate_dir_parallel`](crate::estimate_dir_parallel) 和
+    /// [`estimate_dir_stream`](crate::estimate_dir_stream)，需要启用 `walk` 特性。
+    #[cfg(feature = "walk")]
+    pub include: Vec<String>,
+
+    /// 跳过匹配这些 gitignore 风格模式的文件和目录，例如 `drafts/` 或 `node_modules`（默认：空）
+    ///
+    /// 只用于并行遍历，需要启用 `walk` 特性。
+    #[cfg(feature = "walk")]
+    pub exclude: Vec<String>,
+
+    /// 是否遵循 `.gitignore` 和 `.ignore` 文件并跳过隐藏文件（默认：false）
+    ///
+    /// 只用于并行遍历，需要启用 `walk` 特性。
+    #[cfg(feature = "walk")]
+    pub ignore_files: bool,
+}
+
+impl Default for DirOptions {
+    fn default() -> Self {
+        Self {
+            speed: ReadSpeed::default(),
+            recursive: true,
+            extensions: vec!["md".to_string(), "markdown".to_string()],
+            #[cfg(feature = "walk")]
+            include: Vec::new(),
+            #[cfg(feature = "walk")]
+            exclude: Vec::new(),
+            #[cfg(feature = "walk")]
+            ignore_files: false,
+        }
+    }
+}
+
+impl DirOptions {
+    pub fn speed(mut self, speed: ReadSpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[cfg(feature = "walk")]
+    pub fn include<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.include = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[cfg(feature = "walk")]
+    pub fn exclude<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    #[cfg(feature = "walk")]
+    pub fn ignore_files(mut self, ignore_files: bool) -> Self {
+        self.ignore_files = ignore_files;
+        self
+    }
+
+    /// 判断文件扩展名是否匹配
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                self.extensions
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(ext))
+            })
+    }
+}
+
+/// 批量估算结果
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct BatchReadTime {
+    /// 每个文件的阅读时间，按路径排序
+    pub files: BTreeMap<PathBuf, ReadTime>,
+
+    /// 所有文件的合计阅读时间
+    ///
+    /// 由各文件的计数合并后重新计算，详见 [`ReadTime::merge_all`]。
+    pub total: ReadTime,
+}
+
+impl BatchReadTime {
+    /// 根据各文件结果计算合计
+    pub(crate) fn new(files: BTreeMap<PathBuf, ReadTime>, speed: &ReadSpeed) -> Self {
+        let total = ReadTime::merge_all(files.values(), speed);
+        Self { files, total }
+    }
+}
+
+/// 批量估算目录中的 Markdown 文件
+///
+/// 指向文件的符号链接会被估算，指向目录的符号链接不会进入。
+///
+/// # Arguments
+///
+/// * `path` - 目录路径
+/// * `options` - 批量估算选项
+///
+/// # Returns
+///
+/// 返回每个文件的阅读时间以及合计，读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{estimate_dir, DirOptions};
+///
+/// let batch = estimate_dir("content", &DirOptions::default()).unwrap();
+/// for (path, read_time) in &batch.files {
+///     println!("{}: {}", path.display(), read_time.formatted);
+/// }
+/// println!("合计: {}", batch.total.formatted);
+/// ```
+pub fn estimate_dir(path: impl AsRef<Path>, options: &DirOptions) -> Result<BatchReadTime, Error> {
+    let mut paths = Vec::new();
+    collect_files(path.as_ref(), options, &mut paths)?;
+
+    let mut files = BTreeMap::new();
+    for path in paths {
+        let read_time = estimate_file_with_speed(&path, &options.speed)?;
+        files.insert(path, read_time);
+    }
+    Ok(BatchReadTime::new(files, &options.speed))
+}
+
+/// 收集目录中需要估算的文件
+///
+/// 不进入指向目录的符号链接，避免链接成环时无限递归；指向文件的符号链接照常估算。
+fn collect_files(dir: &Path, options: &DirOptions, paths: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+        if file_type.is_dir() {
+            if options.recursive {
+                collect_files(&path, options, paths)?;
+            }
+        } else if (file_type.is_file() || (file_type.is_symlink() && path.is_file()))
+            && options.matches(&path)
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 批量估算匹配 glob 模式的文件
+///
+/// 支持 `*`、`?`、`[...]` 以及匹配任意层级目录的 `**`。需要启用 `glob` 特性。
+///
+/// # Arguments
+///
+/// * `pattern` - glob 模式，例如 `content/**/*.md`
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回每个文件的阅读时间以及合计，模式无效或读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{estimate_glob, ReadSpeed};
+///
+/// let batch = estimate_glob("content/**/*.md", &ReadSpeed::default()).unwrap();
+/// println!("共 {} 篇，合计: {}", batch.files.len(), batch.total.formatted);
+/// ```
+#[cfg(feature = "glob")]
+pub fn estimate_glob(pattern: &str, speed: &ReadSpeed) -> Result<BatchReadTime, Error> {
+    let entries = glob::glob(pattern).map_err(|err| Error::Pattern(err.to_string()))?;
+
+    let mut files = BTreeMap::new();
+    for entry in entries {
+        let path = entry.map_err(|err| Error::Io(err.into()))?;
+        if path.is_file() {
+            let read_time = estimate_file_with_speed(&path, speed)?;
+            files.insert(path, read_time);
+        }
+    }
+    Ok(BatchReadTime::new(files, speed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    fn fixture(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("markdown-readtime-test-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("a.md"), "# 第一篇\n\n内容").unwrap();
+        fs::write(dir.join("nested/b.MD"), "第二篇的内容").unwrap();
+        fs::write(dir.join("notes.txt"), "不是 Markdown").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_estimate_dir() {
+        let dir = fixture("estimate-dir");
+
+        let batch = estimate_dir(&dir, &DirOptions::default()).unwrap();
+        assert_eq!(batch.files.len(), 2);
+        assert_eq!(batch.files[&dir.join("a.md")], estimate("# 第一篇\n\n内容"));
+        assert_eq!(batch.total.word_count, 11);
+
+        let batch = estimate_dir(&dir, &DirOptions::default().recursive(false)).unwrap();
+        assert_eq!(batch.files.len(), 1);
+
+        let batch = estimate_dir(&dir, &DirOptions::default().extensions(["txt"])).unwrap();
+        assert_eq!(batch.files.len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_estimate_dir_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = fixture("estimate-dir-symlinks");
+        symlink(".", dir.join("loop")).unwrap();
+        symlink("a.md", dir.join("link.md")).unwrap();
+
+        let batch = estimate_dir(&dir, &DirOptions::default()).unwrap();
+        let files: Vec<_> = batch.files.into_keys().collect();
+        assert_eq!(
+            files,
+            [
+                dir.join("a.md"),
+                dir.join("link.md"),
+                dir.join("nested/b.MD")
+            ]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "glob")]
+    #[test]
+    fn test_estimate_glob() {
+        let dir = fixture("estimate-glob");
+
+        let pattern = format!("{}/**/*.md", dir.display());
+        let batch = estimate_glob(&pattern, &ReadSpeed::default()).unwrap();
+        assert_eq!(batch.files.len(), 1);
+        assert!(matches!(
+            estimate_glob("[", &ReadSpeed::default()),
+            Err(Error::Pattern(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
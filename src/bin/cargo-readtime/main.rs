@@ -0,0 +1,371 @@
+//! `cargo readtime` 子命令
+//!
+//! 估算 Cargo 工作区中项目文档的阅读时间：README、`docs/` 目录、CHANGELOG 以及源码中的
+//! `///` 和 `//!` 文档注释，按类别输出合计，让维护者随时了解新人读完文档需要多久：
+//!
+//! ```sh
+//! cargo install markdown-readtime --features cargo
+//! cargo readtime
+//! cargo readtime --manifest-path crates/core/Cargo.toml --english
+//! ```
+//!
+//! 遍历时遵循 `.gitignore` 并跳过隐藏文件和 `target` 目录。
+use clap::{Args, Parser};
+use ignore::WalkBuilder;
+use markdown_readtime::{ReadSpeed, ReadTime, estimate_with_speed};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use unicode_width::UnicodeWidthStr;
+
+/// 作为 cargo 子命令调用时，第一个参数为子命令名
+#[derive(Debug, Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum Cargo {
+    /// 估算工作区中项目文档的阅读时间
+    #[command(name = "readtime", version, about)]
+    Readtime(Cli),
+}
+
+#[derive(Debug, Args)]
+struct Cli {
+    /// 工作区或 crate 的 Cargo.toml，默认从当前目录开始向上查找
+    #[arg(long, value_name = "PATH")]
+    manifest_path: Option<PathBuf>,
+
+    /// 每分钟阅读的单词数
+    #[arg(long, value_name = "WORDS")]
+    wpm: Option<f64>,
+
+    /// 按空白分隔的英文单词计数，默认按中文字数计数
+    #[arg(long)]
+    english: bool,
+}
+
+impl Cli {
+    fn speed(&self) -> ReadSpeed {
+        let mut speed = ReadSpeed::default().chinese(!self.english);
+        if let Some(wpm) = self.wpm {
+            speed = speed.wpm(wpm);
+        }
+        speed
+    }
+}
+
+/// 文档的类别，按输出的顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    Readme,
+    Docs,
+    Changelog,
+    DocComments,
+}
+
+impl Category {
+    const ALL: [Category; 4] = [
+        Category::Readme,
+        Category::Docs,
+        Category::Changelog,
+        Category::DocComments,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Category::Readme => "README",
+            Category::Docs => "docs/",
+            Category::Changelog => "CHANGELOG",
+            Category::DocComments => "文档注释",
+        }
+    }
+
+    /// 按相对工作区根目录的路径判断文件的类别，不属于项目文档时返回 `None`
+    fn of(relative: &Path) -> Option<Self> {
+        let name = relative.file_name()?.to_string_lossy().to_ascii_uppercase();
+        let extension = relative
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+        let markdown = matches!(extension.as_deref(), Some("md" | "markdown"));
+        if extension.as_deref() == Some("rs") {
+            Some(Category::DocComments)
+        } else if name.starts_with("README") && (markdown || extension.is_none()) {
+            Some(Category::Readme)
+        } else if (name.starts_with("CHANGELOG") || name.starts_with("CHANGES")) && markdown {
+            Some(Category::Changelog)
+        } else if markdown
+            && relative
+                .parent()?
+                .components()
+                .any(|c| c.as_os_str() == "docs")
+        {
+            Some(Category::Docs)
+        } else {
+            None
+        }
+    }
+}
+
+/// 从 `dir` 开始向上查找 Cargo.toml，返回工作区的根目录
+///
+/// 与 Cargo 相同：先找到最近的 Cargo.toml，再在其上级目录中查找声明了 `[workspace]` 的清单，
+/// 没有找到时使用最近的清单所在的目录。
+fn workspace_root(dir: &Path) -> Option<PathBuf> {
+    let nearest = dir
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file())?;
+    let root = nearest
+        .ancestors()
+        .find(|dir| {
+            fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|text| {
+                text.lines().any(|line| {
+                    let line = line.trim();
+                    line == "[workspace]" || line.starts_with("[workspace.")
+                })
+            })
+        })
+        .unwrap_or(nearest);
+    Some(root.to_path_buf())
+}
+
+/// 提取 Rust 源码中的 `///` 和 `//!` 文档注释，不相邻的注释之间以空行分隔
+fn doc_comments(source: &str) -> String {
+    let mut out = String::new();
+    let mut in_comment = false;
+    for line in source.lines() {
+        let line = line.trim_start();
+        let text = match line.strip_prefix("///") {
+            Some(text) if !text.starts_with('/') => Some(text),
+            Some(_) => None,
+            None => line.strip_prefix("//!"),
+        };
+        match text {
+            Some(text) => {
+                out.push_str(text.strip_prefix(' ').unwrap_or(text));
+                out.push('\n');
+                in_comment = true;
+            }
+            None if in_comment => {
+                out.push('\n');
+                in_comment = false;
+            }
+            None => {}
+        }
+    }
+    out
+}
+
+/// 估算 `root` 中的项目文档，返回每个类别的文件数和阅读时间，失败的文件交给 `on_error`
+fn estimate_workspace(
+    root: &Path,
+    speed: &ReadSpeed,
+    on_error: &mut dyn FnMut(&Path, String),
+) -> Vec<(Category, Vec<ReadTime>)> {
+    let mut groups: Vec<(Category, Vec<ReadTime>)> = Category::ALL
+        .iter()
+        .map(|&category| (category, Vec::new()))
+        .collect();
+    let walker = WalkBuilder::new(root)
+        .filter_entry(|entry| entry.file_name() != "target")
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                on_error(root, err.to_string());
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|kind| kind.is_file()) {
+            continue;
+        }
+        let path = entry.path();
+        let Some(category) = Category::of(path.strip_prefix(root).unwrap_or(path)) else {
+            continue;
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) => {
+                on_error(path, err.to_string());
+                continue;
+            }
+        };
+        let markdown = match category {
+            Category::DocComments => doc_comments(&text),
+            _ => text,
+        };
+        if markdown.trim().is_empty() {
+            continue;
+        }
+        groups[category as usize]
+            .1
+            .push(estimate_with_speed(&markdown, speed));
+    }
+    groups
+}
+
+/// 每个类别一行的表格以及合计和总结
+fn render(groups: &[(Category, Vec<ReadTime>)], speed: &ReadSpeed) -> String {
+    let mut rows = vec![["类别", "文件", "时间", "字数"].map(String::from)];
+    let mut all = Vec::new();
+    for (category, times) in groups {
+        let total = ReadTime::merge_all(times, speed);
+        rows.push([
+            category.label().to_string(),
+            times.len().to_string(),
+            total.formatted,
+            total.word_count.to_string(),
+        ]);
+        all.extend(times);
+    }
+    let total = ReadTime::merge_all(all.iter().copied(), speed);
+    rows.push([
+        "合计".to_string(),
+        all.len().to_string(),
+        total.formatted.clone(),
+        total.word_count.to_string(),
+    ]);
+
+    let widths: Vec<usize> = (0..4)
+        .map(|i| rows.iter().map(|row| row[i].width()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for row in &rows {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            let padding = " ".repeat(width - cell.width());
+            if i == 0 {
+                line.push_str(cell);
+                line.push_str(&padding);
+            } else {
+                line.push_str("  ");
+                line.push_str(&padding);
+                line.push_str(cell);
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    let _ = writeln!(
+        out,
+        "项目文档共需约 {} 分钟读完",
+        total.total_seconds.div_ceil(60)
+    );
+    out
+}
+
+fn main() -> ExitCode {
+    let Cargo::Readtime(cli) = Cargo::parse();
+    let start = match &cli.manifest_path {
+        Some(path) => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        None => std::env::current_dir().unwrap_or_default(),
+    };
+    let Some(root) = workspace_root(&std::path::absolute(&start).unwrap_or(start)) else {
+        eprintln!("cargo-readtime: 找不到 Cargo.toml");
+        return ExitCode::FAILURE;
+    };
+
+    let speed = cli.speed();
+    let mut failed = false;
+    let groups = estimate_workspace(&root, &speed, &mut |path, err| {
+        eprintln!("cargo-readtime: {}: {}", path.display(), err);
+        failed = true;
+    });
+    print!("{}", render(&groups, &speed));
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli() {
+        let Cargo::Readtime(cli) =
+            Cargo::try_parse_from(["cargo-readtime", "readtime", "--english", "--wpm", "250"])
+                .unwrap();
+        let speed = cli.speed();
+        assert!(!speed.chinese);
+        assert_eq!(speed.words_per_minute, 250.0);
+        assert!(Cargo::try_parse_from(["cargo-readtime", "--english"]).is_err());
+    }
+
+    #[test]
+    fn test_category() {
+        let of = |path: &str| Category::of(Path::new(path));
+        assert_eq!(of("README.md"), Some(Category::Readme));
+        assert_eq!(of("crates/core/readme.markdown"), Some(Category::Readme));
+        assert_eq!(of("README"), Some(Category::Readme));
+        assert_eq!(of("docs/guide/intro.md"), Some(Category::Docs));
+        assert_eq!(of("CHANGELOG.md"), Some(Category::Changelog));
+        assert_eq!(of("src/lib.rs"), Some(Category::DocComments));
+        assert_eq!(of("docs/logo.png"), None);
+        assert_eq!(of("notes.md"), None);
+        assert_eq!(of("README.txt"), None);
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let source = "//! 模块说明\n//!\n//!  缩进\n\n/// 函数说明\n//// 不是文档注释\nfn f() {}\n    /// 方法说明\n";
+        assert_eq!(
+            doc_comments(source),
+            "模块说明\n\n 缩进\n\n函数说明\n\n方法说明\n"
+        );
+    }
+
+    #[test]
+    fn test_estimate_workspace() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-cargo");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("crates/core/src")).unwrap();
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::create_dir_all(dir.join("target/doc")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(dir.join("crates/core/Cargo.toml"), "[package]\n").unwrap();
+        fs::write(dir.join("README.md"), "内容".repeat(100)).unwrap();
+        fs::write(dir.join("docs/guide.md"), "指南").unwrap();
+        fs::write(dir.join("target/doc/README.md"), "构建产物").unwrap();
+        fs::write(
+            dir.join("crates/core/src/lib.rs"),
+            "/// 函数\npub fn f() {}\n",
+        )
+        .unwrap();
+        fs::write(dir.join("crates/core/src/empty.rs"), "fn g() {}\n").unwrap();
+
+        assert_eq!(
+            workspace_root(&dir.join("crates/core/src")),
+            Some(dir.clone())
+        );
+
+        let speed = ReadSpeed::default();
+        let groups = estimate_workspace(&dir, &speed, &mut |path, err| {
+            panic!("{}: {}", path.display(), err)
+        });
+        let counts: Vec<(Category, usize)> = groups
+            .iter()
+            .map(|(category, times)| (*category, times.len()))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                (Category::Readme, 1),
+                (Category::Docs, 1),
+                (Category::Changelog, 0),
+                (Category::DocComments, 1),
+            ]
+        );
+        let out = render(&groups, &speed);
+        assert!(out.starts_with("类别"));
+        assert!(out.contains("\n合计          3  1分2秒   204\n"));
+        assert!(out.ends_with("项目文档共需约 2 分钟读完\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
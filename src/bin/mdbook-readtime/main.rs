@@ -0,0 +1,255 @@
+//! `mdbook-readtime` mdBook 预处理器
+//!
+//! 在每一章的标题下插入该章的阅读时间，并在第一章的标题下插入全书的阅读时间。
+//! 安装后在 `book.toml` 中启用，所有设置均可省略：
+//!
+//! ```toml
+//! [preprocessor.readtime]
+//! wpm = 250
+//! chinese = false
+//! label = "阅读时间："
+//! total = true
+//! total-label = "全书阅读时间："
+//! ```
+//!
+//! 插入的内容为 `<p class="readtime-chapter">` 和 `<p class="readtime-total">` 段落，可以通过主题的 CSS 调整样式。
+use markdown_readtime::{ReadSpeed, ReadTime, estimate_with_speed};
+use pulldown_cmark::{Event, Parser, TagEnd};
+use serde_json::Value;
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+/// 预处理器的设置，对应 `book.toml` 中的 `[preprocessor.readtime]`
+struct Settings {
+    speed: ReadSpeed,
+    label: String,
+    /// 全书阅读时间的前缀，为 `None` 时不插入全书阅读时间
+    total_label: Option<String>,
+}
+
+impl Settings {
+    fn from_context(context: &Value) -> Self {
+        let table = &context["config"]["preprocessor"]["readtime"];
+        let mut speed = ReadSpeed::default();
+        if let Some(wpm) = table["wpm"].as_f64() {
+            speed = speed.wpm(wpm);
+        }
+        if let Some(chinese) = table["chinese"].as_bool() {
+            speed = speed.chinese(chinese);
+        }
+        let text = |key: &str, default: &str| table[key].as_str().unwrap_or(default).to_string();
+        Self {
+            speed,
+            label: text("label", "阅读时间："),
+            total_label: table["total"]
+                .as_bool()
+                .unwrap_or(true)
+                .then(|| text("total-label", "全书阅读时间：")),
+        }
+    }
+}
+
+/// 处理 mdBook 传入的书籍，兼容 `sections`（0.4）和 `items`（0.5）两种结构
+fn preprocess(context: &Value, book: &mut Value) {
+    let settings = Settings::from_context(context);
+    let key = if book.get("items").is_some() {
+        "items"
+    } else {
+        "sections"
+    };
+    let Some(items) = book.get_mut(key).and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    let mut times = Vec::new();
+    annotate(items, &settings, &mut times);
+    if let Some(label) = &settings.total_label
+        && let Some(content) = first_content(items)
+    {
+        let total = ReadTime::merge_all(&times, &settings.speed);
+        let line = format!(
+            "<p class=\"readtime-total\">{}{}</p>",
+            label,
+            total.to_html()
+        );
+        *content = Value::String(insert_after_heading(content.as_str().unwrap_or(""), &line));
+    }
+}
+
+/// 在每一章的标题下插入阅读时间，草稿章节没有内容，不插入也不计入合计
+fn annotate(items: &mut [Value], settings: &Settings, times: &mut Vec<ReadTime>) {
+    for item in items {
+        let Some(chapter) = item.get_mut("Chapter") else {
+            continue;
+        };
+        if let Some(Value::String(content)) = chapter.get_mut("content")
+            && !content.trim().is_empty()
+        {
+            let read_time = estimate_with_speed(content, &settings.speed);
+            let line = format!(
+                "<p class=\"readtime-chapter\">{}{}</p>",
+                settings.label,
+                read_time.to_html()
+            );
+            *content = insert_after_heading(content, &line);
+            times.push(read_time);
+        }
+        if let Some(sub_items) = chapter.get_mut("sub_items").and_then(Value::as_array_mut) {
+            annotate(sub_items, settings, times);
+        }
+    }
+}
+
+/// 第一个有内容的章节的 `content`
+fn first_content(items: &mut [Value]) -> Option<&mut Value> {
+    items.iter_mut().find_map(|item| {
+        let chapter = item.get_mut("Chapter")?;
+        let has_content = chapter["content"]
+            .as_str()
+            .is_some_and(|content| !content.trim().is_empty());
+        if has_content {
+            chapter.get_mut("content")
+        } else {
+            first_content(chapter.get_mut("sub_items")?.as_array_mut()?)
+        }
+    })
+}
+
+/// 在第一个标题之后插入一行 HTML，没有标题时插入到开头
+fn insert_after_heading(content: &str, line: &str) -> String {
+    let at = Parser::new(content)
+        .into_offset_iter()
+        .find_map(|(event, range)| {
+            matches!(event, Event::End(TagEnd::Heading(_))).then_some(range.end)
+        });
+    match at {
+        Some(at) => {
+            let (head, tail) = content.split_at(at);
+            let newline = if head.ends_with('\n') { "" } else { "\n" };
+            format!(
+                "{head}{newline}\n{line}\n\n{}",
+                tail.trim_start_matches('\n')
+            )
+        }
+        None => format!("{line}\n\n{content}"),
+    }
+}
+
+fn main() -> ExitCode {
+    // `mdbook-readtime supports <renderer>`：插入的是 HTML 段落，所有渲染器都可以使用
+    if std::env::args().nth(1).as_deref() == Some("supports") {
+        return ExitCode::SUCCESS;
+    }
+
+    let mut input = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut input) {
+        eprintln!("mdbook-readtime: {}", err);
+        return ExitCode::FAILURE;
+    }
+    let (context, mut book) = match serde_json::from_str::<(Value, Value)>(&input) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("mdbook-readtime: 无法解析 mdBook 的输入: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    preprocess(&context, &mut book);
+    match serde_json::to_writer(io::stdout().lock(), &book) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("mdbook-readtime: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chapter(name: &str, content: &str, sub_items: Value) -> Value {
+        json!({ "Chapter": {
+            "name": name,
+            "content": content,
+            "number": null,
+            "sub_items": sub_items,
+            "path": format!("{}.md", name),
+            "source_path": format!("{}.md", name),
+            "parent_names": []
+        }})
+    }
+
+    fn content<'a>(book: &'a Value, path: &str) -> &'a str {
+        book.pointer(path).and_then(Value::as_str).unwrap()
+    }
+
+    #[test]
+    fn test_preprocess() {
+        let minute = "内容".repeat(100);
+        let mut book = json!({
+            "sections": [
+                chapter("intro", "# 简介\n\n正文", json!([])),
+                "Separator",
+                chapter(
+                    "guide",
+                    &format!("# 指南\n\n{}", minute),
+                    json!([chapter("draft", "", json!([]))])
+                ),
+            ],
+            "__non_exhaustive": null
+        });
+        preprocess(&json!({ "config": {} }), &mut book);
+
+        assert_eq!(
+            content(&book, "/sections/0/Chapter/content"),
+            concat!(
+                "# 简介\n\n",
+                "<p class=\"readtime-total\">全书阅读时间：<span class=\"readtime\" data-seconds=\"62\">1分2秒</span></p>\n\n",
+                "<p class=\"readtime-chapter\">阅读时间：<span class=\"readtime\" data-seconds=\"2\">2秒</span></p>\n\n",
+                "正文"
+            )
+        );
+        assert!(
+            content(&book, "/sections/2/Chapter/content")
+                .starts_with("# 指南\n\n<p class=\"readtime-chapter\">阅读时间：<span class=\"readtime\" data-seconds=\"61\">1分1秒</span></p>\n\n内容")
+        );
+        assert_eq!(
+            content(&book, "/sections/2/Chapter/sub_items/0/Chapter/content"),
+            ""
+        );
+        assert_eq!(book["sections"][1], "Separator");
+        assert!(book.get("__non_exhaustive").is_some());
+    }
+
+    #[test]
+    fn test_preprocess_settings() {
+        let mut book = json!({ "items": [chapter("a", "Some words here", json!([]))] });
+        let context = json!({ "config": { "preprocessor": { "readtime": {
+            "chinese": false,
+            "label": "Reading time: ",
+            "total": false
+        }}}});
+        preprocess(&context, &mut book);
+        assert_eq!(
+            content(&book, "/items/0/Chapter/content"),
+            "<p class=\"readtime-chapter\">Reading time: <span class=\"readtime\" data-seconds=\"1\">1秒</span></p>\n\nSome words here"
+        );
+    }
+
+    #[test]
+    fn test_insert_after_heading() {
+        assert_eq!(
+            insert_after_heading("# 标题", "<p>x</p>"),
+            "# 标题\n\n<p>x</p>\n\n"
+        );
+        assert_eq!(
+            insert_after_heading("前言\n\n标题\n===\n正文", "<p>x</p>"),
+            "前言\n\n标题\n===\n\n<p>x</p>\n\n正文"
+        );
+        assert_eq!(
+            insert_after_heading("```\n# 代码\n```\n", "<p>x</p>"),
+            "<p>x</p>\n\n```\n# 代码\n```\n"
+        );
+    }
+}
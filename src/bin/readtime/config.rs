@@ -0,0 +1,296 @@
+//! 配置文件和预设
+//!
+//! `.readtime.toml` 保存团队共享的阅读速度、排除规则以及按路径覆盖的设置，
+//! 使不同机器上得到相同的结果：
+//!
+//! ```toml
+//! preset = "technical"
+//! locale = "zh"
+//! words_per_minute = 250
+//! exclude = ["drafts/", "node_modules"]
+//!
+//! [[overrides]]
+//! glob = "blog/en/**"
+//! locale = "en"
+//! ```
+use clap::ValueEnum;
+use globset::{GlobBuilder, GlobMatcher};
+use markdown_readtime::ReadSpeed;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 配置文件名，从当前目录开始逐级向上查找
+pub const CONFIG_FILE: &str = ".readtime.toml";
+
+/// 预设的阅读速度，只修改各自相关的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    /// Medium 的算法：每分钟 265 个单词，图片时间从 12 秒递减到 3 秒，向上取整到整分钟
+    Medium,
+    /// 技术文档：每分钟 150 个单词，每个代码块 40 秒
+    Technical,
+}
+
+impl Preset {
+    fn apply(self, speed: ReadSpeed) -> ReadSpeed {
+        match self {
+            Preset::Medium => speed
+                .wpm(265.0)
+                .image_time(12.0)
+                .decay_images(1.0, 3.0)
+                .round_minutes(true)
+                .chinese(false),
+            Preset::Technical => speed.wpm(150.0).code_block_time(40.0),
+        }
+    }
+}
+
+/// 一组可选的速度设置，未设置的字段沿用上一层的值
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct Profile {
+    /// 先于其他字段应用的预设
+    pub preset: Option<Preset>,
+    /// 内容的语言，`zh` 和 `ja` 按字数计数，其他语言按单词计数
+    pub locale: Option<String>,
+    pub words_per_minute: Option<f64>,
+    pub seconds_per_image: Option<f64>,
+    pub seconds_per_code_block: Option<f64>,
+    pub count_emoji: Option<bool>,
+}
+
+impl Profile {
+    /// 在 `speed` 的基础上应用已设置的字段
+    pub fn apply(&self, mut speed: ReadSpeed) -> ReadSpeed {
+        if let Some(preset) = self.preset {
+            speed = preset.apply(speed);
+        }
+        if let Some(locale) = &self.locale {
+            let language = locale.split(['-', '_']).next().unwrap_or_default();
+            speed = speed.chinese(matches!(
+                language.to_ascii_lowercase().as_str(),
+                "zh" | "ja"
+            ));
+        }
+        if let Some(wpm) = self.words_per_minute {
+            speed = speed.wpm(wpm);
+        }
+        if let Some(seconds) = self.seconds_per_image {
+            speed = speed.image_time(seconds);
+        }
+        if let Some(seconds) = self.seconds_per_code_block {
+            speed = speed.code_block_time(seconds);
+        }
+        if let Some(count) = self.count_emoji {
+            speed = speed.emoji(count);
+        }
+        speed
+    }
+}
+
+/// 配置文件的内容
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(flatten)]
+    profile: Profile,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    overrides: Vec<Override>,
+    /// 未知的键，用于报告拼写错误
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+/// 对匹配 `glob` 的文件覆盖的设置
+#[derive(Debug, Deserialize)]
+struct Override {
+    glob: String,
+    #[serde(flatten)]
+    profile: Profile,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+/// 加载后的配置
+#[derive(Debug, Default)]
+pub struct Config {
+    /// 配置文件所在的目录，覆盖规则中的模式相对于该目录匹配
+    root: PathBuf,
+    file: ConfigFile,
+    matchers: Vec<GlobMatcher>,
+}
+
+impl Config {
+    /// 从 `dir` 开始逐级向上查找 [`CONFIG_FILE`]
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// 读取并解析配置文件
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        let root = std::path::absolute(path)
+            .map_err(|err| err.to_string())?
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Self::parse(&text, root)
+    }
+
+    /// 解析配置文件的内容，`root` 为覆盖规则的基准目录
+    fn parse(text: &str, root: PathBuf) -> Result<Self, String> {
+        let file: ConfigFile = toml::from_str(text).map_err(|err| err.to_string())?;
+        let unknown = file
+            .unknown
+            .keys()
+            .chain(file.overrides.iter().flat_map(|o| o.unknown.keys()));
+        if let Some(key) = unknown.into_iter().next() {
+            return Err(format!("未知的配置项 `{}`", key));
+        }
+        let matchers = file
+            .overrides
+            .iter()
+            .map(|o| {
+                GlobBuilder::new(&o.glob)
+                    .literal_separator(true)
+                    .build()
+                    .map(|glob| glob.compile_matcher())
+                    .map_err(|err| err.to_string())
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            root,
+            file,
+            matchers,
+        })
+    }
+
+    /// 配置文件中的排除规则
+    pub fn exclude(&self) -> &[String] {
+        &self.file.exclude
+    }
+
+    /// 依次应用配置文件和命令行设置后的阅读速度
+    pub fn speed(&self, cli: &Profile) -> ReadSpeed {
+        cli.apply(self.file.profile.apply(ReadSpeed::default()))
+    }
+
+    /// 有覆盖规则匹配 `path` 时返回该文件的阅读速度，多条规则按顺序应用，命令行设置最后应用
+    pub fn path_speed(&self, path: &Path, cli: &Profile) -> Option<ReadSpeed> {
+        if self.matchers.is_empty() {
+            return None;
+        }
+        let absolute = std::path::absolute(path).ok()?;
+        let relative = absolute.strip_prefix(&self.root).ok()?;
+        let mut matched = false;
+        let mut speed = self.file.profile.apply(ReadSpeed::default());
+        for (matcher, o) in self.matchers.iter().zip(&self.file.overrides) {
+            if matcher.is_match(relative) {
+                speed = o.profile.apply(speed);
+                matched = true;
+            }
+        }
+        matched.then(|| cli.apply(speed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+preset = "technical"
+seconds_per_image = 20
+exclude = ["drafts/"]
+
+[[overrides]]
+glob = "blog/**"
+preset = "medium"
+
+[[overrides]]
+glob = "blog/zh/*.md"
+locale = "zh-CN"
+"#;
+
+    #[test]
+    fn test_config_speed() {
+        let config = Config::parse(CONFIG, PathBuf::from("/site")).unwrap();
+        assert_eq!(config.exclude(), ["drafts/"]);
+
+        let speed = config.speed(&Profile::default());
+        assert_eq!(speed.words_per_minute, 150.0);
+        assert_eq!(speed.seconds_per_code_block, 40.0);
+        assert_eq!(speed.seconds_per_image, 20.0);
+        assert!(speed.chinese);
+
+        let cli = Profile {
+            words_per_minute: Some(300.0),
+            ..Profile::default()
+        };
+        assert_eq!(config.speed(&cli).words_per_minute, 300.0);
+
+        let speed = config.speed(&Profile {
+            preset: Some(Preset::Medium),
+            ..Profile::default()
+        });
+        assert_eq!(speed.words_per_minute, 265.0);
+        assert_eq!(speed.seconds_per_code_block, 40.0);
+        assert_eq!(speed.image_decay, 1.0);
+        assert!(speed.round_to_minutes);
+        assert!(!speed.chinese);
+    }
+
+    #[test]
+    fn test_config_overrides() {
+        let config = Config::parse(CONFIG, PathBuf::from("/site")).unwrap();
+        let cli = Profile::default();
+        assert!(
+            config
+                .path_speed(Path::new("/site/docs/a.md"), &cli)
+                .is_none()
+        );
+        assert!(
+            config
+                .path_speed(Path::new("/other/blog/a.md"), &cli)
+                .is_none()
+        );
+
+        let speed = config
+            .path_speed(Path::new("/site/blog/en/a.md"), &cli)
+            .unwrap();
+        assert_eq!(speed.words_per_minute, 265.0);
+        assert!(!speed.chinese);
+
+        let speed = config
+            .path_speed(Path::new("/site/blog/zh/a.md"), &cli)
+            .unwrap();
+        assert_eq!(speed.words_per_minute, 265.0);
+        assert!(speed.chinese);
+
+        assert!(
+            Config::default()
+                .path_speed(Path::new("a.md"), &cli)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_config_errors() {
+        let parse = |text: &str| Config::parse(text, PathBuf::new()).unwrap_err();
+        assert_eq!(
+            parse("word_per_minute = 250"),
+            "未知的配置项 `word_per_minute`"
+        );
+        assert_eq!(
+            parse("[[overrides]]\nglob = \"a\"\nlocal = \"en\""),
+            "未知的配置项 `local`"
+        );
+        assert!(parse("preset = \"fast\"").contains("fast"));
+        assert!(!parse("[[overrides]]\nglob = \"[a\"").is_empty());
+    }
+}
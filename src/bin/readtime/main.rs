@@ -0,0 +1,760 @@
+//! `readtime` 命令行工具
+//!
+//! 估算文件或标准输入中 Markdown 内容的阅读时间：
+//!
+//! ```sh
+//! cargo install markdown-readtime --features cli
+//! readtime README.md docs/guide.md
+//! cat post.md | readtime --english
+//! readtime docs/*.md --format csv > audit.csv
+//! readtime ./content --recursive --exclude drafts/
+//! readtime 'posts/**/*.md'
+//! readtime --write --key reading_time -r content/
+//! readtime --preset medium post.md
+//! readtime -r content --min-minutes 15 --sort time
+//! readtime badge README.md --out badge.svg
+//! readtime manifest content --out public/readtime.json
+//! readtime tui content --watch
+//! ```
+//!
+//! 当前目录或上级目录中的 `.readtime.toml` 会被自动读取，命令行参数优先于配置文件。
+mod config;
+mod output;
+#[cfg(feature = "tui")]
+mod tui;
+
+use clap::{Args, Parser, Subcommand};
+use config::{Config, Preset, Profile};
+use markdown_readtime::{
+    DirOptions, Manifest, ReadSpeed, ReadTime, estimate_dir_stream, estimate_file_with_speed,
+    estimate_reader, write_reading_time_file_with_key,
+};
+use output::{Format, Record, Snippet, Sort, render};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// 估算 Markdown 文档的阅读时间
+#[derive(Debug, Parser)]
+#[command(name = "readtime", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// 需要估算的文件、目录或 glob 模式（例如 `'posts/**/*.md'`），省略或为 `-` 时从标准输入读取
+    paths: Vec<PathBuf>,
+
+    /// 递归估算目录中的所有 Markdown 文件
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// 遍历目录时跳过匹配的文件和目录，使用 gitignore 语法，可以重复指定
+    #[arg(long, value_name = "GLOB", global = true)]
+    exclude: Vec<String>,
+
+    /// 遍历目录时不读取 `.gitignore` 和 `.ignore`，也不跳过隐藏文件
+    #[arg(long, global = true)]
+    no_ignore: bool,
+
+    /// 使用预设的阅读速度，其余参数和配置文件中的设置仍然生效
+    #[arg(long, value_enum, global = true)]
+    preset: Option<Preset>,
+
+    /// 配置文件，默认从当前目录开始向上查找 `.readtime.toml`
+    #[arg(long, value_name = "FILE", global = true)]
+    config: Option<PathBuf>,
+
+    /// 每分钟阅读的单词数
+    #[arg(long, value_name = "WORDS", global = true)]
+    wpm: Option<f64>,
+
+    /// 按空白分隔的英文单词计数，默认按中文字数计数或使用配置文件中的 `locale`
+    #[arg(long, global = true)]
+    english: bool,
+
+    /// 不单独计算 emoji
+    #[arg(long, global = true)]
+    no_emoji: bool,
+
+    /// 输出格式
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// 只输出阅读时间不少于该分钟数的文件
+    #[arg(long, value_name = "MINUTES")]
+    min_minutes: Option<f64>,
+
+    /// 只输出阅读时间不超过该分钟数的文件
+    #[arg(long, value_name = "MINUTES")]
+    max_minutes: Option<f64>,
+
+    /// 结果的排序方式，默认按输入的顺序
+    #[arg(long, value_enum)]
+    sort: Option<Sort>,
+
+    /// 把阅读时间（向上取整的分钟数）写入每个文件的 front matter，值未变化的文件保持不变
+    #[arg(short, long)]
+    write: bool,
+
+    /// `--write` 写入的 front matter 键名
+    #[arg(
+        long,
+        value_name = "KEY",
+        default_value = "reading_time",
+        requires = "write"
+    )]
+    key: String,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// 输出可以嵌入页面的 SVG 徽章或 HTML 片段，多个输入时使用合计的阅读时间
+    Badge(Badge),
+
+    /// 递归估算内容目录，输出从 slug 到阅读时间的 JSON 清单，供前端页面和搜索索引使用
+    Manifest(ManifestArgs),
+
+    /// 在终端中浏览目录的阅读时间，可以排序、筛选并查看选中文件的章节，需要启用 `tui` 特性
+    #[cfg(feature = "tui")]
+    Tui(TuiArgs),
+}
+
+#[derive(Debug, Args)]
+struct Badge {
+    /// 需要估算的文件、目录或 glob 模式，目录会被递归估算，省略或为 `-` 时从标准输入读取
+    paths: Vec<PathBuf>,
+
+    /// 输出的片段类型
+    #[arg(long, value_enum, default_value_t = Snippet::Svg)]
+    snippet: Snippet,
+
+    /// 徽章左侧的文字
+    #[arg(long, default_value = "阅读时间")]
+    label: String,
+
+    /// 写入的文件，默认输出到标准输出
+    #[arg(short, long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct ManifestArgs {
+    /// 内容目录，slug 为文件相对该目录的路径
+    root: PathBuf,
+
+    /// 写入的文件，默认输出到标准输出
+    #[arg(short, long, value_name = "FILE")]
+    out: Option<PathBuf>,
+}
+
+#[cfg(feature = "tui")]
+#[derive(Debug, Args)]
+struct TuiArgs {
+    /// 需要浏览的文件、目录或 glob 模式，目录会被递归估算，默认为当前目录
+    paths: Vec<PathBuf>,
+
+    /// 定期重新扫描输入，文件变化后自动更新
+    #[arg(long)]
+    watch: bool,
+
+    /// `--watch` 重新扫描的间隔（秒）
+    #[arg(long, value_name = "SECONDS", default_value_t = 2, requires = "watch")]
+    interval: u64,
+}
+
+impl Badge {
+    /// 所有结果合计的阅读时间对应的片段
+    fn render(&self, records: &[Record], speed: &ReadSpeed) -> String {
+        let total = match records {
+            [record] => record.read_time.clone(),
+            _ => ReadTime::merge_all(records.iter().map(|r| &r.read_time), speed),
+        };
+        match self.snippet {
+            Snippet::Svg => total.to_badge_svg(&self.label),
+            Snippet::Html => total.to_html() + "\n",
+        }
+    }
+}
+
+impl Cli {
+    /// 命令行参数中的速度设置，在配置文件之后应用
+    fn profile(&self) -> Profile {
+        Profile {
+            preset: self.preset,
+            locale: self.english.then(|| "en".into()),
+            words_per_minute: self.wpm,
+            count_emoji: self.no_emoji.then_some(false),
+            ..Profile::default()
+        }
+    }
+
+    /// 指定的或自动找到的配置文件，都没有时为空配置
+    fn config(&self) -> Result<Config, String> {
+        let path = match &self.config {
+            Some(path) => Some(path.clone()),
+            None => Config::find(&std::env::current_dir().map_err(|err| err.to_string())?),
+        };
+        match path {
+            Some(path) => Config::load(&path).map_err(|err| format!("{}: {}", path.display(), err)),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// 应用配置文件和命令行参数后的阅读速度配置
+    fn speed(&self, config: &Config) -> ReadSpeed {
+        config.speed(&self.profile())
+    }
+
+    /// 遍历目录和 glob 模式时使用的选项，排除规则包括配置文件中的规则
+    fn dir_options(&self, config: &Config) -> DirOptions {
+        DirOptions::default()
+            .speed(self.speed(config))
+            .recursive(self.recursive)
+            .exclude(config.exclude().iter().chain(&self.exclude))
+            .ignore_files(!self.no_ignore)
+    }
+
+    /// 按阅读时间筛选结果并排序，合计只包括保留的结果
+    fn select(&self, records: &mut Vec<Record>) {
+        records.retain(|record| {
+            let minutes = record.read_time.total_seconds as f64 / 60.0;
+            self.min_minutes.is_none_or(|min| minutes >= min)
+                && self.max_minutes.is_none_or(|max| minutes <= max)
+        });
+        if let Some(sort) = self.sort {
+            sort.apply(records);
+        }
+    }
+
+    /// 需要估算的输入，没有指定文件时为标准输入
+    fn inputs(&self) -> Vec<PathBuf> {
+        inputs(&self.paths)
+    }
+}
+
+/// 表示标准输入的路径
+const STDIN: &str = "-";
+
+/// 命令行中的路径，为空时为标准输入
+fn inputs(paths: &[PathBuf]) -> Vec<PathBuf> {
+    if paths.is_empty() {
+        vec![PathBuf::from(STDIN)]
+    } else {
+        paths.to_vec()
+    }
+}
+
+fn estimate(path: &Path, speed: &ReadSpeed) -> Result<ReadTime, String> {
+    if path.as_os_str() == STDIN {
+        estimate_reader(io::stdin().lock(), speed).map_err(|err| err.to_string())
+    } else {
+        estimate_file_with_speed(path, speed).map_err(|err| err.to_string())
+    }
+}
+
+/// 把不存在的路径拆分为不含通配符的目录和相对该目录的 glob 模式，不含通配符时返回 `None`
+fn split_glob(input: &Path) -> Option<(PathBuf, String)> {
+    if input.exists() {
+        return None;
+    }
+    let is_glob = |part: &str| part.contains(['*', '?', '[', '{']);
+    let mut base = PathBuf::new();
+    let mut pattern = Vec::new();
+    for component in input.components() {
+        let part = component.as_os_str().to_string_lossy();
+        if pattern.is_empty() && !is_glob(&part) {
+            base.push(component);
+        } else {
+            pattern.push(part.into_owned());
+        }
+    }
+    if pattern.is_empty() {
+        return None;
+    }
+    if base.as_os_str().is_empty() {
+        base.push(".");
+    }
+    Some((base, pattern.join("/")))
+}
+
+/// 估算所有输入，目录和 glob 模式中的文件并行估算并按路径排序，失败的输入交给 `on_error`
+fn collect(
+    inputs: &[PathBuf],
+    options: &DirOptions,
+    on_error: &mut dyn FnMut(&Path, String),
+) -> Vec<Record> {
+    let mut records = Vec::new();
+    for input in inputs {
+        if input.is_dir() {
+            if options.recursive {
+                walk(input, options, &mut records, on_error);
+            } else {
+                on_error(input, "是目录，使用 --recursive 估算其中的文件".into());
+            }
+        } else if let Some((base, pattern)) = split_glob(input) {
+            let options = options.clone().recursive(true).include([pattern]);
+            walk(&base, &options, &mut records, on_error);
+        } else {
+            match estimate(input, &options.speed) {
+                Ok(read_time) => records.push(Record::new(input, read_time)),
+                Err(err) => on_error(input, err),
+            }
+        }
+    }
+    records
+}
+
+/// 并行估算 `root` 中的文件，追加的结果按路径排序
+fn walk(
+    root: &Path,
+    options: &DirOptions,
+    records: &mut Vec<Record>,
+    on_error: &mut dyn FnMut(&Path, String),
+) {
+    let start = records.len();
+    for (path, result) in estimate_dir_stream(root, options) {
+        match result {
+            Ok(read_time) => records.push(Record::new(&path, read_time)),
+            Err(err) => on_error(&path, err.to_string()),
+        }
+    }
+    records[start..].sort_by(|a, b| a.path.cmp(&b.path));
+}
+
+/// 按 `path_speed` 返回的阅读速度重新估算有单独设置的文件
+fn reestimate(
+    records: &mut [Record],
+    path_speed: &dyn Fn(&Path) -> Option<ReadSpeed>,
+    on_error: &mut dyn FnMut(&Path, String),
+) {
+    for record in records {
+        let path = Path::new(&record.path);
+        if path.as_os_str() == STDIN {
+            continue;
+        }
+        if let Some(speed) = path_speed(path) {
+            match estimate_file_with_speed(path, &speed) {
+                Ok(read_time) => record.read_time = read_time,
+                Err(err) => on_error(path, err.to_string()),
+            }
+        }
+    }
+}
+
+/// 以 `root` 为内容目录生成清单
+fn manifest(root: &Path, records: &[Record]) -> Manifest {
+    Manifest::new(
+        root,
+        records
+            .iter()
+            .map(|record| (Path::new(&record.path), &record.read_time)),
+    )
+}
+
+/// 把 `contents` 写入 `out`，没有指定文件时输出到标准输出
+fn emit(out: Option<&Path>, contents: &str, on_error: &mut dyn FnMut(&Path, String)) {
+    match out {
+        Some(out) => {
+            if let Err(err) = std::fs::write(out, contents) {
+                on_error(out, err.to_string());
+            }
+        }
+        None => {
+            let _ = io::stdout().lock().write_all(contents.as_bytes());
+        }
+    }
+}
+
+/// 把每个结果写回对应文件 front matter 中的 `key`，写入失败的文件交给 `on_error`
+fn write_back(
+    records: &mut [Record],
+    speed: &dyn Fn(&Path) -> ReadSpeed,
+    key: &str,
+    on_error: &mut dyn FnMut(&Path, String),
+) {
+    for record in records {
+        let path = Path::new(&record.path);
+        if path.as_os_str() == STDIN {
+            on_error(path, "不能写回标准输入".into());
+            continue;
+        }
+        match write_reading_time_file_with_key(path, &speed(path), key) {
+            Ok(read_time) => record.read_time = read_time,
+            Err(err) => on_error(path, err.to_string()),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let config = match cli.config() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("readtime: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let profile = cli.profile();
+    let speed = cli.speed(&config);
+    let path_speed = |path: &Path| config.path_speed(path, &profile);
+
+    let mut failed = false;
+    let mut on_error = |path: &Path, err: String| {
+        eprintln!("readtime: {}: {}", path.display(), err);
+        failed = true;
+    };
+    match &cli.command {
+        Some(Command::Badge(badge)) => {
+            let options = cli.dir_options(&config).recursive(true);
+            let mut records = collect(&inputs(&badge.paths), &options, &mut on_error);
+            reestimate(&mut records, &path_speed, &mut on_error);
+            if !records.is_empty() {
+                let snippet = badge.render(&records, &speed);
+                emit(badge.out.as_deref(), &snippet, &mut on_error);
+            }
+        }
+        Some(Command::Manifest(args)) => {
+            if args.root.is_dir() {
+                let options = cli.dir_options(&config).recursive(true);
+                let mut records = Vec::new();
+                walk(&args.root, &options, &mut records, &mut on_error);
+                reestimate(&mut records, &path_speed, &mut on_error);
+                let manifest = manifest(&args.root, &records);
+                emit(
+                    args.out.as_deref(),
+                    &(manifest.to_json() + "\n"),
+                    &mut on_error,
+                );
+            } else {
+                on_error(&args.root, "不是目录".into());
+            }
+        }
+        #[cfg(feature = "tui")]
+        Some(Command::Tui(args)) => {
+            let inputs = match args.paths.as_slice() {
+                [] => vec![PathBuf::from(".")],
+                paths => paths.to_vec(),
+            };
+            let options = cli.dir_options(&config).recursive(true);
+            let scan = || {
+                let mut errors = Vec::new();
+                let mut on_error =
+                    |path: &Path, err: String| errors.push(format!("{}: {}", path.display(), err));
+                let mut records = collect(&inputs, &options, &mut on_error);
+                reestimate(&mut records, &path_speed, &mut on_error);
+                (records, errors)
+            };
+            let load = |path: &Path| {
+                let speed = path_speed(path).unwrap_or(speed);
+                std::fs::read_to_string(path)
+                    .map(|markdown| {
+                        markdown_readtime::estimate_sections_with_speed(&markdown, &speed)
+                    })
+                    .map_err(|err| err.to_string())
+            };
+            let watch = args
+                .watch
+                .then(|| std::time::Duration::from_secs(args.interval.max(1)));
+            if let Err(err) = tui::run(tui::App::new(speed, args.watch), &scan, &load, watch) {
+                eprintln!("readtime: {}", err);
+                failed = true;
+            }
+        }
+        None => {
+            let mut records = collect(&cli.inputs(), &cli.dir_options(&config), &mut on_error);
+            reestimate(&mut records, &path_speed, &mut on_error);
+            if cli.write {
+                let speed_for = |path: &Path| path_speed(path).unwrap_or(speed);
+                write_back(&mut records, &speed_for, &cli.key, &mut on_error);
+            }
+            cli.select(&mut records);
+            let _ = io::stdout()
+                .lock()
+                .write_all(render(cli.format, &records, &speed).as_bytes());
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_cli() {
+        let cli = Cli::try_parse_from(["readtime", "--english", "--wpm", "250", "a.md"]).unwrap();
+        let speed = cli.speed(&Config::default());
+        assert_eq!(speed.words_per_minute, 250.0);
+        assert!(!speed.chinese && speed.count_emoji);
+        assert_eq!(cli.inputs(), [PathBuf::from("a.md")]);
+
+        let cli = Cli::try_parse_from(["readtime"]).unwrap();
+        assert_eq!(
+            cli.speed(&Config::default()).words_per_minute,
+            ReadSpeed::default().words_per_minute
+        );
+        assert_eq!(cli.inputs(), [PathBuf::from(STDIN)]);
+        assert_eq!(cli.format, Format::Text);
+        assert!(Cli::try_parse_from(["readtime", "--wpm", "fast"]).is_err());
+
+        let cli = Cli::try_parse_from(["readtime", "--format", "yaml", "-"]).unwrap();
+        assert_eq!(cli.format, Format::Yaml);
+        assert!(Cli::try_parse_from(["readtime", "--format", "xml"]).is_err());
+
+        let cli = Cli::try_parse_from(["readtime", "-w", "a.md"]).unwrap();
+        assert!(cli.write);
+        assert_eq!(cli.key, "reading_time");
+        assert!(Cli::try_parse_from(["readtime", "--key", "minutes", "a.md"]).is_err());
+
+        let cli = Cli::try_parse_from(["readtime", "--preset", "medium", "--wpm", "300", "a.md"])
+            .unwrap();
+        let speed = cli.speed(&Config::default());
+        assert_eq!(speed.words_per_minute, 300.0);
+        assert!(!speed.chinese);
+        assert!(Cli::try_parse_from(["readtime", "--preset", "fast"]).is_err());
+    }
+
+    #[test]
+    fn test_badge() {
+        let cli = Cli::try_parse_from([
+            "readtime",
+            "badge",
+            "a.md",
+            "--snippet",
+            "html",
+            "--english",
+            "-o",
+            "out.html",
+        ])
+        .unwrap();
+        let Some(Command::Badge(badge)) = &cli.command else {
+            panic!("未解析出 badge 子命令");
+        };
+        assert_eq!(badge.paths, [PathBuf::from("a.md")]);
+        assert_eq!(badge.out, Some(PathBuf::from("out.html")));
+        assert!(!cli.speed(&Config::default()).chinese);
+
+        let speed = ReadSpeed::default();
+        let record =
+            |markdown: &str| Record::new(Path::new("a.md"), markdown_readtime::estimate(markdown));
+        let minute = "内容".repeat(100);
+        assert_eq!(
+            badge.render(&[record(&minute), record(&minute)], &speed),
+            "<span class=\"readtime\" data-seconds=\"120\">2分钟</span>\n"
+        );
+
+        let cli = Cli::try_parse_from(["readtime", "badge"]).unwrap();
+        let Some(Command::Badge(badge)) = &cli.command else {
+            panic!("未解析出 badge 子命令");
+        };
+        assert_eq!(badge.snippet, Snippet::Svg);
+        assert!(
+            badge
+                .render(&[record(&minute)], &speed)
+                .contains(">阅读时间</text>")
+        );
+    }
+
+    #[test]
+    fn test_manifest() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-cli-manifest");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("blog/drafts")).unwrap();
+        fs::write(dir.join("_index.md"), "首页").unwrap();
+        fs::write(dir.join("blog/post.md"), "内容".repeat(100)).unwrap();
+        fs::write(dir.join("blog/drafts/wip.md"), "草稿").unwrap();
+
+        let cli = Cli::try_parse_from([
+            "readtime",
+            "manifest",
+            dir.to_str().unwrap(),
+            "--exclude",
+            "drafts/",
+        ])
+        .unwrap();
+        let Some(Command::Manifest(args)) = &cli.command else {
+            panic!("未解析出 manifest 子命令");
+        };
+        assert_eq!(args.out, None);
+
+        let mut records = Vec::new();
+        walk(
+            &args.root,
+            &cli.dir_options(&Config::default()).recursive(true),
+            &mut records,
+            &mut |path, err| panic!("{}: {}", path.display(), err),
+        );
+        assert_eq!(
+            manifest(&args.root, &records).to_json(),
+            concat!(
+                "{\"\":{\"seconds\":1,\"words\":2,\"formatted\":\"1秒\"},",
+                "\"blog/post\":{\"seconds\":60,\"words\":200,\"formatted\":\"1分钟\"}}"
+            )
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_select() {
+        let record = |path: &str, seconds: u64| {
+            let read_time = ReadTime::builder().total_seconds(seconds).build();
+            Record::new(Path::new(path), read_time)
+        };
+        let records = || {
+            vec![
+                record("a.md", 120),
+                record("b.md", 1200),
+                record("c.md", 900),
+            ]
+        };
+        let select = |args: &[&str]| -> Vec<String> {
+            let cli = Cli::try_parse_from([&["readtime"], args].concat()).unwrap();
+            let mut records = records();
+            cli.select(&mut records);
+            records.into_iter().map(|record| record.path).collect()
+        };
+
+        assert_eq!(select(&[]), ["a.md", "b.md", "c.md"]);
+        assert_eq!(select(&["--min-minutes", "15"]), ["b.md", "c.md"]);
+        assert_eq!(select(&["--max-minutes", "15"]), ["a.md", "c.md"]);
+        assert_eq!(
+            select(&["--min-minutes", "2", "--sort", "time"]),
+            ["b.md", "c.md", "a.md"]
+        );
+        assert_eq!(
+            select(&["--min-minutes", "2.5", "--max-minutes", "16"]),
+            ["c.md"]
+        );
+        assert!(Cli::try_parse_from(["readtime", "--sort", "size"]).is_err());
+    }
+
+    #[test]
+    fn test_reestimate() {
+        let path = std::env::temp_dir().join("markdown-readtime-test-cli-reestimate.md");
+        fs::write(&path, "内容".repeat(200)).unwrap();
+        let default = estimate_file_with_speed(&path, &ReadSpeed::default()).unwrap();
+        let mut records = vec![
+            Record::new(&path, default.clone()),
+            Record::new(Path::new(STDIN), default.clone()),
+        ];
+
+        let mut errors = Vec::new();
+        let slow = ReadSpeed::default().wpm(100.0);
+        reestimate(&mut records, &|_| Some(slow), &mut |path, _| {
+            errors.push(path.to_path_buf())
+        });
+        assert!(errors.is_empty());
+        assert_eq!(
+            records[0].read_time.total_seconds,
+            2 * default.total_seconds
+        );
+        assert_eq!(records[1].read_time, default);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_collect_recursive() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-cli-recursive");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("b.md"), "第二篇").unwrap();
+        fs::write(dir.join("nested/a.md"), "# 第一篇\n\n内容").unwrap();
+        fs::write(dir.join("notes.txt"), "不是 Markdown").unwrap();
+        let inputs = [dir.clone(), dir.join("missing.md")];
+        let options = DirOptions::default();
+
+        let mut errors = Vec::new();
+        let records = collect(&inputs, &options, &mut |path, _| {
+            errors.push(path.to_path_buf())
+        });
+        let paths: Vec<&str> = records.iter().map(|record| record.path.as_str()).collect();
+        let expected = [dir.join("b.md"), dir.join("nested/a.md")];
+        assert_eq!(paths, expected.map(|path| path.display().to_string()));
+        assert_eq!(errors, [dir.join("missing.md")]);
+
+        errors.clear();
+        let options = options.recursive(false);
+        let records = collect(&inputs[..1], &options, &mut |path, _| {
+            errors.push(path.to_path_buf())
+        });
+        assert!(records.is_empty());
+        assert_eq!(errors, std::slice::from_ref(&dir));
+
+        errors.clear();
+        let pattern = dir.join("**/a.*");
+        let records = collect(&[pattern], &options, &mut |path, _| {
+            errors.push(path.to_path_buf())
+        });
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].path,
+            dir.join("nested/a.md").display().to_string()
+        );
+        assert!(errors.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_back() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-cli-write");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.md"), "---\ntitle: 标题\n---\n\n正文").unwrap();
+        fs::write(dir.join("b.md"), "+++\nminutes = 7\n+++\n\n正文").unwrap();
+        let options = DirOptions::default();
+
+        let mut errors = Vec::new();
+        for _ in 0..2 {
+            let mut records = collect(std::slice::from_ref(&dir), &options, &mut |path, _| {
+                errors.push(path.to_path_buf())
+            });
+            write_back(
+                &mut records,
+                &|_| options.speed,
+                "minutes",
+                &mut |path, _| errors.push(path.to_path_buf()),
+            );
+            assert_eq!(records.len(), 2);
+        }
+        assert!(errors.is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.join("a.md")).unwrap(),
+            "---\ntitle: 标题\nminutes: 1\n---\n\n正文"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("b.md")).unwrap(),
+            "+++\nminutes = 1\n+++\n\n正文"
+        );
+
+        let mut records = vec![Record::new(Path::new(STDIN), ReadTime::default())];
+        write_back(
+            &mut records,
+            &|_| options.speed,
+            "minutes",
+            &mut |path, _| errors.push(path.to_path_buf()),
+        );
+        assert_eq!(errors, [PathBuf::from(STDIN)]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_split_glob() {
+        let split = |input: &str| split_glob(Path::new(input));
+        assert_eq!(
+            split("posts/**/*.md"),
+            Some((PathBuf::from("posts"), "**/*.md".to_string()))
+        );
+        assert_eq!(
+            split("*.md"),
+            Some((PathBuf::from("."), "*.md".to_string()))
+        );
+        assert_eq!(split("missing.md"), None);
+        assert_eq!(split("src"), None);
+    }
+}
@@ -0,0 +1,413 @@
+//! 结构化输出格式
+
+use clap::ValueEnum;
+use markdown_readtime::{ReadSpeed, ReadTime};
+use serde_json::{Map, Value};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// 便于阅读的摘要，多个输入时为表格和合计
+    #[default]
+    Text,
+    /// JSON 数组，每个输入一个对象
+    Json,
+    /// 逗号分隔，嵌套字段展开为 `breakdown.prose` 形式的列
+    Csv,
+    /// 制表符分隔，列与 CSV 相同
+    Tsv,
+    /// YAML 列表，每个输入一项
+    Yaml,
+}
+
+/// `badge` 子命令输出的片段类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Snippet {
+    /// shields.io 风格的 SVG 徽章
+    Svg,
+    /// `<span class="readtime">` HTML 片段
+    Html,
+}
+
+/// 结果的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Sort {
+    /// 阅读时间从长到短
+    Time,
+    /// 字数从多到少
+    Words,
+    /// 按路径排序
+    Path,
+}
+
+impl Sort {
+    /// 稳定排序，相等的结果保持原来的顺序
+    pub fn apply(self, records: &mut [Record]) {
+        match self {
+            Sort::Time => records.sort_by_key(|record| Reverse(record.read_time.total_seconds)),
+            Sort::Words => records.sort_by_key(|record| Reverse(record.read_time.word_count)),
+            Sort::Path => records.sort_by(|a, b| a.path.cmp(&b.path)),
+        }
+    }
+}
+
+/// 单个输入的估算结果
+pub struct Record {
+    /// 输入的名称，标准输入为 `-`
+    pub path: String,
+    pub read_time: ReadTime,
+}
+
+impl Record {
+    pub fn new(path: &Path, read_time: ReadTime) -> Self {
+        Self {
+            path: path.display().to_string(),
+            read_time,
+        }
+    }
+
+    /// 包含 `path` 和 [`ReadTime`] 全部字段的对象
+    fn to_value(&self) -> Value {
+        let mut object = Map::new();
+        object.insert("path".into(), Value::String(self.path.clone()));
+        if let Ok(Value::Object(fields)) = serde_json::to_value(&self.read_time) {
+            object.extend(fields);
+        }
+        Value::Object(object)
+    }
+}
+
+/// 按 `format` 输出所有结果，`speed` 用于计算合计
+pub fn render(format: Format, records: &[Record], speed: &ReadSpeed) -> String {
+    match format {
+        Format::Text => text(records, speed),
+        Format::Json => {
+            let values = records.iter().map(Record::to_value).collect();
+            let mut out = serde_json::to_string_pretty(&Value::Array(values)).unwrap_or_default();
+            out.push('\n');
+            out
+        }
+        Format::Csv => table(records, ','),
+        Format::Tsv => table(records, '\t'),
+        Format::Yaml => {
+            let mut out = String::new();
+            for record in records {
+                yaml_item(&mut out, &record.to_value(), 0);
+            }
+            if records.is_empty() {
+                out.push_str("[]\n");
+            }
+            out
+        }
+    }
+}
+
+/// 一行摘要：格式化时间、字数以及图片和代码块数量
+fn summary(read_time: &ReadTime) -> String {
+    format!(
+        "{}，{} 字，{} 张图片，{} 个代码块",
+        read_time.formatted,
+        read_time.word_count,
+        read_time.image_count,
+        read_time.code_block_count
+    )
+}
+
+/// 只有一个输入时输出摘要，否则输出每个文件一行的表格以及合计、平均和最长的文件
+fn text(records: &[Record], speed: &ReadSpeed) -> String {
+    if let [record] = records {
+        return format!("{}\n", summary(&record.read_time));
+    }
+    let Some(longest) = records.iter().max_by_key(|r| r.read_time.total_seconds) else {
+        return String::new();
+    };
+    let total = ReadTime::merge_all(records.iter().map(|r| &r.read_time), speed);
+    let average = total.total_seconds.div_ceil(records.len() as u64);
+
+    let mut rows = vec![row("文件", "时间", "字数", "图片", "代码块")];
+    for record in records {
+        rows.push(counts(&record.path, &record.read_time));
+    }
+    rows.push(counts(&format!("合计（{} 篇）", records.len()), &total));
+    rows.push(row(
+        "平均",
+        &ReadTime::builder().total_seconds(average).build().formatted,
+        &(total.word_count / records.len()).to_string(),
+        "",
+        "",
+    ));
+
+    let mut out = align(&rows);
+    let _ = writeln!(
+        out,
+        "最长: {}（{}）",
+        longest.path, longest.read_time.formatted
+    );
+    out
+}
+
+fn row(name: &str, time: &str, words: &str, images: &str, code: &str) -> Vec<String> {
+    [name, time, words, images, code].map(String::from).to_vec()
+}
+
+fn counts(name: &str, read_time: &ReadTime) -> Vec<String> {
+    row(
+        name,
+        &read_time.formatted,
+        &read_time.word_count.to_string(),
+        &read_time.image_count.to_string(),
+        &read_time.code_block_count.to_string(),
+    )
+}
+
+/// 按显示宽度对齐各列，第一列左对齐，其余列右对齐
+fn align(rows: &[Vec<String>]) -> String {
+    let columns = rows.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().map(|row| row[i].width()).max().unwrap_or(0))
+        .collect();
+    let mut out = String::new();
+    for row in rows {
+        let mut line = String::new();
+        for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            let padding = " ".repeat(width - cell.width());
+            if i == 0 {
+                line.push_str(cell);
+                line.push_str(&padding);
+            } else {
+                line.push_str("  ");
+                line.push_str(&padding);
+                line.push_str(cell);
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// 把嵌套对象展开为以 `.` 连接的列名
+fn flatten(prefix: &str, value: &Value, columns: &mut Map<String, Value>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let name = match prefix {
+                    "" => key.clone(),
+                    _ => format!("{}.{}", prefix, key),
+                };
+                flatten(&name, value, columns);
+            }
+        }
+        _ => {
+            columns.insert(prefix.into(), value.clone());
+        }
+    }
+}
+
+/// CSV 和 TSV：第一列为 `path`，其余列为所有结果中出现过的字段
+fn table(records: &[Record], separator: char) -> String {
+    let rows: Vec<Map<String, Value>> = records
+        .iter()
+        .map(|record| {
+            let mut columns = Map::new();
+            flatten(
+                "",
+                &serde_json::to_value(&record.read_time).unwrap_or_default(),
+                &mut columns,
+            );
+            columns
+        })
+        .collect();
+    let names: BTreeSet<&String> = rows.iter().flat_map(|row| row.keys()).collect();
+
+    let mut out = String::from("path");
+    for name in &names {
+        out.push(separator);
+        out.push_str(&cell(name, separator));
+    }
+    out.push('\n');
+    for (record, row) in records.iter().zip(&rows) {
+        out.push_str(&cell(&record.path, separator));
+        for name in &names {
+            out.push(separator);
+            let value = match row.get(*name) {
+                Some(Value::String(text)) => text.clone(),
+                Some(value) => value.to_string(),
+                None => String::new(),
+            };
+            out.push_str(&cell(&value, separator));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// 单元格内容：CSV 中包含分隔符、引号或换行时加引号，TSV 中把制表符和换行替换为空格
+fn cell(text: &str, separator: char) -> String {
+    if separator == '\t' {
+        text.replace(['\t', '\n', '\r'], " ")
+    } else if text.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// 列表中的一项，对象的第一个字段跟在 `- ` 之后
+fn yaml_item(out: &mut String, value: &Value, indent: usize) {
+    let _ = write!(out, "{:indent$}- ", "");
+    match value {
+        Value::Object(object) if !object.is_empty() => yaml_fields(out, object, indent + 2, true),
+        _ => {
+            yaml_scalar(out, value);
+            out.push('\n');
+        }
+    }
+}
+
+fn yaml_fields(out: &mut String, object: &Map<String, Value>, indent: usize, inline_first: bool) {
+    for (i, (key, value)) in object.iter().enumerate() {
+        if i > 0 || !inline_first {
+            let _ = write!(out, "{:indent$}", "");
+        }
+        yaml_scalar(out, &Value::String(key.clone()));
+        out.push(':');
+        match value {
+            Value::Object(child) if !child.is_empty() => {
+                out.push('\n');
+                yaml_fields(out, child, indent + 2, false);
+            }
+            Value::Array(items) if !items.is_empty() => {
+                out.push('\n');
+                for item in items {
+                    yaml_item(out, item, indent);
+                }
+            }
+            _ => {
+                out.push(' ');
+                yaml_scalar(out, value);
+                out.push('\n');
+            }
+        }
+    }
+}
+
+/// 字符串使用 JSON 的双引号写法，它同时是合法的 YAML
+fn yaml_scalar(out: &mut String, value: &Value) {
+    match value {
+        Value::String(text) if is_plain(text) => out.push_str(text),
+        Value::Object(_) => out.push_str("{}"),
+        Value::Array(_) => out.push_str("[]"),
+        _ => out.push_str(&value.to_string()),
+    }
+}
+
+/// 可以不加引号的字符串：只含字母、数字和下划线，且不会被解析为其他类型
+fn is_plain(text: &str) -> bool {
+    text.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !matches!(
+            text.to_ascii_lowercase().as_str(),
+            "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use markdown_readtime::estimate;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                path: "a.md".into(),
+                read_time: estimate("# 标题\n\n![图](a.png)"),
+            },
+            Record {
+                path: "b, \"c\".md".into(),
+                read_time: estimate("内容"),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_sort() {
+        let paths = |records: &[Record]| -> Vec<String> {
+            records.iter().map(|record| record.path.clone()).collect()
+        };
+        let mut records = records();
+        records.push(Record {
+            path: "0.md".into(),
+            read_time: estimate("内容内容"),
+        });
+        Sort::Time.apply(&mut records);
+        assert_eq!(paths(&records), ["a.md", "0.md", "b, \"c\".md"]);
+        Sort::Words.apply(&mut records);
+        assert_eq!(paths(&records), ["0.md", "a.md", "b, \"c\".md"]);
+        Sort::Time.apply(&mut records);
+        Sort::Path.apply(&mut records);
+        assert_eq!(paths(&records), ["0.md", "a.md", "b, \"c\".md"]);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let records = records();
+        let speed = ReadSpeed::default();
+        assert_eq!(
+            render(Format::Text, &records[..1], &speed),
+            "13秒，2 字，1 张图片，0 个代码块\n"
+        );
+        assert_eq!(
+            render(Format::Text, &records, &speed),
+            concat!(
+                "文件          时间  字数  图片  代码块\n",
+                "a.md          13秒     2     1       0\n",
+                "b, \"c\".md      1秒     2     0       0\n",
+                "合计（2 篇）  14秒     4     1       0\n",
+                "平均           7秒     2\n",
+                "最长: a.md（13秒）\n",
+            )
+        );
+        assert_eq!(render(Format::Text, &[], &speed), "");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: Value =
+            serde_json::from_str(&render(Format::Json, &records(), &ReadSpeed::default())).unwrap();
+        assert_eq!(json[0]["path"], "a.md");
+        assert_eq!(json[0]["image_count"], 1);
+        assert_eq!(json[0]["breakdown"]["images"], 12.0);
+        assert_eq!(json[1]["word_count"], 2);
+    }
+
+    #[test]
+    fn test_render_table() {
+        let csv = render(Format::Csv, &records(), &ReadSpeed::default());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("path,breakdown.code,breakdown.images,"));
+        assert!(lines[1].starts_with("a.md,0.0,12.0,"));
+        assert!(lines[2].starts_with("\"b, \"\"c\"\".md\","));
+
+        let tsv = render(Format::Tsv, &records(), &ReadSpeed::default());
+        assert_eq!(tsv.lines().next().unwrap(), lines[0].replace(',', "\t"));
+    }
+
+    #[test]
+    fn test_render_yaml() {
+        let yaml = render(Format::Yaml, &records()[..1], &ReadSpeed::default());
+        assert!(yaml.starts_with("- breakdown:\n    code: 0.0\n"));
+        assert!(yaml.contains("\n  custom_elements: {}\n"));
+        assert!(yaml.contains("\n  formatted: \"13秒\"\n"));
+        assert!(yaml.contains("\n  path: \"a.md\"\n"));
+        assert_eq!(render(Format::Yaml, &[], &ReadSpeed::default()), "[]\n");
+    }
+}
@@ -0,0 +1,443 @@
+//! `tui` 子命令：内容目录的终端仪表盘
+
+use crate::output::{Record, Sort};
+use markdown_readtime::{ReadSpeed, ReadTime, SectionReadTime};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// 一次扫描的结果和失败的输入
+pub type Scan = (Vec<Record>, Vec<String>);
+
+/// 等待按键的间隔，也是检查监视结果的间隔
+const TICK: Duration = Duration::from_millis(200);
+
+/// 按键对应的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    None,
+    Quit,
+    Rescan,
+}
+
+/// 仪表盘的状态
+pub struct App {
+    /// 按当前排序方式排列的全部结果
+    records: Vec<Record>,
+    speed: ReadSpeed,
+    sort: Sort,
+    /// 路径中需要包含的文字，不区分大小写
+    filter: String,
+    /// 正在输入筛选文字
+    editing: bool,
+    /// 选中的行，相对筛选后的结果
+    state: TableState,
+    /// 详情中显示的文件和它的章节
+    detail: Option<(String, Result<Vec<SectionReadTime>, String>)>,
+    /// 最近一次扫描中失败的输入
+    errors: Vec<String>,
+    watching: bool,
+}
+
+impl App {
+    pub fn new(speed: ReadSpeed, watching: bool) -> Self {
+        Self {
+            records: Vec::new(),
+            speed,
+            sort: Sort::Path,
+            filter: String::new(),
+            editing: false,
+            state: TableState::default(),
+            detail: None,
+            errors: Vec::new(),
+            watching,
+        }
+    }
+
+    /// 替换为新的扫描结果，尽量保持选中同一个文件
+    fn replace(&mut self, (mut records, errors): Scan) {
+        let selected = self.selected().map(|record| record.path.clone());
+        self.sort.apply(&mut records);
+        self.records = records;
+        self.errors = errors;
+        // 文件内容可能已经变化，章节需要重新估算
+        self.detail = None;
+        self.select_path(selected.as_deref());
+    }
+
+    /// 筛选后的结果
+    fn visible(&self) -> Vec<&Record> {
+        let filter = self.filter.to_lowercase();
+        self.records
+            .iter()
+            .filter(|record| record.path.to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    fn selected(&self) -> Option<&Record> {
+        self.state
+            .selected()
+            .and_then(|index| self.visible().get(index).copied())
+    }
+
+    /// 选中路径为 `path` 的结果，不在筛选后的结果中时选中第一行
+    fn select_path(&mut self, path: Option<&str>) {
+        let visible = self.visible();
+        let index = path
+            .and_then(|path| visible.iter().position(|record| record.path == path))
+            .or((!visible.is_empty()).then_some(0));
+        self.state.select(index);
+    }
+
+    /// 移动选中的行，停在第一行和最后一行
+    fn step(&mut self, offset: isize) {
+        let len = self.visible().len();
+        if len == 0 {
+            return;
+        }
+        let index = self.state.selected().unwrap_or(0);
+        self.state
+            .select(Some(index.saturating_add_signed(offset).min(len - 1)));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if self.editing {
+            let selected = self.selected().map(|record| record.path.clone());
+            match key.code {
+                KeyCode::Enter => self.editing = false,
+                KeyCode::Esc => {
+                    self.editing = false;
+                    self.filter.clear();
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => return Action::None,
+            }
+            self.select_path(selected.as_deref());
+            return Action::None;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('r') => return Action::Rescan,
+            KeyCode::Char('/') => self.editing = true,
+            KeyCode::Char('s') => {
+                let selected = self.selected().map(|record| record.path.clone());
+                self.sort = match self.sort {
+                    Sort::Path => Sort::Time,
+                    Sort::Time => Sort::Words,
+                    Sort::Words => Sort::Path,
+                };
+                self.sort.apply(&mut self.records);
+                self.select_path(selected.as_deref());
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.step(1),
+            KeyCode::Up | KeyCode::Char('k') => self.step(-1),
+            KeyCode::PageDown => self.step(10),
+            KeyCode::PageUp => self.step(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.step(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.step(isize::MAX),
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// 选中的文件变化后通过 `load` 估算它的章节
+    fn load_detail(&mut self, load: &dyn Fn(&Path) -> Result<Vec<SectionReadTime>, String>) {
+        let Some(path) = self.selected().map(|record| record.path.clone()) else {
+            self.detail = None;
+            return;
+        };
+        if self.detail.as_ref().is_none_or(|(shown, _)| *shown != path) {
+            let sections = load(Path::new(&path));
+            self.detail = Some((path, sections));
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(2)]).areas(frame.area());
+        let [list, detail] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(main);
+
+        let visible = self.visible();
+        let total = ReadTime::merge_all(visible.iter().map(|r| &r.read_time), &self.speed);
+        let rows = visible.iter().map(|record| {
+            Row::new([
+                Cell::from(record.path.as_str()),
+                Cell::from(record.read_time.formatted.as_str()),
+                Cell::from(record.read_time.word_count.to_string()),
+            ])
+        });
+        let title = format!(
+            " 共 {} 篇，合计 {}，按{}排序 ",
+            visible.len(),
+            total.formatted,
+            match self.sort {
+                Sort::Path => "路径",
+                Sort::Time => "阅读时间",
+                Sort::Words => "字数",
+            }
+        );
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(12),
+                Constraint::Length(8),
+            ],
+        )
+        .header(Row::new(["文件", "时间", "字数"]).style(Style::new().add_modifier(Modifier::BOLD)))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(title));
+        // 表格渲染时会更新滚动位置
+        let mut state = self.state.clone();
+        frame.render_stateful_widget(table, list, &mut state);
+        self.state = state;
+
+        let (title, lines) = match &self.detail {
+            Some((path, Ok(sections))) => (
+                format!(" {} ", path),
+                sections
+                    .iter()
+                    .map(|section| {
+                        let heading = match section.level {
+                            0 => "（开头）".to_string(),
+                            level => {
+                                format!("{}{}", "  ".repeat(level as usize - 1), section.heading)
+                            }
+                        };
+                        Line::from(format!(
+                            "{}  {}，{} 字",
+                            heading,
+                            section.read_time.formatted,
+                            section.word_count()
+                        ))
+                    })
+                    .collect(),
+            ),
+            Some((path, Err(err))) => (format!(" {} ", path), vec![Line::from(err.as_str())]),
+            None => (" 章节 ".to_string(), Vec::new()),
+        };
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title(title)),
+            detail,
+        );
+
+        let mut lines = vec![if self.editing {
+            Line::from(format!("筛选: {}▏  Enter 确定  Esc 清除", self.filter))
+        } else {
+            let filter = if self.filter.is_empty() {
+                String::new()
+            } else {
+                format!("筛选: {}  ", self.filter)
+            };
+            let watch = if self.watching { "监视中  " } else { "" };
+            Line::from(format!(
+                "{}{}↑↓ 选择  s 排序  / 筛选  r 刷新  q 退出",
+                filter, watch
+            ))
+        }];
+        if let Some(err) = self.errors.first() {
+            let more = match self.errors.len() {
+                1 => String::new(),
+                n => format!("（另有 {} 个错误）", n - 1),
+            };
+            lines.push(Line::from(format!("{}{}", err, more)));
+        }
+        frame.render_widget(Paragraph::new(lines), status);
+    }
+}
+
+/// 运行仪表盘直到按下 `q`
+///
+/// `scan` 估算全部输入，启用监视时每隔 `watch` 在后台线程中重新扫描；`load` 估算详情中显示的章节。
+pub fn run(
+    mut app: App,
+    scan: &(dyn Fn() -> Scan + Sync),
+    load: &dyn Fn(&Path) -> Result<Vec<SectionReadTime>, String>,
+    watch: Option<Duration>,
+) -> io::Result<()> {
+    app.replace(scan());
+    let (results, received) = mpsc::channel();
+    // 丢弃 `stop` 后监视线程在下一次等待时退出
+    let (stop, stopped) = mpsc::channel::<()>();
+    thread::scope(|scope| {
+        if let Some(interval) = watch {
+            scope.spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                    if results.send(scan()).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        let mut terminal = ratatui::init();
+        let result = event_loop(&mut terminal, &mut app, scan, load, &received);
+        ratatui::restore();
+        drop(stop);
+        result
+    })
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    scan: &(dyn Fn() -> Scan + Sync),
+    load: &dyn Fn(&Path) -> Result<Vec<SectionReadTime>, String>,
+    received: &mpsc::Receiver<Scan>,
+) -> io::Result<()> {
+    loop {
+        if let Some(latest) = received.try_iter().last() {
+            app.replace(latest);
+        }
+        app.load_detail(load);
+        terminal.draw(|frame| app.draw(frame))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match app.handle_key(key) {
+                Action::Quit => return Ok(()),
+                Action::Rescan => app.replace(scan()),
+                Action::None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use markdown_readtime::estimate_sections;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use std::mem;
+    use unicode_width::UnicodeWidthStr;
+
+    fn record(path: &str, markdown: &str) -> Record {
+        Record::new(Path::new(path), markdown_readtime::estimate(markdown))
+    }
+
+    fn scan() -> Scan {
+        let records = vec![
+            record("docs/b.md", &"内容".repeat(300)),
+            record("docs/a.md", "短文"),
+            record("blog/c.md", &"内容".repeat(100)),
+        ];
+        (records, Vec::new())
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            app.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+    }
+
+    fn paths(app: &App) -> Vec<&str> {
+        app.visible()
+            .into_iter()
+            .map(|record| record.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_and_filter() {
+        let mut app = App::new(ReadSpeed::default(), false);
+        app.replace(scan());
+        assert_eq!(paths(&app), ["blog/c.md", "docs/a.md", "docs/b.md"]);
+        assert_eq!(app.selected().unwrap().path, "blog/c.md");
+
+        press(&mut app, "js");
+        assert_eq!(app.sort, Sort::Time);
+        assert_eq!(paths(&app), ["docs/b.md", "blog/c.md", "docs/a.md"]);
+        assert_eq!(app.selected().unwrap().path, "docs/a.md");
+
+        press(&mut app, "/DOCS");
+        assert!(app.editing);
+        assert_eq!(paths(&app), ["docs/b.md", "docs/a.md"]);
+        app.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(!app.editing);
+        assert_eq!(app.selected().unwrap().path, "docs/a.md");
+
+        press(&mut app, "gG");
+        assert_eq!(app.selected().unwrap().path, "docs/a.md");
+        press(&mut app, "/");
+        app.handle_key(KeyEvent::from(KeyCode::Esc));
+        assert_eq!(paths(&app).len(), 3);
+        assert_eq!(
+            app.handle_key(KeyEvent::from(KeyCode::Char('r'))),
+            Action::Rescan
+        );
+        assert_eq!(
+            app.handle_key(KeyEvent::from(KeyCode::Char('q'))),
+            Action::Quit
+        );
+
+        press(&mut app, "/missing");
+        app.handle_key(KeyEvent::from(KeyCode::Enter));
+        assert!(app.visible().is_empty());
+        assert_eq!(app.state.selected(), None);
+        press(&mut app, "j");
+        assert_eq!(app.state.selected(), None);
+    }
+
+    #[test]
+    fn test_replace_keeps_selection() {
+        let mut app = App::new(ReadSpeed::default(), true);
+        app.replace(scan());
+        press(&mut app, "j");
+        app.load_detail(&|_| Ok(Vec::new()));
+        assert!(app.detail.is_some());
+
+        let (mut records, _) = scan();
+        records.remove(0);
+        app.replace((records, vec!["gone.md: 文件不存在".into()]));
+        assert_eq!(app.selected().unwrap().path, "docs/a.md");
+        assert!(app.detail.is_none());
+        assert_eq!(app.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut app = App::new(ReadSpeed::default(), true);
+        app.replace(scan());
+        app.load_detail(&|_| Ok(estimate_sections("前言\n\n# 第一章\n\n## 小节\n\n正文")));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        // 宽字符之后的单元格只占位，拼接时跳过
+        let mut skip = false;
+        let mut screen = String::new();
+        for (index, cell) in terminal.backend().buffer().content().iter().enumerate() {
+            if index % 80 == 0 {
+                screen.push('\n');
+                skip = false;
+            }
+            if !mem::take(&mut skip) {
+                screen.push_str(cell.symbol());
+                skip = cell.symbol().width() > 1;
+            }
+        }
+        assert!(screen.contains("┌ 共 3 篇，合计 4分1秒，按路径排序 ─"));
+        assert!(screen.contains("│blog/c.md  "));
+        assert!(screen.contains(" 3分钟 "));
+        assert!(screen.contains("┌ blog/c.md ─"));
+        assert!(screen.contains("│（开头）  1秒，2 字"));
+        assert!(screen.contains("│第一章  1秒，3 字"));
+        assert!(screen.contains("│  小节  2秒，4 字"));
+        assert!(screen.contains("监视中"));
+    }
+}
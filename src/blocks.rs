@@ -0,0 +1,254 @@
+use crate::estimator::Counters;
+use crate::utils::ceil_seconds;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::mem;
+use std::ops::Range;
+
+/// 单个块级元素的阅读时间
+///
+/// 块为段落、标题、代码块、列表项等不再包含其他块的元素。
+/// 容器内不属于任何子块的内容（例如紧凑列表项中位于嵌套列表之前的文字）会单独成为一块。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct BlockReadTime {
+    /// 在源文本中的字节范围
+    pub range: Range<usize>,
+
+    /// 单词数量
+    pub word_count: usize,
+
+    /// 图片数量
+    pub image_count: usize,
+
+    /// 代码块数量
+    pub code_block_count: usize,
+
+    /// 阅读时间（秒），未取整，便于累加
+    pub seconds: f64,
+}
+
+impl BlockReadTime {
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+}
+
+/// 合并多个块的计数和阅读时间
+pub(crate) fn merge_blocks<'a>(
+    blocks: impl IntoIterator<Item = &'a BlockReadTime>,
+    speed: &ReadSpeed,
+) -> ReadTime {
+    let mut total = BlockReadTime::default();
+    for block in blocks {
+        total.word_count += block.word_count;
+        total.image_count += block.image_count;
+        total.code_block_count += block.code_block_count;
+        total.seconds += block.seconds;
+    }
+    ReadTime::builder()
+        .total_seconds(ceil_seconds(total.seconds))
+        .word_count(total.word_count)
+        .image_count(total.image_count)
+        .code_block_count(total.code_block_count)
+        .pages(speed.pages(total.word_count))
+        .build()
+}
+
+fn is_block(tag: &Tag<'_>) -> bool {
+    matches!(
+        tag,
+        Tag::Paragraph
+            | Tag::Heading { .. }
+            | Tag::BlockQuote
+            | Tag::CodeBlock(_)
+            | Tag::HtmlBlock
+            | Tag::List(_)
+            | Tag::Item
+            | Tag::FootnoteDefinition(_)
+            | Tag::Table(_)
+            | Tag::MetadataBlock(_)
+    )
+}
+
+fn is_block_end(tag: &TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::Paragraph
+            | TagEnd::Heading(_)
+            | TagEnd::BlockQuote
+            | TagEnd::CodeBlock
+            | TagEnd::HtmlBlock
+            | TagEnd::List(_)
+            | TagEnd::Item
+            | TagEnd::FootnoteDefinition
+            | TagEnd::Table
+            | TagEnd::MetadataBlock(_)
+    )
+}
+
+/// 按块收集计数
+struct BlockCollector<'s> {
+    speed: &'s ReadSpeed,
+    blocks: Vec<BlockReadTime>,
+    /// 上一次输出时的计数
+    flushed: Counters,
+    /// 上一次输出的块的结束位置
+    offset: usize,
+    /// 尚未结束的块：起始位置以及是否包含子块
+    open: Vec<(usize, bool)>,
+}
+
+impl BlockCollector<'_> {
+    fn observe(&mut self, event: &Event<'_>, range: Range<usize>, counters: &Counters) {
+        match event {
+            Event::Start(tag) if is_block(tag) => {
+                let mut start = self.offset;
+                if let Some((parent, has_child)) = self.open.last_mut() {
+                    start = start.max(*parent);
+                    *has_child = true;
+                }
+                self.flush(start..range.start, counters, false);
+                self.open.push((range.start, false));
+            }
+            Event::End(tag) if is_block_end(tag) => {
+                let (start, has_child) = self.open.pop().unwrap_or((range.start, true));
+                if has_child {
+                    self.flush(self.offset.max(start)..range.end, counters, false);
+                } else {
+                    self.flush(start..range.end, counters, true);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 输出上一次输出之后的计数，`force` 为假时跳过没有内容的块
+    fn flush(&mut self, range: Range<usize>, counters: &Counters, force: bool) {
+        let delta = counters.since(&self.flushed);
+        if !force && delta.is_empty() {
+            return;
+        }
+        let range = range.start.min(range.end)..range.end;
+        self.offset = self.offset.max(range.end);
+        self.blocks.push(BlockReadTime {
+            range,
+            word_count: delta.word_count,
+            image_count: delta.image_count,
+            code_block_count: delta.code_block_count,
+            seconds: counters.seconds_since(&self.flushed, self.speed),
+        });
+        self.flushed = counters.clone();
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算每个块级元素的阅读时间及其在源文本中的字节范围
+    ///
+    /// 所有块的秒数之和等于整篇文档取整前的阅读时间。
+    /// 后端不提供位置信息时字节范围为空，参见
+    /// [`MarkdownBackend::parse_with_offsets`](crate::MarkdownBackend::parse_with_offsets)。
+    pub fn estimate_blocks(&mut self, markdown: &str) -> Vec<BlockReadTime> {
+        let speed = *self.speed();
+        let mut open = mem::take(&mut self.scratch.open);
+        open.clear();
+        let mut collector = BlockCollector {
+            speed: &speed,
+            blocks: Vec::new(),
+            flushed: Counters::default(),
+            offset: 0,
+            open,
+        };
+        let total = self.count(markdown, &mut |event, range, counters| {
+            collector.observe(event, range, counters)
+        });
+        let range = collector.offset..markdown.len().max(collector.offset);
+        collector.flush(range, &total, false);
+        self.scratch.open = collector.open;
+        collector.blocks
+    }
+}
+
+/// 估算每个块级元素的阅读时间及其在源文本中的字节范围
+///
+/// 使用默认的阅读速度配置。阅读类应用可以据此把时间映射到源文本位置，例如高亮当前段落。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`BlockReadTime`] 列表。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_blocks;
+///
+/// let markdown = "# 标题\n\n第一段内容\n";
+/// let blocks = estimate_blocks(markdown);
+/// assert_eq!(blocks.len(), 2);
+/// assert_eq!(&markdown[blocks[1].range.clone()], "第一段内容\n");
+/// assert_eq!(blocks[1].word_count, 5);
+/// ```
+pub fn estimate_blocks(markdown: &str) -> Vec<BlockReadTime> {
+    estimate_blocks_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算每个块级元素的阅读时间及其在源文本中的字节范围
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`BlockReadTime`] 列表。
+pub fn estimate_blocks_with_speed(markdown: &str, speed: &ReadSpeed) -> Vec<BlockReadTime> {
+    Estimator::new(*speed).estimate_blocks(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_estimate_blocks() {
+        let markdown = "# 标题\n\n段落内容\n\n- 列表项\n  - 嵌套项\n\n```rust\nfn main() {}\n```\n\n![图片](a.png)\n";
+        let blocks = estimate_blocks(markdown);
+        let texts: Vec<_> = blocks.iter().map(|b| &markdown[b.range()]).collect();
+        assert_eq!(
+            texts,
+            [
+                "# 标题\n",
+                "段落内容\n",
+                "- 列表项\n  ",
+                "- 嵌套项\n\n",
+                "```rust\nfn main() {}\n```",
+                "![图片](a.png)\n",
+            ]
+        );
+        assert_eq!(blocks[2].word_count, 3);
+        assert_eq!(blocks[4].code_block_count, 1);
+        assert_eq!(blocks[5].image_count, 1);
+
+        let read_time = estimate(markdown);
+        let words: usize = blocks.iter().map(BlockReadTime::word_count).sum();
+        let seconds: f64 = blocks.iter().map(BlockReadTime::seconds).sum();
+        assert_eq!(words, read_time.word_count);
+        assert_eq!(seconds.ceil() as u64, read_time.total_seconds);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::ReadSpeed;
+use crate::estimator::Counters;
+use std::collections::BTreeMap;
+use std::ops::{Add, AddAssign};
+
+/// 阅读时间的构成
+///
+/// 按来源拆分取整前的阅读时间（秒），各项之和等于取整前的总时间，
+/// 启用 [`ReadSpeed::round_to_minutes`] 时不包括取整到整分钟的部分。
+/// 估算结果偏离预期时，可以据此判断是正文速度还是元素权重需要调整。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate;
+///
+/// let read_time = estimate("# 标题\n\n![图片](a.png)\n\n```rust\nfn main() {}\n```\n");
+/// let breakdown = &read_time.breakdown;
+/// assert_eq!(breakdown.images, 12.0);
+/// assert_eq!(breakdown.code, 20.0);
+/// assert_eq!(breakdown.total().ceil() as u64, read_time.total_seconds);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Breakdown {
+    /// 按单词数计算的正文阅读时间
+    pub prose: f64,
+
+    /// 图片的额外时间
+    pub images: f64,
+
+    /// 代码块的额外时间
+    pub code: f64,
+
+    /// 表格的额外时间
+    pub tables: f64,
+
+    /// 链接的额外时间
+    pub links: f64,
+
+    /// 访问器为各类自定义元素添加的额外时间
+    pub elements: BTreeMap<String, f64>,
+
+    /// 访问器添加的、未标记为自定义元素的额外时间
+    pub other: f64,
+}
+
+impl Breakdown {
+    /// 根据计数结果按 `speed` 计算各项时间
+    pub(crate) fn from_counters(counters: &Counters, speed: &ReadSpeed) -> Self {
+        let elements = counters.custom_seconds.clone();
+        let tagged: f64 = elements.values().sum();
+        Self {
+            prose: counters.word_count as f64 / speed.words_per_minute * 60.0,
+            images: speed.image_seconds(counters.image_count)
+                + counters.image_alt_chars as f64 * speed.seconds_per_alt_char,
+            code: counters.code_block_count as f64 * speed.seconds_per_code_block
+                + counters.code_line_count as f64 * speed.seconds_per_code_line,
+            tables: counters.table_count as f64 * speed.seconds_per_table
+                + counters.table_cell_count as f64 * speed.seconds_per_table_cell,
+            links: counters.link_count as f64 * speed.seconds_per_link,
+            other: counters.extra_seconds - tagged,
+            elements,
+        }
+    }
+
+    pub fn prose(&self) -> f64 {
+        self.prose
+    }
+
+    pub fn images(&self) -> f64 {
+        self.images
+    }
+
+    pub fn code(&self) -> f64 {
+        self.code
+    }
+
+    pub fn tables(&self) -> f64 {
+        self.tables
+    }
+
+    pub fn links(&self) -> f64 {
+        self.links
+    }
+
+    pub fn elements(&self) -> &BTreeMap<String, f64> {
+        &self.elements
+    }
+
+    pub fn other(&self) -> f64 {
+        self.other
+    }
+
+    /// 各项之和，即取整前的总阅读时间
+    pub fn total(&self) -> f64 {
+        self.prose
+            + self.images
+            + self.code
+            + self.tables
+            + self.links
+            + self.elements.values().sum::<f64>()
+            + self.other
+    }
+}
+
+impl Add for Breakdown {
+    type Output = Breakdown;
+
+    fn add(mut self, rhs: Breakdown) -> Breakdown {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for Breakdown {
+    fn add_assign(&mut self, rhs: Breakdown) {
+        self.prose += rhs.prose;
+        self.images += rhs.images;
+        self.code += rhs.code;
+        self.tables += rhs.tables;
+        self.links += rhs.links;
+        self.other += rhs.other;
+        for (kind, seconds) in rhs.elements {
+            *self.elements.entry(kind).or_insert(0.0) += seconds;
+        }
+    }
+}
+
+/// 字数的文字构成
+///
+/// 按文字分别统计正文中的计数单位，与 [`ReadSpeed::chinese`] 无关，
+/// 一次估算即可同时显示“字数”和英文的“words”：
+///
+/// - `cjk`：汉字、谚文、平假名和片假名，每个字计为一个单位；
+/// - `latin`：其余文字组成的单词，以空白、中日韩字符和 emoji 分隔，只含标点的片段不计入；
+/// - `emoji`：每个 emoji 字符计为一个单位，默认以文本样式显示的字符后跟 VS16 时才计入。
+///
+/// 三者之和不一定等于 [`ReadTime::word_count`](crate::ReadTime::word_count)，
+/// 后者按配置的中文或英文模式计数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate;
+///
+/// let read_time = estimate("# Rust 入门\n\n学习 Rust language 很有趣 😀");
+/// let words = read_time.word_count_breakdown;
+/// assert_eq!(words.cjk, 7);
+/// assert_eq!(words.latin, 3);
+/// assert_eq!(words.emoji, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct WordCountBreakdown {
+    /// 中日韩字符数
+    pub cjk: usize,
+
+    /// 拉丁等其他文字的单词数
+    pub latin: usize,
+
+    /// emoji 数量
+    pub emoji: usize,
+}
+
+impl WordCountBreakdown {
+    pub fn cjk(&self) -> usize {
+        self.cjk
+    }
+
+    pub fn latin(&self) -> usize {
+        self.latin
+    }
+
+    pub fn emoji(&self) -> usize {
+        self.emoji
+    }
+
+    /// 各项之和
+    pub fn total(&self) -> usize {
+        self.cjk + self.latin + self.emoji
+    }
+
+    /// 从 `earlier` 到当前的增量
+    pub(crate) fn since(&self, earlier: &WordCountBreakdown) -> WordCountBreakdown {
+        WordCountBreakdown {
+            cjk: self.cjk - earlier.cjk,
+            latin: self.latin - earlier.latin,
+            emoji: self.emoji - earlier.emoji,
+        }
+    }
+}
+
+impl Add for WordCountBreakdown {
+    type Output = WordCountBreakdown;
+
+    fn add(mut self, rhs: WordCountBreakdown) -> WordCountBreakdown {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for WordCountBreakdown {
+    fn add_assign(&mut self, rhs: WordCountBreakdown) {
+        self.cjk += rhs.cjk;
+        self.latin += rhs.latin;
+        self.emoji += rhs.emoji;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Estimator, EventVisitor, ReadSpeed, Visit, estimate_with_speed};
+    use pulldown_cmark::{Event, Tag};
+
+    struct Callouts;
+
+    impl EventVisitor for Callouts {
+        fn visit(&mut self, event: &Event<'_>, _: &crate::Counters) -> Visit {
+            match event {
+                Event::Start(Tag::BlockQuote) => Visit::count().extra_seconds(30.0).tag("callout"),
+                Event::Rule => Visit::count().extra_seconds(5.0),
+                _ => Visit::count(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let markdown = "正文内容\n\n> 提示\n\n> 注意\n\n***\n\n![图片](a.png)\n";
+        let speed = ReadSpeed::default().image_time(10.0);
+        let read_time = Estimator::new(speed).visitor(Callouts).estimate(markdown);
+        let breakdown = read_time.breakdown();
+        assert_eq!(breakdown.images, 10.0);
+        assert_eq!(breakdown.code, 0.0);
+        assert_eq!(breakdown.elements["callout"], 60.0);
+        assert_eq!(breakdown.other, 5.0);
+        assert_eq!(breakdown.total().ceil() as u64, read_time.total_seconds);
+
+        let plain = estimate_with_speed("正文内容", &speed);
+        let merged = read_time.merge(&plain, &speed);
+        assert_eq!(merged.breakdown.elements["callout"], 60.0);
+        assert_eq!(
+            merged.breakdown.prose,
+            breakdown.prose + plain.breakdown.prose
+        );
+        let summed = read_time.clone() + plain;
+        assert_eq!(summed.breakdown, merged.breakdown);
+    }
+
+    #[test]
+    fn test_word_count_breakdown() {
+        let markdown = "# 混排 Mixed\n\n中文 and English 😀，`code`\n\n![图片 alt](a.png)\n\n```\n代码 code\n```\n";
+        let chinese = estimate_with_speed(markdown, &ReadSpeed::default());
+        let words = chinese.word_count_breakdown;
+        assert_eq!((words.cjk, words.latin, words.emoji), (4, 4, 1));
+        assert_eq!(words.total(), 9);
+
+        // 与计数模式无关
+        let english = estimate_with_speed(markdown, &ReadSpeed::default().chinese(false));
+        assert_ne!(english.word_count, chinese.word_count);
+        assert_eq!(english.word_count_breakdown, words);
+
+        let merged = chinese.merge(&english, &ReadSpeed::default());
+        assert_eq!(merged.word_count_breakdown, words + words);
+        let summed = chinese + english;
+        assert_eq!(summed.word_count_breakdown.latin, 8);
+    }
+}
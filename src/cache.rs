@@ -0,0 +1,248 @@
+use crate::{ReadSpeed, ReadTime, estimate_with_speed};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+/// 缓存键
+///
+/// 由内容哈希和速度配置哈希组成。哈希使用 SHA-256 算法，同一版本的不同进程之间保持一致，
+/// 无法构造出碰撞的内容，因此可以直接用作多个用户共享的持久化存储的键。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CacheKey {
+    /// 内容的 SHA-256 哈希
+    pub content_hash: [u8; 32],
+
+    /// 速度配置的 SHA-256 哈希
+    pub speed_hash: [u8; 32],
+}
+
+impl CacheKey {
+    /// 计算文档和速度配置对应的缓存键
+    ///
+    /// 速度配置哈希中还包含 crate 的版本号：升级后计数规则可能变化，旧版本写入持久化存储的结果不会再被命中。
+    pub fn new(markdown: &str, speed: &ReadSpeed) -> Self {
+        Self {
+            content_hash: Sha256::digest(markdown.as_bytes()).into(),
+            speed_hash: speed_hash(speed),
+        }
+    }
+}
+
+/// 按固定顺序哈希 crate 版本号和速度配置的每个字段
+fn speed_hash(speed: &ReadSpeed) -> [u8; 32] {
+    // 完整解构而不使用 `..`，新增字段时编译器会提示在这里加入哈希
+    let ReadSpeed {
+        words_per_minute,
+        seconds_per_image,
+        image_decay,
+        min_seconds_per_image,
+        seconds_per_alt_char,
+        seconds_per_code_block,
+        seconds_per_code_line,
+        seconds_per_table,
+        seconds_per_table_cell,
+        seconds_per_link,
+        dedupe_links,
+        count_emoji,
+        chinese,
+        skim_rate,
+        words_per_page,
+        chars_per_page,
+        round_to_minutes,
+    } = *speed;
+    let mut hasher = Sha256::new();
+    // 版本号以长度开头，与之后的定长字段区分
+    let version = env!("CARGO_PKG_VERSION");
+    hasher.update((version.len() as u64).to_le_bytes());
+    hasher.update(version.as_bytes());
+    for value in [
+        words_per_minute,
+        seconds_per_image,
+        image_decay,
+        min_seconds_per_image,
+        seconds_per_alt_char,
+        seconds_per_code_block,
+        seconds_per_code_line,
+        seconds_per_table,
+        seconds_per_table_cell,
+        seconds_per_link,
+        skim_rate,
+        words_per_page,
+        chars_per_page,
+    ] {
+        hasher.update(value.to_bits().to_le_bytes());
+    }
+    for flag in [dedupe_links, count_emoji, chinese, round_to_minutes] {
+        hasher.update([u8::from(flag)]);
+    }
+    hasher.finalize().into()
+}
+
+/// 阅读时间缓存
+///
+/// 实现该 trait 即可接入磁盘、Redis 等持久化存储。
+pub trait Cache {
+    /// 查询缓存
+    fn get(&mut self, key: &CacheKey) -> Option<ReadTime>;
+
+    /// 写入缓存
+    fn insert(&mut self, key: CacheKey, read_time: ReadTime);
+}
+
+/// 基于内存的 LRU 缓存
+///
+/// 超出容量时淘汰最久未使用的结果。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate_cached, LruCache, ReadSpeed};
+///
+/// let mut cache = LruCache::new(1024);
+/// let speed = ReadSpeed::default();
+/// let first = estimate_cached("# 标题\n\n这是内容", &speed, &mut cache);
+/// let second = estimate_cached("# 标题\n\n这是内容", &speed, &mut cache);
+/// assert_eq!(first, second);
+/// assert_eq!(cache.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LruCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<CacheKey, (ReadTime, u64)>,
+    recency: BTreeMap<u64, CacheKey>,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// 标记缓存项为最近使用
+    fn touch(&mut self, key: &CacheKey) {
+        self.tick += 1;
+        if let Some((_, stamp)) = self.entries.get_mut(key) {
+            self.recency.remove(stamp);
+            *stamp = self.tick;
+            self.recency.insert(self.tick, *key);
+        }
+    }
+}
+
+impl Cache for LruCache {
+    fn get(&mut self, key: &CacheKey) -> Option<ReadTime> {
+        self.touch(key);
+        self.entries
+            .get(key)
+            .map(|(read_time, _)| read_time.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, read_time: ReadTime) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some((_, stamp)) = self.entries.remove(&key) {
+            self.recency.remove(&stamp);
+        }
+        while self.entries.len() >= self.capacity {
+            match self.recency.pop_first() {
+                Some((_, oldest)) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(key, (read_time, self.tick));
+        self.recency.insert(self.tick, key);
+    }
+}
+
+/// 使用缓存估算阅读时间
+///
+/// 内容和速度配置均未变化时直接返回缓存结果，否则估算后写入缓存。需要启用 `cache` 特性。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+/// * `cache` - 缓存实现
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+pub fn estimate_cached(markdown: &str, speed: &ReadSpeed, cache: &mut impl Cache) -> ReadTime {
+    let key = CacheKey::new(markdown, speed);
+    if let Some(read_time) = cache.get(&key) {
+        return read_time;
+    }
+    let read_time = estimate_with_speed(markdown, speed);
+    cache.insert(key, read_time.clone());
+    read_time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_key() {
+        let speed = ReadSpeed::default();
+        assert_eq!(CacheKey::new("内容", &speed), CacheKey::new("内容", &speed));
+        assert_ne!(CacheKey::new("内容", &speed), CacheKey::new("其他", &speed));
+        assert_ne!(
+            CacheKey::new("内容", &speed),
+            CacheKey::new("内容", &speed.wpm(100.0))
+        );
+        assert_ne!(
+            CacheKey::new("内容", &speed),
+            CacheKey::new("内容", &speed.dedupe_links(true))
+        );
+        assert_ne!(
+            CacheKey::new("内容", &speed.chinese(false)),
+            CacheKey::new("内容", &speed.chinese(false).emoji(false))
+        );
+        assert_eq!(
+            CacheKey::new("a", &speed).content_hash[..4],
+            [0xca, 0x97, 0x81, 0x12]
+        );
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let speed = ReadSpeed::default();
+        let mut cache = LruCache::new(2);
+        estimate_cached("一", &speed, &mut cache);
+        estimate_cached("二", &speed, &mut cache);
+        // 访问“一”后，“二”成为最久未使用的项
+        assert!(cache.get(&CacheKey::new("一", &speed)).is_some());
+        estimate_cached("三", &speed, &mut cache);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&CacheKey::new("一", &speed)).is_some());
+        assert!(cache.get(&CacheKey::new("二", &speed)).is_none());
+        assert!(cache.get(&CacheKey::new("三", &speed)).is_some());
+    }
+}
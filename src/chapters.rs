@@ -0,0 +1,137 @@
+use crate::{Estimator, ReadSpeed, ReadTime, SectionReadTime};
+
+/// 按章拆分后的阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Chapters {
+    /// 按文档顺序排列的各章，每章包含其下所有更深级别的小节
+    pub chapters: Vec<SectionReadTime>,
+
+    /// 整篇文档的阅读时间
+    pub total: ReadTime,
+}
+
+impl Chapters {
+    /// 根据章节列表按 `split_level` 合并为章
+    ///
+    /// 级别不超过 `split_level` 的标题开始新的一章，更深级别的小节并入前一章。
+    /// 第一个拆分标题之前的内容成为标题为空、级别为 0 的一章。
+    pub fn from_sections(sections: &[SectionReadTime], split_level: u8, speed: &ReadSpeed) -> Self {
+        let mut groups: Vec<(&SectionReadTime, Vec<&ReadTime>)> = Vec::new();
+        let preamble = SectionReadTime::default();
+        for section in sections {
+            let starts_chapter = section.level <= split_level;
+            match groups.last_mut() {
+                Some((_, read_times)) if !starts_chapter => read_times.push(&section.read_time),
+                _ if starts_chapter => groups.push((section, vec![&section.read_time])),
+                _ => groups.push((&preamble, vec![&section.read_time])),
+            }
+        }
+
+        let chapters = groups
+            .into_iter()
+            .map(|(first, read_times)| SectionReadTime {
+                heading: String::from(first.heading.as_str()),
+                level: first.level,
+                read_time: ReadTime::merge_all(read_times, speed),
+            })
+            .collect();
+        let total = ReadTime::merge_all(sections.iter().map(|section| &section.read_time), speed);
+        Self { chapters, total }
+    }
+
+    pub fn chapters(&self) -> &[SectionReadTime] {
+        &self.chapters
+    }
+
+    pub fn total(&self) -> &ReadTime {
+        &self.total
+    }
+}
+
+impl Estimator<'_> {
+    /// 按 `split_level` 级标题拆分并估算每章的阅读时间
+    pub fn estimate_chapters(&mut self, markdown: &str, split_level: u8) -> Chapters {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("chapters", split_level, chapters = tracing::field::Empty)
+            .entered();
+        let sections = self.estimate_sections(markdown);
+        let chapters = Chapters::from_sections(&sections, split_level, self.speed());
+        #[cfg(feature = "tracing")]
+        span.record("chapters", chapters.chapters.len());
+        chapters
+    }
+}
+
+/// 按 `split_level` 级标题拆分并估算每章的阅读时间
+///
+/// 使用默认的阅读速度配置。与 [`estimate_sections`](crate::estimate_sections) 不同，
+/// 更深级别的小节会并入所在的章，适合 mdBook 源文件、长篇规范等书籍长度的文档。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `split_level` - 拆分的标题级别，通常为 1
+///
+/// # Returns
+///
+/// 返回包含各章阅读时间和总阅读时间的 [`Chapters`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_chapters;
+///
+/// let markdown = "# 第一章\n\n内容\n\n## 第一节\n\n内容\n\n# 第二章\n\n内容";
+/// let chapters = estimate_chapters(markdown, 1);
+/// assert_eq!(chapters.chapters.len(), 2);
+/// assert_eq!(chapters.chapters[0].heading, "第一章");
+/// assert_eq!(chapters.total.word_count, 15);
+/// ```
+pub fn estimate_chapters(markdown: &str, split_level: u8) -> Chapters {
+    estimate_chapters_with_speed(markdown, split_level, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置按 `split_level` 级标题拆分并估算每章的阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `split_level` - 拆分的标题级别，通常为 1
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含各章阅读时间和总阅读时间的 [`Chapters`]。
+pub fn estimate_chapters_with_speed(
+    markdown: &str,
+    split_level: u8,
+    speed: &ReadSpeed,
+) -> Chapters {
+    Estimator::new(*speed).estimate_chapters(markdown, split_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_estimate_chapters() {
+        let markdown = "## 序\n\n前言\n\n# 第一章\n\n内容\n\n### 细节\n\n内容\n\n## 第一节\n\n内容\n\n# 第二章\n\n![图片](a.png)\n";
+        let chapters = estimate_chapters(markdown, 1);
+        let headings: Vec<_> = chapters.chapters.iter().map(|c| c.heading()).collect();
+        assert_eq!(headings, ["", "第一章", "第二章"]);
+        assert_eq!(chapters.chapters[0].level, 0);
+        assert_eq!(chapters.chapters[1].word_count(), 3 + 2 + 2 + 2 + 3 + 2);
+        assert_eq!(chapters.chapters[2].read_time.image_count, 1);
+        assert_eq!(chapters.total, estimate(markdown));
+
+        let chapters = estimate_chapters(markdown, 2);
+        assert_eq!(chapters.chapters.len(), 4);
+        assert_eq!(chapters.chapters[0].heading, "序");
+        assert_eq!(chapters.chapters[2].heading, "第一节");
+    }
+}
@@ -0,0 +1,304 @@
+//! 字符分类查找表
+//!
+//! 计数时每个字符都需要判断是否为空白、控制字符或 emoji。查找表在编译期生成：
+//! 第一级按码位的高位找到 256 个字符一组的块，第二级直接取出类别，每个字符只需两次数组访问。
+//! 只需要区分空白和控制字符时，按 UTF-8 首字节查表即可，大部分字符不需要解码。
+//! emoji 的范围位于 `chars/emoji.rs`，由 `tools/emoji-table` 根据 Unicode emoji 数据生成。
+
+mod emoji;
+
+use emoji::{EMOJI_RANGES, TEXT_EMOJI_RANGES};
+
+/// 空白字符，与 [`char::is_whitespace`] 一致
+pub(crate) const SPACE: u8 = 1;
+
+/// 控制字符，与 [`char::is_control`] 一致
+pub(crate) const CONTROL: u8 = 1 << 1;
+
+/// 默认以 emoji 样式显示的字符
+pub(crate) const EMOJI: u8 = 1 << 2;
+
+/// 默认以文本样式显示，后跟 VS16 (U+FE0F) 时才是 emoji 的字符，例如 `❤` 和 `☺`
+pub(crate) const TEXT_EMOJI: u8 = 1 << 3;
+
+/// 变体选择符 U+FE0E 和 U+FE0F，只改变前一个字符的显示样式
+pub(crate) const VARIATION: u8 = 1 << 4;
+
+/// Unicode `White_Space` 属性中的字符
+const SPACE_RANGES: &[(u32, u32)] = &[
+    (0x0009, 0x000D),
+    (0x0020, 0x0020),
+    (0x0085, 0x0085),
+    (0x00A0, 0x00A0),
+    (0x1680, 0x1680),
+    (0x2000, 0x200A),
+    (0x2028, 0x2029),
+    (0x202F, 0x202F),
+    (0x205F, 0x205F),
+    (0x3000, 0x3000),
+];
+
+/// `Cc` 类别的控制字符
+const CONTROL_RANGES: &[(u32, u32)] = &[(0x0000, 0x001F), (0x007F, 0x009F)];
+
+const VARIATION_RANGES: &[(u32, u32)] = &[(0xFE0E, 0xFE0F)];
+
+/// 所有类别的范围
+const CLASS_RANGES: &[(u8, &[(u32, u32)])] = &[
+    (SPACE, SPACE_RANGES),
+    (CONTROL, CONTROL_RANGES),
+    (EMOJI, EMOJI_RANGES),
+    (TEXT_EMOJI, TEXT_EMOJI_RANGES),
+    (VARIATION, VARIATION_RANGES),
+];
+
+/// UTF-8 后续字节
+const CONTINUATION: u8 = 1 << 5;
+
+/// 需要解码后再分类的首字节，非 ASCII 的空白和控制字符都以这些字节开头
+const DECODE: u8 = 1 << 6;
+
+/// 按字节分类：ASCII 字节直接给出类别，其余首字节开头的字符不属于空白和控制字符
+static BYTES: [u8; 256] = build_bytes();
+
+/// 查找表覆盖的码位范围，之后的字符都不属于任何类别
+const TABLE_END: u32 = table_end();
+
+const BLOCK_COUNT: usize = (TABLE_END >> 8) as usize;
+
+/// 含有分类字符的块数，第 0 块全部为 0，由其余所有块共用
+const CLASS_BLOCKS: usize = count_class_blocks();
+
+struct Table {
+    /// 每个块在 `classes` 中的序号
+    blocks: [u8; BLOCK_COUNT],
+    classes: [[u8; 256]; CLASS_BLOCKS],
+}
+
+static TABLE: Table = build_table();
+
+const fn in_ranges(ranges: &[(u32, u32)], code: u32) -> bool {
+    let mut i = 0;
+    while i < ranges.len() {
+        if ranges[i].0 <= code && code <= ranges[i].1 {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// 直接按范围计算的类别，只在生成按字节查找的表时使用
+const fn compute_class(code: u32) -> u8 {
+    let mut class = 0;
+    let mut i = 0;
+    while i < CLASS_RANGES.len() {
+        if in_ranges(CLASS_RANGES[i].1, code) {
+            class |= CLASS_RANGES[i].0;
+        }
+        i += 1;
+    }
+    class
+}
+
+/// 最后一个分类字符所在块的结束位置
+const fn table_end() -> u32 {
+    let mut end = 0;
+    let mut i = 0;
+    while i < CLASS_RANGES.len() {
+        let ranges = CLASS_RANGES[i].1;
+        let mut j = 0;
+        while j < ranges.len() {
+            if ranges[j].1 >= end {
+                end = (ranges[j].1 | 0xFF) + 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    end
+}
+
+/// 块中是否有属于任何类别的字符
+const fn has_class(block: usize) -> bool {
+    let start = (block as u32) << 8;
+    let mut i = 0;
+    while i < CLASS_RANGES.len() {
+        if overlaps(CLASS_RANGES[i].1, start) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn overlaps(ranges: &[(u32, u32)], start: u32) -> bool {
+    let mut i = 0;
+    while i < ranges.len() {
+        if ranges[i].0 <= start + 0xFF && start <= ranges[i].1 {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+const fn count_class_blocks() -> usize {
+    let mut count = 1;
+    let mut block = 0;
+    while block < BLOCK_COUNT {
+        if has_class(block) {
+            count += 1;
+        }
+        block += 1;
+    }
+    count
+}
+
+/// 先为含有分类字符的块分配序号，再逐个范围写入类别
+const fn build_table() -> Table {
+    let mut table = Table {
+        blocks: [0; BLOCK_COUNT],
+        classes: [[0; 256]; CLASS_BLOCKS],
+    };
+    let mut next = 1;
+    let mut block = 0;
+    while block < BLOCK_COUNT {
+        if has_class(block) {
+            table.blocks[block] = next as u8;
+            next += 1;
+        }
+        block += 1;
+    }
+    let mut i = 0;
+    while i < CLASS_RANGES.len() {
+        let (class, ranges) = CLASS_RANGES[i];
+        let mut j = 0;
+        while j < ranges.len() {
+            let mut code = ranges[j].0;
+            while code <= ranges[j].1 {
+                let block = table.blocks[(code >> 8) as usize] as usize;
+                table.classes[block][(code & 0xFF) as usize] |= class;
+                code += 1;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    table
+}
+
+const fn build_bytes() -> [u8; 256] {
+    let mut bytes = [0; 256];
+    let mut b = 0;
+    while b < 256 {
+        bytes[b] = match b {
+            0x00..=0x7F => compute_class(b as u32),
+            0x80..=0xBF => CONTINUATION,
+            0xC2 | 0xE1 | 0xE2 | 0xE3 => DECODE,
+            _ => 0,
+        };
+        b += 1;
+    }
+    bytes
+}
+
+/// 统计不属于 `skip` 中任何类别的字符数，`skip` 只能包含 [`SPACE`] 和 [`CONTROL`]
+///
+/// 逐字节查表，只有可能是空白或控制字符的少数首字节需要解码。
+pub(crate) fn count_chars(text: &str, skip: u8) -> usize {
+    let mut count = 0;
+    for (i, &b) in text.as_bytes().iter().enumerate() {
+        let kind = BYTES[b as usize];
+        if kind & DECODE != 0 {
+            let c = text[i..].chars().next().unwrap_or_default();
+            count += usize::from(class(c) & skip == 0);
+        } else {
+            count += usize::from(kind & (skip | CONTINUATION) == 0);
+        }
+    }
+    count
+}
+
+/// 字符的类别，为 [`SPACE`]、[`CONTROL`]、[`EMOJI`]、[`TEXT_EMOJI`] 和 [`VARIATION`] 的组合
+#[inline]
+pub(crate) fn class(c: char) -> u8 {
+    let code = c as u32;
+    match TABLE.blocks.get((code >> 8) as usize) {
+        Some(&block) => TABLE.classes[block as usize][(code & 0xFF) as usize],
+        None => 0,
+    }
+}
+
+/// 中日韩文字：汉字、谚文、平假名和片假名
+///
+/// 范围与 Hugo 判断中日韩语言时匹配的文字相同。
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x11FF
+            | 0x2E80..=0x2FDF
+            | 0x3005
+            | 0x3007
+            | 0x3021..=0x3029
+            | 0x302E..=0x302F
+            | 0x3038..=0x303B
+            | 0x3041..=0x309F
+            | 0x30A1..=0x30FA
+            | 0x30FD..=0x30FF
+            | 0x3131..=0x318E
+            | 0x31F0..=0x31FF
+            | 0x3200..=0x321E
+            | 0x3260..=0x327E
+            | 0x32D0..=0x3357
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA960..=0xA97C
+            | 0xAC00..=0xD7FB
+            | 0xF900..=0xFAFF
+            | 0xFF66..=0xFF6F
+            | 0xFF71..=0xFF9D
+            | 0xFFA0..=0xFFDC
+            | 0x1AFF0..=0x1B16F
+            | 0x1F200
+            | 0x20000..=0x323AF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_matches_std() {
+        for c in (0..=char::MAX as u32).filter_map(char::from_u32) {
+            let class = class(c);
+            assert_eq!(class & SPACE != 0, c.is_whitespace(), "{:?}", c);
+            assert_eq!(class & CONTROL != 0, c.is_control(), "{:?}", c);
+            if (c as u32) < TABLE_END {
+                assert_eq!(class, compute_class(c as u32), "{:?}", c);
+            } else {
+                assert_eq!(class & !(SPACE | CONTROL), 0, "{:?}", c);
+            }
+        }
+        assert_eq!(class('😀'), EMOJI);
+        assert_eq!(class('🇨'), EMOJI);
+        assert_eq!(class('❤'), TEXT_EMOJI);
+        assert_eq!(class('©'), TEXT_EMOJI);
+        assert_eq!(class('\u{FE0F}'), VARIATION);
+        assert_eq!(class('\u{1F650}'), 0);
+        assert_eq!(class('\u{3000}'), SPACE);
+        assert_eq!(class('字'), 0);
+    }
+
+    #[test]
+    fn test_count_chars() {
+        let text = "中文 text\u{3000}全角\u{a0}\u{85}\u{1}😀\u{2000}…\u{e000}";
+        for skip in [SPACE, SPACE | CONTROL] {
+            let expected = text
+                .chars()
+                .filter(|&c| compute_class(c as u32) & skip == 0);
+            assert_eq!(count_chars(text, skip), expected.count());
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::ReadTime;
+
+/// 多篇文档阅读时间的统计
+///
+/// 汇总总量、平均值、中位数、百分位数和直方图，统计均基于每篇文档取整后的总秒数。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Corpus, estimate};
+///
+/// let docs = ["短文", "稍长一些的文章内容", "# 标题\n\n![图片](a.png)"];
+/// let corpus: Corpus = docs.iter().map(|doc| estimate(doc)).collect();
+/// assert_eq!(corpus.len(), 3);
+/// println!("合计 {}，中位数 {}秒", corpus.total().formatted, corpus.median());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Corpus {
+    /// 按升序排列的各篇总秒数
+    seconds: Vec<u64>,
+    total: ReadTime,
+}
+
+/// 直方图中的一个区间
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct HistogramBucket {
+    /// 区间起点（秒，包含）
+    pub start: u64,
+
+    /// 区间终点（秒，不包含）
+    pub end: u64,
+
+    /// 落在区间内的文档数量
+    pub count: usize,
+}
+
+impl Corpus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 加入一篇文档的估算结果
+    pub fn push(&mut self, read_time: ReadTime) {
+        let index = self
+            .seconds
+            .partition_point(|&seconds| seconds <= read_time.total_seconds);
+        self.seconds.insert(index, read_time.total_seconds);
+        self.total += read_time;
+    }
+
+    /// 文档数量
+    pub fn len(&self) -> usize {
+        self.seconds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seconds.is_empty()
+    }
+
+    /// 所有文档的合计，各项计数与总秒数直接相加
+    pub fn total(&self) -> &ReadTime {
+        &self.total
+    }
+
+    /// 平均秒数，没有文档时为 0
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.total.total_seconds as f64 / self.len() as f64
+    }
+
+    /// 中位数秒数，文档数量为偶数时取中间两篇的平均值
+    pub fn median(&self) -> f64 {
+        let len = self.len();
+        match len {
+            0 => 0.0,
+            _ if len % 2 == 1 => self.seconds[len / 2] as f64,
+            _ => (self.seconds[len / 2 - 1] + self.seconds[len / 2]) as f64 / 2.0,
+        }
+    }
+
+    /// 第 `percent` 百分位数（最近秩法），`percent` 会被截断到 0 到 100 之间
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{Corpus, ReadTime};
+    ///
+    /// let corpus: Corpus = (1..=10)
+    ///     .map(|minutes| ReadTime::builder().total_seconds(minutes * 60).build())
+    ///     .collect();
+    /// assert_eq!(corpus.percentile(90.0), 540);
+    /// assert_eq!(corpus.percentile(100.0), 600);
+    /// ```
+    pub fn percentile(&self, percent: f64) -> u64 {
+        if self.is_empty() {
+            return 0;
+        }
+        let percent = if percent.is_nan() {
+            0.0
+        } else {
+            percent.clamp(0.0, 100.0)
+        };
+        let rank = (percent / 100.0 * self.len() as f64).ceil() as usize;
+        self.seconds[rank.saturating_sub(1)]
+    }
+
+    /// 按 `bucket_seconds` 宽度划分的直方图，从 0 开始直到覆盖最长的文档
+    ///
+    /// `bucket_seconds` 为 0 时按 1 处理。
+    pub fn histogram(&self, bucket_seconds: u64) -> Vec<HistogramBucket> {
+        let width = bucket_seconds.max(1);
+        let Some(&max) = self.seconds.last() else {
+            return Vec::new();
+        };
+        let mut buckets: Vec<HistogramBucket> = (0..=max / width)
+            .map(|i| HistogramBucket {
+                start: i * width,
+                end: (i + 1) * width,
+                count: 0,
+            })
+            .collect();
+        for &seconds in &self.seconds {
+            buckets[(seconds / width) as usize].count += 1;
+        }
+        buckets
+    }
+}
+
+impl Extend<ReadTime> for Corpus {
+    fn extend<I: IntoIterator<Item = ReadTime>>(&mut self, iter: I) {
+        for read_time in iter {
+            self.push(read_time);
+        }
+    }
+}
+
+impl FromIterator<ReadTime> for Corpus {
+    fn from_iter<I: IntoIterator<Item = ReadTime>>(iter: I) -> Self {
+        let mut corpus = Corpus::new();
+        corpus.extend(iter);
+        corpus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_time(seconds: u64) -> ReadTime {
+        ReadTime::builder()
+            .total_seconds(seconds)
+            .word_count(seconds as usize)
+            .build()
+    }
+
+    #[test]
+    fn test_corpus_statistics() {
+        let mut corpus: Corpus = [30, 10, 200, 45].into_iter().map(read_time).collect();
+        assert_eq!(corpus.len(), 4);
+        assert_eq!(corpus.total().total_seconds, 285);
+        assert_eq!(corpus.total().word_count, 285);
+        assert_eq!(corpus.mean(), 71.25);
+        assert_eq!(corpus.median(), 37.5);
+        assert_eq!(corpus.percentile(50.0), 30);
+        assert_eq!(corpus.percentile(0.0), 10);
+        assert_eq!(corpus.percentile(99.0), 200);
+
+        corpus.push(read_time(60));
+        assert_eq!(corpus.median(), 45.0);
+
+        let histogram = corpus.histogram(60);
+        let counts: Vec<_> = histogram.iter().map(|bucket| bucket.count).collect();
+        assert_eq!(counts, [3, 1, 0, 1]);
+        assert_eq!(histogram[3].start, 180);
+
+        let empty = Corpus::new();
+        assert_eq!(empty.mean(), 0.0);
+        assert_eq!(empty.percentile(50.0), 0);
+        assert!(empty.histogram(60).is_empty());
+    }
+}
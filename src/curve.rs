@@ -0,0 +1,166 @@
+use crate::{BlockReadTime, Estimator, ReadSpeed};
+
+/// 累计阅读时间曲线上的一个点
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct CurvePoint {
+    /// 源文本中的字节偏移
+    pub offset: usize,
+
+    /// 读到该位置时的累计阅读时间（秒），未取整
+    pub seconds: f64,
+}
+
+/// 按字节偏移的累计阅读时间曲线
+///
+/// 曲线上的点按偏移和累计时间单调不减，查询使用二分查找，点之间按线性插值。
+/// 前端可以据此把滚动位置映射为已读时间或剩余时间。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ReadingCurve {
+    /// 曲线上的点
+    pub points: Vec<CurvePoint>,
+}
+
+impl ReadingCurve {
+    /// 根据块列表构建曲线，每个块贡献起点和终点两个点
+    pub fn from_blocks(blocks: &[BlockReadTime]) -> Self {
+        let mut points = Vec::with_capacity(blocks.len() * 2);
+        let mut offset = 0;
+        let mut seconds = 0.0;
+        for block in blocks {
+            offset = block.range.start.max(offset);
+            points.push(CurvePoint { offset, seconds });
+            offset = block.range.end.max(offset);
+            seconds += block.seconds;
+            points.push(CurvePoint { offset, seconds });
+        }
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[CurvePoint] {
+        &self.points
+    }
+
+    /// 整篇文档的阅读时间（秒），未取整
+    pub fn total_seconds(&self) -> f64 {
+        self.points.last().map_or(0.0, |point| point.seconds)
+    }
+
+    /// 读到 `offset` 时的累计阅读时间（秒）
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::estimate_curve;
+    ///
+    /// let markdown = "第一段内容\n\n第二段内容\n";
+    /// let curve = estimate_curve(markdown);
+    /// assert_eq!(curve.seconds_at(0), 0.0);
+    /// assert_eq!(curve.seconds_at(markdown.len()), curve.total_seconds());
+    /// assert!(curve.seconds_at(markdown.len() / 2) > 0.0);
+    /// ```
+    pub fn seconds_at(&self, offset: usize) -> f64 {
+        let index = self.points.partition_point(|point| point.offset <= offset);
+        match (
+            index.checked_sub(1).map(|i| self.points[i]),
+            self.points.get(index),
+        ) {
+            (None, _) => 0.0,
+            (Some(before), None) => before.seconds,
+            (Some(before), Some(after)) => {
+                let span = (after.offset - before.offset) as f64;
+                let progress = (offset - before.offset) as f64 / span;
+                before.seconds + (after.seconds - before.seconds) * progress
+            }
+        }
+    }
+
+    /// 累计阅读时间达到 `seconds` 时的字节偏移，是 [`ReadingCurve::seconds_at`] 的逆运算
+    pub fn offset_at(&self, seconds: f64) -> usize {
+        let index = self.points.partition_point(|point| point.seconds < seconds);
+        match (
+            index.checked_sub(1).map(|i| self.points[i]),
+            self.points.get(index),
+        ) {
+            (None, Some(after)) => after.offset,
+            (None, None) => 0,
+            (Some(before), None) => before.offset,
+            (Some(before), Some(after)) => {
+                let progress = (seconds - before.seconds) / (after.seconds - before.seconds);
+                before.offset + ((after.offset - before.offset) as f64 * progress) as usize
+            }
+        }
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算按字节偏移的累计阅读时间曲线
+    pub fn estimate_curve(&mut self, markdown: &str) -> ReadingCurve {
+        ReadingCurve::from_blocks(&self.estimate_blocks(markdown))
+    }
+}
+
+/// 估算按字节偏移的累计阅读时间曲线
+///
+/// 使用默认的阅读速度配置。基于 [`estimate_blocks`](crate::estimate_blocks) 的块范围，
+/// 构建一次后可以反复查询。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回 [`ReadingCurve`]。
+pub fn estimate_curve(markdown: &str) -> ReadingCurve {
+    estimate_curve_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算按字节偏移的累计阅读时间曲线
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回 [`ReadingCurve`]。
+pub fn estimate_curve_with_speed(markdown: &str, speed: &ReadSpeed) -> ReadingCurve {
+    Estimator::new(*speed).estimate_curve(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading_curve() {
+        let markdown = "# 标题\n\n段落内容\n\n![图片](a.png)\n";
+        let curve = estimate_curve(markdown);
+        assert!(
+            curve
+                .points()
+                .windows(2)
+                .all(|w| w[0].offset <= w[1].offset && w[0].seconds <= w[1].seconds)
+        );
+
+        // 图片块内部按线性插值
+        let image = markdown.find('!').unwrap();
+        let before = curve.seconds_at(image);
+        let after = curve.seconds_at(markdown.len());
+        assert_eq!(after - before, 12.0);
+        assert!(curve.seconds_at(image + 3) > before);
+        assert_eq!(curve.seconds_at(usize::MAX), curve.total_seconds());
+
+        assert_eq!(curve.offset_at(0.0), 0);
+        assert_eq!(curve.offset_at(before), markdown.find("\n\n!").unwrap() + 1);
+        assert_eq!(curve.offset_at(f64::MAX), markdown.len());
+        assert_eq!(ReadingCurve::default().seconds_at(10), 0.0);
+    }
+}
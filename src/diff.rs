@@ -0,0 +1,89 @@
+use crate::blocks::merge_blocks;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use std::collections::BTreeMap;
+
+impl Estimator<'_> {
+    /// 估算 `new` 相对 `old` 新增或修改的内容的阅读时间
+    ///
+    /// 按块比较：`new` 中的块若在 `old` 中找不到内容相同（忽略首尾空白）的块，
+    /// 则视为新增或修改。仅移动位置的块不会计入。
+    pub fn estimate_diff(&mut self, old: &str, new: &str) -> ReadTime {
+        let mut unchanged: BTreeMap<&str, usize> = BTreeMap::new();
+        for block in self.estimate_blocks(old) {
+            *unchanged.entry(old[block.range()].trim()).or_insert(0) += 1;
+        }
+
+        let blocks = self.estimate_blocks(new);
+        let changed =
+            blocks
+                .iter()
+                .filter(|block| match unchanged.get_mut(new[block.range()].trim()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                });
+        merge_blocks(changed, self.speed())
+    }
+}
+
+/// 估算两个版本之间新增或修改的内容的阅读时间
+///
+/// 使用默认的阅读速度配置。适合在文档评审工具中显示“本次修改增加了约 6 分钟的阅读量”。
+///
+/// # Arguments
+///
+/// * `old` - 修改前的 Markdown 文本
+/// * `new` - 修改后的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回新增或修改的块合计的 [`ReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_diff;
+///
+/// let old = "# 标题\n\n第一段\n";
+/// let new = "# 标题\n\n第一段\n\n新增的一段\n";
+/// assert_eq!(estimate_diff(old, new).word_count, 5);
+/// assert_eq!(estimate_diff(old, old).total_seconds, 0);
+/// ```
+pub fn estimate_diff(old: &str, new: &str) -> ReadTime {
+    estimate_diff_with_speed(old, new, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算两个版本之间新增或修改的内容的阅读时间
+///
+/// # Arguments
+///
+/// * `old` - 修改前的 Markdown 文本
+/// * `new` - 修改后的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回新增或修改的块合计的 [`ReadTime`]。
+pub fn estimate_diff_with_speed(old: &str, new: &str, speed: &ReadSpeed) -> ReadTime {
+    Estimator::new(*speed).estimate_diff(old, new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_diff() {
+        let old = "# 标题\n\n段落一\n\n段落二\n\n段落二\n";
+        let new = "段落二\n\n# 标题\n\n段落一（修改）\n\n段落二\n\n段落二\n\n![图片](a.png)\n";
+        let diff = estimate_diff(old, new);
+        // 移动的块不计入，重复的块按次数匹配
+        assert_eq!(diff.word_count, "段落一（修改）".chars().count() + 3);
+        assert_eq!(diff.image_count, 1);
+
+        assert_eq!(estimate_diff("", new).word_count, crate::words(new));
+        assert_eq!(estimate_diff(new, "").total_seconds, 0);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::archive::{elements, read_entry, zip_error};
+use crate::backend::{WordDocument, attribute};
+use crate::{Error, Estimator, ReadSpeed, ReadTime};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// 正文部件的默认路径
+const DEFAULT_DOCUMENT: &str = "word/document.xml";
+
+impl Estimator<'_> {
+    /// 估算 DOCX 文档的阅读时间
+    ///
+    /// 正文部件始终按 WordprocessingML 解析，事件访问器和阅读速度与其他估算相同。
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - DOCX 文件内容
+    ///
+    /// # Returns
+    ///
+    /// 返回包含阅读时间信息的 [`ReadTime`]，读取失败或不是有效的 DOCX 时返回 [`Error`]。
+    pub fn estimate_docx(&mut self, reader: impl Read + Seek) -> Result<ReadTime, Error> {
+        let document = main_document(reader)?;
+        let backend = std::mem::replace(&mut self.backend, Box::new(WordDocument));
+        let read_time = self.estimate(&document);
+        self.backend = backend;
+        Ok(read_time)
+    }
+}
+
+/// 估算 DOCX 文件的阅读时间
+///
+/// 使用默认的阅读速度配置，详见 [`estimate_docx_with_speed`]。
+///
+/// # Arguments
+///
+/// * `path` - DOCX 文件路径
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`]，读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::estimate_docx;
+///
+/// let read_time = estimate_docx("draft.docx").unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// ```
+pub fn estimate_docx(path: impl AsRef<Path>) -> Result<ReadTime, Error> {
+    estimate_docx_with_speed(path, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算 DOCX 文件的阅读时间
+///
+/// 只统计正文部件：段落和表格中的文本计入字数，嵌入的图片计为图片，
+/// `Title` 和 `Heading1` 等样式的段落计为标题；页眉、页脚、批注和已删除的修订不计入。
+///
+/// # Arguments
+///
+/// * `path` - DOCX 文件路径
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`]，读取失败时返回 [`Error`]。
+pub fn estimate_docx_with_speed(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+) -> Result<ReadTime, Error> {
+    let file = BufReader::new(File::open(path)?);
+    Estimator::new(*speed).estimate_docx(file)
+}
+
+/// 读取正文部件，路径取自包关系 `_rels/.rels`，缺少关系时使用默认路径
+fn main_document(reader: impl Read + Seek) -> Result<String, Error> {
+    let mut archive = ZipArchive::new(reader).map_err(|err| zip_error(err, Error::Docx))?;
+    let path = read_entry(&mut archive, "_rels/.rels", Error::Docx)
+        .ok()
+        .and_then(|rels| {
+            elements(&rels)
+                .filter(|(name, _)| *name == "Relationship")
+                .filter(|(_, attributes)| {
+                    attribute(attributes, "Type")
+                        .is_some_and(|kind| kind.ends_with("/officeDocument"))
+                })
+                .find_map(|(_, attributes)| attribute(attributes, "Target"))
+                .map(|target| String::from(target.trim_start_matches('/')))
+        })
+        .unwrap_or_else(|| String::from(DEFAULT_DOCUMENT));
+    read_entry(&mut archive, &path, Error::Docx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+    use std::io::{Cursor, Write};
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn build_docx(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn test_estimate_docx() {
+        let rels = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/main.xml"/>
+</Relationships>"#;
+        let document = r#"<w:document><w:body>
+<w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t>标题</w:t></w:r></w:p>
+<w:p><w:r><w:t>这是</w:t></w:r><w:r><w:t>内容</w:t></w:r></w:p>
+<w:p><w:r><w:drawing/></w:r></w:p>
+</w:body></w:document>"#;
+        let docx = build_docx(&[("_rels/.rels", rels), ("word/main.xml", document)]);
+        let read_time = Estimator::new(ReadSpeed::default())
+            .estimate_docx(docx)
+            .unwrap();
+        assert_eq!(read_time, estimate("# 标题\n\n这是内容\n\n![](a.png)"));
+
+        let docx = build_docx(&[("word/document.xml", document)]);
+        let read_time = Estimator::new(ReadSpeed::default())
+            .estimate_docx(docx)
+            .unwrap();
+        assert_eq!(read_time.word_count, 6);
+    }
+
+    #[test]
+    fn test_estimate_docx_invalid() {
+        let docx = build_docx(&[("[Content_Types].xml", "<Types/>")]);
+        let err = Estimator::new(ReadSpeed::default())
+            .estimate_docx(docx)
+            .unwrap_err();
+        assert!(matches!(err, Error::Docx(_)));
+    }
+}
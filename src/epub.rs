@@ -0,0 +1,331 @@
+use crate::archive::{elements, read_entry, zip_error};
+use crate::backend::attribute;
+use crate::{Error, Estimator, Html, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// EPUB 中一个书脊项目（通常是一章）的阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct EpubChapter {
+    /// 章节文件在 EPUB 中的路径
+    pub path: String,
+
+    /// 章节中第一个标题的文本，没有标题时为空
+    pub title: String,
+
+    /// 本章的阅读时间
+    pub read_time: ReadTime,
+}
+
+impl EpubChapter {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+}
+
+/// 按书脊顺序估算的 EPUB 阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct EpubReadTime {
+    /// 按书脊顺序排列的各章
+    pub chapters: Vec<EpubChapter>,
+
+    /// 整本书的阅读时间
+    pub total: ReadTime,
+}
+
+impl EpubReadTime {
+    pub fn chapters(&self) -> &[EpubChapter] {
+        &self.chapters
+    }
+
+    pub fn total(&self) -> &ReadTime {
+        &self.total
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算 EPUB 中每个书脊项目以及整本书的阅读时间
+    ///
+    /// 书脊中的 XHTML 文档始终使用 [`Html`] 后端解析，事件访问器和阅读速度与其他估算相同。
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - EPUB 文件内容
+    ///
+    /// # Returns
+    ///
+    /// 返回各章和整本书的阅读时间，读取失败或不是有效的 EPUB 时返回 [`Error`]。
+    pub fn estimate_epub(&mut self, reader: impl Read + Seek) -> Result<EpubReadTime, Error> {
+        let documents = spine_documents(reader)?;
+        let backend = std::mem::replace(&mut self.backend, Box::new(Html));
+        let chapters = documents
+            .into_iter()
+            .map(|(path, xhtml)| {
+                let mut title = String::new();
+                let mut in_heading = false;
+                let counters = self.count(&xhtml, &mut |event, _, _| match event {
+                    Event::Start(Tag::Heading { .. }) => in_heading = title.trim().is_empty(),
+                    Event::End(TagEnd::Heading(_)) => in_heading = false,
+                    Event::Text(text) | Event::Code(text) if in_heading => title.push_str(text),
+                    _ => {}
+                });
+                EpubChapter {
+                    path,
+                    title: String::from(title.trim()),
+                    read_time: ReadTime::from_counters(counters, &self.speed),
+                }
+            })
+            .collect::<Vec<_>>();
+        self.backend = backend;
+
+        let total = ReadTime::merge_all(
+            chapters.iter().map(|chapter| &chapter.read_time),
+            &self.speed,
+        );
+        Ok(EpubReadTime { chapters, total })
+    }
+}
+
+/// 估算 EPUB 文件中每章以及整本书的阅读时间
+///
+/// 使用默认的阅读速度配置，详见 [`estimate_epub_with_speed`]。
+///
+/// # Arguments
+///
+/// * `path` - EPUB 文件路径
+///
+/// # Returns
+///
+/// 返回包含各章和整本书阅读时间的 [`EpubReadTime`]，读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::estimate_epub;
+///
+/// let book = estimate_epub("book.epub").unwrap();
+/// for chapter in &book.chapters {
+///     println!("{}: {}", chapter.title, chapter.read_time.formatted);
+/// }
+/// println!("全书: {}", book.total.formatted);
+/// ```
+pub fn estimate_epub(path: impl AsRef<Path>) -> Result<EpubReadTime, Error> {
+    estimate_epub_with_speed(path, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算 EPUB 文件中每章以及整本书的阅读时间
+///
+/// 按 OPF 书脊的顺序估算每个 XHTML 文档，非 XHTML 的书脊项目会被忽略。
+/// 章节标题取自文档中第一个标题，适合计算“本章剩余”和“全书剩余”的阅读时间。
+///
+/// # Arguments
+///
+/// * `path` - EPUB 文件路径
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含各章和整本书阅读时间的 [`EpubReadTime`]，读取失败时返回 [`Error`]。
+pub fn estimate_epub_with_speed(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+) -> Result<EpubReadTime, Error> {
+    let file = BufReader::new(File::open(path)?);
+    Estimator::new(*speed).estimate_epub(file)
+}
+
+/// 按书脊顺序读取所有 XHTML 文档的路径和内容
+fn spine_documents(reader: impl Read + Seek) -> Result<Vec<(String, String)>, Error> {
+    let mut archive = ZipArchive::new(reader).map_err(|err| zip_error(err, Error::Epub))?;
+    let container = read_entry(&mut archive, "META-INF/container.xml", Error::Epub)?;
+    let package_path = elements(&container)
+        .filter(|(name, _)| *name == "rootfile")
+        .find_map(|(_, attributes)| attribute(attributes, "full-path"))
+        .ok_or_else(|| Error::Epub(String::from("container.xml 中没有 rootfile")))?;
+    let package_path = resolve("", package_path);
+    let package = read_entry(&mut archive, &package_path, Error::Epub)?;
+    let base = package_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+    let manifest: Vec<_> = elements(&package)
+        .filter(|(name, _)| *name == "item")
+        .filter_map(|(_, attributes)| {
+            let id = attribute(attributes, "id")?;
+            let href = attribute(attributes, "href")?;
+            let media_type = attribute(attributes, "media-type").unwrap_or_default();
+            Some((id, href, media_type))
+        })
+        .collect();
+
+    let mut documents = Vec::new();
+    for idref in elements(&package)
+        .filter(|(name, _)| *name == "itemref")
+        .filter_map(|(_, attributes)| attribute(attributes, "idref"))
+    {
+        let Some(&(_, href, media_type)) = manifest.iter().find(|(id, ..)| *id == idref) else {
+            continue;
+        };
+        if !matches!(media_type, "application/xhtml+xml" | "text/html") {
+            continue;
+        }
+        let path = resolve(base, href);
+        let xhtml = read_entry(&mut archive, &path, Error::Epub)?;
+        documents.push((path, xhtml));
+    }
+    Ok(documents)
+}
+
+/// 将相对于 `base` 目录的 URL 解析为压缩包中的路径
+fn resolve(base: &str, href: &str) -> String {
+    let href = href.split(['#', '?']).next().unwrap_or_default();
+    let mut bytes = Vec::new();
+    let mut rest = href.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let escaped = (b == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                bytes.push(byte);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(b);
+                rest = tail;
+            }
+        }
+    }
+    let href = String::from_utf8_lossy(&bytes);
+
+    let mut segments: Vec<&str> = if href.starts_with('/') {
+        Vec::new()
+    } else {
+        base.split('/').filter(|s| !s.is_empty()).collect()
+    };
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+    use std::io::{Cursor, Write};
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn build_epub(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        let mut cursor = writer.finish().unwrap();
+        cursor.set_position(0);
+        cursor
+    }
+
+    #[test]
+    fn test_estimate_epub() {
+        let container = r#"<?xml version="1.0"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>"#;
+        let package = r#"<?xml version="1.0"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+  <manifest>
+    <item id="nav" href="nav.xhtml" media-type="application/xhtml+xml" properties="nav"/>
+    <item id="css" href="style.css" media-type="text/css"/>
+    <item id="ch1" href="Text/chapter%201.xhtml" media-type="application/xhtml+xml"/>
+    <item id="ch2" href="Text/ch2.xhtml" media-type="application/xhtml+xml"/>
+  </manifest>
+  <spine>
+    <itemref idref="ch1"/>
+    <itemref idref="css"/>
+    <itemref idref="ch2"/>
+  </spine>
+</package>"#;
+        let chapter1 = "<html><head><title>书名</title></head><body><h1>第一章</h1><p>这是内容</p></body></html>";
+        let chapter2 = "<html><body><h1>第二章 <em>结尾</em></h1><p>更多内容</p><img src=\"../a.png\"/></body></html>";
+        let epub = build_epub(&[
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", container),
+            ("OEBPS/content.opf", package),
+            ("OEBPS/Text/chapter 1.xhtml", chapter1),
+            ("OEBPS/Text/ch2.xhtml", chapter2),
+        ]);
+
+        let book = Estimator::new(ReadSpeed::default())
+            .estimate_epub(epub)
+            .unwrap();
+        let titles: Vec<_> = book.chapters.iter().map(EpubChapter::title).collect();
+        assert_eq!(titles, ["第一章", "第二章 结尾"]);
+        assert_eq!(book.chapters[0].path, "OEBPS/Text/chapter 1.xhtml");
+        assert_eq!(book.chapters[0].read_time, estimate("# 第一章\n\n这是内容"));
+        assert_eq!(book.chapters[1].read_time.image_count, 1);
+        assert_eq!(
+            book.total,
+            ReadTime::merge_all(
+                book.chapters.iter().map(|chapter| &chapter.read_time),
+                &ReadSpeed::default()
+            )
+        );
+    }
+
+    #[test]
+    fn test_estimate_epub_invalid() {
+        let epub = build_epub(&[("mimetype", "application/epub+zip")]);
+        let err = Estimator::new(ReadSpeed::default())
+            .estimate_epub(epub)
+            .unwrap_err();
+        assert!(matches!(err, Error::Epub(_)));
+
+        let err = Estimator::new(ReadSpeed::default())
+            .estimate_epub(Cursor::new(b"not a zip".to_vec()))
+            .unwrap_err();
+        assert!(matches!(err, Error::Epub(_)));
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("OEBPS", "Text/ch1.xhtml#top"),
+            "OEBPS/Text/ch1.xhtml"
+        );
+        assert_eq!(
+            resolve("OEBPS/Text", "../Images/a%20b.png"),
+            "OEBPS/Images/a b.png"
+        );
+        assert_eq!(resolve("", "content.opf"), "content.opf");
+        assert_eq!(resolve("OEBPS", "/root.xhtml"), "root.xhtml");
+    }
+}
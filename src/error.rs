@@ -0,0 +1,72 @@
+use crate::LimitExceeded;
+use std::fmt;
+use std::io;
+
+/// 估算过程中可能出现的错误
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// 读取内容时发生的 I/O 错误
+    Io(io::Error),
+
+    /// 无效的匹配模式
+    Pattern(String),
+
+    /// 输入超出 [`Limits`](crate::Limits) 中的资源限制
+    Limit(LimitExceeded),
+
+    /// 无法解析的 DOCX 文件
+    #[cfg(feature = "docx")]
+    Docx(String),
+
+    /// 无法解析的 EPUB 文件
+    #[cfg(feature = "epub")]
+    Epub(String),
+
+    /// 请求失败或响应状态码表示错误
+    #[cfg(feature = "http")]
+    Http(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "读取内容失败: {}", err),
+            Error::Pattern(msg) => write!(f, "无效的匹配模式: {}", msg),
+            Error::Limit(limit) => write!(f, "超出资源限制: {}", limit),
+            #[cfg(feature = "docx")]
+            Error::Docx(msg) => write!(f, "无效的 DOCX 文件: {}", msg),
+            #[cfg(feature = "epub")]
+            Error::Epub(msg) => write!(f, "无效的 EPUB 文件: {}", msg),
+            #[cfg(feature = "http")]
+            Error::Http(msg) => write!(f, "请求失败: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Pattern(_) => None,
+            Error::Limit(_) => None,
+            #[cfg(feature = "docx")]
+            Error::Docx(_) => None,
+            #[cfg(feature = "epub")]
+            Error::Epub(_) => None,
+            #[cfg(feature = "http")]
+            Error::Http(_) => None,
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<LimitExceeded> for Error {
+    fn from(limit: LimitExceeded) -> Self {
+        Error::Limit(limit)
+    }
+}
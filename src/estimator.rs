@@ -0,0 +1,839 @@
+use crate::attributes::AttributeFilter;
+use crate::backend::{Dialect, MarkdownBackend, PulldownCmark};
+use crate::exclude::{Exclude, ExcludeFilter};
+#[cfg(feature = "regex")]
+use crate::exclude::{HeadingPatterns, SectionFilter};
+use crate::front_matter::front_matter_len;
+use crate::limits::{LimitExceeded, Limits};
+use crate::utils::*;
+use crate::{Error, ReadSpeed, ReadTime, WordCountBreakdown};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::AddAssign;
+use std::ops::{ControlFlow, Range};
+
+/// 估算过程中的当前计数
+///
+/// 在调用 [`EventVisitor`] 时传入，反映处理到当前事件之前的累计结果。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct Counters {
+    /// 已统计的单词数量
+    pub word_count: usize,
+
+    /// 已统计的计数单位按文字的构成
+    pub word_count_breakdown: WordCountBreakdown,
+
+    /// 已统计的图片数量
+    pub image_count: usize,
+
+    /// 已统计的图片替代文本字符数（不含空白），仅在 [`ReadSpeed::seconds_per_alt_char`] 大于 0 时统计
+    pub image_alt_chars: usize,
+
+    /// 已统计的代码块数量
+    pub code_block_count: usize,
+
+    /// 已统计的代码行数，仅在 [`ReadSpeed::seconds_per_code_line`] 大于 0 时统计
+    pub code_line_count: usize,
+
+    /// 已统计的表格数量，仅在 [`ReadSpeed::seconds_per_table`] 大于 0 时统计
+    pub table_count: usize,
+
+    /// 已统计的表格单元格数量（包括表头），仅在 [`ReadSpeed::seconds_per_table_cell`] 大于 0 时统计
+    pub table_cell_count: usize,
+
+    /// 已统计的链接数量，仅在 [`ReadSpeed::seconds_per_link`] 大于 0 时统计
+    pub link_count: usize,
+
+    /// 访问器累计添加的额外时间（秒）
+    pub extra_seconds: f64,
+
+    /// 访问器标记的自定义元素数量
+    pub custom_elements: BTreeMap<String, usize>,
+
+    /// 访问器为各类自定义元素添加的额外时间（秒），已计入 `extra_seconds`
+    pub custom_seconds: BTreeMap<String, f64>,
+
+    /// 跳过的 front matter 字节数
+    pub front_matter_bytes: usize,
+
+    /// 当前是否位于代码块内
+    pub in_code_block: bool,
+
+    /// 当前是否位于图片的替代文本内
+    pub in_image_alt: bool,
+}
+
+impl Counters {
+    /// 从 `earlier` 到当前增加的阅读时间，图片按在整篇文档中的序号递减
+    pub(crate) fn seconds_since(&self, earlier: &Counters, speed: &ReadSpeed) -> f64 {
+        let delta = self.since(earlier);
+        let seconds = delta.seconds(speed);
+        if speed.image_decay <= 0.0 || delta.image_count == 0 {
+            return seconds;
+        }
+        seconds - speed.image_seconds(delta.image_count) + speed.image_seconds(self.image_count)
+            - speed.image_seconds(earlier.image_count)
+    }
+
+    /// 从 `earlier` 到当前的计数增量
+    pub(crate) fn since(&self, earlier: &Counters) -> Counters {
+        let mut custom_elements = BTreeMap::new();
+        for (kind, &count) in &self.custom_elements {
+            let before = earlier.custom_elements.get(kind).copied().unwrap_or(0);
+            if count > before {
+                custom_elements.insert(kind.clone(), count - before);
+            }
+        }
+        let mut custom_seconds = BTreeMap::new();
+        for (kind, &seconds) in &self.custom_seconds {
+            let before = earlier.custom_seconds.get(kind).copied().unwrap_or(0.0);
+            if seconds != before {
+                custom_seconds.insert(kind.clone(), seconds - before);
+            }
+        }
+        Counters {
+            word_count: self.word_count - earlier.word_count,
+            word_count_breakdown: self
+                .word_count_breakdown
+                .since(&earlier.word_count_breakdown),
+            image_count: self.image_count - earlier.image_count,
+            image_alt_chars: self.image_alt_chars - earlier.image_alt_chars,
+            code_block_count: self.code_block_count - earlier.code_block_count,
+            code_line_count: self.code_line_count - earlier.code_line_count,
+            table_count: self.table_count - earlier.table_count,
+            table_cell_count: self.table_cell_count - earlier.table_cell_count,
+            link_count: self.link_count - earlier.link_count,
+            extra_seconds: self.extra_seconds - earlier.extra_seconds,
+            custom_elements,
+            custom_seconds,
+            ..Counters::default()
+        }
+    }
+
+    /// 按 `speed` 计算的阅读时间（秒），未取整
+    pub(crate) fn seconds(&self, speed: &ReadSpeed) -> f64 {
+        // 计算基础阅读时间（基于单词数）
+        let base_seconds = (self.word_count as f64 / speed.words_per_minute) * 60.0;
+
+        // 添加图片、代码块、表格和链接的额外时间
+        let image_seconds = speed.image_seconds(self.image_count)
+            + self.image_alt_chars as f64 * speed.seconds_per_alt_char;
+        let code_seconds = self.code_block_count as f64 * speed.seconds_per_code_block
+            + self.code_line_count as f64 * speed.seconds_per_code_line;
+        let table_seconds = self.table_count as f64 * speed.seconds_per_table
+            + self.table_cell_count as f64 * speed.seconds_per_table_cell;
+        let link_seconds = self.link_count as f64 * speed.seconds_per_link;
+
+        base_seconds
+            + image_seconds
+            + code_seconds
+            + table_seconds
+            + link_seconds
+            + self.extra_seconds
+    }
+
+    /// 是否没有任何计数
+    pub(crate) fn is_empty(&self) -> bool {
+        self.word_count == 0
+            && self.image_count == 0
+            && self.image_alt_chars == 0
+            && self.code_block_count == 0
+            && self.code_line_count == 0
+            && self.table_count == 0
+            && self.table_cell_count == 0
+            && self.link_count == 0
+            && self.extra_seconds == 0.0
+            && self.custom_elements.is_empty()
+    }
+}
+
+/// 访问器对单个事件的处理结果
+///
+/// 默认值表示按正常规则计数，不添加额外时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::Visit;
+///
+/// // 跳过当前事件，并额外增加 5 秒
+/// let visit = Visit::skip().extra_seconds(5.0);
+/// // 正常计数，同时标记为自定义元素
+/// let visit = Visit::count().tag("callout");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Visit {
+    /// 是否跳过对当前事件的计数
+    pub skip: bool,
+
+    /// 需要额外添加的时间（秒）
+    pub extra_seconds: f64,
+
+    /// 自定义元素类型
+    pub tag: Option<String>,
+}
+
+impl Visit {
+    pub fn count() -> Self {
+        Self::default()
+    }
+
+    pub fn skip() -> Self {
+        Self {
+            skip: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn extra_seconds(mut self, seconds: f64) -> Self {
+        self.extra_seconds += seconds;
+        self
+    }
+
+    pub fn tag(mut self, kind: impl Into<String>) -> Self {
+        self.tag = Some(kind.into());
+        self
+    }
+}
+
+/// 事件访问器
+///
+/// 对每个解析出的 Markdown 事件调用，可以否决计数、添加额外时间或标记自定义元素。
+/// 任何 `FnMut(&Event, &Counters) -> Visit` 闭包都自动实现了该 trait。
+pub trait EventVisitor {
+    fn visit(&mut self, event: &Event<'_>, counters: &Counters) -> Visit;
+}
+
+impl<F> EventVisitor for F
+where
+    F: FnMut(&Event<'_>, &Counters) -> Visit,
+{
+    fn visit(&mut self, event: &Event<'_>, counters: &Counters) -> Visit {
+        self(event, counters)
+    }
+}
+
+/// 估算器持有的缓冲区，在多次调用之间复用，避免长期运行的服务为每篇文档重新分配内存
+#[derive(Debug, Default)]
+pub(crate) struct Scratch {
+    /// 读取输入使用的字节缓冲区
+    pub(crate) bytes: Vec<u8>,
+
+    /// 拼接文本使用的缓冲区，例如章节标题
+    pub(crate) text: String,
+
+    /// 按标题拆分时各章节的起点
+    pub(crate) sections: Vec<crate::sections::Boundary>,
+
+    /// 尚未结束的块级元素：起始位置以及是否包含子块
+    pub(crate) open: Vec<(usize, bool)>,
+}
+
+/// 可配置的阅读时间估算器
+///
+/// 在 [`ReadSpeed`] 的基础上允许注册 [`EventVisitor`]，用于处理站点特有的内容，
+/// 也可以通过 [`MarkdownBackend`] 替换默认的 pulldown-cmark 解析器。
+///
+/// 估算器内部的缓冲区在多次调用之间复用，高吞吐的服务可以为每个线程保留一个估算器，
+/// 处理完超大文档后调用 [`reset`](Self::reset) 释放占用的内存。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, ReadSpeed, Visit};
+/// use markdown_readtime::pulldown_cmark::{Event, Tag};
+///
+/// // 每个 HTML 块视为一个视频，额外增加 60 秒
+/// let mut estimator = Estimator::new(ReadSpeed::default()).visitor(|event: &Event<'_>, _: &_| {
+///     match event {
+///         Event::Start(Tag::HtmlBlock) => Visit::count().extra_seconds(60.0).tag("video"),
+///         _ => Visit::count(),
+///     }
+/// });
+///
+/// let read_time = estimator.estimate("<div class=\"video\"></div>\n");
+/// assert_eq!(read_time.custom_elements["video"], 1);
+/// assert_eq!(read_time.total_seconds, 60);
+/// ```
+pub struct Estimator<'v> {
+    pub(crate) speed: ReadSpeed,
+    visitor: Option<Box<dyn EventVisitor + 'v>>,
+    pub(crate) backend: Box<dyn MarkdownBackend + 'v>,
+    skip_front_matter: bool,
+    strip_attributes: bool,
+    pub(crate) limits: Limits,
+    exclude: Vec<Exclude>,
+    #[cfg(feature = "regex")]
+    exclude_sections: Option<HeadingPatterns>,
+    pub(crate) scratch: Scratch,
+}
+
+impl<'v> Estimator<'v> {
+    pub fn new(speed: ReadSpeed) -> Self {
+        Self {
+            speed,
+            visitor: None,
+            backend: Box::new(PulldownCmark::default()),
+            skip_front_matter: true,
+            strip_attributes: false,
+            limits: Limits::default(),
+            exclude: Vec::new(),
+            #[cfg(feature = "regex")]
+            exclude_sections: None,
+            scratch: Scratch::default(),
+        }
+    }
+
+    pub fn visitor(mut self, visitor: impl EventVisitor + 'v) -> Self {
+        self.visitor = Some(Box::new(visitor));
+        self
+    }
+
+    /// 设置解析 Markdown 使用的后端，默认为 [`PulldownCmark`]
+    pub fn backend(mut self, backend: impl MarkdownBackend + 'v) -> Self {
+        self.backend = Box::new(backend);
+        self
+    }
+
+    /// 使用指定方言的 [`PulldownCmark`] 后端，默认为 [`Dialect::CommonMark`]
+    ///
+    /// 与 [`backend`](Self::backend) 互相覆盖，以后调用的为准。
+    pub fn dialect(self, dialect: Dialect) -> Self {
+        self.backend(PulldownCmark::dialect(dialect))
+    }
+
+    /// 设置是否跳过文档开头的 front matter，默认跳过
+    ///
+    /// 跳过时 front matter 中的元数据不计入阅读时间，跳过的字节数记录在
+    /// [`ReadTime::front_matter_bytes`] 中，事件的字节范围仍相对于完整的源文本。
+    pub fn skip_front_matter(mut self, skip: bool) -> Self {
+        self.skip_front_matter = skip;
+        self
+    }
+
+    /// 设置是否去掉 Pandoc 风格的属性块，默认不去掉
+    ///
+    /// 启用时标题末尾和紧跟在图片之后的 `{#id .class key=val}` 不计入字数，
+    /// 适合使用 Pandoc、Quarto 等渲染器的文档。严格的 CommonMark 会把属性块显示为正文，保持默认即可。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{Estimator, ReadSpeed};
+    ///
+    /// let mut estimator = Estimator::new(ReadSpeed::default()).strip_attributes(true);
+    /// assert_eq!(estimator.estimate("# 简介 {#intro .unnumbered}").word_count, 2);
+    /// ```
+    pub fn strip_attributes(mut self, strip: bool) -> Self {
+        self.strip_attributes = strip;
+        self
+    }
+
+    /// 设置 [`try_estimate`](Self::try_estimate) 使用的资源限制，默认为 [`Limits::default`]
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 添加排除规则，可以多次调用，参见 [`Exclude`]
+    pub fn exclude(mut self, rule: Exclude) -> Self {
+        self.exclude.push(rule);
+        self
+    }
+
+    /// 排除标题匹配 `patterns` 的整个章节，参见 [`HeadingPatterns`]
+    #[cfg(feature = "regex")]
+    pub fn exclude_sections(mut self, patterns: HeadingPatterns) -> Self {
+        self.exclude_sections = Some(patterns);
+        self
+    }
+
+    /// 释放复用的缓冲区
+    ///
+    /// 缓冲区只会增长，处理过超大文档的长期实例可以调用该方法归还内存，
+    /// 之后的估算结果不受影响。
+    pub fn reset(&mut self) {
+        self.scratch = Scratch::default();
+    }
+
+    /// 当前使用的阅读速度配置
+    pub fn speed(&self) -> &ReadSpeed {
+        &self.speed
+    }
+
+    /// 估算 Markdown 文本的阅读时间
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - 需要估算阅读时间的 Markdown 文本
+    ///
+    /// # Returns
+    ///
+    /// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+    pub fn estimate(&mut self, markdown: &str) -> ReadTime {
+        let counters = self.count(markdown, &mut |_, _, _| {});
+        ReadTime::from_counters(counters, &self.speed)
+    }
+
+    /// 在资源限制内估算 Markdown 文本的阅读时间
+    ///
+    /// 与 [`estimate`](Self::estimate) 相同，但会检查通过 [`limits`](Self::limits) 设置的限制。
+    /// 超出限制后 pulldown-cmark 等逐步解析的后端立即停止解析；一次生成全部事件的后端
+    /// （例如 HTML 和 reStructuredText）仍会解析完剩余的输入，只是不再计数，因此应同时限制输入大小。
+    ///
+    /// # Errors
+    ///
+    /// 超出任意一项限制时返回 [`Error::Limit`]。
+    pub fn try_estimate(&mut self, markdown: &str) -> Result<ReadTime, Error> {
+        let limits = self.limits;
+        let counters = self.count_limited(markdown, &limits, &mut |_, _, _| {})?;
+        Ok(ReadTime::from_counters(counters, &self.speed))
+    }
+
+    /// 统计 Markdown 文本，每个事件在计数之前连同源文本字节范围和当前计数传给 `observer`
+    pub(crate) fn count(
+        &mut self,
+        markdown: &str,
+        observer: &mut dyn FnMut(&Event<'_>, Range<usize>, &Counters),
+    ) -> Counters {
+        match self.count_limited(markdown, &Limits::NONE, observer) {
+            Ok(counters) => counters,
+            Err(_) => unreachable!("未设置任何限制"),
+        }
+    }
+
+    /// 在 `limits` 内统计 Markdown 文本，超出限制后停止解析
+    fn count_limited(
+        &mut self,
+        markdown: &str,
+        limits: &Limits,
+        observer: &mut dyn FnMut(&Event<'_>, Range<usize>, &Counters),
+    ) -> Result<Counters, LimitExceeded> {
+        if let Some(max) = limits.max_input_bytes
+            && markdown.len() > max
+        {
+            return Err(LimitExceeded::InputBytes(max));
+        }
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+        let skipped = if self.skip_front_matter && self.backend.front_matter() {
+            front_matter_len(markdown)
+        } else {
+            0
+        };
+        #[cfg(feature = "tracing")]
+        let span = {
+            use tracing::field::Empty;
+            tracing::debug_span!(
+                "estimate",
+                bytes = markdown.len(),
+                front_matter_bytes = skipped,
+                events = Empty,
+                words = Empty,
+                images = Empty,
+                code_blocks = Empty,
+                seconds = Empty,
+            )
+            .entered()
+        };
+        let mut counters = Counters {
+            front_matter_bytes: skipped,
+            ..Counters::default()
+        };
+        let Self {
+            speed,
+            visitor,
+            backend,
+            exclude,
+            #[cfg(feature = "regex")]
+            exclude_sections,
+            strip_attributes,
+            ..
+        } = self;
+        let mut events = 0;
+        let mut depth = 0;
+        let mut exceeded = None;
+        let mut rules = ExcludeFilter::new(exclude);
+        let mut attributes = strip_attributes.then(AttributeFilter::default);
+        // 已计入时间的链接地址，用于 `dedupe_links`
+        let mut links = BTreeSet::new();
+        #[cfg(feature = "regex")]
+        let mut filter = exclude_sections.as_ref().map(SectionFilter::new);
+
+        // 处理一个事件，被排除的事件不传给访问器，也不计数
+        let mut process = |event: &Event<'_>, range: Range<usize>, excluded: bool| {
+            // 去掉属性块后的文本，访问器和观察者都只看到剩余的部分
+            let stripped;
+            let event = match attributes.as_mut().and_then(|filter| filter.strip(event)) {
+                Some(text) => {
+                    stripped = Event::Text(text.into());
+                    &stripped
+                }
+                None => event,
+            };
+            observer(event, range.start + skipped..range.end + skipped, &counters);
+            // 规则需要看到每个事件以跟踪列表层数
+            let source = markdown[skipped..].get(range.clone()).unwrap_or_default();
+            let excluded = rules.excludes(event, source) || excluded;
+            let visit = match visitor.as_mut() {
+                _ if excluded => Visit::skip(),
+                Some(visitor) => visitor.visit(event, &counters),
+                None => Visit::default(),
+            };
+            counters.extra_seconds += visit.extra_seconds;
+            if let Some(kind) = visit.tag {
+                if visit.extra_seconds != 0.0 {
+                    add_to(&mut counters.custom_seconds, &kind, visit.extra_seconds);
+                }
+                add_to(&mut counters.custom_elements, &kind, 1);
+            }
+            count_event(speed, event, &mut counters, &mut links, !visit.skip);
+        };
+
+        // 后端逐个产生事件，计数在回调中完成，因此 `parse` 包括计数的耗时
+        #[cfg(feature = "tracing")]
+        let parse = tracing::debug_span!("parse").entered();
+        backend.parse_with_offsets(&markdown[skipped..], &mut |event, range| {
+            if exceeded.is_some() {
+                return ControlFlow::Break(());
+            }
+            events += 1;
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth = usize::saturating_sub(depth, 1),
+                _ => {}
+            }
+            if let Some(max) = limits.max_events
+                && events > max
+            {
+                exceeded = Some(LimitExceeded::Events(max));
+                return ControlFlow::Break(());
+            }
+            if let Some(max) = limits.max_depth
+                && depth > max
+            {
+                exceeded = Some(LimitExceeded::Depth(max));
+                return ControlFlow::Break(());
+            }
+            #[cfg(feature = "regex")]
+            if let Some(filter) = filter.as_mut() {
+                filter.push(event, range, &mut process);
+                return ControlFlow::Continue(());
+            }
+            process(&event, range, false);
+            ControlFlow::Continue(())
+        });
+        #[cfg(feature = "regex")]
+        if exceeded.is_none()
+            && let Some(filter) = filter.as_mut()
+        {
+            filter.finish(&mut process);
+        }
+        #[cfg(feature = "tracing")]
+        drop(parse);
+
+        match exceeded {
+            Some(limit) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(%limit, "超出资源限制");
+                Err(limit)
+            }
+            None => {
+                #[cfg(feature = "metrics")]
+                crate::metrics::record(markdown.len(), started.elapsed(), counters.seconds(speed));
+                #[cfg(feature = "tracing")]
+                {
+                    span.record("events", events);
+                    span.record("words", counters.word_count);
+                    span.record("images", counters.image_count);
+                    span.record("code_blocks", counters.code_block_count);
+                    span.record("seconds", counters.seconds(speed));
+                }
+                Ok(counters)
+            }
+        }
+    }
+}
+
+/// 累加 `kind` 对应的值，只在第一次出现时复制名称
+fn add_to<V: AddAssign>(map: &mut BTreeMap<String, V>, kind: &str, value: V) {
+    match map.get_mut(kind) {
+        Some(total) => *total += value,
+        None => {
+            map.insert(String::from(kind), value);
+        }
+    }
+}
+
+/// 更新状态并在 `count` 为真时计数
+///
+/// `links` 为已计入时间的链接地址。
+fn count_event(
+    speed: &ReadSpeed,
+    event: &Event<'_>,
+    counters: &mut Counters,
+    links: &mut BTreeSet<String>,
+    count: bool,
+) {
+    match event {
+        Event::Start(tag) => match tag {
+            Tag::Table(_) if count && speed.seconds_per_table > 0.0 => {
+                counters.table_count += 1;
+            }
+            Tag::TableCell if count && speed.seconds_per_table_cell > 0.0 => {
+                counters.table_cell_count += 1;
+            }
+            Tag::Link { dest_url, .. }
+                if count
+                    && speed.seconds_per_link > 0.0
+                    && (!speed.dedupe_links || links.insert(String::from(&**dest_url))) =>
+            {
+                counters.link_count += 1;
+            }
+            Tag::Image { .. } => {
+                if count {
+                    counters.image_count += 1;
+                }
+                counters.in_image_alt = true;
+            }
+            Tag::CodeBlock(_) => {
+                if count {
+                    counters.code_block_count += 1;
+                }
+                counters.in_code_block = true;
+            }
+            _ => {}
+        },
+        Event::End(tag) => match tag {
+            TagEnd::Image => {
+                counters.in_image_alt = false;
+            }
+            TagEnd::CodeBlock => {
+                counters.in_code_block = false;
+            }
+            _ => {}
+        },
+        Event::Text(text)
+            if count && counters.in_code_block && speed.seconds_per_code_line > 0.0 =>
+        {
+            counters.code_line_count += text.matches('\n').count();
+        }
+        Event::Text(text) if count && counters.in_image_alt && speed.seconds_per_alt_char > 0.0 => {
+            counters.image_alt_chars += text.chars().filter(|c| !c.is_whitespace()).count();
+        }
+        Event::Text(text) if count && !counters.in_image_alt && !counters.in_code_block => {
+            count_text(speed, text, counters);
+        }
+        Event::Code(code) if count && !counters.in_code_block => {
+            count_text(speed, code, counters);
+        }
+        _ => {}
+    }
+}
+
+/// 统计正文文本的单词数及其文字构成
+fn count_text(speed: &ReadSpeed, text: &str, counters: &mut Counters) {
+    if speed.chinese {
+        counters.word_count += count_words(text, speed.count_emoji);
+    } else {
+        counters.word_count += count_english_words(text, speed.count_emoji);
+    }
+    count_scripts(text, &mut counters.word_count_breakdown);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_element_costs() {
+        let markdown = "![系统 架构](a.png)\n\n```\nfn a() {}\nfn b() {}\nfn c() {}\n```\n\n\
+                        | a | b |\n|---|---|\n| 1 | 2 |\n";
+        let read_time = Estimator::new(ReadSpeed::default()).estimate(markdown);
+        assert_eq!(read_time.image_alt_chars, 0);
+        assert_eq!(read_time.code_line_count, 0);
+        assert_eq!((read_time.table_count, read_time.table_cell_count), (0, 0));
+
+        let speed = ReadSpeed::default()
+            .alt_text_time(0.5)
+            .code_line_time(2.0)
+            .table_time(10.0, 1.0);
+        let read_time = Estimator::new(speed)
+            .backend(PulldownCmark::new(pulldown_cmark::Options::ENABLE_TABLES))
+            .estimate(markdown);
+        assert_eq!(read_time.image_alt_chars, 4);
+        assert_eq!(read_time.code_line_count, 3);
+        assert_eq!((read_time.table_count, read_time.table_cell_count), (1, 4));
+        let breakdown = &read_time.breakdown;
+        assert_eq!(breakdown.images, 14.0);
+        assert_eq!(breakdown.code, 26.0);
+        assert_eq!(breakdown.tables, 14.0);
+        assert_eq!(
+            read_time.total_seconds,
+            read_time.breakdown.total().ceil() as u64
+        );
+        assert_eq!(
+            ReadTime::merge_all([&read_time, &read_time], &speed)
+                .breakdown
+                .tables,
+            28.0
+        );
+    }
+
+    #[test]
+    fn test_link_time() {
+        let markdown = "见[文档](a.md)和[文档](a.md)，另见[附录](b.md)与<https://example.com>";
+        let read_time = Estimator::new(ReadSpeed::default()).estimate(markdown);
+        assert_eq!(read_time.link_count, 0);
+
+        let speed = ReadSpeed::default().link_time(5.0);
+        let read_time = Estimator::new(speed).estimate(markdown);
+        assert_eq!(read_time.link_count, 4);
+        assert_eq!(read_time.breakdown.links, 20.0);
+
+        let read_time = Estimator::new(speed.dedupe_links(true)).estimate(markdown);
+        assert_eq!(read_time.link_count, 3);
+        assert_eq!(read_time.breakdown.links, 15.0);
+        assert_eq!(
+            read_time.total_seconds,
+            read_time.breakdown.total().ceil() as u64
+        );
+    }
+
+    #[test]
+    fn test_visitor_skip() {
+        let markdown = "# 标题\n\n正文内容\n\n![图片](a.png)";
+        let mut estimator = Estimator::new(ReadSpeed::default()).visitor(
+            |event: &Event<'_>, counters: &Counters| match event {
+                Event::Start(Tag::Image { .. }) => Visit::skip(),
+                Event::Text(_) if counters.word_count > 0 => Visit::skip(),
+                _ => Visit::count(),
+            },
+        );
+        let read_time = estimator.estimate(markdown);
+        assert_eq!(read_time.word_count, 2);
+        assert_eq!(read_time.image_count, 0);
+    }
+
+    #[test]
+    fn test_reuse_scratch() {
+        use crate::{estimate_blocks, estimate_sections};
+
+        let documents = [
+            "# 第一篇\n\n内容\n\n## 小节\n\n- 列表",
+            "前言\n\n# 第二篇\n\n> 引用",
+        ];
+        let mut estimator = Estimator::new(ReadSpeed::default());
+        for markdown in documents.iter().chain(&documents) {
+            assert_eq!(
+                estimator.estimate_sections(markdown),
+                estimate_sections(markdown)
+            );
+            assert_eq!(
+                estimator.estimate_blocks(markdown),
+                estimate_blocks(markdown)
+            );
+        }
+        assert!(estimator.scratch.text.capacity() > 0);
+
+        estimator.reset();
+        assert_eq!(estimator.scratch.text.capacity(), 0);
+        assert_eq!(
+            estimator.estimate_sections(documents[0]),
+            estimate_sections(documents[0])
+        );
+    }
+
+    #[test]
+    fn test_visitor_extra_seconds_and_tags() {
+        let markdown = "段落一\n\n段落二";
+        let mut estimator =
+            Estimator::new(ReadSpeed::default()).visitor(|event: &Event<'_>, _: &Counters| {
+                match event {
+                    Event::Start(Tag::Paragraph) => Visit::count().extra_seconds(10.0).tag("para"),
+                    _ => Visit::count(),
+                }
+            });
+        let read_time = estimator.estimate(markdown);
+        assert_eq!(read_time.word_count, 6);
+        assert_eq!(read_time.extra_seconds, 20.0);
+        assert_eq!(read_time.custom_elements["para"], 2);
+        assert_eq!(read_time.total_seconds, 22);
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn test_tracing_spans() {
+        use std::string::{String, ToString};
+        use std::sync::{Arc, Mutex};
+        use std::vec::Vec;
+        use tracing::field::{Field, Visit as FieldVisit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        type Fields = Vec<(String, String)>;
+
+        /// 按创建顺序保存 span 的名称和字段
+        #[derive(Clone, Default)]
+        struct Spans(Arc<Mutex<Vec<(&'static str, Fields)>>>);
+
+        struct Recorder<'a>(&'a mut Fields);
+
+        impl FieldVisit for Recorder<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .push((field.name().to_string(), format!("{:?}", value)));
+            }
+        }
+
+        impl Subscriber for Spans {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut spans = self.0.lock().unwrap();
+                let mut fields = Vec::new();
+                span.record(&mut Recorder(&mut fields));
+                spans.push((span.metadata().name(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut spans = self.0.lock().unwrap();
+                let index = span.into_u64() as usize - 1;
+                values.record(&mut Recorder(&mut spans[index].1));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let spans = Spans::default();
+        let markdown = "# 标题\n\n正文\n\n![图片](a.png)";
+        tracing::subscriber::with_default(spans.clone(), || {
+            Estimator::new(ReadSpeed::default()).estimate_sections(markdown);
+        });
+        let spans = spans.0.lock().unwrap();
+        let names: Vec<&str> = spans.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["sections", "estimate", "parse"]);
+        let field = |span: usize, name: &str| {
+            spans[span]
+                .1
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(field(0, "sections").as_deref(), Some("1"));
+        assert_eq!(
+            field(1, "bytes").as_deref(),
+            Some(markdown.len().to_string().as_str())
+        );
+        assert_eq!(field(1, "words").as_deref(), Some("4"));
+        assert_eq!(field(1, "images").as_deref(), Some("1"));
+        assert_eq!(field(1, "seconds").as_deref(), Some("13.2"));
+    }
+}
@@ -0,0 +1,419 @@
+//! 排除部分内容
+//!
+//! [`Exclude`] 在遍历事件时按元素类型和位置排除内容，例如引用块、代码块或过深的嵌套列表。
+//!
+//! 启用 `regex` 特性后还可以按标题排除章节：标题文本与任一模式完整匹配（不区分大小写）时，
+//! 从该标题开始到下一个同级或更高级标题之前的内容都不计入阅读时间，更深的小节一并排除。
+//! 判断需要完整的标题文本，因此标题内的事件会先缓存，到标题结束时再依次交给访问器和计数。
+//!
+//! 排除的事件不会传给访问器。
+#[cfg(feature = "regex")]
+use crate::Error;
+use crate::utils::{fnv1a, fnv1a_extend};
+use pulldown_cmark::{Event, Tag, TagEnd};
+#[cfg(feature = "regex")]
+use regex::{RegexSet, RegexSetBuilder};
+use std::collections::BTreeSet;
+#[cfg(feature = "regex")]
+use std::ops::Range;
+
+/// 排除规则
+///
+/// 通过 [`Estimator::exclude`](crate::Estimator::exclude) 添加，多条规则中任一条匹配即排除。
+/// 被排除的元素及其包含的所有内容都不计入阅读时间。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, Exclude, ReadSpeed};
+///
+/// let mut estimator = Estimator::new(ReadSpeed::default())
+///     .exclude(Exclude::BlockQuotes)
+///     .exclude(Exclude::ListsDeeperThan(2));
+/// let markdown = "正文\n\n> 引用\n\n- 一\n  - 二\n    - 三\n";
+/// assert_eq!(estimator.estimate(markdown).word_count, 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Exclude {
+    /// 引用块
+    BlockQuotes,
+
+    /// 代码块，包括其额外时间
+    CodeBlocks,
+
+    /// 图片，包括其额外时间
+    Images,
+
+    /// 表格
+    Tables,
+
+    /// 脚注定义
+    FootnoteDefinitions,
+
+    /// 嵌套层级超过指定值的列表，最外层列表为第 1 层
+    ListsDeeperThan(u8),
+
+    /// 第一个指定级别的标题及其之后的全部内容，例如 `AfterHeading(2)` 只统计第一个二级标题之前的内容
+    AfterHeading(u8),
+
+    /// 与前面某个顶层块完全相同的块，例如每个章节重复出现的免责声明
+    ///
+    /// 按合并空白后的源文本判断，标题除外。需要后端提供源文本位置，
+    /// 不提供位置的后端不会排除任何块。
+    DuplicateBlocks,
+}
+
+/// 按 [`Exclude`] 规则逐个事件判断是否排除
+pub(crate) struct ExcludeFilter<'e> {
+    rules: &'e [Exclude],
+    /// 当前所在列表的层数
+    list_depth: u8,
+    /// 被排除的元素内尚未结束的标签数，为 0 时不在被排除的元素内
+    excluded_depth: usize,
+    /// 是否已遇到 [`Exclude::AfterHeading`] 匹配的标题
+    stopped: bool,
+    /// 尚未结束的标签数
+    depth: usize,
+    /// 启用 [`Exclude::DuplicateBlocks`] 时已出现的顶层块的哈希
+    seen: Option<BTreeSet<u64>>,
+}
+
+impl<'e> ExcludeFilter<'e> {
+    pub(crate) fn new(rules: &'e [Exclude]) -> Self {
+        Self {
+            rules,
+            list_depth: 0,
+            excluded_depth: 0,
+            stopped: false,
+            depth: 0,
+            seen: rules
+                .contains(&Exclude::DuplicateBlocks)
+                .then(BTreeSet::new),
+        }
+    }
+
+    /// 事件是否被排除，每个事件都需要按顺序传入，`source` 为事件对应的源文本
+    pub(crate) fn excludes(&mut self, event: &Event<'_>, source: &str) -> bool {
+        if self.rules.is_empty() || self.stopped {
+            return self.stopped;
+        }
+        match event {
+            Event::Start(tag) => {
+                if let Tag::List(_) = tag {
+                    self.list_depth = self.list_depth.saturating_add(1);
+                }
+                let top = self.depth == 0;
+                self.depth += 1;
+                if self.excluded_depth > 0 {
+                    self.excluded_depth += 1;
+                } else if self.matches(tag) || (top && self.repeated(tag, source)) {
+                    self.excluded_depth = 1;
+                } else if let Tag::Heading { level, .. } = tag
+                    && self.rules.iter().any(
+                        |rule| matches!(*rule, Exclude::AfterHeading(stop) if *level as u8 == stop),
+                    )
+                {
+                    self.stopped = true;
+                }
+                self.stopped || self.excluded_depth > 0
+            }
+            Event::End(tag) => {
+                if let TagEnd::List(_) = tag {
+                    self.list_depth = self.list_depth.saturating_sub(1);
+                }
+                self.depth = self.depth.saturating_sub(1);
+                let excluded = self.excluded_depth > 0;
+                self.excluded_depth = self.excluded_depth.saturating_sub(1);
+                excluded
+            }
+            _ => self.excluded_depth > 0,
+        }
+    }
+
+    fn matches(&self, tag: &Tag<'_>) -> bool {
+        self.rules.iter().any(|rule| match rule {
+            Exclude::BlockQuotes => matches!(tag, Tag::BlockQuote),
+            Exclude::CodeBlocks => matches!(tag, Tag::CodeBlock(_)),
+            Exclude::Images => matches!(tag, Tag::Image { .. }),
+            Exclude::Tables => matches!(tag, Tag::Table(_)),
+            Exclude::FootnoteDefinitions => matches!(tag, Tag::FootnoteDefinition(_)),
+            Exclude::ListsDeeperThan(max) => matches!(tag, Tag::List(_)) && self.list_depth > *max,
+            Exclude::AfterHeading(_) | Exclude::DuplicateBlocks => false,
+        })
+    }
+
+    /// 顶层块是否与前面的某个块相同，第一次出现时记录下来
+    fn repeated(&mut self, tag: &Tag<'_>, source: &str) -> bool {
+        let Some(seen) = self.seen.as_mut() else {
+            return false;
+        };
+        if matches!(tag, Tag::Heading { .. }) || source.trim().is_empty() {
+            return false;
+        }
+        // 合并空白后的哈希，换行和缩进的差异不影响判断
+        let hash = source.split_whitespace().fold(fnv1a(b""), |hash, word| {
+            fnv1a_extend(fnv1a_extend(hash, word.as_bytes()), b" ")
+        });
+        !seen.insert(hash)
+    }
+}
+
+#[cfg(feature = "regex")]
+/// 需要排除的章节标题模式
+///
+/// 每个模式都是正则表达式，需要与去掉首尾空白的标题文本完整匹配，不区分大小写。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Estimator, HeadingPatterns, ReadSpeed};
+///
+/// let patterns = HeadingPatterns::new(["References", "Appendix.*"]).unwrap();
+/// let mut estimator = Estimator::new(ReadSpeed::default()).exclude_sections(patterns);
+/// let markdown = "# 正文\n\n内容\n\n## References\n\n1. 很长的参考文献\n\n## Appendix A\n\n附录";
+/// assert_eq!(estimator.estimate(markdown).word_count, 4);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeadingPatterns {
+    set: RegexSet,
+}
+
+#[cfg(feature = "regex")]
+impl HeadingPatterns {
+    /// 编译标题模式
+    ///
+    /// # Errors
+    ///
+    /// 任一模式不是有效的正则表达式时返回 [`Error::Pattern`]。
+    pub fn new<I, S>(patterns: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| format!("^(?:{})$", pattern.as_ref()));
+        let set = RegexSetBuilder::new(patterns)
+            .case_insensitive(true)
+            .build()
+            .map_err(|err| Error::Pattern(err.to_string()))?;
+        Ok(Self { set })
+    }
+
+    /// 标题是否匹配任一模式
+    pub fn is_match(&self, heading: &str) -> bool {
+        self.set.is_match(heading.trim())
+    }
+}
+
+#[cfg(feature = "regex")]
+/// 缓存中的标题：级别、文本和标题内的事件
+type PendingHeading<'a> = (u8, String, Vec<(Event<'a>, Range<usize>)>);
+
+#[cfg(feature = "regex")]
+/// 逐个事件判断是否位于被排除的章节中
+pub(crate) struct SectionFilter<'p, 'a> {
+    patterns: &'p HeadingPatterns,
+    heading: Option<PendingHeading<'a>>,
+    /// 正在排除的章节的标题级别
+    excluded: Option<u8>,
+}
+
+#[cfg(feature = "regex")]
+impl<'p, 'a> SectionFilter<'p, 'a> {
+    pub(crate) fn new(patterns: &'p HeadingPatterns) -> Self {
+        Self {
+            patterns,
+            heading: None,
+            excluded: None,
+        }
+    }
+
+    /// 传入一个事件，可以确定是否排除的事件依次连同判断结果传给 `process`
+    pub(crate) fn push(
+        &mut self,
+        event: Event<'a>,
+        range: Range<usize>,
+        process: &mut dyn FnMut(&Event<'a>, Range<usize>, bool),
+    ) {
+        if let Some((_, text, events)) = &mut self.heading {
+            if let Event::Text(part) | Event::Code(part) = &event {
+                text.push_str(part);
+            }
+            let end = matches!(event, Event::End(TagEnd::Heading(_)));
+            events.push((event, range));
+            if end && let Some((level, text, events)) = self.heading.take() {
+                if self.patterns.is_match(&text) {
+                    self.excluded = Some(level);
+                }
+                let excluded = self.excluded.is_some();
+                for (event, range) in events {
+                    process(&event, range, excluded);
+                }
+            }
+            return;
+        }
+
+        if let Event::Start(Tag::Heading { level, .. }) = &event {
+            let level = *level as u8;
+            if self.excluded.is_some_and(|excluded| level <= excluded) {
+                self.excluded = None;
+            }
+            if self.excluded.is_none() {
+                self.heading = Some((level, String::new(), Vec::from([(event, range)])));
+                return;
+            }
+        }
+        process(&event, range, self.excluded.is_some());
+    }
+
+    /// 输入结束时处理未结束的标题中缓存的事件
+    pub(crate) fn finish(&mut self, process: &mut dyn FnMut(&Event<'a>, Range<usize>, bool)) {
+        if let Some((_, _, events)) = self.heading.take() {
+            for (event, range) in events {
+                process(&event, range, false);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed};
+    #[cfg(feature = "regex")]
+    use crate::{Visit, estimate_sections};
+
+    #[test]
+    fn test_exclude_rules() {
+        let markdown = "# 标题\n\n正文\n\n> 引用\n> > 嵌套\n\n![图片](a.png)\n\n\
+                        ```\ncode\n```\n\n| 表 | 格 |\n| - | - |\n| 一 | 二 |\n\n\
+                        - 一\n  - 二\n    - 三\n\n脚注[^1]\n\n[^1]: 说明\n\n## 小节\n\n结尾\n";
+        let speed = ReadSpeed::default();
+        let estimate = |rules: &[Exclude]| {
+            let mut estimator = Estimator::new(speed).backend(crate::PulldownCmark::new(
+                pulldown_cmark::Options::ENABLE_TABLES | pulldown_cmark::Options::ENABLE_FOOTNOTES,
+            ));
+            for &rule in rules {
+                estimator = estimator.exclude(rule);
+            }
+            estimator.estimate(markdown)
+        };
+        let full = estimate(&[]);
+        assert_eq!(full.image_count, 1);
+        assert_eq!(full.code_block_count, 1);
+
+        let words = |rules: &[Exclude]| full.word_count - estimate(rules).word_count;
+        assert_eq!(words(&[Exclude::BlockQuotes]), 4);
+        assert_eq!(words(&[Exclude::Tables]), 4);
+        assert_eq!(words(&[Exclude::ListsDeeperThan(1)]), 2);
+        assert_eq!(words(&[Exclude::ListsDeeperThan(2)]), 1);
+        assert_eq!(words(&[Exclude::FootnoteDefinitions]), 2);
+        assert_eq!(words(&[Exclude::AfterHeading(2)]), 4);
+        assert_eq!(words(&[Exclude::AfterHeading(1)]), full.word_count);
+        assert_eq!(words(&[Exclude::BlockQuotes, Exclude::Tables]), 8);
+
+        let images = estimate(&[Exclude::Images, Exclude::CodeBlocks]);
+        assert_eq!(images.word_count, full.word_count);
+        assert_eq!((images.image_count, images.code_block_count), (0, 0));
+        assert_eq!(images.total_seconds, full.total_seconds - 32);
+    }
+
+    #[test]
+    fn test_duplicate_blocks() {
+        let disclaimer = "> **注意**：以上内容\n> 仅供参考。";
+        let markdown = format!(
+            "# 第一章\n\n正文一\n\n{disclaimer}\n\n# 第二章\n\n正文二\n\n\
+             >  **注意**：以上内容\n>   仅供参考。\n\n# 第一章\n\n正文一\n\n{disclaimer}\n"
+        );
+        let speed = ReadSpeed::default();
+        let full = Estimator::new(speed).estimate(&markdown);
+        let deduped = Estimator::new(speed)
+            .exclude(Exclude::DuplicateBlocks)
+            .estimate(&markdown);
+        // 重复的标题仍然计入，两次重复的声明和一次重复的正文不计入
+        assert_eq!(full.word_count - deduped.word_count, 12 * 2 + 3);
+
+        // 嵌套的块不单独判断
+        let nested = "- 相同\n- 相同\n";
+        let read_time = Estimator::new(speed)
+            .exclude(Exclude::DuplicateBlocks)
+            .estimate(nested);
+        assert_eq!(read_time.word_count, 4);
+    }
+
+    #[cfg(feature = "regex")]
+    fn estimator(patterns: &[&str]) -> Estimator<'static> {
+        Estimator::new(ReadSpeed::default().chinese(false))
+            .exclude_sections(HeadingPatterns::new(patterns).unwrap())
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_exclude_sections() {
+        let markdown = "# Post\n\nBody text here.\n\n## References\n\n1. Long citation list\n\n\
+                        ### Nested **notes**\n\nStill excluded\n\n## Changelog\n\n- v1 changes\n\n\
+                        ## Appendix *B*\n\n![chart](a.png)\n\n```\ncode\n```\n\n# Next\n\nCounted again\n";
+        let read_time = estimator(&["references", "Changelog", "Appendix.*"]).estimate(markdown);
+        // Post / Body text here. / Next / Counted again
+        assert_eq!(read_time.word_count, 7);
+        assert_eq!(read_time.image_count, 0);
+        assert_eq!(read_time.code_block_count, 0);
+
+        // 只匹配完整的标题
+        let partial = estimator(&["Ref"]).estimate(markdown);
+        assert_eq!(partial.word_count, estimate_with_english(markdown));
+        let nested = estimator(&["Nested notes"]).estimate(markdown);
+        assert_eq!(nested.word_count, estimate_with_english(markdown) - 4);
+    }
+
+    #[cfg(feature = "regex")]
+    fn estimate_with_english(markdown: &str) -> usize {
+        Estimator::new(ReadSpeed::default().chinese(false))
+            .estimate(markdown)
+            .word_count
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_excluded_events_skip_visitor() {
+        let markdown = "正文\n\n# 参考文献\n\n> 引用\n\n# 结尾\n\n> 引用";
+        let mut visits = 0;
+        let read_time = Estimator::new(ReadSpeed::default())
+            .exclude_sections(HeadingPatterns::new(["参考文献"]).unwrap())
+            .visitor(|event: &Event<'_>, _: &_| match event {
+                Event::Start(Tag::BlockQuote) => {
+                    visits += 1;
+                    Visit::count().extra_seconds(10.0)
+                }
+                _ => Visit::count(),
+            })
+            .estimate(markdown);
+        assert_eq!(visits, 1);
+        assert_eq!(read_time.word_count, 6);
+        assert_eq!(read_time.extra_seconds, 10.0);
+
+        // 章节估算中被排除的章节计数为 0，标题仍然保留
+        let sections = Estimator::new(ReadSpeed::default())
+            .exclude_sections(HeadingPatterns::new(["参考文献"]).unwrap())
+            .estimate_sections(markdown);
+        assert_eq!(sections.len(), estimate_sections(markdown).len());
+        assert_eq!(sections[1].heading, "参考文献");
+        assert_eq!(sections[1].word_count(), 0);
+        assert_eq!(sections[2], estimate_sections(markdown)[2]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(matches!(
+            HeadingPatterns::new(["("]),
+            Err(Error::Pattern(_))
+        ));
+        let patterns = HeadingPatterns::new(["附录 \\d+"]).unwrap();
+        assert!(patterns.is_match(" 附录 12 "));
+        assert!(!patterns.is_match("附录 A"));
+    }
+}
@@ -0,0 +1,171 @@
+use crate::utils::decode_text;
+use crate::{Error, Estimator, Html, ReadSpeed, ReadTime};
+
+/// HTML 文档的媒体类型
+const HTML_TYPES: &[&str] = &["text/html", "application/xhtml+xml"];
+
+impl Estimator<'_> {
+    /// 下载 `url` 指向的文档并估算阅读时间
+    ///
+    /// 响应的 `Content-Type` 为 HTML 时使用 [`Html`] 后端解析，其余内容按当前后端解析；
+    /// 没有 `Content-Type` 或为 `text/plain` 时，以 `<!DOCTYPE html>` 或 `<html>` 开头的内容同样按 HTML 解析。
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - 文档地址，支持 `http` 和 `https`
+    ///
+    /// # Returns
+    ///
+    /// 返回包含阅读时间信息的 [`ReadTime`]，请求失败或响应状态码表示错误时返回 [`Error`]。
+    pub fn estimate_url(&mut self, url: &str) -> Result<ReadTime, Error> {
+        let mut response = ureq::get(url).call().map_err(http_error)?;
+        let content_type = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| {
+                value
+                    .split(';')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_ascii_lowercase()
+            });
+        let bytes = response.body_mut().read_to_vec().map_err(http_error)?;
+        let text = decode_text(&bytes);
+
+        if !is_html(content_type.as_deref(), &text) {
+            return Ok(self.estimate(&text));
+        }
+        let backend = std::mem::replace(&mut self.backend, Box::new(Html));
+        let read_time = self.estimate(&text);
+        self.backend = backend;
+        Ok(read_time)
+    }
+}
+
+/// 下载 `url` 指向的 Markdown 或 HTML 文档并估算阅读时间
+///
+/// 使用默认的阅读速度配置，详见 [`estimate_url_with_speed`]。
+///
+/// # Arguments
+///
+/// * `url` - 文档地址
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`]，请求失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::estimate_url;
+///
+/// let read_time = estimate_url("https://example.com/post.html").unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// ```
+pub fn estimate_url(url: &str) -> Result<ReadTime, Error> {
+    estimate_url_with_speed(url, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置下载并估算文档的阅读时间
+///
+/// 按响应的 `Content-Type` 判断文档类型：HTML 使用 [`Html`] 后端，其他内容按 Markdown 解析。
+///
+/// # Arguments
+///
+/// * `url` - 文档地址
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`]，请求失败时返回 [`Error`]。
+pub fn estimate_url_with_speed(url: &str, speed: &ReadSpeed) -> Result<ReadTime, Error> {
+    Estimator::new(*speed).estimate_url(url)
+}
+
+/// 响应内容是否为 HTML 文档
+fn is_html(content_type: Option<&str>, text: &str) -> bool {
+    match content_type {
+        Some(content_type) if HTML_TYPES.contains(&content_type) => true,
+        None | Some("text/plain") => {
+            let text = text.trim_start();
+            ["<!doctype html", "<html"].iter().any(|prefix| {
+                text.get(..prefix.len())
+                    .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+            })
+        }
+        Some(_) => false,
+    }
+}
+
+fn http_error(err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Io(err) => Error::Io(err),
+        err => Error::Http(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// 在本地端口上返回一次固定响应
+    fn serve_once(status: &str, content_type: &str, body: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/post", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        url
+    }
+
+    #[test]
+    fn test_estimate_url_html() {
+        let html = "<html><head><title>标题</title></head><body><h1>第一章</h1><p>这是内容</p><img src=\"a.png\"></body></html>";
+        let url = serve_once("200 OK", "text/html; charset=utf-8", html);
+        assert_eq!(
+            estimate_url(&url).unwrap(),
+            estimate("# 第一章\n\n这是内容\n\n![](a.png)")
+        );
+    }
+
+    #[test]
+    fn test_estimate_url_markdown() {
+        let markdown = "# 标题\n\n<p>这是内容</p>\n";
+        let url = serve_once("200 OK", "text/markdown", markdown);
+        assert_eq!(estimate_url(&url).unwrap(), estimate(markdown));
+    }
+
+    #[test]
+    fn test_estimate_url_error_status() {
+        let url = serve_once("404 Not Found", "text/plain", "not found");
+        assert!(matches!(estimate_url(&url), Err(Error::Http(_))));
+    }
+
+    #[test]
+    fn test_is_html() {
+        assert!(is_html(Some("application/xhtml+xml"), ""));
+        assert!(is_html(None, "  <!DOCTYPE html><html></html>"));
+        assert!(is_html(Some("text/plain"), "<HTML></HTML>"));
+        assert!(!is_html(Some("text/markdown"), "<html></html>"));
+        assert!(!is_html(None, "# 标题"));
+    }
+}
@@ -0,0 +1,224 @@
+//! 模板引擎的 `readtime` 过滤器
+//!
+//! 为 Tera 和 MiniJinja 注册 `readtime` 过滤器，为 Askama 提供可以直接引入 `filters` 模块的函数。
+//! 过滤器的输入为 Markdown 文本，`unit` 参数决定输出：
+//!
+//! - `formatted`（默认）：格式化的阅读时间，例如 `"2分30秒"`
+//! - `seconds`：总秒数
+//! - `minutes`：向上取整的分钟数
+//! - `words`：字数
+use crate::ReadTime;
+#[cfg(any(feature = "tera", feature = "minijinja"))]
+use crate::{ReadSpeed, estimate_with_speed};
+
+/// 按 `unit` 从估算结果中取出过滤器的输出，文本和数字分别由 `text` 和 `number` 转换为模板引擎的值
+#[cfg(any(feature = "tera", feature = "minijinja"))]
+fn output<T>(
+    markdown: &str,
+    unit: Option<&str>,
+    speed: &ReadSpeed,
+    text: impl FnOnce(String) -> T,
+    number: impl FnOnce(u64) -> T,
+) -> Result<T, String> {
+    let read_time = estimate_with_speed(markdown, speed);
+    match unit.unwrap_or("formatted") {
+        "formatted" => Ok(text(read_time.formatted)),
+        "seconds" => Ok(number(read_time.total_seconds)),
+        "minutes" => Ok(number(minutes(&read_time))),
+        "words" => Ok(number(read_time.word_count as u64)),
+        unit => Err(format!(
+            "未知的单位 `{}`，可选值为 formatted、seconds、minutes、words",
+            unit
+        )),
+    }
+}
+
+fn minutes(read_time: &ReadTime) -> u64 {
+    read_time.total_seconds.div_ceil(60)
+}
+
+/// 为 Tera 注册 `readtime` 过滤器
+///
+/// 需要启用 `tera` 特性。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadSpeed, register_tera};
+///
+/// let mut tera = tera::Tera::default();
+/// register_tera(&mut tera, ReadSpeed::default());
+/// tera.add_raw_template("post", "{{ content | readtime }} / {{ content | readtime(unit=\"seconds\") }}")
+///     .unwrap();
+///
+/// let mut context = tera::Context::new();
+/// context.insert("content", &"内容".repeat(250));
+/// assert_eq!(tera.render("post", &context).unwrap(), "2分30秒 / 150");
+/// ```
+#[cfg(feature = "tera")]
+pub fn register_tera(tera: &mut tera::Tera, speed: ReadSpeed) {
+    use std::collections::HashMap;
+    use tera::{Error, Value};
+
+    tera.register_filter(
+        "readtime",
+        move |value: &Value, args: &HashMap<String, Value>| {
+            let markdown = value
+                .as_str()
+                .ok_or_else(|| Error::msg("readtime 过滤器的输入必须是字符串"))?;
+            let unit = match args.get("unit") {
+                Some(unit) => Some(
+                    unit.as_str()
+                        .ok_or_else(|| Error::msg("readtime 过滤器的 unit 参数必须是字符串"))?,
+                ),
+                None => None,
+            };
+            output(markdown, unit, &speed, Value::from, Value::from).map_err(Error::msg)
+        },
+    );
+}
+
+/// 为 MiniJinja 注册 `readtime` 过滤器
+///
+/// 需要启用 `minijinja` 特性。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadSpeed, register_minijinja};
+///
+/// let mut env = minijinja::Environment::new();
+/// register_minijinja(&mut env, ReadSpeed::default());
+///
+/// let content = "内容".repeat(250);
+/// let rendered = env
+///     .render_str("{{ content|readtime }} / {{ content|readtime(unit='minutes') }}", minijinja::context! { content })
+///     .unwrap();
+/// assert_eq!(rendered, "2分30秒 / 3");
+/// ```
+#[cfg(feature = "minijinja")]
+pub fn register_minijinja(env: &mut minijinja::Environment<'_>, speed: ReadSpeed) {
+    use minijinja::value::Kwargs;
+    use minijinja::{Error, ErrorKind, Value};
+
+    env.add_filter("readtime", move |markdown: &str, kwargs: Kwargs| {
+        let unit: Option<&str> = kwargs.get("unit")?;
+        kwargs.assert_all_used()?;
+        output(markdown, unit, &speed, Value::from, Value::from)
+            .map_err(|msg| Error::new(ErrorKind::InvalidOperation, msg))
+    });
+}
+
+/// Askama 的 `readtime` 过滤器
+///
+/// 在模板所在的模块中引入即可使用，阅读速度为默认配置。需要启用 `askama` 特性。
+///
+/// ```
+/// use askama::Template;
+///
+/// mod filters {
+///     pub use markdown_readtime::askama_filters::*;
+/// }
+///
+/// #[derive(Template)]
+/// #[template(source = "{{ content|readtime }}（{{ content|readtime_minutes }} 分钟）", ext = "txt")]
+/// struct Post<'a> {
+///     content: &'a str,
+/// }
+///
+/// let content = "内容".repeat(250);
+/// assert_eq!(Post { content: &content }.render().unwrap(), "2分30秒（3 分钟）");
+/// ```
+#[cfg(feature = "askama")]
+pub mod askama_filters {
+    use crate::estimate;
+    use std::fmt::Display;
+
+    /// 格式化的阅读时间，例如 `2分30秒`
+    pub fn readtime<T: Display>(markdown: T, _: &dyn askama::Values) -> askama::Result<String> {
+        Ok(estimate(&markdown.to_string()).formatted)
+    }
+
+    /// 阅读时间的总秒数
+    pub fn readtime_seconds<T: Display>(
+        markdown: T,
+        _: &dyn askama::Values,
+    ) -> askama::Result<u64> {
+        Ok(estimate(&markdown.to_string()).total_seconds)
+    }
+
+    /// 向上取整的阅读分钟数
+    pub fn readtime_minutes<T: Display>(
+        markdown: T,
+        _: &dyn askama::Values,
+    ) -> askama::Result<u64> {
+        Ok(super::minutes(&estimate(&markdown.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "tera")]
+    fn test_tera() {
+        let mut tera = tera::Tera::default();
+        register_tera(&mut tera, ReadSpeed::default().wpm(100.0));
+        let mut context = tera::Context::new();
+        context.insert("content", "内容".repeat(50).as_str());
+        let render = |tera: &mut tera::Tera, template: &str| tera.render_str(template, &context);
+
+        assert_eq!(
+            render(&mut tera, "{{ content | readtime }}").unwrap(),
+            "1分钟"
+        );
+        assert_eq!(
+            render(&mut tera, "{{ content | readtime(unit=\"words\") }}").unwrap(),
+            "100"
+        );
+        assert!(render(&mut tera, "{{ content | readtime(unit=\"hours\") }}").is_err());
+        assert!(render(&mut tera, "{{ 1 | readtime }}").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "minijinja")]
+    fn test_minijinja() {
+        let mut env = minijinja::Environment::new();
+        register_minijinja(&mut env, ReadSpeed::default().wpm(100.0));
+        let content = "内容".repeat(50);
+        let render = |template: &str| env.render_str(template, minijinja::context! { content });
+
+        assert_eq!(render("{{ content|readtime }}").unwrap(), "1分钟");
+        assert_eq!(
+            render("{{ content|readtime(unit='seconds') }}").unwrap(),
+            "60"
+        );
+        assert_eq!(
+            render("{% if content|readtime(unit='minutes') > 0 %}长文{% endif %}").unwrap(),
+            "长文"
+        );
+        assert!(render("{{ content|readtime(unit='hours') }}").is_err());
+        assert!(render("{{ content|readtime(speed=1) }}").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "askama")]
+    fn test_askama() {
+        let values = askama::NO_VALUES;
+        let content = "内容".repeat(250);
+        assert_eq!(
+            askama_filters::readtime(&content, values).unwrap(),
+            "2分30秒"
+        );
+        assert_eq!(
+            askama_filters::readtime_seconds(&content, values).unwrap(),
+            150
+        );
+        assert_eq!(
+            askama_filters::readtime_minutes(&content, values).unwrap(),
+            3
+        );
+    }
+}
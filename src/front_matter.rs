@@ -0,0 +1,335 @@
+use std::ops::Range;
+
+/// front matter 的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FrontMatterKind {
+    /// `---` 分隔的 YAML
+    Yaml,
+
+    /// `+++` 分隔的 TOML（Hugo、Zola）
+    Toml,
+
+    /// 位于开头的 JSON 对象
+    Json,
+}
+
+/// 文档开头的 front matter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FrontMatter {
+    pub(crate) kind: FrontMatterKind,
+
+    /// 包括开头的 BOM、空行和分隔线在内的字节长度
+    pub(crate) len: usize,
+
+    /// 分隔线之间的内容，JSON 为整个对象
+    pub(crate) content: Range<usize>,
+}
+
+/// BOM 和开头空行的字节长度
+fn leading_len(markdown: &str) -> usize {
+    let mut len = if markdown.starts_with('\u{FEFF}') {
+        '\u{FEFF}'.len_utf8()
+    } else {
+        0
+    };
+    for line in markdown[len..].split_inclusive('\n') {
+        if !line.ends_with('\n') || !line.trim().is_empty() {
+            break;
+        }
+        len += line.len();
+    }
+    len
+}
+
+/// 查找以 `open` 行开始、以 `close` 中任一行结束的块
+fn delimited(text: &str, open: &str, close: &[&str]) -> Option<(usize, Range<usize>)> {
+    let mut lines = text.split_inclusive('\n');
+    let first = lines.next().filter(|line| line.trim_end() == open)?;
+    let mut len = first.len();
+    for line in lines {
+        if close.contains(&line.trim_end()) {
+            return Some((len + line.len(), first.len()..len));
+        }
+        len += line.len();
+    }
+    None
+}
+
+/// 是否为 YAML 的 `key: value` 行
+fn is_key_line(line: &str) -> bool {
+    let line = line.trim_end();
+    !line.starts_with(char::is_whitespace)
+        && !line.starts_with(['#', '-'])
+        && line.match_indices(':').any(|(at, _)| {
+            at > 0
+                && line[at + 1..]
+                    .chars()
+                    .next()
+                    .is_none_or(char::is_whitespace)
+        })
+}
+
+/// 两条 `---` 之间的内容是否为 YAML 映射
+///
+/// 以 `---` 开头的文档也可能只是用分隔线隔开的普通段落，这时不能把中间的正文当作 front matter。
+/// 内容为空，或者第一个非空行是 `key:` 行、之后的非空行都是 `key:` 行、缩进的行、列表项或注释时才接受。
+fn is_yaml_mapping(content: &str) -> bool {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    match lines.next() {
+        None => true,
+        Some(first) => {
+            is_key_line(first)
+                && lines.all(|line| {
+                    is_key_line(line)
+                        || line.starts_with(char::is_whitespace)
+                        || line.starts_with(['#', '-'])
+                })
+        }
+    }
+}
+
+/// 查找位于开头的 JSON 对象
+///
+/// 对象的第一个键必须是字符串，对象结束后的同一行只允许空白。
+fn json_object(text: &str) -> Option<(usize, Range<usize>)> {
+    let body = text.strip_prefix('{')?.trim_start();
+    if !body.starts_with('"') && !body.starts_with('}') {
+        return None;
+    }
+    let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    let end = i + 1;
+                    let rest = &text[end..];
+                    let line_end = rest.find('\n').map_or(rest.len(), |i| i + 1);
+                    if !rest[..line_end].trim().is_empty() {
+                        return None;
+                    }
+                    return Some((end + line_end, 0..end));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+impl FrontMatter {
+    /// 识别文档开头的 front matter，允许前面有 BOM 和空行；未闭合的块不视为 front matter
+    pub(crate) fn detect(markdown: &str) -> Option<Self> {
+        let start = leading_len(markdown);
+        let text = &markdown[start..];
+        let yaml = delimited(text, "---", &["---", "..."])
+            .filter(|(_, content)| is_yaml_mapping(&text[content.clone()]));
+        let (kind, (len, content)) = if let Some(found) = yaml {
+            (FrontMatterKind::Yaml, found)
+        } else if let Some(found) = delimited(text, "+++", &["+++"]) {
+            (FrontMatterKind::Toml, found)
+        } else {
+            (FrontMatterKind::Json, json_object(text)?)
+        };
+        Some(Self {
+            kind,
+            len: start + len,
+            content: start + content.start..start + content.end,
+        })
+    }
+}
+
+impl FrontMatter {
+    /// 顶层的键值对，值为去掉引号前的原始文本
+    ///
+    /// 只解析单行的标量值，嵌套的表、列表和多行字符串会被忽略。
+    pub(crate) fn entries<'a>(&self, markdown: &'a str) -> Vec<(&'a str, &'a str)> {
+        let content = &markdown[self.content.clone()];
+        match self.kind {
+            FrontMatterKind::Yaml => line_entries(content, ':', false),
+            FrontMatterKind::Toml => line_entries(content, '=', true),
+            FrontMatterKind::Json => json_entries(content),
+        }
+    }
+}
+
+/// 按行解析 `key: value` 或 `key = value`，`tables` 为真时遇到 `[table]` 后停止
+fn line_entries(content: &str, separator: char, tables: bool) -> Vec<(&str, &str)> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        if line.starts_with(char::is_whitespace) || line.starts_with('#') {
+            continue;
+        }
+        if tables && line.starts_with('[') {
+            break;
+        }
+        // 带引号的键中可能包含分隔符
+        let entry = match line.chars().next() {
+            Some(quote @ ('"' | '\'')) => line[1..].find(quote).and_then(|end| {
+                let value = line[end + 2..].trim_start().strip_prefix(separator)?;
+                Some((&line[1..end + 1], value))
+            }),
+            _ => line
+                .split_once(separator)
+                .map(|(key, value)| (key.trim(), value)),
+        };
+        if let Some((key, value)) = entry {
+            entries.push((key, value.trim()));
+        }
+    }
+    entries
+}
+
+/// 解析 JSON 对象顶层的键值对
+fn json_entries(content: &str) -> Vec<(&str, &str)> {
+    let mut entries = Vec::new();
+    let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+    let mut key: Option<&str> = None;
+    let mut start = 0;
+    let mut value_start = None;
+    for (i, c) in content.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => {
+                in_string = !in_string;
+                if in_string {
+                    start = i + 1;
+                } else if depth == 1 && value_start.is_none() {
+                    key = Some(&content[start..i]);
+                }
+            }
+            _ if in_string => {}
+            ':' if depth == 1 => value_start = Some(i + 1),
+            '{' | '[' => depth += 1,
+            '}' | ']' | ',' => {
+                if depth == 1
+                    && let (Some(key), Some(value_start)) = (key.take(), value_start.take())
+                {
+                    entries.push((key, content[value_start..i].trim()));
+                }
+                if c != ',' {
+                    depth = depth.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// 文档开头 front matter 的字节长度，没有时为 0
+pub(crate) fn front_matter_len(markdown: &str) -> usize {
+    FrontMatter::detect(markdown).map_or(0, |front_matter| front_matter.len)
+}
+
+/// 拆分文档开头的 front matter 和正文
+///
+/// 支持 `---` 分隔的 YAML、`+++` 分隔的 TOML 和位于开头的 JSON 对象，
+/// 开头的 BOM 和空行归入 front matter 部分。没有 front matter 时，第一部分为空字符串。
+/// 两条 `---` 之间的内容不是 `key: value` 映射时，它们视为普通的分隔线。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::split_front_matter;
+///
+/// let (front_matter, body) = split_front_matter("---\ntitle: 你好\n---\n# 正文\n");
+/// assert_eq!(front_matter, "---\ntitle: 你好\n---\n");
+/// assert_eq!(body, "# 正文\n");
+///
+/// assert_eq!(split_front_matter("# 正文\n"), ("", "# 正文\n"));
+/// ```
+pub fn split_front_matter(markdown: &str) -> (&str, &str) {
+    markdown.split_at(front_matter_len(markdown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed, estimate};
+
+    #[test]
+    fn test_front_matter() {
+        assert_eq!(front_matter_len("---\r\na: 1\r\n...\r\n正文"), 16);
+        assert_eq!(front_matter_len("---\na: 1\n"), 0);
+        assert_eq!(front_matter_len("----\na: 1\n---\n"), 0);
+        assert_eq!(front_matter_len("正文\n---\n"), 0);
+        assert_eq!(front_matter_len("---\n---\n正文"), 8);
+        assert_eq!(
+            front_matter_len("---\n\ntitle: a\ntags:\n- x\n---\n正文"),
+            28
+        );
+
+        let toml = "\u{FEFF}\n  \n+++\ntitle = \"你好\"\n+++\n正文";
+        let front_matter = FrontMatter::detect(toml).unwrap();
+        assert_eq!(front_matter.kind, FrontMatterKind::Toml);
+        assert_eq!(&toml[front_matter.content], "title = \"你好\"\n");
+        assert_eq!(&toml[front_matter.len..], "正文");
+
+        let json = "{\"title\": \"a } b\", \"tags\": {\"x\": \"\\\"\"}}\n\n正文";
+        let front_matter = FrontMatter::detect(json).unwrap();
+        assert_eq!(front_matter.kind, FrontMatterKind::Json);
+        assert_eq!(&json[front_matter.len..], "\n正文");
+        assert_eq!(front_matter_len("{ 不是 JSON } 正文"), 0);
+        assert_eq!(front_matter_len("{\"a\": 1"), 0);
+        assert_eq!(front_matter_len("{ 也不是 }\n正文"), 0);
+    }
+
+    #[test]
+    fn test_thematic_breaks_are_not_front_matter() {
+        let markdown = "---\n\nparagraph\n\n---\n\nmore";
+        assert_eq!(front_matter_len(markdown), 0);
+        let read_time = estimate(markdown);
+        assert_eq!(
+            read_time.word_count,
+            estimate("paragraph\n\nmore").word_count
+        );
+        assert_eq!(read_time.front_matter_bytes, 0);
+
+        assert_eq!(front_matter_len("---\n正文\ntitle: a\n---\n"), 0);
+        assert_eq!(front_matter_len("---\n\n# 标题\n\n---\n"), 0);
+    }
+
+    #[test]
+    fn test_front_matter_entries() {
+        fn entries(markdown: &str) -> Vec<(&str, &str)> {
+            FrontMatter::detect(markdown).unwrap().entries(markdown)
+        }
+        assert_eq!(
+            entries("---\ntitle: \"a: b\"\ntags:\n  - x\nwpm: 150\n\"a: b\": 1\n---\n"),
+            [
+                ("title", "\"a: b\""),
+                ("tags", ""),
+                ("wpm", "150"),
+                ("a: b", "1")
+            ]
+        );
+        assert_eq!(
+            entries("+++\nwpm = 150\n[extra]\nwpm = 1\n+++\n"),
+            [("wpm", "150")]
+        );
+        assert_eq!(
+            entries("{\"a\": {\"wpm\": 1}, \"tags\": [1, 2], \"wpm\": 150}\n"),
+            [("a", "{\"wpm\": 1}"), ("tags", "[1, 2]"), ("wpm", "150")]
+        );
+
+        let markdown = "---\ntitle: 一个很长的标题\ntags: [测试]\n---\n\n正文内容\n";
+        let read_time = estimate(markdown);
+        assert_eq!(read_time.word_count, 4);
+        assert_eq!(
+            read_time.front_matter_bytes,
+            markdown.find("\n\n").unwrap() + 1
+        );
+
+        let read_time = Estimator::new(ReadSpeed::default())
+            .skip_front_matter(false)
+            .estimate(markdown);
+        assert!(read_time.word_count > 4);
+        assert_eq!(read_time.front_matter_bytes, 0);
+    }
+}
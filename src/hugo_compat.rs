@@ -0,0 +1,322 @@
+//! 与 Hugo 的 `.ReadingTime` 和 `.WordCount` 一致的计数方式
+//!
+//! Hugo 在渲染后的 HTML 去除标签得到的纯文本上计数：
+//!
+//! - 按空白拆分字段，每个字段计为一个单词，块级元素之间、换行处视为空白，
+//!   行内元素（强调、链接、行内代码等）与相邻文字连在一起；
+//! - 代码计入字数，图片的替代文本和原始 HTML 不计入（Hugo 默认不输出原始 HTML）；
+//! - 启用 `hasCJKLanguage` 且正文含有汉字、谚文、平假名或片假名时，纯 ASCII 的字段计为 1，
+//!   其余字段按渲染后的字符数计数，HTML 实体（包括 Typographer 扩展生成的弯引号、破折号）按实体的长度计算；
+//! - 阅读时间为整分钟数：英文为 `(字数 + 212) / 213`，中日韩为 `(字数 + 500) / 501`。
+use crate::chars::is_cjk;
+use crate::estimator::Counters;
+use crate::utils::format_time;
+use crate::{Breakdown, Estimator, PulldownCmark, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Options, Tag, TagEnd};
+
+/// 英文每分钟阅读的单词数
+const WORDS_PER_MINUTE: usize = 213;
+
+/// 中日韩语言每分钟阅读的字数
+const CJK_WORDS_PER_MINUTE: usize = 501;
+
+/// Hugo 默认启用的 Goldmark 扩展中 pulldown-cmark 支持的部分
+const HUGO_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_FOOTNOTES);
+
+/// 按 Hugo 的规则统计去除标签后的纯文本
+#[derive(Default)]
+struct HugoWords {
+    /// 空白分隔的字段数
+    fields: usize,
+    /// 中日韩语言下的字数
+    cjk_words: usize,
+    /// 当前字段渲染后的字符数，为 0 时不在字段内
+    chars: usize,
+    /// 当前字段是否只含 ASCII 字符
+    ascii: bool,
+}
+
+impl HugoWords {
+    /// 追加正文文字，按 Typographer 扩展和 HTML 转义后的长度计数
+    fn push_text(&mut self, text: &str) {
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let (len, width) = match c {
+                '.' if rest.starts_with("...") => (3, "&hellip;".len()),
+                '-' if rest.starts_with("---") => (3, "&mdash;".len()),
+                '-' if rest.starts_with("--") => (2, "&ndash;".len()),
+                '<' if rest.starts_with("<<") => (2, "&laquo;".len()),
+                '>' if rest.starts_with(">>") => (2, "&raquo;".len()),
+                '\'' => (1, "&rsquo;".len()),
+                '"' => (1, "&rdquo;".len()),
+                _ => (c.len_utf8(), escaped_width(c)),
+            };
+            self.push_char(c, width);
+            rest = &rest[len..];
+        }
+    }
+
+    /// 追加代码，只按 HTML 转义后的长度计数
+    fn push_code(&mut self, code: &str) {
+        for c in code.chars() {
+            self.push_char(c, escaped_width(c));
+        }
+    }
+
+    fn push_char(&mut self, c: char, width: usize) {
+        if c.is_whitespace() {
+            self.split();
+        } else {
+            if self.chars == 0 {
+                self.ascii = true;
+            }
+            self.chars += width;
+            self.ascii &= c.is_ascii();
+        }
+    }
+
+    /// 结束当前字段
+    fn split(&mut self) {
+        if self.chars > 0 {
+            self.fields += 1;
+            self.cjk_words += if self.ascii { 1 } else { self.chars };
+            self.chars = 0;
+        }
+    }
+}
+
+/// 字符经 HTML 转义后的长度
+fn escaped_width(c: char) -> usize {
+    match c {
+        '&' => "&amp;".len(),
+        '<' => "&lt;".len(),
+        '>' => "&gt;".len(),
+        '"' => "&quot;".len(),
+        _ => 1,
+    }
+}
+
+/// 行内元素的开始标签，前后不产生空白
+fn is_inline(tag: &Tag<'_>) -> bool {
+    matches!(
+        tag,
+        Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. } | Tag::Image { .. }
+    )
+}
+
+/// 行内元素的结束标签
+fn is_inline_end(tag: &TagEnd) -> bool {
+    matches!(
+        tag,
+        TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link | TagEnd::Image
+    )
+}
+
+impl Estimator<'_> {
+    /// 按 Hugo 的规则估算阅读时间
+    ///
+    /// [`ReadSpeed::chinese`] 对应 Hugo 的 `hasCJKLanguage` 设置，其余速度配置不会生效。
+    /// 使用的后端应启用与 Hugo 相同的扩展（表格、删除线、任务列表和脚注），
+    /// [`estimate_hugo`](crate::estimate_hugo) 会自动启用。
+    /// 返回结果中的 `word_count` 与 Hugo 的 `.WordCount` 相同，`total_seconds` 为
+    /// `.ReadingTime` 的分钟数乘以 60，图片和代码块数量与 [`estimate`](Self::estimate) 相同。
+    pub fn estimate_hugo(&mut self, markdown: &str) -> ReadTime {
+        let speed = *self.speed();
+        let mut words = HugoWords::default();
+        // 脚注引用渲染为按首次出现顺序编号的上标，编号与相邻文字连在一起
+        let mut footnotes: Vec<String> = Vec::new();
+        let counters = self.count(markdown, &mut |event, _, counters| match event {
+            Event::Start(tag) if !is_inline(tag) => words.split(),
+            Event::End(tag) if !is_inline_end(tag) => words.split(),
+            Event::Text(text) if counters.in_code_block => words.push_code(text),
+            Event::Text(text) if !counters.in_image_alt => words.push_text(text),
+            Event::Code(code) if !counters.in_image_alt => words.push_code(code),
+            Event::FootnoteReference(label) => {
+                let number = match footnotes.iter().position(|l| l == label.as_ref()) {
+                    Some(index) => index + 1,
+                    None => {
+                        footnotes.push(label.to_string());
+                        footnotes.len()
+                    }
+                };
+                words.push_code(&number.to_string());
+            }
+            Event::SoftBreak | Event::HardBreak | Event::Rule => words.split(),
+            _ => {}
+        });
+        words.split();
+
+        let cjk = speed.chinese && markdown[counters.front_matter_bytes..].chars().any(is_cjk);
+        let (word_count, per_minute) = if cjk {
+            (words.cjk_words, CJK_WORDS_PER_MINUTE)
+        } else {
+            (words.fields, WORDS_PER_MINUTE)
+        };
+        let total_seconds = word_count.div_ceil(per_minute) as u64 * 60;
+        hugo_read_time(counters, word_count, total_seconds, &speed)
+    }
+}
+
+/// 用 Hugo 的字数和阅读时间替换计数结果中的对应部分
+fn hugo_read_time(
+    counters: Counters,
+    word_count: usize,
+    total_seconds: u64,
+    speed: &ReadSpeed,
+) -> ReadTime {
+    ReadTime {
+        total_seconds,
+        formatted: format_time(total_seconds),
+        word_count,
+        word_count_breakdown: counters.word_count_breakdown,
+        image_count: counters.image_count,
+        image_alt_chars: counters.image_alt_chars,
+        code_block_count: counters.code_block_count,
+        code_line_count: counters.code_line_count,
+        table_count: counters.table_count,
+        table_cell_count: counters.table_cell_count,
+        link_count: counters.link_count,
+        extra_seconds: 0.0,
+        pages: speed.pages(word_count),
+        front_matter_bytes: counters.front_matter_bytes,
+        custom_elements: counters.custom_elements,
+        breakdown: Breakdown {
+            prose: total_seconds as f64,
+            ..Breakdown::default()
+        },
+    }
+}
+
+/// 按 Hugo 的规则估算阅读时间
+///
+/// 结果与 Hugo 页面的 `.WordCount` 和 `.ReadingTime` 一致，迁移到本库的站点显示的数字不会变化。
+/// 使用 Hugo 的默认设置，即不启用 `hasCJKLanguage`，中文按空白分隔的字段计数。
+/// 带短代码的内容可以配合 [`Hugo`](crate::Hugo) 后端使用 [`Estimator::estimate_hugo`]。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，`total_seconds` 为整分钟。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_hugo;
+///
+/// let read_time = estimate_hugo(&"word ".repeat(214));
+/// assert_eq!(read_time.word_count, 214);
+/// assert_eq!(read_time.formatted, "2分钟");
+/// ```
+pub fn estimate_hugo(markdown: &str) -> ReadTime {
+    estimate_hugo_with_speed(markdown, &ReadSpeed::hugo())
+}
+
+/// 使用自定义速度配置按 Hugo 的规则估算阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 阅读速度配置，只有 [`ReadSpeed::chinese`]（对应 `hasCJKLanguage`）会生效
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，`total_seconds` 为整分钟。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadSpeed, estimate_hugo_with_speed};
+///
+/// let speed = ReadSpeed::hugo().chinese(true);
+/// let read_time = estimate_hugo_with_speed("这是 Hugo 的计数方式", &speed);
+/// assert_eq!(read_time.word_count, 2 + 1 + 5);
+/// assert_eq!(read_time.total_seconds, 60);
+/// ```
+pub fn estimate_hugo_with_speed(markdown: &str, speed: &ReadSpeed) -> ReadTime {
+    Estimator::new(*speed)
+        .backend(PulldownCmark::new(HUGO_OPTIONS))
+        .estimate_hugo(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cjk(markdown: &str) -> ReadTime {
+        estimate_hugo_with_speed(markdown, &ReadSpeed::hugo().chinese(true))
+    }
+
+    #[test]
+    fn test_hugo_word_count() {
+        // 行内元素与相邻文字连在一起，块级元素和换行处断开
+        assert_eq!(estimate_hugo("Hello *wor*ld").word_count, 2);
+        assert_eq!(estimate_hugo("# Title\nText\nmore").word_count, 3);
+        assert_eq!(estimate_hugo("- a\n- b\n\n> c").word_count, 3);
+        assert_eq!(
+            estimate_hugo("| a | b |\n|---|---|\n| c | d |").word_count,
+            4
+        );
+        // 代码计入，图片替代文本和原始 HTML 不计入
+        assert_eq!(
+            estimate_hugo("run `cargo test` now\n\n```\nfn main() {}\n```").word_count,
+            7
+        );
+        assert_eq!(
+            estimate_hugo("![alt text](a.png) <span>raw</span> text").word_count,
+            2
+        );
+        assert_eq!(estimate_hugo("- [x] done ~~not~~yet").word_count, 2);
+        assert_eq!(
+            estimate_hugo("a[^n] b[^m] c[^n]\n\n[^n]: note\n[^m]: other").word_count,
+            5
+        );
+        assert_eq!(estimate_hugo("---\ntitle: a b c\n---\nbody").word_count, 1);
+        // 不启用 hasCJKLanguage 时中文按字段计数
+        assert_eq!(estimate_hugo("中文内容 with English").word_count, 3);
+    }
+
+    #[test]
+    fn test_hugo_cjk_word_count() {
+        assert_eq!(cjk("中文内容 with English").word_count, 6);
+        assert_eq!(cjk("中文**加粗**").word_count, 4);
+        // 非 ASCII 字段按渲染后的长度计数：`&amp;` 和 `&rsquo;` 各占多个字符
+        assert_eq!(cjk("中文&x").word_count, 2 + 5 + 1);
+        assert_eq!(cjk("中文's").word_count, 2 + 7 + 1);
+        assert_eq!(cjk("it's 中文").word_count, 1 + 2);
+        assert_eq!(cjk("中文[^n]\n\n[^n]: 注").word_count, 3 + 1);
+        assert_eq!(cjk("日本語のテキスト").word_count, 8);
+        assert_eq!(cjk("한국어 텍스트").word_count, 6);
+    }
+
+    #[test]
+    fn test_hugo_reading_time() {
+        assert_eq!(estimate_hugo("").total_seconds, 0);
+        assert_eq!(estimate_hugo("word").total_seconds, 60);
+        assert_eq!(estimate_hugo(&"word ".repeat(213)).total_seconds, 60);
+        assert_eq!(estimate_hugo(&"word ".repeat(214)).total_seconds, 120);
+        assert_eq!(cjk(&"字".repeat(501)).total_seconds, 60);
+        assert_eq!(cjk(&"字".repeat(502)).total_seconds, 120);
+        // 启用 hasCJKLanguage 但正文没有中日韩文字时按英文计算
+        assert_eq!(cjk(&"word ".repeat(214)).total_seconds, 120);
+
+        let read_time = estimate_hugo("![a](a.png)\n\n```\ncode\n```");
+        assert_eq!(read_time.image_count, 1);
+        assert_eq!(read_time.code_block_count, 1);
+        assert_eq!(read_time.breakdown.total(), 60.0);
+    }
+
+    #[test]
+    #[cfg(feature = "hugo")]
+    fn test_hugo_shortcodes() {
+        let read_time = Estimator::new(ReadSpeed::hugo())
+            .backend(crate::Hugo::default())
+            .estimate_hugo("text {{< youtube id >}} more");
+        assert_eq!(read_time.word_count, 2);
+    }
+}
@@ -0,0 +1,163 @@
+//! 按本地图片的尺寸调整图片时间
+use crate::{Counters, EventVisitor, ReadSpeed, Visit};
+use pulldown_cmark::{Event, Tag};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// 按本地图片文件的像素尺寸调整图片的额外时间，需要启用 `imagesize` 特性
+///
+/// 作为 [`EventVisitor`] 使用。以相对路径引用的图片在 `base_dir` 下查找，只读取文件头中的尺寸：
+///
+/// - 宽和高都不超过 [`icon_size`](Self::icon_size) 的小图标视为装饰，不计为图片，也不增加时间；
+/// - 其余图片按像素面积与参考尺寸之比缩放 [`ReadSpeed::seconds_per_image`]，
+///   比例限制在 [`scale_limits`](Self::scale_limits) 之内，差额作为额外时间添加。
+///
+/// 远程地址、绝对路径、找不到或无法识别的文件按普通图片计时。同一路径只读取一次。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{Estimator, ImageSizes, ReadSpeed};
+///
+/// let speed = ReadSpeed::default();
+/// let read_time = Estimator::new(speed)
+///     .visitor(ImageSizes::new("content/posts", &speed))
+///     .estimate("![](icons/star.png) 收藏\n\n![架构](images/architecture.png)");
+/// println!("{}", read_time.formatted);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageSizes {
+    base_dir: PathBuf,
+    seconds_per_image: f64,
+    icon_size: usize,
+    reference_pixels: f64,
+    min_scale: f64,
+    max_scale: f64,
+    /// 已读取的尺寸，无法读取时为 `None`
+    sizes: BTreeMap<String, Option<(usize, usize)>>,
+}
+
+impl ImageSizes {
+    /// 在 `base_dir` 下查找图片，按 `speed` 中每张图片的额外时间缩放
+    ///
+    /// 默认 32 像素以内为图标，参考尺寸为 800×600，缩放比例限制在 0.5 到 2 之间。
+    pub fn new(base_dir: impl Into<PathBuf>, speed: &ReadSpeed) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            seconds_per_image: speed.seconds_per_image,
+            icon_size: 32,
+            reference_pixels: 800.0 * 600.0,
+            min_scale: 0.5,
+            max_scale: 2.0,
+            sizes: BTreeMap::new(),
+        }
+    }
+
+    /// 设置图标的最大边长（像素），为 0 时不识别图标
+    pub fn icon_size(mut self, pixels: usize) -> Self {
+        self.icon_size = pixels;
+        self
+    }
+
+    /// 设置按正常时间计算的参考尺寸
+    pub fn reference_size(mut self, width: usize, height: usize) -> Self {
+        self.reference_pixels = (width * height) as f64;
+        self
+    }
+
+    /// 设置缩放比例的下限和上限
+    pub fn scale_limits(mut self, min: f64, max: f64) -> Self {
+        self.min_scale = min;
+        self.max_scale = max;
+        self
+    }
+
+    /// 图片地址对应的尺寸，不是本地相对路径或无法读取时返回 `None`
+    fn size(&mut self, url: &str) -> Option<(usize, usize)> {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        if path.is_empty() || path.starts_with('/') || path.contains(':') {
+            return None;
+        }
+        if let Some(&size) = self.sizes.get(path) {
+            return size;
+        }
+        let size = imagesize::size(self.base_dir.join(path))
+            .ok()
+            .map(|size| (size.width, size.height));
+        self.sizes.insert(String::from(path), size);
+        size
+    }
+
+    /// 尺寸为 `width`×`height` 的图片相对普通图片增加的时间，图标返回 `None`
+    fn adjustment(&self, width: usize, height: usize) -> Option<f64> {
+        if width <= self.icon_size && height <= self.icon_size {
+            return None;
+        }
+        if self.reference_pixels <= 0.0 {
+            return Some(0.0);
+        }
+        let scale = ((width * height) as f64 / self.reference_pixels)
+            .clamp(self.min_scale, self.max_scale.max(self.min_scale));
+        Some((scale - 1.0) * self.seconds_per_image)
+    }
+}
+
+impl EventVisitor for ImageSizes {
+    fn visit(&mut self, event: &Event<'_>, _: &Counters) -> Visit {
+        let Event::Start(Tag::Image { dest_url, .. }) = event else {
+            return Visit::count();
+        };
+        let Some((width, height)) = self.size(dest_url) else {
+            return Visit::count();
+        };
+        match self.adjustment(width, height) {
+            Some(seconds) => Visit::count().extra_seconds(seconds),
+            None => Visit::skip(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Estimator;
+    use std::fs;
+
+    /// 只含文件头的 PNG，足以读取尺寸
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend(width.to_be_bytes());
+        bytes.extend(height.to_be_bytes());
+        bytes.extend([8, 6, 0, 0, 0, 0, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_image_sizes() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-image-sizes");
+        fs::create_dir_all(dir.join("images")).unwrap();
+        fs::write(dir.join("images/icon.png"), png(16, 16)).unwrap();
+        fs::write(dir.join("images/small.png"), png(200, 150)).unwrap();
+        fs::write(dir.join("images/diagram.png"), png(1600, 1200)).unwrap();
+        fs::write(dir.join("images/photo.png"), png(800, 600)).unwrap();
+
+        let speed = ReadSpeed::default();
+        let markdown = "![](images/icon.png) ![](images/icon.png?v=2)\n\n\
+                        ![](images/small.png)\n\n![](images/diagram.png#full)\n\n\
+                        ![](images/photo.png)\n\n![](missing.png) ![](https://example.com/a.png)";
+        let read_time = Estimator::new(speed)
+            .visitor(ImageSizes::new(&dir, &speed))
+            .estimate(markdown);
+        assert_eq!(read_time.image_count, 5);
+        // 小图按下限减半，大图按上限加倍，其余三张按正常时间
+        assert_eq!(read_time.extra_seconds, -6.0 + 12.0);
+        assert_eq!(read_time.total_seconds, 6 + 24 + 12 * 3);
+
+        let read_time = Estimator::new(speed)
+            .visitor(ImageSizes::new(&dir, &speed).icon_size(0))
+            .estimate(markdown);
+        assert_eq!(read_time.image_count, 7);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
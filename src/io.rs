@@ -0,0 +1,197 @@
+use crate::utils::decode_text;
+use crate::{Error, Estimator, LimitExceeded, ReadSpeed, ReadTime, estimate_with_speed};
+use std::fs;
+use std::io::{self, Read};
+use std::mem;
+use std::path::Path;
+
+/// 从 [`Read`] 中读取内容并估算阅读时间
+///
+/// 先把全部内容读入内存再估算，内存占用与输入大小成正比，编码处理与
+/// [`estimate_file_with_speed`] 相同。来源不受信任或大小未知时（例如解压流和网络响应），
+/// 应使用 [`Estimator::try_estimate_reader`] 限制读取的字节数。
+///
+/// # Arguments
+///
+/// * `reader` - 内容来源，例如文件、网络响应或解压流
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，读取失败时返回对应的 I/O 错误。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate_reader, ReadSpeed};
+///
+/// let input = "# 标题\n\n这是内容".as_bytes();
+/// let read_time = estimate_reader(input, &ReadSpeed::default()).unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// ```
+pub fn estimate_reader(reader: impl Read, speed: &ReadSpeed) -> io::Result<ReadTime> {
+    Estimator::new(*speed).estimate_reader(reader)
+}
+
+impl Estimator<'_> {
+    /// 从 [`Read`] 中读取内容并估算阅读时间
+    ///
+    /// 与 [`estimate_reader`] 相同，读取使用的缓冲区在多次调用之间复用。
+    pub fn estimate_reader(&mut self, mut reader: impl Read) -> io::Result<ReadTime> {
+        let mut buffer = mem::take(&mut self.scratch.bytes);
+        buffer.clear();
+        let result = reader.read_to_end(&mut buffer);
+        let read_time = result.map(|_| self.estimate(&decode_text(&buffer)));
+        self.scratch.bytes = buffer;
+        read_time
+    }
+
+    /// 在资源限制内从 [`Read`] 中读取内容并估算阅读时间
+    ///
+    /// 最多读取 [`Limits::max_input_bytes`](crate::Limits::max_input_bytes) 加一个字节，
+    /// 超出时停止读取，因此内存占用不超过该上限。读取完成后按
+    /// [`try_estimate`](Self::try_estimate) 检查其余的限制。
+    ///
+    /// # Errors
+    ///
+    /// 读取失败时返回 [`Error::Io`]，超出任意一项限制时返回 [`Error::Limit`]。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{Error, Estimator, LimitExceeded, Limits, ReadSpeed};
+    ///
+    /// let mut estimator = Estimator::new(ReadSpeed::default())
+    ///     .limits(Limits::default().max_input_bytes(Some(16)));
+    /// let read_time = estimator.try_estimate_reader("# 标题".as_bytes()).unwrap();
+    /// assert_eq!(read_time.word_count, 2);
+    ///
+    /// let endless = std::io::repeat(b'a');
+    /// assert!(matches!(
+    ///     estimator.try_estimate_reader(endless),
+    ///     Err(Error::Limit(LimitExceeded::InputBytes(16)))
+    /// ));
+    /// ```
+    pub fn try_estimate_reader(&mut self, mut reader: impl Read) -> Result<ReadTime, Error> {
+        let max = self.limits.max_input_bytes;
+        let mut buffer = mem::take(&mut self.scratch.bytes);
+        buffer.clear();
+        let result = match max {
+            Some(max) => reader
+                .by_ref()
+                .take(max as u64 + 1)
+                .read_to_end(&mut buffer),
+            None => reader.read_to_end(&mut buffer),
+        };
+        let read_time = match (result, max) {
+            (Err(err), _) => Err(Error::Io(err)),
+            (Ok(len), Some(max)) if len > max => Err(LimitExceeded::InputBytes(max).into()),
+            (Ok(_), _) => self.try_estimate(&decode_text(&buffer)),
+        };
+        self.scratch.bytes = buffer;
+        read_time
+    }
+}
+
+/// 估算文件的阅读时间
+///
+/// 使用默认的阅读速度配置，详见 [`estimate_file_with_speed`]。
+///
+/// # Arguments
+///
+/// * `path` - Markdown 文件路径
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::estimate_file;
+///
+/// let read_time = estimate_file("README.md").unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// ```
+pub fn estimate_file(path: impl AsRef<Path>) -> Result<ReadTime, Error> {
+    estimate_file_with_speed(path, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算文件的阅读时间
+///
+/// 文件内容默认按 UTF-8 解码并去除 BOM；带有 UTF-16 BOM 的文件会按 UTF-16 解码。
+/// 无效的字节序列会被忽略，不计入字数。
+///
+/// # Arguments
+///
+/// * `path` - Markdown 文件路径
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，读取失败时返回 [`Error`]。
+pub fn estimate_file_with_speed(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+) -> Result<ReadTime, Error> {
+    let bytes = fs::read(path)?;
+    Ok(estimate_with_speed(&decode_text(&bytes), speed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Limits, estimate};
+
+    #[test]
+    fn test_estimate_reader() {
+        let markdown = "# 标题\n\n这是内容";
+        let read_time = estimate_reader(markdown.as_bytes(), &ReadSpeed::default()).unwrap();
+        assert_eq!(read_time, estimate(markdown));
+
+        let chained = "# 标题\n\n".as_bytes().chain("这是内容".as_bytes());
+        let read_time = estimate_reader(chained, &ReadSpeed::default()).unwrap();
+        assert_eq!(read_time, estimate(markdown));
+
+        let mut estimator = Estimator::new(ReadSpeed::default());
+        for markdown in ["# 很长的标题\n\n很长的内容", markdown] {
+            let read_time = estimator.estimate_reader(markdown.as_bytes()).unwrap();
+            assert_eq!(read_time, estimate(markdown));
+        }
+    }
+
+    #[test]
+    fn test_try_estimate_reader() {
+        let markdown = "# 标题\n\n这是内容";
+        let mut estimator = Estimator::new(ReadSpeed::default());
+        let read_time = estimator.try_estimate_reader(markdown.as_bytes()).unwrap();
+        assert_eq!(read_time, estimate(markdown));
+
+        // 超出上限后不再继续读取
+        let mut estimator = estimator.limits(Limits::NONE.max_input_bytes(Some(markdown.len())));
+        let mut source = markdown.as_bytes().chain(io::repeat(b'a'));
+        assert!(matches!(
+            estimator.try_estimate_reader(&mut source),
+            Err(Error::Limit(LimitExceeded::InputBytes(_)))
+        ));
+        let mut rest = [0; 4];
+        source.read_exact(&mut rest).unwrap();
+        assert_eq!(&rest, b"aaaa");
+        assert!(estimator.scratch.bytes.len() <= markdown.len() + 1);
+    }
+
+    #[test]
+    fn test_estimate_file() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-estimate-file");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("post.md");
+        fs::write(&path, "\u{FEFF}# 标题\n\n这是内容").unwrap();
+
+        let read_time = estimate_file(&path).unwrap();
+        assert_eq!(read_time, estimate("# 标题\n\n这是内容"));
+
+        let missing = estimate_file(dir.join("missing.md"));
+        assert!(matches!(missing, Err(Error::Io(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,393 @@
+//! 高频词提取
+//!
+//! 在估算阅读时间的同一次遍历中统计计入字数的正文文本：
+//!
+//! - 拉丁等以空白分隔的文字按字母和数字组成的单词计数，统一转为小写，
+//!   忽略单个字符、纯数字和英文停用词；
+//! - 中日韩文字没有词边界，按相邻两个字组成的词计数，含有常见虚词（例如“的”“了”“是”）的不计入。
+//!
+//! 行内代码、代码块和图片的替代文本不参与统计。
+use crate::chars::is_cjk;
+use crate::estimator::Counters;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::Event;
+use std::collections::BTreeMap;
+
+/// 英文停用词
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "don",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "ll",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "re",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "us",
+    "ve",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+/// 中文虚词和代词，含有这些字的双字词不计入
+const CJK_STOP_CHARS: &[char] = &[
+    '的', '了', '是', '在', '和', '与', '及', '或', '也', '都', '就', '而', '这', '那', '我', '你',
+    '他', '她', '它', '们', '个', '不', '有', '为', '以', '之', '其', '于', '把', '被', '让', '给',
+    '对', '从', '到', '将', '会', '能', '可', '要', '很', '还', '又', '并', '但', '如', '等', '吗',
+    '呢', '吧', '啊', '着', '过', '该', '每', '些', '所', '因', '此', '则', '即',
+];
+
+/// 一个高频词
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Keyword {
+    /// 词语，拉丁文字为小写形式
+    pub term: String,
+
+    /// 出现次数
+    pub count: usize,
+}
+
+impl Keyword {
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// 带高频词的估算结果
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct KeywordReadTime {
+    /// 与 [`estimate`](crate::estimate) 相同的估算结果
+    pub read_time: ReadTime,
+
+    /// 按出现次数从多到少排列的高频词，次数相同时按词语排序
+    pub keywords: Vec<Keyword>,
+}
+
+impl KeywordReadTime {
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+
+    pub fn keywords(&self) -> &[Keyword] {
+        &self.keywords
+    }
+}
+
+/// 统计计入字数的正文文本中的词频
+#[derive(Default)]
+struct TermCounter {
+    terms: BTreeMap<String, usize>,
+    /// 上一个事件之前的计数
+    last_words: usize,
+    /// 上一个事件的正文文本，下一个事件时根据字数是否增加判断它是否被计数
+    pending: Option<String>,
+    /// 当前的拉丁单词
+    word: String,
+}
+
+impl TermCounter {
+    fn observe(&mut self, event: &Event<'_>, counters: &Counters) {
+        self.settle(counters);
+        if let Event::Text(text) = event {
+            self.pending = Some(text.to_string());
+        }
+    }
+
+    fn settle(&mut self, counters: &Counters) {
+        let words = std::mem::replace(&mut self.last_words, counters.word_count);
+        if let Some(text) = self.pending.take()
+            && counters.word_count > words
+        {
+            self.push_text(&text);
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut previous: Option<char> = None;
+        for c in text.chars() {
+            if is_cjk(c) {
+                self.push_word();
+                if let Some(first) = previous
+                    && !CJK_STOP_CHARS.contains(&first)
+                    && !CJK_STOP_CHARS.contains(&c)
+                {
+                    let mut term = String::new();
+                    term.push(first);
+                    term.push(c);
+                    *self.terms.entry(term).or_insert(0) += 1;
+                }
+                previous = Some(c);
+                continue;
+            }
+            previous = None;
+            if c.is_alphanumeric() {
+                self.word.extend(c.to_lowercase());
+            } else {
+                self.push_word();
+            }
+        }
+        self.push_word();
+    }
+
+    fn push_word(&mut self) {
+        let word = std::mem::take(&mut self.word);
+        if word.chars().nth(1).is_some()
+            && !word.chars().all(|c| c.is_numeric())
+            && ENGLISH_STOPWORDS.binary_search(&word.as_str()).is_err()
+        {
+            *self.terms.entry(word).or_insert(0) += 1;
+        }
+    }
+
+    /// 出现次数最多的 `limit` 个词
+    fn top(self, limit: usize) -> Vec<Keyword> {
+        let mut keywords: Vec<Keyword> = self
+            .terms
+            .into_iter()
+            .map(|(term, count)| Keyword { term, count })
+            .collect();
+        // 词语已按字典序排列，稳定排序后次数相同的词保持该顺序
+        keywords.sort_by_key(|keyword| std::cmp::Reverse(keyword.count));
+        keywords.truncate(limit);
+        keywords
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算阅读时间并统计出现次数最多的 `limit` 个词
+    pub fn estimate_keywords(&mut self, markdown: &str, limit: usize) -> KeywordReadTime {
+        let speed = *self.speed();
+        let mut counter = TermCounter::default();
+        let counters = self.count(markdown, &mut |event, _, counters| {
+            counter.observe(event, counters)
+        });
+        counter.settle(&counters);
+        KeywordReadTime {
+            read_time: ReadTime::from_counters(counters, &speed),
+            keywords: counter.top(limit),
+        }
+    }
+}
+
+/// 估算阅读时间并统计出现次数最多的 `limit` 个词
+///
+/// 使用默认的阅读速度配置。高频词在估算的同一次遍历中统计，内容看板显示关键词时
+/// 不需要再用其他分词库处理一遍，统计规则参见模块说明。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `limit` - 返回的高频词数量上限
+///
+/// # Returns
+///
+/// 返回包含估算结果和高频词的 [`KeywordReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_keywords;
+///
+/// let result = estimate_keywords("# Rust 异步\n\nRust 的异步运行时。The runtime drives futures.", 2);
+/// let terms: Vec<&str> = result.keywords.iter().map(|keyword| keyword.term.as_str()).collect();
+/// assert_eq!(terms, ["rust", "异步"]);
+/// assert_eq!(result.keywords[0].count, 2);
+/// ```
+pub fn estimate_keywords(markdown: &str, limit: usize) -> KeywordReadTime {
+    estimate_keywords_with_speed(markdown, limit, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算阅读时间并统计出现次数最多的 `limit` 个词
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `limit` - 返回的高频词数量上限
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含估算结果和高频词的 [`KeywordReadTime`]。
+pub fn estimate_keywords_with_speed(
+    markdown: &str,
+    limit: usize,
+    speed: &ReadSpeed,
+) -> KeywordReadTime {
+    Estimator::new(*speed).estimate_keywords(markdown, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Visit, estimate};
+
+    fn terms(result: &KeywordReadTime) -> Vec<(&str, usize)> {
+        result
+            .keywords
+            .iter()
+            .map(|keyword| (keyword.term.as_str(), keyword.count))
+            .collect()
+    }
+
+    #[test]
+    fn test_stopwords_sorted() {
+        assert!(ENGLISH_STOPWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_estimate_keywords() {
+        let markdown = "# Parser 设计\n\nThe parser reads tokens. Parsers and the PARSER!\n\n\
+                        解析器的设计：解析器读取记号。\n\n`parser` 2024\n\n\
+                        ![parser 图片](a.png)\n\n```\nparser parser\n```\n";
+        let result = estimate_keywords(markdown, 4);
+        assert_eq!(result.read_time, estimate(markdown));
+        assert_eq!(
+            terms(&result),
+            [("parser", 3), ("析器", 2), ("解析", 2), ("设计", 2)]
+        );
+        assert!(estimate_keywords(markdown, 0).keywords.is_empty());
+        assert_eq!(estimate_keywords(markdown, 100).keywords.len(), 11);
+    }
+
+    #[test]
+    fn test_skipped_text() {
+        let markdown = "quoted quoted\n\nvisible";
+        let mut estimator =
+            Estimator::new(ReadSpeed::default()).visitor(|event: &Event<'_>, _: &Counters| {
+                match event {
+                    Event::Text(text) if text.starts_with("quoted") => Visit::skip(),
+                    _ => Visit::count(),
+                }
+            });
+        let result = estimator.estimate_keywords(markdown, 10);
+        assert_eq!(terms(&result), [("visible", 1)]);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Tag};
+
+/// 常见博客系统使用的摘要分隔标记
+pub const MORE_MARKER: &str = "<!-- more -->";
+
+impl Estimator<'_> {
+    /// 估算导语的阅读时间
+    ///
+    /// 导语为第一个标题或包含 `marker` 的 HTML 之前的内容，两者都不存在时为整篇文档。
+    pub fn estimate_lead(&mut self, markdown: &str, marker: &str) -> ReadTime {
+        let mut lead = None;
+        let total = self.count(markdown, &mut |event, _, counters| {
+            if lead.is_some() {
+                return;
+            }
+            let ends = match event {
+                Event::Start(Tag::Heading { .. }) => true,
+                Event::Html(html) | Event::InlineHtml(html) => {
+                    !marker.is_empty() && html.contains(marker)
+                }
+                _ => false,
+            };
+            if ends {
+                lead = Some(counters.clone());
+            }
+        });
+        ReadTime::from_counters(lead.unwrap_or(total), self.speed())
+    }
+}
+
+/// 估算导语的阅读时间
+///
+/// 使用默认的阅读速度配置。只统计第一个标题或 `marker`（例如 [`MORE_MARKER`]）之前的内容，
+/// 适合在博客首页的摘要旁显示阅读时间。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `marker` - 摘要分隔标记，为空字符串时只按标题截断
+///
+/// # Returns
+///
+/// 返回导语的 [`ReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{MORE_MARKER, estimate_lead};
+///
+/// let markdown = "这是摘要。\n\n<!-- more -->\n\n这是正文的其余部分。\n";
+/// let lead = estimate_lead(markdown, MORE_MARKER);
+/// assert_eq!(lead.word_count, 5);
+///
+/// let lead = estimate_lead("导语\n\n# 第一章\n\n正文", MORE_MARKER);
+/// assert_eq!(lead.word_count, 2);
+/// ```
+pub fn estimate_lead(markdown: &str, marker: &str) -> ReadTime {
+    estimate_lead_with_speed(markdown, marker, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算导语的阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `marker` - 摘要分隔标记，为空字符串时只按标题截断
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回导语的 [`ReadTime`]。
+pub fn estimate_lead_with_speed(markdown: &str, marker: &str, speed: &ReadSpeed) -> ReadTime {
+    Estimator::new(*speed).estimate_lead(markdown, marker)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_estimate_lead() {
+        let markdown = "摘要 <!--cut--> 之后\n\n![图片](a.png)\n\n# 标题\n\n正文";
+        assert_eq!(estimate_lead(markdown, "<!--cut-->").word_count, 2);
+
+        let lead = estimate_lead(markdown, MORE_MARKER);
+        assert_eq!(lead.word_count, 4);
+        assert_eq!(lead.image_count, 1);
+        assert_eq!(estimate_lead(markdown, "").word_count, 4);
+
+        assert_eq!(estimate_lead("全文内容", MORE_MARKER), estimate("全文内容"));
+        assert_eq!(estimate_lead("# 标题", MORE_MARKER).word_count, 0);
+    }
+}
@@ -62,13 +62,203 @@
 //! let read_time = estimate_with_speed(markdown_content, &speed);
 //! println!("自定义配置下的阅读时间: {}秒", read_time.total_seconds);
 //! ```
+
+#[cfg(any(feature = "docx", feature = "epub"))]
+mod archive;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod attributes;
+mod backend;
+mod badge;
+mod batch;
+mod blocks;
+mod breakdown;
+#[cfg(feature = "cache")]
+mod cache;
+mod chapters;
+mod chars;
+mod corpus;
+mod curve;
+mod diff;
+#[cfg(feature = "docx")]
+mod docx;
+#[cfg(feature = "epub")]
+mod epub;
+mod error;
+mod estimator;
+mod exclude;
+#[cfg(feature = "http")]
+mod fetch;
+#[cfg(any(feature = "tera", feature = "minijinja", feature = "askama"))]
+mod filters;
+mod front_matter;
+mod hugo_compat;
+#[cfg(feature = "imagesize")]
+mod image_size;
+mod io;
+#[cfg(feature = "keywords")]
+mod keywords;
+mod lead;
+mod limits;
+mod listen;
+mod manifest;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "node")]
+pub mod node;
+mod npm_compat;
+mod overrides;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod plain;
+#[cfg(feature = "http")]
+mod probe;
+mod progress;
+#[cfg(feature = "python")]
+pub mod python;
+mod readability;
+mod sections;
+#[cfg(feature = "serve")]
+pub mod serve;
+mod sessions;
+mod skim;
+mod slides;
+mod source;
+mod toc;
 mod utils;
-use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+mod verbose;
+#[cfg(feature = "walk")]
+mod walk;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod writeback;
+#[cfg(feature = "tokio")]
+pub use async_io::{estimate_async, estimate_many_async};
+#[cfg(feature = "comrak")]
+pub use backend::Comrak;
+#[cfg(feature = "gemtext")]
+pub use backend::Gemtext;
+#[cfg(feature = "handlebars")]
+pub use backend::Handlebars;
+#[cfg(feature = "html")]
+pub use backend::Html;
+#[cfg(feature = "hugo")]
+pub use backend::Hugo;
+#[cfg(feature = "latex")]
+pub use backend::Latex;
+#[cfg(feature = "liquid")]
+pub use backend::Liquid;
+#[cfg(feature = "mediawiki")]
+pub use backend::MediaWiki;
+#[cfg(feature = "org")]
+pub use backend::Org;
+#[cfg(feature = "pandoc")]
+pub use backend::Pandoc;
+#[cfg(feature = "rst")]
+pub use backend::Rst;
+#[cfg(any(
+    feature = "handlebars",
+    feature = "hugo",
+    feature = "liquid",
+    feature = "zola"
+))]
+pub use backend::TemplateTags;
+#[cfg(feature = "textile")]
+pub use backend::Textile;
+#[cfg(feature = "tree-sitter")]
+pub use backend::TreeSitter;
+#[cfg(feature = "zola")]
+pub use backend::Zola;
+#[cfg(feature = "asciidoc")]
+pub use backend::{AsciiDoc, IncludeResolver};
+pub use backend::{Dialect, MarkdownBackend, PulldownCmark};
+#[cfg(feature = "mdx")]
+pub use backend::{Mdx, MdxComponents};
+#[cfg(feature = "glob")]
+pub use batch::estimate_glob;
+pub use batch::{BatchReadTime, DirOptions, estimate_dir};
+pub use blocks::{BlockReadTime, estimate_blocks, estimate_blocks_with_speed};
+pub use breakdown::{Breakdown, WordCountBreakdown};
+#[cfg(feature = "cache")]
+pub use cache::{Cache, CacheKey, LruCache, estimate_cached};
+pub use chapters::{Chapters, estimate_chapters, estimate_chapters_with_speed};
+pub use corpus::{Corpus, HistogramBucket};
+pub use curve::{CurvePoint, ReadingCurve, estimate_curve, estimate_curve_with_speed};
+pub use diff::{estimate_diff, estimate_diff_with_speed};
+#[cfg(feature = "docx")]
+pub use docx::{estimate_docx, estimate_docx_with_speed};
+#[cfg(feature = "epub")]
+pub use epub::{EpubChapter, EpubReadTime, estimate_epub, estimate_epub_with_speed};
+pub use error::Error;
+pub use estimator::{Counters, Estimator, EventVisitor, Visit};
+pub use exclude::Exclude;
+#[cfg(feature = "regex")]
+pub use exclude::HeadingPatterns;
+#[cfg(feature = "http")]
+pub use fetch::{estimate_url, estimate_url_with_speed};
+#[cfg(feature = "askama")]
+pub use filters::askama_filters;
+#[cfg(feature = "minijinja")]
+pub use filters::register_minijinja;
+#[cfg(feature = "tera")]
+pub use filters::register_tera;
+pub use front_matter::split_front_matter;
+pub use hugo_compat::{estimate_hugo, estimate_hugo_with_speed};
+#[cfg(feature = "imagesize")]
+pub use image_size::ImageSizes;
+pub use io::{estimate_file, estimate_file_with_speed, estimate_reader};
+#[cfg(feature = "keywords")]
+pub use keywords::{Keyword, KeywordReadTime, estimate_keywords, estimate_keywords_with_speed};
+pub use lead::{MORE_MARKER, estimate_lead, estimate_lead_with_speed};
+pub use limits::{LimitExceeded, Limits, try_estimate, try_estimate_with_speed};
+pub use listen::{ListenSpeed, SpeakPolicy, estimate_listen, estimate_listen_with_speed};
+pub use manifest::{Manifest, ManifestEntry, estimate_manifest};
+#[cfg(feature = "mmap")]
+pub use mmap::{estimate_file_mmap, estimate_file_mmap_with_speed};
+pub use npm_compat::{estimate_npm, estimate_npm_with_speed};
+pub use overrides::{Overrides, estimate_with_overrides, estimate_with_overrides_with_speed};
+#[cfg(feature = "rayon")]
+pub use parallel::{estimate_many_parallel, estimate_parallel};
+pub use plain::{estimate_plain_text, estimate_plain_text_with_speed};
+#[cfg(feature = "http")]
+pub use probe::RemoteAssets;
+pub use progress::{Progress, ReadTimeProgress, RemainingTime};
+pub use pulldown_cmark;
+pub use readability::{
+    ReadabilityIndex, ReadabilityReadTime, ReadabilityScore, ReadabilityStats,
+    estimate_readability, estimate_readability_with_speed,
+};
+pub use sections::{SectionReadTime, estimate_sections, estimate_sections_with_speed};
+pub use sessions::{ReadingSession, split_sessions, split_sessions_with_speed};
+pub use skim::{estimate_skim, estimate_skim_with_speed};
+pub use slides::{SlideReadTime, Slides, estimate_slides, estimate_slides_with_speed};
+pub use source::EstimateSource;
+use std::collections::BTreeMap;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+pub use toc::{Toc, TocEntry, estimate_toc, estimate_toc_with_speed};
 use utils::*;
+pub use verbose::{
+    CountedElement, ElementKind, SNAPSHOT_VERSION, VerboseReadTime, estimate_verbose,
+    estimate_verbose_with_speed,
+};
+#[cfg(feature = "walk")]
+pub use walk::{estimate_dir_parallel, estimate_dir_stream};
+pub use writeback::{write_reading_time, write_reading_time_with_key};
+pub use writeback::{write_reading_time_file, write_reading_time_file_with_key};
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 /// 阅读时间估算结果
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
 pub struct ReadTime {
     /// 总阅读时间（秒）
     ///
@@ -87,15 +277,457 @@ pub struct ReadTime {
     /// - 英文：计算空格分隔的单词数
     pub word_count: usize,
 
+    /// 字数的文字构成
+    ///
+    /// 正文中的中日韩字符、其他文字的单词和 emoji 分别计数，与是否为中文模式无关，
+    /// 参见 [`WordCountBreakdown`]。
+    pub word_count_breakdown: WordCountBreakdown,
+
     /// 图片数量
     ///
     /// Markdown 中 `![alt text](image_url)` 格式的图片数量。
     pub image_count: usize,
 
+    /// 图片替代文本的字符数（不含空白）
+    ///
+    /// 仅在 [`ReadSpeed::seconds_per_alt_char`] 大于 0 时统计。
+    pub image_alt_chars: usize,
+
     /// 代码块数量
     ///
     /// Markdown 中 ```code``` 格式的代码块数量。
     pub code_block_count: usize,
+
+    /// 代码块的行数
+    ///
+    /// 仅在 [`ReadSpeed::seconds_per_code_line`] 大于 0 时统计。
+    pub code_line_count: usize,
+
+    /// 表格数量
+    ///
+    /// 仅在 [`ReadSpeed::seconds_per_table`] 大于 0 时统计。
+    pub table_count: usize,
+
+    /// 表格单元格数量，包括表头
+    ///
+    /// 仅在 [`ReadSpeed::seconds_per_table_cell`] 大于 0 时统计。
+    pub table_cell_count: usize,
+
+    /// 链接数量
+    ///
+    /// 仅在 [`ReadSpeed::seconds_per_link`] 大于 0 时统计，启用 [`ReadSpeed::dedupe_links`]
+    /// 时同一地址只计一次。
+    pub link_count: usize,
+
+    /// 额外时间（秒）
+    ///
+    /// 由 [`EventVisitor`] 添加的额外时间，已计入总阅读时间。
+    pub extra_seconds: f64,
+
+    /// 自定义元素数量
+    ///
+    /// 由 [`EventVisitor`] 标记的自定义元素类型及其数量。
+    pub custom_elements: BTreeMap<String, usize>,
+
+    /// 页数
+    ///
+    /// 按 [`ReadSpeed::words_per_page`] 或 [`ReadSpeed::chars_per_page`] 向上取整的电子书页数，
+    /// 两者为 0 时不计算页数。
+    pub pages: u64,
+
+    /// 跳过的 front matter 字节数
+    ///
+    /// 文档开头的 front matter 默认不计入阅读时间，参见 [`Estimator::skip_front_matter`]。
+    pub front_matter_bytes: usize,
+
+    /// 阅读时间的构成
+    ///
+    /// 正文、图片、代码块和各类自定义元素分别贡献的秒数，参见 [`Breakdown`]。
+    pub breakdown: Breakdown,
+}
+
+impl ReadTime {
+    /// 创建用于手动构造结果的构建器
+    ///
+    /// 主要用于测试或模拟数据。未设置格式化字符串时会根据总秒数自动生成。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::ReadTime;
+    ///
+    /// let read_time = ReadTime::builder().total_seconds(150).word_count(500).build();
+    /// assert_eq!(read_time.formatted(), "2分30秒");
+    /// assert_eq!(read_time.word_count(), 500);
+    /// ```
+    pub fn builder() -> ReadTimeBuilder {
+        ReadTimeBuilder::default()
+    }
+
+    pub fn total_seconds(&self) -> u64 {
+        self.total_seconds
+    }
+
+    pub fn formatted(&self) -> &str {
+        &self.formatted
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    pub fn word_count_breakdown(&self) -> &WordCountBreakdown {
+        &self.word_count_breakdown
+    }
+
+    pub fn image_count(&self) -> usize {
+        self.image_count
+    }
+
+    pub fn image_alt_chars(&self) -> usize {
+        self.image_alt_chars
+    }
+
+    pub fn code_block_count(&self) -> usize {
+        self.code_block_count
+    }
+
+    pub fn code_line_count(&self) -> usize {
+        self.code_line_count
+    }
+
+    pub fn table_count(&self) -> usize {
+        self.table_count
+    }
+
+    pub fn table_cell_count(&self) -> usize {
+        self.table_cell_count
+    }
+
+    pub fn link_count(&self) -> usize {
+        self.link_count
+    }
+
+    pub fn extra_seconds(&self) -> f64 {
+        self.extra_seconds
+    }
+
+    pub fn custom_elements(&self) -> &BTreeMap<String, usize> {
+        &self.custom_elements
+    }
+
+    pub fn pages(&self) -> u64 {
+        self.pages
+    }
+
+    pub fn front_matter_bytes(&self) -> usize {
+        self.front_matter_bytes
+    }
+
+    pub fn breakdown(&self) -> &Breakdown {
+        &self.breakdown
+    }
+
+    /// 根据计数结果计算阅读时间
+    pub(crate) fn from_counters(counters: Counters, speed: &ReadSpeed) -> Self {
+        let mut total_seconds = ceil_seconds(counters.seconds(speed));
+        if speed.round_to_minutes {
+            total_seconds = total_seconds.div_ceil(60) * 60;
+        }
+        let breakdown = Breakdown::from_counters(&counters, speed);
+
+        ReadTime {
+            total_seconds,
+            formatted: format_time(total_seconds),
+            word_count: counters.word_count,
+            word_count_breakdown: counters.word_count_breakdown,
+            image_count: counters.image_count,
+            image_alt_chars: counters.image_alt_chars,
+            code_block_count: counters.code_block_count,
+            code_line_count: counters.code_line_count,
+            table_count: counters.table_count,
+            table_cell_count: counters.table_cell_count,
+            link_count: counters.link_count,
+            extra_seconds: counters.extra_seconds,
+            pages: speed.pages(counters.word_count),
+            front_matter_bytes: counters.front_matter_bytes,
+            custom_elements: counters.custom_elements,
+            breakdown,
+        }
+    }
+
+    /// 将结果还原为计数
+    fn counters(&self) -> Counters {
+        Counters {
+            word_count: self.word_count,
+            word_count_breakdown: self.word_count_breakdown,
+            image_count: self.image_count,
+            image_alt_chars: self.image_alt_chars,
+            code_block_count: self.code_block_count,
+            code_line_count: self.code_line_count,
+            table_count: self.table_count,
+            table_cell_count: self.table_cell_count,
+            link_count: self.link_count,
+            extra_seconds: self.extra_seconds,
+            custom_elements: self.custom_elements.clone(),
+            custom_seconds: self.breakdown.elements.clone(),
+            front_matter_bytes: self.front_matter_bytes,
+            ..Counters::default()
+        }
+    }
+
+    /// 使用原始速度配置合并两个阅读时间
+    ///
+    /// 与 `+` 直接累加各自向上取整后的秒数不同，`merge` 先累加单词、图片和代码块数量，
+    /// 再按 `speed` 重新计算总时间，避免合并大量小文档时的取整误差累积。
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - 需要合并的另一个阅读时间
+    /// * `speed` - 估算时使用的阅读速度配置
+    ///
+    /// # Returns
+    ///
+    /// 重新计算后的 [`ReadTime`]。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{estimate_with_speed, ReadSpeed};
+    ///
+    /// let speed = ReadSpeed::default();
+    /// let first = estimate_with_speed("第一篇", &speed);
+    /// let second = estimate_with_speed("第二篇", &speed);
+    /// let merged = first.merge(&second, &speed);
+    /// assert_eq!(merged.total_seconds, 2);
+    /// ```
+    pub fn merge(&self, other: &ReadTime, speed: &ReadSpeed) -> ReadTime {
+        ReadTime::merge_all([self, other], speed)
+    }
+
+    /// 使用原始速度配置合并多个阅读时间
+    ///
+    /// 等价于依次调用 [`ReadTime::merge`]，但只在最后计算一次总时间。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{estimate_with_speed, ReadSpeed, ReadTime};
+    ///
+    /// let speed = ReadSpeed::default();
+    /// let results: Vec<ReadTime> = ["一", "二", "三"]
+    ///     .iter()
+    ///     .map(|doc| estimate_with_speed(doc, &speed))
+    ///     .collect();
+    /// let merged = ReadTime::merge_all(&results, &speed);
+    /// assert_eq!(merged.word_count, 3);
+    /// assert_eq!(merged.total_seconds, 1);
+    /// ```
+    pub fn merge_all<'a, I>(read_times: I, speed: &ReadSpeed) -> ReadTime
+    where
+        I: IntoIterator<Item = &'a ReadTime>,
+    {
+        let mut counters = Counters::default();
+        for read_time in read_times {
+            add_counters(&mut counters, read_time);
+        }
+        ReadTime::from_counters(counters, speed)
+    }
+}
+
+impl Default for ReadTime {
+    fn default() -> Self {
+        Self {
+            total_seconds: 0,
+            formatted: format_time(0),
+            word_count: 0,
+            word_count_breakdown: WordCountBreakdown::default(),
+            image_count: 0,
+            image_alt_chars: 0,
+            code_block_count: 0,
+            code_line_count: 0,
+            table_count: 0,
+            table_cell_count: 0,
+            link_count: 0,
+            extra_seconds: 0.0,
+            custom_elements: BTreeMap::new(),
+            pages: 0,
+            front_matter_bytes: 0,
+            breakdown: Breakdown::default(),
+        }
+    }
+}
+
+/// [`ReadTime`] 构建器
+///
+/// 通过 [`ReadTime::builder`] 创建。
+#[derive(Debug, Clone, Default)]
+pub struct ReadTimeBuilder {
+    read_time: ReadTime,
+    formatted: Option<String>,
+}
+
+impl ReadTimeBuilder {
+    pub fn total_seconds(mut self, seconds: u64) -> Self {
+        self.read_time.total_seconds = seconds;
+        self
+    }
+
+    pub fn formatted(mut self, formatted: impl Into<String>) -> Self {
+        self.formatted = Some(formatted.into());
+        self
+    }
+
+    pub fn word_count(mut self, count: usize) -> Self {
+        self.read_time.word_count = count;
+        self
+    }
+
+    pub fn word_count_breakdown(mut self, words: WordCountBreakdown) -> Self {
+        self.read_time.word_count_breakdown = words;
+        self
+    }
+
+    pub fn image_count(mut self, count: usize) -> Self {
+        self.read_time.image_count = count;
+        self
+    }
+
+    pub fn image_alt_chars(mut self, count: usize) -> Self {
+        self.read_time.image_alt_chars = count;
+        self
+    }
+
+    pub fn code_block_count(mut self, count: usize) -> Self {
+        self.read_time.code_block_count = count;
+        self
+    }
+
+    pub fn code_line_count(mut self, count: usize) -> Self {
+        self.read_time.code_line_count = count;
+        self
+    }
+
+    pub fn table_count(mut self, count: usize) -> Self {
+        self.read_time.table_count = count;
+        self
+    }
+
+    pub fn table_cell_count(mut self, count: usize) -> Self {
+        self.read_time.table_cell_count = count;
+        self
+    }
+
+    pub fn link_count(mut self, count: usize) -> Self {
+        self.read_time.link_count = count;
+        self
+    }
+
+    pub fn extra_seconds(mut self, seconds: f64) -> Self {
+        self.read_time.extra_seconds = seconds;
+        self
+    }
+
+    pub fn custom_element(mut self, kind: impl Into<String>, count: usize) -> Self {
+        self.read_time.custom_elements.insert(kind.into(), count);
+        self
+    }
+
+    pub fn pages(mut self, pages: u64) -> Self {
+        self.read_time.pages = pages;
+        self
+    }
+
+    pub fn breakdown(mut self, breakdown: Breakdown) -> Self {
+        self.read_time.breakdown = breakdown;
+        self
+    }
+
+    pub fn build(self) -> ReadTime {
+        let mut read_time = self.read_time;
+        read_time.formatted = self
+            .formatted
+            .unwrap_or_else(|| format_time(read_time.total_seconds));
+        read_time
+    }
+}
+
+/// 累加结果中的计数
+fn add_counters(counters: &mut Counters, read_time: &ReadTime) {
+    counters.word_count += read_time.word_count;
+    counters.word_count_breakdown += read_time.word_count_breakdown;
+    counters.image_count += read_time.image_count;
+    counters.image_alt_chars += read_time.image_alt_chars;
+    counters.code_block_count += read_time.code_block_count;
+    counters.code_line_count += read_time.code_line_count;
+    counters.table_count += read_time.table_count;
+    counters.table_cell_count += read_time.table_cell_count;
+    counters.link_count += read_time.link_count;
+    counters.extra_seconds += read_time.extra_seconds;
+    counters.front_matter_bytes += read_time.front_matter_bytes;
+    for (kind, count) in &read_time.custom_elements {
+        *counters.custom_elements.entry(kind.clone()).or_insert(0) += count;
+    }
+    for (kind, seconds) in &read_time.breakdown.elements {
+        *counters.custom_seconds.entry(kind.clone()).or_insert(0.0) += seconds;
+    }
+}
+
+/// 合并两个文档的阅读时间
+///
+/// 各项计数与总秒数直接相加，并根据新的总秒数重新生成格式化字符串。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate, ReadTime};
+///
+/// let docs = ["# 第一篇\n\n内容", "# 第二篇\n\n更多内容"];
+/// let total: ReadTime = docs.iter().map(|doc| estimate(doc)).sum();
+/// println!("合计阅读时间: {}", total.formatted);
+/// ```
+impl Add for ReadTime {
+    type Output = ReadTime;
+
+    fn add(mut self, rhs: ReadTime) -> ReadTime {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for ReadTime {
+    fn add_assign(&mut self, rhs: ReadTime) {
+        let mut counters = self.counters();
+        add_counters(&mut counters, &rhs);
+        self.total_seconds += rhs.total_seconds;
+        self.formatted = format_time(self.total_seconds);
+        self.pages += rhs.pages;
+        self.word_count = counters.word_count;
+        self.word_count_breakdown = counters.word_count_breakdown;
+        self.image_count = counters.image_count;
+        self.image_alt_chars = counters.image_alt_chars;
+        self.code_block_count = counters.code_block_count;
+        self.code_line_count = counters.code_line_count;
+        self.table_count = counters.table_count;
+        self.table_cell_count = counters.table_cell_count;
+        self.link_count = counters.link_count;
+        self.extra_seconds = counters.extra_seconds;
+        self.front_matter_bytes = counters.front_matter_bytes;
+        self.custom_elements = counters.custom_elements;
+        self.breakdown += rhs.breakdown;
+    }
+}
+
+impl Sum for ReadTime {
+    fn sum<I: Iterator<Item = ReadTime>>(iter: I) -> ReadTime {
+        iter.fold(ReadTime::default(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ReadTime> for ReadTime {
+    fn sum<I: Iterator<Item = &'a ReadTime>>(iter: I) -> ReadTime {
+        iter.cloned().sum()
+    }
 }
 
 /// 阅读速度配置
@@ -117,7 +749,13 @@ pub struct ReadTime {
 /// // 或者直接创建
 /// let speed = ReadSpeed::new(180.0, 15.0, 25.0, false, true);
 /// ```
+///
+/// 启用 `serde` 特性后可以序列化保存。反序列化时缺失的字段会使用默认值，
+/// 因此旧版本保存的配置在新增字段后仍然可以正常加载。
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
 pub struct ReadSpeed {
     /// 每分钟阅读单词数（默认：200）
     ///
@@ -129,11 +767,51 @@ pub struct ReadSpeed {
     /// 每发现一张图片就会增加相应的时间，因为读者通常需要额外时间查看图片。
     pub seconds_per_image: f64,
 
+    /// 每张图片额外时间的递减量（秒，默认：0）
+    ///
+    /// 读者看过几张图片后会越看越快：第 n 张图片（从 0 开始）的额外时间为
+    /// `seconds_per_image - n * image_decay`，不低于 [`min_seconds_per_image`](Self::min_seconds_per_image)。
+    /// 为 0 时每张图片的时间相同。逐块和逐项明细按图片在整篇文档中的序号计算，
+    /// 每个章节的结果则各自从第一张图片开始。
+    pub image_decay: f64,
+
+    /// 图片额外时间递减的下限（秒，默认：0）
+    pub min_seconds_per_image: f64,
+
+    /// 图片替代文本每个字符的额外时间（秒，默认：0）
+    ///
+    /// 与 [`seconds_per_image`](Self::seconds_per_image) 相加，描述详细的图表比装饰图片需要更长时间。
+    /// 空白不计入字符数。
+    pub seconds_per_alt_char: f64,
+
     /// 每个代码块额外时间（秒，默认：20）
     ///
     /// 每发现一个代码块就会增加相应的时间，因为代码通常需要更仔细的阅读。
     pub seconds_per_code_block: f64,
 
+    /// 代码块每行的额外时间（秒，默认：0）
+    ///
+    /// 与 [`seconds_per_code_block`](Self::seconds_per_code_block) 相加，较长的代码块需要更长时间。
+    pub seconds_per_code_line: f64,
+
+    /// 每个表格额外时间（秒，默认：0）
+    pub seconds_per_table: f64,
+
+    /// 表格每个单元格的额外时间（秒，默认：0）
+    ///
+    /// 与 [`seconds_per_table`](Self::seconds_per_table) 相加，表头单元格同样计入。
+    pub seconds_per_table_cell: f64,
+
+    /// 每个链接额外时间（秒，默认：0）
+    ///
+    /// 读者可能会打开链接查看引用的内容。为 0 时不统计链接。
+    pub seconds_per_link: f64,
+
+    /// 同一地址的链接是否只计一次额外时间（默认：false）
+    ///
+    /// 反复引用同一来源的文档中，重复的链接不再增加时间。按链接地址原样比较。
+    pub dedupe_links: bool,
+
     /// 是否考虑emoji（默认：true）
     ///
     /// 当启用时，emoji 会被单独计数，影响总的阅读时间估算。
@@ -145,16 +823,49 @@ pub struct ReadSpeed {
     /// - `true`: 使用中文计数方式（计算字符数）
     /// - `false`: 使用英文计数方式（计算单词数）
     pub chinese: bool,
+
+    /// 略读时次要内容的时间系数（默认：0.25）
+    ///
+    /// 仅用于 [`estimate_skim`]：标题、段落首句、加粗文本和图注按正常速度计算，
+    /// 其余文本以及图片、代码块的时间乘以该系数。
+    pub skim_rate: f64,
+
+    /// 每页单词数（默认：250）
+    ///
+    /// 英文计数方式下用于计算 [`ReadTime::pages`]，为 0 时不计算页数。
+    pub words_per_page: f64,
+
+    /// 每页字数（默认：500）
+    ///
+    /// 中文计数方式下用于计算 [`ReadTime::pages`]，为 0 时不计算页数。
+    pub chars_per_page: f64,
+
+    /// 是否把总时间向上取整到整分钟（默认：false）
+    ///
+    /// 只影响 [`ReadTime::total_seconds`] 和 `formatted`，[`ReadTime::breakdown`] 仍为取整前的时间。
+    pub round_to_minutes: bool,
 }
 
 impl Default for ReadSpeed {
     fn default() -> Self {
         Self {
-            words_per_minute: 300.0,
-            seconds_per_image: 30.0,
+            words_per_minute: 200.0,
+            seconds_per_image: 12.0,
+            image_decay: 0.0,
+            min_seconds_per_image: 0.0,
+            seconds_per_alt_char: 0.0,
             seconds_per_code_block: 20.0,
+            seconds_per_code_line: 0.0,
+            seconds_per_table: 0.0,
+            seconds_per_table_cell: 0.0,
+            seconds_per_link: 0.0,
+            dedupe_links: false,
             count_emoji: true,
             chinese: true,
+            skim_rate: 0.25,
+            words_per_page: 250.0,
+            chars_per_page: 500.0,
+            round_to_minutes: false,
         }
     }
 }
@@ -173,6 +884,87 @@ impl ReadSpeed {
             seconds_per_code_block,
             count_emoji,
             chinese,
+            ..Self::default()
+        }
+    }
+
+    /// Hugo 的阅读速度：每分钟 213 个单词，图片和代码块不增加时间，按英文计数
+    ///
+    /// 与 [`estimate_hugo_with_speed`] 一起使用时结果与 Hugo 的 `.ReadingTime` 完全一致，
+    /// 此时只有 [`chinese`](Self::chinese)（对应 Hugo 的 `hasCJKLanguage`）会生效。
+    /// 用于其他估算函数时只是近似：秒数不按整分钟取整，字数也按本库的规则计数。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{ReadSpeed, estimate_hugo_with_speed};
+    ///
+    /// let speed = ReadSpeed::hugo();
+    /// assert_eq!(speed.words_per_minute, 213.0);
+    /// assert_eq!(estimate_hugo_with_speed("Hello world", &speed).formatted, "1分钟");
+    /// ```
+    pub fn hugo() -> Self {
+        Self {
+            words_per_minute: 213.0,
+            seconds_per_image: 0.0,
+            seconds_per_code_block: 0.0,
+            count_emoji: false,
+            chinese: false,
+            ..Self::default()
+        }
+    }
+
+    /// Medium 的阅读速度：每分钟 265 个单词，第一张图片 12 秒，之后每张减少 1 秒直到 3 秒，
+    /// 代码块不增加时间，按英文计数，总时间向上取整到整分钟
+    ///
+    /// 与 Medium 公布的算法一致，`formatted` 即文章页显示的 “N min read” 中的分钟数。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{ReadSpeed, estimate_with_speed};
+    ///
+    /// let markdown = "word ".repeat(1000) + "\n\n![a](a.png) ![b](b.png) ![c](c.png)";
+    /// let read_time = estimate_with_speed(&markdown, &ReadSpeed::medium());
+    /// // 1000 / 265 分钟 ≈ 226.4 秒，加上 12 + 11 + 10 秒的图片时间，向上取整为 5 分钟
+    /// assert_eq!(read_time.formatted, "5分钟");
+    /// ```
+    pub fn medium() -> Self {
+        Self {
+            words_per_minute: 265.0,
+            seconds_per_image: 12.0,
+            image_decay: 1.0,
+            min_seconds_per_image: 3.0,
+            seconds_per_code_block: 0.0,
+            count_emoji: false,
+            chinese: false,
+            round_to_minutes: true,
+            ..Self::default()
+        }
+    }
+
+    /// npm 包 `reading-time` 的阅读速度：每分钟 200 个单词，图片和代码块不增加时间，按英文计数
+    ///
+    /// 与 [`estimate_npm_with_speed`] 一起使用时结果与该包的 `readingTime` 完全一致，
+    /// 此时只有 [`words_per_minute`](Self::words_per_minute)（对应 `wordsPerMinute`）会生效。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::{ReadSpeed, estimate_npm_with_speed};
+    ///
+    /// let speed = ReadSpeed::npm();
+    /// assert_eq!(speed.words_per_minute, 200.0);
+    /// assert_eq!(estimate_npm_with_speed("Hello world", &speed).formatted, "1分钟");
+    /// ```
+    pub fn npm() -> Self {
+        Self {
+            words_per_minute: 200.0,
+            seconds_per_image: 0.0,
+            seconds_per_code_block: 0.0,
+            count_emoji: false,
+            chinese: false,
+            ..Self::default()
         }
     }
 
@@ -186,11 +978,49 @@ impl ReadSpeed {
         self
     }
 
+    /// 设置每张图片额外时间的递减量和下限，见 [`image_decay`](Self::image_decay)
+    pub fn decay_images(mut self, decay: f64, min_seconds: f64) -> Self {
+        self.image_decay = decay;
+        self.min_seconds_per_image = min_seconds;
+        self
+    }
+
+    /// 设置图片替代文本每个字符的额外时间
+    pub fn alt_text_time(mut self, seconds_per_char: f64) -> Self {
+        self.seconds_per_alt_char = seconds_per_char;
+        self
+    }
+
     pub fn code_block_time(mut self, seconds: f64) -> Self {
         self.seconds_per_code_block = seconds;
         self
     }
 
+    /// 设置代码块每行的额外时间
+    pub fn code_line_time(mut self, seconds: f64) -> Self {
+        self.seconds_per_code_line = seconds;
+        self
+    }
+
+    /// 设置每个表格的额外时间和每个单元格的额外时间
+    pub fn table_time(mut self, seconds: f64, seconds_per_cell: f64) -> Self {
+        self.seconds_per_table = seconds;
+        self.seconds_per_table_cell = seconds_per_cell;
+        self
+    }
+
+    /// 设置每个链接的额外时间
+    pub fn link_time(mut self, seconds: f64) -> Self {
+        self.seconds_per_link = seconds;
+        self
+    }
+
+    /// 设置同一地址的链接是否只计一次，见 [`dedupe_links`](Self::dedupe_links)
+    pub fn dedupe_links(mut self, dedupe: bool) -> Self {
+        self.dedupe_links = dedupe;
+        self
+    }
+
     pub fn emoji(mut self, count: bool) -> Self {
         self.count_emoji = count;
         self
@@ -200,6 +1030,120 @@ impl ReadSpeed {
         self.chinese = is_chinese;
         self
     }
+
+    pub fn skim(mut self, rate: f64) -> Self {
+        self.skim_rate = rate;
+        self
+    }
+
+    pub fn round_minutes(mut self, round: bool) -> Self {
+        self.round_to_minutes = round;
+        self
+    }
+
+    /// 设置每页单词数和每页字数
+    pub fn page_size(mut self, words_per_page: f64, chars_per_page: f64) -> Self {
+        self.words_per_page = words_per_page;
+        self.chars_per_page = chars_per_page;
+        self
+    }
+
+    pub fn words_per_minute(&self) -> f64 {
+        self.words_per_minute
+    }
+
+    pub fn seconds_per_image(&self) -> f64 {
+        self.seconds_per_image
+    }
+
+    pub fn image_decay(&self) -> f64 {
+        self.image_decay
+    }
+
+    pub fn min_seconds_per_image(&self) -> f64 {
+        self.min_seconds_per_image
+    }
+
+    pub fn seconds_per_alt_char(&self) -> f64 {
+        self.seconds_per_alt_char
+    }
+
+    pub fn seconds_per_code_block(&self) -> f64 {
+        self.seconds_per_code_block
+    }
+
+    pub fn seconds_per_code_line(&self) -> f64 {
+        self.seconds_per_code_line
+    }
+
+    pub fn seconds_per_table(&self) -> f64 {
+        self.seconds_per_table
+    }
+
+    pub fn seconds_per_table_cell(&self) -> f64 {
+        self.seconds_per_table_cell
+    }
+
+    pub fn seconds_per_link(&self) -> f64 {
+        self.seconds_per_link
+    }
+
+    pub fn dedupes_links(&self) -> bool {
+        self.dedupe_links
+    }
+
+    pub fn counts_emoji(&self) -> bool {
+        self.count_emoji
+    }
+
+    pub fn is_chinese(&self) -> bool {
+        self.chinese
+    }
+
+    pub fn skim_rate(&self) -> f64 {
+        self.skim_rate
+    }
+
+    pub fn words_per_page(&self) -> f64 {
+        self.words_per_page
+    }
+
+    pub fn chars_per_page(&self) -> f64 {
+        self.chars_per_page
+    }
+
+    pub fn rounds_to_minutes(&self) -> bool {
+        self.round_to_minutes
+    }
+
+    /// 前 `count` 张图片的额外时间之和，按 [`image_decay`](Self::image_decay) 递减
+    pub(crate) fn image_seconds(&self, count: usize) -> f64 {
+        if self.image_decay <= 0.0 {
+            return count as f64 * self.seconds_per_image;
+        }
+        let min = self.min_seconds_per_image.min(self.seconds_per_image);
+        // 递减到下限之后每张图片的时间相同
+        let decaying = ((self.seconds_per_image - min) / self.image_decay).ceil() as usize;
+        let decaying = decaying.min(count);
+        let decayed: f64 = (0..decaying)
+            .map(|n| self.seconds_per_image - n as f64 * self.image_decay)
+            .sum();
+        decayed + (count - decaying) as f64 * min
+    }
+
+    /// 按当前计数方式计算 `word_count` 对应的页数
+    pub(crate) fn pages(&self, word_count: usize) -> u64 {
+        let per_page = if self.chinese {
+            self.chars_per_page
+        } else {
+            self.words_per_page
+        };
+        if per_page > 0.0 {
+            ceil_seconds(word_count as f64 / per_page)
+        } else {
+            0
+        }
+    }
 }
 
 /// 估算Markdown的阅读时间
@@ -251,74 +1195,50 @@ pub fn estimate(markdown: &str) -> ReadTime {
 /// println!("阅读需要 {} 时间", read_time.formatted);
 /// ```
 pub fn estimate_with_speed(markdown: &str, speed: &ReadSpeed) -> ReadTime {
-    let parser = Parser::new(markdown);
-
-    let mut word_count = 0;
-    let mut image_count = 0;
-    let mut code_block_count = 0;
-    let mut in_code_block = false;
-    let mut in_image_alt = false;
-
-    for event in parser {
-        match event {
-            Event::Start(tag) => match tag {
-                Tag::Image { .. } => {
-                    image_count += 1;
-                    in_image_alt = true;
-                }
-                Tag::CodeBlock(_) => {
-                    code_block_count += 1;
-                    in_code_block = true;
-                }
-                _ => {}
-            },
-            Event::End(tag) => match tag {
-                TagEnd::Image { .. } => {
-                    in_image_alt = false;
-                }
-                TagEnd::CodeBlock => {
-                    in_code_block = false;
-                }
-                _ => {}
-            },
-            Event::Text(text) => {
-                if !in_image_alt && !in_code_block {
-                    if speed.chinese {
-                        word_count += count_words(&text, speed.count_emoji);
-                    } else {
-                        word_count += count_english_words(&text, speed.count_emoji);
-                    }
-                }
-            }
-            Event::Code(code) => {
-                if !in_code_block {
-                    if speed.chinese {
-                        word_count += count_words(&code, speed.count_emoji);
-                    } else {
-                        word_count += count_english_words(&code, speed.count_emoji);
-                    }
-                }
-            }
-            _ => {}
-        }
-    }
-
-    // 计算基础阅读时间（基于单词数）
-    let base_seconds = (word_count as f64 / speed.words_per_minute) * 60.0;
-
-    // 添加图片和代码块的额外时间
-    let image_seconds = image_count as f64 * speed.seconds_per_image;
-    let code_seconds = code_block_count as f64 * speed.seconds_per_code_block;
+    Estimator::new(*speed).estimate(markdown)
+}
 
-    let total_seconds = (base_seconds + image_seconds + code_seconds).ceil() as u64;
+/// 估算字节内容的阅读时间
+///
+/// 使用默认的阅读速度配置。内容按 UTF-8 解码，无效的字节序列会被忽略，不计入字数。
+///
+/// # Arguments
+///
+/// * `bytes` - 需要估算阅读时间的 Markdown 字节内容
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate, estimate_bytes};
+///
+/// let bytes = b"# Title\n\ncontent \xFF\xFE";
+/// let read_time = estimate_bytes(bytes);
+/// assert_eq!(read_time, estimate("# Title\n\ncontent "));
+/// ```
+pub fn estimate_bytes(bytes: &[u8]) -> ReadTime {
+    estimate_bytes_with_speed(bytes, &ReadSpeed::default())
+}
 
-    ReadTime {
-        total_seconds,
-        formatted: format_time(total_seconds),
-        word_count,
-        image_count,
-        code_block_count,
-    }
+/// 使用自定义速度配置估算字节内容的阅读时间
+///
+/// 内容按 UTF-8 解码并去除 BOM，带有 UTF-16 BOM 的内容会按 UTF-16 解码。
+/// 无效的字节序列会被忽略，不计入字数。
+/// 内容为合法 UTF-8 时不会产生额外的复制。
+///
+/// # Arguments
+///
+/// * `bytes` - 需要估算阅读时间的 Markdown 字节内容
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+pub fn estimate_bytes_with_speed(bytes: &[u8], speed: &ReadSpeed) -> ReadTime {
+    estimate_with_speed(&decode_text(bytes), speed)
 }
 
 /// 快捷函数：获取分钟数
@@ -344,7 +1264,7 @@ pub fn estimate_with_speed(markdown: &str, speed: &ReadSpeed) -> ReadTime {
 /// ```
 pub fn minutes(markdown: &str) -> u64 {
     let read_time = estimate(markdown);
-    (read_time.total_seconds as f64 / 60.0).ceil() as u64
+    read_time.total_seconds.div_ceil(60)
 }
 
 /// 快捷函数：获取单词数
@@ -453,6 +1373,46 @@ This is a test paragraph. It contains some words.
         assert_eq!(read_time.formatted, "3秒");
     }
 
+    #[test]
+    fn test_medium() {
+        let speed = ReadSpeed::medium();
+        let images = |count: usize| "![图片](a.png)\n\n".repeat(count);
+        let medium = |words: usize, count: usize| {
+            estimate_with_speed(&("word ".repeat(words) + "\n\n" + &images(count)), &speed)
+        };
+
+        // 第一张图片 12 秒，之后每张减少 1 秒，第 10 张起为 3 秒
+        assert_eq!(speed.image_seconds(0), 0.0);
+        assert_eq!(speed.image_seconds(3), 12.0 + 11.0 + 10.0);
+        assert_eq!(speed.image_seconds(10), 75.0);
+        assert_eq!(speed.image_seconds(12), 81.0);
+        assert_eq!(
+            speed.decay_images(2.0, 3.0).image_seconds(7),
+            12.0 + 10.0 + 8.0 + 6.0 + 4.0 + 6.0
+        );
+        assert_eq!(ReadSpeed::default().image_seconds(3), 36.0);
+
+        // 向上取整到整分钟
+        assert_eq!(medium(265, 0).total_seconds, 60);
+        assert_eq!(medium(266, 0).total_seconds, 120);
+        assert_eq!(medium(1000, 3).total_seconds, 300);
+        assert_eq!(medium(0, 12).formatted, "2分钟");
+        assert_eq!(medium(2000, 10).formatted, "9分钟");
+        assert_eq!(estimate_with_speed("", &speed).total_seconds, 0);
+
+        let read_time = medium(530, 2);
+        assert_eq!(read_time.total_seconds, 180);
+        assert_eq!(read_time.breakdown.images, 23.0);
+        assert_eq!(read_time.breakdown.total(), 143.0);
+        // 代码块不增加时间
+        assert_eq!(
+            estimate_with_speed("```\nfn main() {}\n```", &speed)
+                .breakdown
+                .code,
+            0.0
+        );
+    }
+
     #[test]
     fn test_formatted() {
         let md_txt = r#"
@@ -466,4 +1426,109 @@ This is a test paragraph. It contains some words.
         let formatted_time = formatted(md_txt);
         assert_eq!(formatted_time, "6秒");
     }
+
+    #[test]
+    fn test_sum() {
+        let first = estimate("# 标题\n\n这是内容");
+        let second = estimate("![图片](a.png)\n\n```rust\nfn main() {}\n```");
+
+        let mut total = first.clone() + second.clone();
+        assert_eq!(
+            total.total_seconds,
+            first.total_seconds + second.total_seconds
+        );
+        assert_eq!(total.word_count, first.word_count + second.word_count);
+        assert_eq!(total.image_count, 1);
+        assert_eq!(total.code_block_count, 1);
+        assert_eq!(total.formatted, format_time(total.total_seconds));
+
+        total += first.clone();
+        let summed: ReadTime = [first.clone(), second, first].iter().sum();
+        assert_eq!(summed, total);
+
+        let empty: ReadTime = std::iter::empty::<ReadTime>().sum();
+        assert_eq!(empty.total_seconds, 0);
+        assert_eq!(empty.formatted, "0秒");
+    }
+
+    #[test]
+    fn test_merge() {
+        let speed = ReadSpeed::default();
+        let docs: Vec<ReadTime> = (0..10)
+            .map(|_| estimate_with_speed("短文", &speed))
+            .collect();
+
+        // 逐个相加会累积取整误差
+        let summed: ReadTime = docs.iter().sum();
+        assert_eq!(summed.total_seconds, 10);
+
+        let merged = ReadTime::merge_all(&docs, &speed);
+        assert_eq!(merged.word_count, 20);
+        assert_eq!(merged.total_seconds, 6);
+        assert_eq!(merged.formatted, "6秒");
+
+        let pair = docs[0].merge(&docs[1], &speed);
+        assert_eq!(pair.word_count, 4);
+        assert_eq!(pair.total_seconds, 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_defaults() {
+        let speed: ReadSpeed = serde_json::from_str(r#"{"words_per_minute": 150.0}"#).unwrap();
+        assert_eq!(speed.words_per_minute, 150.0);
+        assert_eq!(speed.seconds_per_image, 12.0);
+        assert!(speed.chinese);
+
+        let read_time: ReadTime = serde_json::from_str(r#"{"total_seconds": 90}"#).unwrap();
+        assert_eq!(read_time.total_seconds, 90);
+        assert_eq!(read_time.word_count, 0);
+
+        let json = serde_json::to_string(&estimate("# 标题")).unwrap();
+        let restored: ReadTime = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, estimate("# 标题"));
+    }
+
+    #[test]
+    fn test_builder() {
+        let read_time = ReadTime::builder()
+            .total_seconds(60)
+            .word_count(200)
+            .image_count(1)
+            .custom_element("video", 2)
+            .build();
+        assert_eq!(read_time.formatted(), "1分钟");
+        assert_eq!(read_time.word_count(), 200);
+        assert_eq!(read_time.image_count(), 1);
+        assert_eq!(read_time.custom_elements()["video"], 2);
+
+        let read_time = ReadTime::builder().total_seconds(5).formatted("5s").build();
+        assert_eq!(read_time.formatted(), "5s");
+    }
+
+    #[test]
+    fn test_pages() {
+        let chinese = "字".repeat(1001);
+        assert_eq!(estimate(&chinese).pages, 3);
+        assert_eq!(estimate("").pages, 0);
+
+        let english = "word ".repeat(250);
+        let speed = ReadSpeed::default().chinese(false);
+        assert_eq!(estimate_with_speed(&english, &speed).pages, 1);
+        let speed = speed.page_size(100.0, 0.0);
+        let read_time = estimate_with_speed(&english, &speed);
+        assert_eq!(read_time.pages, 3);
+        assert_eq!(read_time.merge(&read_time, &speed).pages, 5);
+        assert_eq!(estimate_with_speed(&chinese, &speed.chinese(true)).pages, 0);
+    }
+
+    #[test]
+    fn test_estimate_bytes() {
+        let read_time = estimate_bytes("你好\u{FFFD}".as_bytes());
+        assert_eq!(read_time.word_count, 3);
+
+        let read_time = estimate_bytes(b"\xE4\xBD\xA0\xFF\xE5\xA5\xBD");
+        assert_eq!(read_time.word_count, 2);
+        assert_eq!(estimate_bytes(b"\xFF\xFE\xFD").word_count, 0);
+    }
 }
@@ -0,0 +1,219 @@
+//! 资源限制
+//!
+//! 在服务端估算用户上传的内容时，超大或嵌套极深的输入会占用大量内存和 CPU。
+//! [`Limits`] 限制输入大小、嵌套深度和事件数量，超出时 [`try_estimate`] 停止解析并返回
+//! [`Error::Limit`]。输入大小在解析之前检查；嵌套深度和事件数量在解析过程中检查，
+//! 默认的 pulldown-cmark 后端在超出时立即停止，不会解析剩余的输入。
+use crate::{Error, Estimator, ReadSpeed, ReadTime};
+use std::fmt;
+
+/// 估算时的资源限制
+///
+/// 默认值适合处理不受信任的输入，每一项都可以设为 `None` 取消限制。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::Limits;
+///
+/// let limits = Limits::default()
+///     .max_input_bytes(Some(1024 * 1024))
+///     .max_depth(Some(32))
+///     .max_events(None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Limits {
+    /// 输入的最大字节数（默认：16 MiB）
+    pub max_input_bytes: Option<usize>,
+
+    /// 元素的最大嵌套深度，例如嵌套的引用块和列表（默认：128）
+    pub max_depth: Option<usize>,
+
+    /// 解析出的最大事件数量（默认：1,000,000）
+    pub max_events: Option<usize>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_input_bytes: Some(16 * 1024 * 1024),
+            max_depth: Some(128),
+            max_events: Some(1_000_000),
+        }
+    }
+}
+
+impl Limits {
+    /// 不做任何限制，[`Estimator::estimate`] 使用该配置
+    pub const NONE: Limits = Limits {
+        max_input_bytes: None,
+        max_depth: None,
+        max_events: None,
+    };
+
+    pub fn max_input_bytes(mut self, max: Option<usize>) -> Self {
+        self.max_input_bytes = max;
+        self
+    }
+
+    pub fn max_depth(mut self, max: Option<usize>) -> Self {
+        self.max_depth = max;
+        self
+    }
+
+    pub fn max_events(mut self, max: Option<usize>) -> Self {
+        self.max_events = max;
+        self
+    }
+}
+
+/// 超出的资源限制，包含对应的上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LimitExceeded {
+    /// 输入超过 [`Limits::max_input_bytes`]
+    InputBytes(usize),
+
+    /// 嵌套超过 [`Limits::max_depth`]
+    Depth(usize),
+
+    /// 事件数量超过 [`Limits::max_events`]
+    Events(usize),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::InputBytes(max) => write!(f, "输入超过 {} 字节", max),
+            LimitExceeded::Depth(max) => write!(f, "嵌套深度超过 {}", max),
+            LimitExceeded::Events(max) => write!(f, "事件数量超过 {}", max),
+        }
+    }
+}
+
+/// 在资源限制内估算阅读时间，使用默认的阅读速度配置
+///
+/// # Errors
+///
+/// 输入超出 `limits` 中的任意一项时返回 [`Error::Limit`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{Error, LimitExceeded, Limits, try_estimate};
+///
+/// let read_time = try_estimate("# 标题\n\n内容", &Limits::default()).unwrap();
+/// assert_eq!(read_time.word_count, 4);
+///
+/// let nested = ">".repeat(100) + " 内容";
+/// let limits = Limits::default().max_depth(Some(10));
+/// assert!(matches!(
+///     try_estimate(&nested, &limits),
+///     Err(Error::Limit(LimitExceeded::Depth(10)))
+/// ));
+/// ```
+pub fn try_estimate(markdown: &str, limits: &Limits) -> Result<ReadTime, Error> {
+    try_estimate_with_speed(markdown, &ReadSpeed::default(), limits)
+}
+
+/// 使用自定义速度配置在资源限制内估算阅读时间
+///
+/// # Errors
+///
+/// 输入超出 `limits` 中的任意一项时返回 [`Error::Limit`]。
+pub fn try_estimate_with_speed(
+    markdown: &str,
+    speed: &ReadSpeed,
+    limits: &Limits,
+) -> Result<ReadTime, Error> {
+    Estimator::new(*speed)
+        .limits(*limits)
+        .try_estimate(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MarkdownBackend, PulldownCmark, estimate};
+    use pulldown_cmark::Event;
+    use std::cell::Cell;
+    use std::ops::{ControlFlow, Range};
+
+    #[test]
+    fn test_try_estimate() {
+        let markdown = "# 标题\n\n- 列表\n  - 嵌套\n\n> 引用";
+        assert_eq!(
+            try_estimate(markdown, &Limits::default()).unwrap(),
+            estimate(markdown)
+        );
+        assert_eq!(
+            try_estimate(markdown, &Limits::NONE).unwrap(),
+            estimate(markdown)
+        );
+
+        let limits = Limits::NONE.max_input_bytes(Some(8));
+        assert!(matches!(
+            try_estimate(markdown, &limits),
+            Err(Error::Limit(LimitExceeded::InputBytes(8)))
+        ));
+
+        let limits = Limits::NONE.max_events(Some(5));
+        assert!(matches!(
+            try_estimate(markdown, &limits),
+            Err(Error::Limit(LimitExceeded::Events(5)))
+        ));
+
+        // 文档中最深处为 列表 > 列表项 > 列表 > 列表项
+        let limits = Limits::NONE.max_depth(Some(4));
+        assert!(try_estimate(markdown, &limits).is_ok());
+        let limits = Limits::NONE.max_depth(Some(3));
+        assert!(matches!(
+            try_estimate(markdown, &limits),
+            Err(Error::Limit(LimitExceeded::Depth(3)))
+        ));
+    }
+
+    /// 记录 pulldown-cmark 传出了多少个事件的后端
+    struct Counting<'c>(&'c Cell<usize>);
+
+    impl MarkdownBackend for Counting<'_> {
+        fn parse<'a>(&self, markdown: &'a str, sink: &mut dyn FnMut(Event<'a>)) {
+            PulldownCmark::default().parse(markdown, sink);
+        }
+
+        fn parse_with_offsets<'a>(
+            &self,
+            markdown: &'a str,
+            sink: &mut dyn FnMut(Event<'a>, Range<usize>) -> ControlFlow<()>,
+        ) {
+            PulldownCmark::default().parse_with_offsets(markdown, &mut |event, range| {
+                self.0.set(self.0.get() + 1);
+                sink(event, range)
+            });
+        }
+    }
+
+    #[test]
+    fn test_try_estimate_stops_parsing() {
+        let markdown = "段落\n\n".repeat(1000);
+        let events = Cell::new(0);
+        let mut estimator = Estimator::new(ReadSpeed::default())
+            .backend(Counting(&events))
+            .limits(Limits::NONE.max_events(Some(5)));
+        assert!(matches!(
+            estimator.try_estimate(&markdown),
+            Err(Error::Limit(LimitExceeded::Events(5)))
+        ));
+        // 第 6 个事件超出限制，之后后端不再传出任何事件
+        assert_eq!(events.get(), 6);
+    }
+
+    #[test]
+    fn test_try_estimate_nested() {
+        let nested = String::from("- ").repeat(10_000) + "内容";
+        let err = try_estimate(&nested, &Limits::default()).unwrap_err();
+        assert!(matches!(err, Error::Limit(LimitExceeded::Depth(128))));
+        assert_eq!(err.to_string(), "超出资源限制: 嵌套深度超过 128");
+    }
+}
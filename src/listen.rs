@@ -0,0 +1,301 @@
+use crate::estimator::Counters;
+use crate::utils::{ceil_seconds, count_english_words, count_words, format_time};
+use crate::{Breakdown, Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::Event;
+
+/// 朗读时对代码块和链接地址的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum SpeakPolicy {
+    /// 跳过，不朗读
+    Skip,
+
+    /// 用一句简短的提示代替，例如“此处省略代码”
+    #[default]
+    Summarize,
+
+    /// 逐字朗读
+    SpellOut,
+}
+
+/// 语音合成的朗读速度配置
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ListenSpeed, SpeakPolicy};
+///
+/// let listen = ListenSpeed::default()
+///     .cpm(280.0)                          // 中文每分钟朗读280个字
+///     .code_blocks(SpeakPolicy::Skip)      // 不朗读代码块
+///     .urls(SpeakPolicy::SpellOut);        // 逐字朗读链接地址
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ListenSpeed {
+    /// 英文每分钟朗读单词数（默认：160）
+    pub words_per_minute: f64,
+
+    /// 中文每分钟朗读字数（默认：250）
+    pub chars_per_minute: f64,
+
+    /// 代码块的处理方式（默认：[`SpeakPolicy::Summarize`]）
+    pub code_blocks: SpeakPolicy,
+
+    /// 正文中链接地址的处理方式（默认：[`SpeakPolicy::Summarize`]）
+    pub urls: SpeakPolicy,
+
+    /// 每条简短提示的朗读时间（秒，默认：2）
+    pub summary_seconds: f64,
+}
+
+impl Default for ListenSpeed {
+    fn default() -> Self {
+        Self {
+            words_per_minute: 160.0,
+            chars_per_minute: 250.0,
+            code_blocks: SpeakPolicy::Summarize,
+            urls: SpeakPolicy::Summarize,
+            summary_seconds: 2.0,
+        }
+    }
+}
+
+impl ListenSpeed {
+    pub fn wpm(mut self, wpm: f64) -> Self {
+        self.words_per_minute = wpm;
+        self
+    }
+
+    pub fn cpm(mut self, cpm: f64) -> Self {
+        self.chars_per_minute = cpm;
+        self
+    }
+
+    pub fn code_blocks(mut self, policy: SpeakPolicy) -> Self {
+        self.code_blocks = policy;
+        self
+    }
+
+    pub fn urls(mut self, policy: SpeakPolicy) -> Self {
+        self.urls = policy;
+        self
+    }
+
+    pub fn summary_time(mut self, seconds: f64) -> Self {
+        self.summary_seconds = seconds;
+        self
+    }
+
+    /// 按计数方式选择朗读速度
+    fn per_minute(&self, chinese: bool) -> f64 {
+        if chinese {
+            self.chars_per_minute
+        } else {
+            self.words_per_minute
+        }
+    }
+
+    /// 按处理方式计算朗读时间，`units` 为逐字朗读的单位数
+    fn policy_seconds(
+        &self,
+        policy: SpeakPolicy,
+        count: usize,
+        units: usize,
+        chinese: bool,
+    ) -> f64 {
+        match policy {
+            SpeakPolicy::Skip => 0.0,
+            SpeakPolicy::Summarize => count as f64 * self.summary_seconds,
+            SpeakPolicy::SpellOut => units as f64 / self.per_minute(chinese) * 60.0,
+        }
+    }
+}
+
+fn is_url(token: &str) -> bool {
+    token.starts_with("http://") || token.starts_with("https://") || token.starts_with("www.")
+}
+
+/// 统计代码块和链接地址
+#[derive(Default)]
+struct ListenCollector {
+    /// 上一个事件中的链接地址：单词数、个数和字符数
+    pending: (usize, usize, usize),
+    words: usize,
+    url_words: usize,
+    url_count: usize,
+    url_chars: usize,
+    code_words: usize,
+}
+
+impl ListenCollector {
+    fn observe(&mut self, speed: &ReadSpeed, event: &Event<'_>, counters: &Counters) {
+        self.settle(counters);
+        let count = |text: &str| {
+            if speed.chinese {
+                count_words(text, speed.count_emoji)
+            } else {
+                count_english_words(text, speed.count_emoji)
+            }
+        };
+        match event {
+            Event::Text(text) if counters.in_code_block => self.code_words += count(text),
+            Event::Text(text) if !counters.in_image_alt => {
+                for token in text.split_whitespace().filter(|token| is_url(token)) {
+                    self.pending.0 += count(token);
+                    self.pending.1 += 1;
+                    self.pending.2 += token.chars().count();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 上一个事件被计入正文时才记录其中的链接地址
+    fn settle(&mut self, counters: &Counters) {
+        if counters.word_count > self.words {
+            self.url_words += self.pending.0;
+            self.url_count += self.pending.1;
+            self.url_chars += self.pending.2;
+        }
+        self.words = counters.word_count;
+        self.pending = (0, 0, 0);
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算语音合成的朗读时间
+    ///
+    /// 正文按 `listen` 中对应语言的朗读速度计算，图片不朗读，
+    /// 代码块和正文中的链接地址按各自的 [`SpeakPolicy`] 处理。
+    pub fn estimate_listen(&mut self, markdown: &str, listen: &ListenSpeed) -> ReadTime {
+        let speed = *self.speed();
+        let mut collector = ListenCollector::default();
+        let counters = self.count(markdown, &mut |event, _, counters| {
+            collector.observe(&speed, event, counters)
+        });
+        collector.settle(&counters);
+
+        let chinese = speed.chinese;
+        let prose_words = counters.word_count.saturating_sub(collector.url_words);
+        let url_seconds = listen.policy_seconds(
+            listen.urls,
+            collector.url_count,
+            collector.url_chars,
+            chinese,
+        );
+        let extra = Breakdown::from_counters(&counters, &speed);
+        let breakdown = Breakdown {
+            prose: prose_words as f64 / listen.per_minute(chinese) * 60.0 + url_seconds,
+            images: 0.0,
+            code: listen.policy_seconds(
+                listen.code_blocks,
+                counters.code_block_count,
+                collector.code_words,
+                chinese,
+            ),
+            tables: 0.0,
+            links: 0.0,
+            elements: extra.elements,
+            other: extra.other,
+        };
+        let total_seconds = ceil_seconds(breakdown.total());
+        ReadTime {
+            total_seconds,
+            formatted: format_time(total_seconds),
+            word_count: counters.word_count,
+            word_count_breakdown: counters.word_count_breakdown,
+            image_count: counters.image_count,
+            image_alt_chars: counters.image_alt_chars,
+            code_block_count: counters.code_block_count,
+            code_line_count: counters.code_line_count,
+            table_count: counters.table_count,
+            table_cell_count: counters.table_cell_count,
+            link_count: counters.link_count,
+            extra_seconds: counters.extra_seconds,
+            pages: speed.pages(counters.word_count),
+            front_matter_bytes: counters.front_matter_bytes,
+            custom_elements: counters.custom_elements,
+            breakdown,
+        }
+    }
+}
+
+/// 估算语音合成的朗读时间
+///
+/// 使用默认的阅读速度和朗读速度配置。适合在合成语音之前为“收听本文”功能显示时长。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算朗读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回朗读的 [`ReadTime`]，计数与阅读估算相同。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_listen;
+///
+/// let listen = estimate_listen("# 标题\n\n这是一段需要朗读的内容。");
+/// assert_eq!(listen.total_seconds, 4);
+/// ```
+pub fn estimate_listen(markdown: &str) -> ReadTime {
+    estimate_listen_with_speed(markdown, &ReadSpeed::default(), &ListenSpeed::default())
+}
+
+/// 使用自定义速度配置估算语音合成的朗读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算朗读时间的 Markdown 文本
+/// * `speed` - 阅读速度配置，决定计数方式
+/// * `listen` - 朗读速度配置
+///
+/// # Returns
+///
+/// 返回朗读的 [`ReadTime`]，计数与阅读估算相同。
+pub fn estimate_listen_with_speed(
+    markdown: &str,
+    speed: &ReadSpeed,
+    listen: &ListenSpeed,
+) -> ReadTime {
+    Estimator::new(*speed).estimate_listen(markdown, listen)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_listen() {
+        let markdown = "Read the docs at https://example.com/a/b today\n\n```rust\nfn main() {}\n```\n\n![alt text](a.png)\n";
+        let speed = ReadSpeed::default().chinese(false);
+        let listen = ListenSpeed::default().wpm(60.0).summary_time(3.0);
+
+        let read_time = estimate_listen_with_speed(markdown, &speed, &listen);
+        assert_eq!(read_time.word_count, 6);
+        assert_eq!(read_time.image_count, 1);
+        assert_eq!(read_time.breakdown.prose, 5.0 + 3.0);
+        assert_eq!(read_time.breakdown.code, 3.0);
+        assert_eq!(read_time.breakdown.images, 0.0);
+
+        let listen = listen
+            .code_blocks(SpeakPolicy::SpellOut)
+            .urls(SpeakPolicy::Skip);
+        let read_time = estimate_listen_with_speed(markdown, &speed, &listen);
+        assert_eq!(read_time.breakdown.prose, 5.0);
+        assert_eq!(read_time.breakdown.code, 3.0);
+
+        let listen = listen
+            .code_blocks(SpeakPolicy::Skip)
+            .urls(SpeakPolicy::SpellOut);
+        let read_time = estimate_listen_with_speed(markdown, &speed, &listen);
+        assert_eq!(read_time.breakdown.prose, 5.0 + 23.0);
+        assert_eq!(read_time.breakdown.code, 0.0);
+    }
+}
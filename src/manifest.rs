@@ -0,0 +1,200 @@
+use crate::utils::push_json_string;
+use crate::{BatchReadTime, DirOptions, Error, ReadTime, estimate_dir};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Component, Path};
+
+/// 站点清单中一篇文档的阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ManifestEntry {
+    /// 总阅读时间（秒）
+    pub seconds: u64,
+
+    /// 单词数量
+    pub words: usize,
+
+    /// 格式化后的阅读时间，例如 "2分30秒"
+    pub formatted: String,
+}
+
+impl From<&ReadTime> for ManifestEntry {
+    fn from(read_time: &ReadTime) -> Self {
+        Self {
+            seconds: read_time.total_seconds,
+            words: read_time.word_count,
+            formatted: read_time.formatted.clone(),
+        }
+    }
+}
+
+/// 站点清单：从 slug 到阅读时间的映射
+///
+/// 构建站点时预先生成，供前端页面和搜索索引直接读取，不需要在浏览器中解析 Markdown。
+/// slug 为文件相对内容目录的路径，使用 `/` 分隔并去掉扩展名，`index` 和 `_index`
+/// 文件使用所在目录的 slug，例如 `blog/post.md` 为 `blog/post`，`blog/index.md` 为 `blog`，
+/// 内容目录下的 `index.md` 为空字符串。
+///
+/// 启用 `serde` 特性后序列化为以 slug 为键的对象，与 [`to_json`](Self::to_json) 的输出相同。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[non_exhaustive]
+pub struct Manifest {
+    /// 每篇文档的阅读时间，按 slug 排序
+    pub entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    /// 根据内容目录 `root` 中各文件的阅读时间生成清单，不在 `root` 中的文件使用完整路径计算 slug
+    pub fn new<'a, I>(root: &Path, files: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a Path, &'a ReadTime)>,
+    {
+        let entries = files
+            .into_iter()
+            .map(|(path, read_time)| {
+                let relative = path.strip_prefix(root).unwrap_or(path);
+                (slug(relative), ManifestEntry::from(read_time))
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// 序列化为紧凑的 JSON 对象，例如 `{"blog/post":{"seconds":150,"words":500,"formatted":"2分30秒"}}`
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        for (index, (slug, entry)) in self.entries.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            push_json_string(&mut out, slug);
+            let _ = write!(
+                out,
+                ":{{\"seconds\":{},\"words\":{},\"formatted\":",
+                entry.seconds, entry.words
+            );
+            push_json_string(&mut out, &entry.formatted);
+            out.push('}');
+        }
+        out.push('}');
+        out
+    }
+}
+
+impl BatchReadTime {
+    /// 生成以 `root` 为内容目录的站点清单，详见 [`Manifest`]
+    pub fn manifest(&self, root: &Path) -> Manifest {
+        Manifest::new(
+            root,
+            self.files
+                .iter()
+                .map(|(path, read_time)| (path.as_path(), read_time)),
+        )
+    }
+}
+
+/// 相对路径对应的 slug
+fn slug(relative: &Path) -> String {
+    let mut parts: Vec<String> = relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    if let Some(last) = parts.pop() {
+        let stem = Path::new(&last)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or(last);
+        if stem != "index" && stem != "_index" {
+            parts.push(stem);
+        }
+    }
+    parts.join("/")
+}
+
+/// 估算内容目录中的 Markdown 文件并生成站点清单
+///
+/// # Arguments
+///
+/// * `root` - 内容目录，slug 相对该目录计算
+/// * `options` - 批量估算选项
+///
+/// # Returns
+///
+/// 返回 [`Manifest`]，读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{DirOptions, estimate_manifest};
+///
+/// let manifest = estimate_manifest("content", &DirOptions::default()).unwrap();
+/// std::fs::write("public/readtime.json", manifest.to_json()).unwrap();
+/// ```
+pub fn estimate_manifest(root: impl AsRef<Path>, options: &DirOptions) -> Result<Manifest, Error> {
+    let root = root.as_ref();
+    Ok(estimate_dir(root, options)?.manifest(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+    use std::fs;
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug(Path::new("blog/post.md")), "blog/post");
+        assert_eq!(slug(Path::new("./blog/index.md")), "blog");
+        assert_eq!(slug(Path::new("docs/_index.markdown")), "docs");
+        assert_eq!(slug(Path::new("index.md")), "");
+        assert_eq!(slug(Path::new("a.b.md")), "a.b");
+    }
+
+    #[test]
+    fn test_manifest_json() {
+        let read_time = ReadTime::builder()
+            .total_seconds(150)
+            .word_count(500)
+            .build();
+        let other = ReadTime::builder().formatted("\"短\"\n").build();
+        let manifest = Manifest::new(
+            Path::new("content"),
+            [
+                (Path::new("content/blog/post.md"), &read_time),
+                (Path::new("elsewhere/a.md"), &other),
+            ],
+        );
+        assert_eq!(
+            manifest.to_json(),
+            concat!(
+                "{\"blog/post\":{\"seconds\":150,\"words\":500,\"formatted\":\"2分30秒\"},",
+                "\"elsewhere/a\":{\"seconds\":0,\"words\":0,\"formatted\":\"\\\"短\\\"\\n\"}}"
+            )
+        );
+        assert_eq!(Manifest::default().to_json(), "{}");
+    }
+
+    #[test]
+    fn test_estimate_manifest() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-manifest");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("blog")).unwrap();
+        fs::write(dir.join("index.md"), "# 首页").unwrap();
+        fs::write(dir.join("blog/post.md"), "文章内容").unwrap();
+
+        let manifest = estimate_manifest(&dir, &DirOptions::default()).unwrap();
+        let slugs: Vec<&str> = manifest.entries.keys().map(String::as_str).collect();
+        assert_eq!(slugs, ["", "blog/post"]);
+        assert_eq!(
+            manifest.entries["blog/post"],
+            ManifestEntry::from(&estimate("文章内容"))
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,142 @@
+//! 指标
+//!
+//! 启用 `metrics` 特性后，每次通过 [`Estimator`](crate::Estimator) 完成估算都会经由
+//! [`metrics`](https://docs.rs/metrics) 门面记录以下指标，由应用安装的导出器（例如
+//! `metrics-exporter-prometheus`）决定如何暴露：
+//!
+//! | 名称 | 类型 | 说明 |
+//! | --- | --- | --- |
+//! | [`DOCUMENTS`] | 计数器 | 估算的文档数 |
+//! | [`BYTES`] | 计数器 | 处理的字节数，包括跳过的 front matter |
+//! | [`DURATION`] | 直方图 | 单次估算的耗时（秒） |
+//! | [`READ_TIME`] | 直方图 | 估算得到的阅读时间（秒，取整前） |
+//!
+//! 没有安装导出器时记录操作为空操作。超出 [`Limits`](crate::Limits) 的估算不会记录。
+use metrics::{Unit, counter, describe_counter, describe_histogram, histogram};
+use std::time::Duration;
+
+/// 估算的文档数
+pub const DOCUMENTS: &str = "markdown_readtime_documents_total";
+
+/// 处理的字节数
+pub const BYTES: &str = "markdown_readtime_bytes_total";
+
+/// 单次估算的耗时（秒）
+pub const DURATION: &str = "markdown_readtime_estimate_duration_seconds";
+
+/// 估算得到的阅读时间（秒）
+pub const READ_TIME: &str = "markdown_readtime_read_time_seconds";
+
+/// 向已安装的导出器登记各指标的单位和说明
+///
+/// 只影响导出器输出的元数据（例如 Prometheus 的 `# HELP`），不调用也会正常记录。
+///
+/// # Examples
+///
+/// ```
+/// markdown_readtime::metrics::describe();
+/// let read_time = markdown_readtime::estimate("# 标题\n\n正文");
+/// ```
+pub fn describe() {
+    describe_counter!(DOCUMENTS, Unit::Count, "估算的 Markdown 文档数");
+    describe_counter!(BYTES, Unit::Bytes, "估算时处理的字节数");
+    describe_histogram!(DURATION, Unit::Seconds, "单次估算的耗时");
+    describe_histogram!(READ_TIME, Unit::Seconds, "估算得到的阅读时间");
+}
+
+/// 记录一次完成的估算
+pub(crate) fn record(bytes: usize, elapsed: Duration, read_seconds: f64) {
+    counter!(DOCUMENTS).increment(1);
+    counter!(BYTES).increment(bytes as u64);
+    histogram!(DURATION).record(elapsed.as_secs_f64());
+    histogram!(READ_TIME).record(read_seconds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, Limits, ReadSpeed};
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+        SharedString,
+    };
+    use std::sync::{Arc, Mutex};
+
+    /// 按名称保存记录的值
+    #[derive(Default)]
+    struct Values {
+        values: Mutex<Vec<(String, f64)>>,
+    }
+
+    struct Handle {
+        name: String,
+        values: Arc<Values>,
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            self.record(value as f64);
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, value: f64) {
+            let mut values = self.values.values.lock().unwrap();
+            values.push((self.name.clone(), value));
+        }
+    }
+
+    struct TestRecorder(Arc<Values>);
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            Arc::new(Handle {
+                name: key.name().to_string(),
+                values: self.0.clone(),
+            })
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(self.handle(key))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::from_arc(self.handle(key))
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let values = Arc::new(Values::default());
+        let recorder = TestRecorder(values.clone());
+        let markdown = "---\ntitle: a\n---\n正文内容";
+        metrics::with_local_recorder(&recorder, || {
+            describe();
+            let mut estimator = Estimator::new(ReadSpeed::default().wpm(60.0));
+            estimator.estimate(markdown);
+            let mut limited = Estimator::new(ReadSpeed::default())
+                .limits(Limits::default().max_input_bytes(Some(1)));
+            assert!(limited.try_estimate(markdown).is_err());
+        });
+
+        let values = values.values.lock().unwrap();
+        let names: Vec<&str> = values.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, [DOCUMENTS, BYTES, DURATION, READ_TIME]);
+        assert_eq!(values[0].1, 1.0);
+        assert_eq!(values[1].1, markdown.len() as f64);
+        assert!(values[2].1 >= 0.0);
+        assert_eq!(values[3].1, 4.0);
+    }
+}
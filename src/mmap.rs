@@ -0,0 +1,93 @@
+use crate::utils::decode_text;
+use crate::{Error, ReadSpeed, ReadTime, estimate_with_speed};
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// 通过内存映射估算文件的阅读时间
+///
+/// 使用默认的阅读速度配置，详见 [`estimate_file_mmap_with_speed`]。
+///
+/// # Arguments
+///
+/// * `path` - Markdown 文件路径
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，打开或映射文件失败时返回 [`Error`]。
+///
+/// # Safety
+///
+/// 与 [`estimate_file_mmap_with_speed`] 相同，调用方需要保证估算期间文件不被修改或截断。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::estimate_file_mmap;
+///
+/// // SAFETY: 导出文件在估算期间不会被其他进程修改
+/// let read_time = unsafe { estimate_file_mmap("export.md") }.unwrap();
+/// println!("阅读时间: {}", read_time.formatted);
+/// ```
+pub unsafe fn estimate_file_mmap(path: impl AsRef<Path>) -> Result<ReadTime, Error> {
+    // SAFETY: 由调用方保证
+    unsafe { estimate_file_mmap_with_speed(path, &ReadSpeed::default()) }
+}
+
+/// 通过内存映射使用自定义速度配置估算文件的阅读时间
+///
+/// 文件内容直接映射到内存中交给解析器，有效的 UTF-8 文件不会复制到 `String`，
+/// 适合几百 MB 的合并导出文件。编码处理与 [`estimate_file_with_speed`](crate::estimate_file_with_speed) 相同，
+/// 带有 UTF-16 BOM 或含有无效字节序列的文件仍需解码为新的字符串。
+///
+/// 无法保证文件不被修改时使用 [`estimate_file_with_speed`](crate::estimate_file_with_speed)。
+///
+/// # Arguments
+///
+/// * `path` - Markdown 文件路径
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，打开或映射文件失败时返回 [`Error`]。
+///
+/// # Safety
+///
+/// 估算期间该文件不能被本进程或其他进程修改或截断。映射的内容发生变化时，
+/// 解析器看到的 `&str` 可能不再是有效的 UTF-8，文件被截断时访问映射会导致进程收到 `SIGBUS`，
+/// 两者都是未定义行为。
+pub unsafe fn estimate_file_mmap_with_speed(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+) -> Result<ReadTime, Error> {
+    let file = File::open(path)?;
+    // SAFETY: 映射只读，调用方保证估算期间文件不被修改
+    let map = unsafe { Mmap::map(&file)? };
+    Ok(estimate_with_speed(&decode_text(&map), speed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{estimate, estimate_file};
+    use std::fs;
+
+    #[test]
+    fn test_estimate_file_mmap() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-estimate-file-mmap");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("post.md");
+        fs::write(&path, "\u{FEFF}# 标题\n\n这是内容\n\n![图片](a.png)").unwrap();
+        // SAFETY: 测试目录中的文件只由本测试写入
+        let mmap = |path: &Path| unsafe { estimate_file_mmap(path) };
+        assert_eq!(mmap(&path).unwrap(), estimate_file(&path).unwrap());
+
+        let empty = dir.join("empty.md");
+        fs::write(&empty, "").unwrap();
+        assert_eq!(mmap(&empty).unwrap(), estimate(""));
+
+        let missing = mmap(&dir.join("missing.md"));
+        assert!(matches!(missing, Err(Error::Io(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+//! UniFFI 绑定
+//!
+//! 通过 UniFFI 过程宏生成接口定义，iOS/Android 应用可以从 Swift/Kotlin 调用。
+//! 构建动态库后使用 uniffi-bindgen 生成对应语言的绑定代码：
+//!
+//! ```sh
+//! cargo rustc --lib --release --features uniffi --crate-type cdylib
+//! uniffi-bindgen generate --library target/release/libmarkdown_readtime.so --language kotlin --out-dir out
+//! ```
+use crate::{ReadSpeed, ReadTime};
+use std::collections::HashMap;
+
+/// 移动端的阅读速度配置
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ReadSpeedOptions {
+    pub words_per_minute: f64,
+    pub seconds_per_image: f64,
+    pub seconds_per_code_block: f64,
+    pub count_emoji: bool,
+    pub chinese: bool,
+}
+
+impl From<ReadSpeedOptions> for ReadSpeed {
+    fn from(options: ReadSpeedOptions) -> Self {
+        ReadSpeed::new(
+            options.words_per_minute,
+            options.seconds_per_image,
+            options.seconds_per_code_block,
+            options.count_emoji,
+            options.chinese,
+        )
+    }
+}
+
+impl From<ReadSpeed> for ReadSpeedOptions {
+    fn from(speed: ReadSpeed) -> Self {
+        Self {
+            words_per_minute: speed.words_per_minute,
+            seconds_per_image: speed.seconds_per_image,
+            seconds_per_code_block: speed.seconds_per_code_block,
+            count_emoji: speed.count_emoji,
+            chinese: speed.chinese,
+        }
+    }
+}
+
+/// 移动端的阅读时间估算结果
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ReadTimeResult {
+    pub total_seconds: u64,
+    pub formatted: String,
+    pub word_count: u64,
+    pub image_count: u64,
+    pub code_block_count: u64,
+    pub extra_seconds: f64,
+    pub custom_elements: HashMap<String, u64>,
+}
+
+impl From<ReadTime> for ReadTimeResult {
+    fn from(read_time: ReadTime) -> Self {
+        Self {
+            total_seconds: read_time.total_seconds,
+            formatted: read_time.formatted,
+            word_count: read_time.word_count as u64,
+            image_count: read_time.image_count as u64,
+            code_block_count: read_time.code_block_count as u64,
+            extra_seconds: read_time.extra_seconds,
+            custom_elements: read_time
+                .custom_elements
+                .into_iter()
+                .map(|(kind, count)| (kind, count as u64))
+                .collect(),
+        }
+    }
+}
+
+/// 返回默认的阅读速度配置
+#[uniffi::export]
+pub fn default_read_speed() -> ReadSpeedOptions {
+    ReadSpeed::default().into()
+}
+
+/// 使用默认配置估算阅读时间
+#[uniffi::export]
+pub fn estimate(markdown: String) -> ReadTimeResult {
+    crate::estimate(&markdown).into()
+}
+
+/// 使用自定义配置估算阅读时间
+#[uniffi::export]
+pub fn estimate_with_speed(markdown: String, speed: ReadSpeedOptions) -> ReadTimeResult {
+    crate::estimate_with_speed(&markdown, &speed.into()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mobile_round_trip() {
+        let speed = default_read_speed();
+        assert_eq!(
+            speed.words_per_minute,
+            ReadSpeed::default().words_per_minute
+        );
+
+        let result = estimate_with_speed(
+            "hello world".to_string(),
+            ReadSpeedOptions {
+                chinese: false,
+                ..speed
+            },
+        );
+        assert_eq!(result.word_count, 2);
+        assert_eq!(estimate("你好".to_string()).word_count, 2);
+    }
+}
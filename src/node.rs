@@ -0,0 +1,124 @@
+//! Node.js N-API 绑定
+//!
+//! 基于 napi-rs，使用原生代码估算阅读时间。配置对象和返回结果的字段名采用 JS 惯用的驼峰命名，
+//! 配置对象中缺失的字段使用默认值。`estimateAsync` 在 libuv 线程池中执行，不会阻塞事件循环。
+//!
+//! ```js
+//! const { estimate, estimateAsync } = require("./markdown-readtime.node");
+//!
+//! const result = estimate("# Title\n\nSome content", { wordsPerMinute: 250, chinese: false });
+//! console.log(result.formatted, result.wordCount);
+//! ```
+use crate::{ReadSpeed, ReadTime, estimate_with_speed};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use std::collections::HashMap;
+
+/// JS 中的阅读速度配置
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct JsReadSpeed {
+    pub words_per_minute: Option<f64>,
+    pub seconds_per_image: Option<f64>,
+    pub seconds_per_code_block: Option<f64>,
+    pub count_emoji: Option<bool>,
+    pub chinese: Option<bool>,
+}
+
+impl From<JsReadSpeed> for ReadSpeed {
+    fn from(options: JsReadSpeed) -> Self {
+        let mut speed = ReadSpeed::default();
+        if let Some(wpm) = options.words_per_minute {
+            speed = speed.wpm(wpm);
+        }
+        if let Some(seconds) = options.seconds_per_image {
+            speed = speed.image_time(seconds);
+        }
+        if let Some(seconds) = options.seconds_per_code_block {
+            speed = speed.code_block_time(seconds);
+        }
+        if let Some(count) = options.count_emoji {
+            speed = speed.emoji(count);
+        }
+        if let Some(is_chinese) = options.chinese {
+            speed = speed.chinese(is_chinese);
+        }
+        speed
+    }
+}
+
+/// JS 中的阅读时间估算结果
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsReadTime {
+    pub total_seconds: i64,
+    pub formatted: String,
+    pub word_count: i64,
+    pub image_count: i64,
+    pub code_block_count: i64,
+    pub extra_seconds: f64,
+    pub custom_elements: HashMap<String, i64>,
+}
+
+impl From<ReadTime> for JsReadTime {
+    fn from(read_time: ReadTime) -> Self {
+        Self {
+            total_seconds: read_time.total_seconds as i64,
+            formatted: read_time.formatted,
+            word_count: read_time.word_count as i64,
+            image_count: read_time.image_count as i64,
+            code_block_count: read_time.code_block_count as i64,
+            extra_seconds: read_time.extra_seconds,
+            custom_elements: read_time
+                .custom_elements
+                .into_iter()
+                .map(|(kind, count)| (kind, count as i64))
+                .collect(),
+        }
+    }
+}
+
+/// 估算阅读时间
+#[napi(js_name = "estimate")]
+pub fn estimate_js(markdown: String, options: Option<JsReadSpeed>) -> JsReadTime {
+    let speed = options.map(ReadSpeed::from).unwrap_or_default();
+    estimate_with_speed(&markdown, &speed).into()
+}
+
+/// 批量估算阅读时间，结果顺序与输入顺序一致
+#[napi(js_name = "estimateMany")]
+pub fn estimate_many_js(docs: Vec<String>, options: Option<JsReadSpeed>) -> Vec<JsReadTime> {
+    let speed = options.map(ReadSpeed::from).unwrap_or_default();
+    docs.iter()
+        .map(|doc| estimate_with_speed(doc, &speed).into())
+        .collect()
+}
+
+/// 在线程池中执行的估算任务
+pub struct EstimateTask {
+    markdown: String,
+    speed: ReadSpeed,
+}
+
+impl Task for EstimateTask {
+    type Output = ReadTime;
+    type JsValue = JsReadTime;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(estimate_with_speed(&self.markdown, &self.speed))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// 在 libuv 线程池中估算阅读时间，返回 Promise
+#[napi(js_name = "estimateAsync")]
+pub fn estimate_async_js(
+    markdown: String,
+    options: Option<JsReadSpeed>,
+) -> AsyncTask<EstimateTask> {
+    let speed = options.map(ReadSpeed::from).unwrap_or_default();
+    AsyncTask::new(EstimateTask { markdown, speed })
+}
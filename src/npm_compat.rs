@@ -0,0 +1,238 @@
+//! 与 npm 包 [`reading-time`](https://github.com/ngryman/reading-time) 一致的计数方式
+//!
+//! 该包直接在传入的字符串上计数，不解析 Markdown：
+//!
+//! - 按 UTF-16 代码单元逐个判断，空格、换行、回车和制表符为单词边界；
+//! - 平假名、中日韩统一表意文字和谚文音节每个字计为一个单词，紧随其后的标点和边界被跳过，
+//!   片假名、扩展区汉字（代理对）和 emoji 与字母一样按边界分隔；
+//! - 阅读时间为 `字数 / 每分钟字数` 分钟，显示的分钟数先保留两位小数再向上取整。
+use crate::utils::{count_scripts, format_time};
+use crate::{Breakdown, ReadSpeed, ReadTime, WordCountBreakdown};
+
+/// 单词边界，`None` 对应 JavaScript 中越界访问得到的 `undefined`
+fn is_word_bound(unit: Option<u16>) -> bool {
+    matches!(unit, Some(0x20 | 0x0A | 0x0D | 0x09))
+}
+
+/// 单独计为一个单词的字符
+///
+/// 该包还列出了 `0x20000..=0x2EBE0`，但比较的是单个代码单元，这一范围永远不会匹配。
+fn is_cjk(unit: Option<u16>) -> bool {
+    matches!(
+        unit,
+        Some(0x3040..=0x309F | 0x4E00..=0x9FFF | 0xAC00..=0xD7A3)
+    )
+}
+
+/// 紧随中日韩字符时被跳过的标点
+fn is_punctuation(unit: Option<u16>) -> bool {
+    matches!(
+        unit,
+        Some(
+            0x21..=0x2F
+            | 0x3A..=0x40
+            | 0x5B..=0x60
+            | 0x7B..=0x7E
+            | 0x3000..=0x303F
+            | 0xFF00..=0xFFEF,
+        )
+    )
+}
+
+/// 与 `countWords` 相同的字数
+fn count_words(text: &str) -> usize {
+    let units: Vec<u16> = text.encode_utf16().collect();
+    let at = |index: isize| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| units.get(i).copied())
+    };
+    // 末尾追加的换行，使最后一个单词也以边界结束
+    let normalized = |index: isize| match index {
+        _ if index == units.len() as isize => Some(0x0A),
+        _ => at(index),
+    };
+
+    let mut start = 0;
+    let mut end = units.len() as isize - 1;
+    while is_word_bound(at(start)) {
+        start += 1;
+    }
+    while is_word_bound(at(end)) {
+        end -= 1;
+    }
+
+    let mut words = 0;
+    let mut i = start;
+    while i <= end {
+        let unit = normalized(i);
+        let next = normalized(i + 1);
+        if is_cjk(unit) || (!is_word_bound(unit) && (is_word_bound(next) || is_cjk(next))) {
+            words += 1;
+        }
+        if is_cjk(unit) {
+            while i <= end
+                && (is_punctuation(normalized(i + 1)) || is_word_bound(normalized(i + 1)))
+            {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    words
+}
+
+/// 显示的分钟数：`Math.ceil(parseFloat(minutes.toFixed(2)))`
+///
+/// 保留两位小数时按二进制的精确值舍入，例如 1.005 实际略小于 1.005，显示为 1 分钟。
+fn displayed_minutes(minutes: f64) -> u64 {
+    // 恰好在两位小数中间的值不会跨过整数，舍入方向不影响结果
+    format!("{:.2}", minutes)
+        .parse::<f64>()
+        .map_or(0, |rounded| rounded.ceil() as u64)
+}
+
+/// 按 npm 包 `reading-time` 的规则估算阅读时间
+///
+/// 结果与该包 `readingTime(text)` 的 `words.total` 和 `text` 中的分钟数一致，
+/// 从 Node 迁移的站点在过渡期间显示的数字不会变化。该包不解析 Markdown，
+/// 传入的字符串原样计数；原来先去掉 front matter 的站点需要同样去掉后再调用。
+///
+/// # Arguments
+///
+/// * `text` - 需要估算阅读时间的文本
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，`total_seconds` 为显示的整分钟数乘以 60。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_npm;
+///
+/// let read_time = estimate_npm(&"word ".repeat(201));
+/// assert_eq!(read_time.word_count, 201);
+/// assert_eq!(read_time.formatted, "1分钟");
+/// ```
+pub fn estimate_npm(text: &str) -> ReadTime {
+    estimate_npm_with_speed(text, &ReadSpeed::npm())
+}
+
+/// 使用自定义速度配置按 npm 包 `reading-time` 的规则估算阅读时间
+///
+/// # Arguments
+///
+/// * `text` - 需要估算阅读时间的文本
+/// * `speed` - 阅读速度配置，只有 [`ReadSpeed::words_per_minute`]（对应 `wordsPerMinute`）会生效
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，`total_seconds` 为显示的整分钟数乘以 60。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadSpeed, estimate_npm_with_speed};
+///
+/// let speed = ReadSpeed::npm().wpm(275.0);
+/// let read_time = estimate_npm_with_speed("中文，标点。English words", &speed);
+/// assert_eq!(read_time.word_count, 6);
+/// assert_eq!(read_time.total_seconds, 60);
+/// ```
+pub fn estimate_npm_with_speed(text: &str, speed: &ReadSpeed) -> ReadTime {
+    let word_count = count_words(text);
+    let minutes = word_count as f64 / speed.words_per_minute;
+    let total_seconds = displayed_minutes(minutes).saturating_mul(60);
+    let mut word_count_breakdown = WordCountBreakdown::default();
+    count_scripts(text, &mut word_count_breakdown);
+    ReadTime {
+        total_seconds,
+        formatted: format_time(total_seconds),
+        word_count,
+        word_count_breakdown,
+        pages: speed.pages(word_count),
+        breakdown: Breakdown {
+            prose: total_seconds as f64,
+            ..Breakdown::default()
+        },
+        ..ReadTime::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 与 npm 包共用的数据，每行依次为每分钟字数、字数、毫秒数、显示文字和输入
+    const FIXTURES: &str = include_str!("../tests/fixtures/reading-time.tsv");
+
+    /// 还原 `\n`、`\t`、`\r` 和 `\\` 转义
+    fn unescape(input: &str) -> String {
+        let mut out = String::new();
+        let mut chars = input.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('r') => out.push('\r'),
+                    Some(other) => out.push(other),
+                    None => {}
+                },
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_npm_fixtures() {
+        let lines = FIXTURES.lines().filter(|line| !line.starts_with('#'));
+        let mut checked = 0;
+        for line in lines {
+            let fields: Vec<&str> = line.splitn(5, '\t').collect();
+            let [wpm, words, time, text, input] = fields[..] else {
+                panic!("无效的数据: {:?}", line);
+            };
+            let input = unescape(input);
+            let speed = ReadSpeed::npm().wpm(wpm.parse().unwrap());
+            let read_time = estimate_npm_with_speed(&input, &speed);
+
+            assert_eq!(read_time.word_count.to_string(), words, "{:?}", input);
+            // `time` 为 `Math.round(minutes * 60 * 1000)`
+            let minutes = read_time.word_count as f64 / speed.words_per_minute;
+            assert_eq!((minutes * 60.0 * 1000.0).round().to_string(), time);
+            assert_eq!(
+                format!("{} min read", read_time.total_seconds / 60),
+                text,
+                "{:?}",
+                input
+            );
+            checked += 1;
+        }
+        assert!(checked > 30);
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words(" \n"), 0);
+        assert_eq!(count_words("a"), 1);
+        assert_eq!(count_words("a\u{a0}b"), 1);
+        // 中日韩字符之后的标点和空白被跳过，不再单独计为单词
+        assert_eq!(count_words("中 , x"), 2);
+        assert_eq!(count_words("x , 中"), 3);
+        assert_eq!(count_words("テキスト"), 1);
+    }
+
+    #[test]
+    fn test_displayed_minutes() {
+        assert_eq!(displayed_minutes(0.0), 0);
+        assert_eq!(displayed_minutes(0.005), 1);
+        assert_eq!(displayed_minutes(1.0), 1);
+        assert_eq!(displayed_minutes(1.005), 1);
+        assert_eq!(displayed_minutes(1.006), 2);
+        assert_eq!(displayed_minutes(201.0 / 200.0), 1);
+    }
+}
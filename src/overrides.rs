@@ -0,0 +1,205 @@
+use crate::front_matter::FrontMatter;
+use crate::{Estimator, ReadSpeed, ReadTime};
+
+/// 文档 front matter 中对阅读速度配置的覆盖
+///
+/// 识别以下顶层键，无法解析或超出范围的值会被忽略：
+///
+/// | 键 | 作用 |
+/// |----|------|
+/// | `readtime: false` | 跳过该文档的估算 |
+/// | `readtime_wpm` | 覆盖 [`ReadSpeed::words_per_minute`]，不能小于 1 |
+/// | `readtime_image_time` | 覆盖 [`ReadSpeed::seconds_per_image`]，不能为负数 |
+/// | `readtime_code_block_time` | 覆盖 [`ReadSpeed::seconds_per_code_block`]，不能为负数 |
+/// | `readtime_chinese` | 覆盖 [`ReadSpeed::chinese`] |
+/// | `readtime_emoji` | 覆盖 [`ReadSpeed::count_emoji`] |
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Overrides {
+    /// 是否跳过估算
+    pub skip: bool,
+
+    /// 每分钟阅读单词数
+    pub wpm: Option<f64>,
+
+    /// 每张图片额外时间（秒）
+    pub image_time: Option<f64>,
+
+    /// 每个代码块额外时间（秒）
+    pub code_block_time: Option<f64>,
+
+    /// 是否中文
+    pub chinese: Option<bool>,
+
+    /// 是否考虑emoji
+    pub emoji: Option<bool>,
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// 解析不小于 `min` 的有限数
+///
+/// front matter 来自文档本身，`readtime_wpm: 0` 之类的值会让估算结果溢出，因此与无法解析的值一样忽略。
+fn number(value: &str, min: f64) -> Option<f64> {
+    value
+        .parse()
+        .ok()
+        .filter(|number: &f64| number.is_finite() && *number >= min)
+}
+
+impl Overrides {
+    /// 从文档的 front matter 中读取覆盖，没有 front matter 时全部为空
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::Overrides;
+    ///
+    /// let overrides = Overrides::from_front_matter("---\nreadtime_wpm: 150\n---\n正文");
+    /// assert_eq!(overrides.wpm, Some(150.0));
+    /// assert!(Overrides::from_front_matter("+++\nreadtime = false\n+++\n").skip);
+    /// ```
+    pub fn from_front_matter(markdown: &str) -> Self {
+        let mut overrides = Self::default();
+        let Some(front_matter) = FrontMatter::detect(markdown) else {
+            return overrides;
+        };
+        for (key, value) in front_matter.entries(markdown) {
+            let value = unquote(value);
+            match key {
+                "readtime" => overrides.skip = value == "false",
+                "readtime_wpm" => overrides.wpm = number(value, 1.0),
+                "readtime_image_time" => overrides.image_time = number(value, 0.0),
+                "readtime_code_block_time" => overrides.code_block_time = number(value, 0.0),
+                "readtime_chinese" => overrides.chinese = value.parse().ok(),
+                "readtime_emoji" => overrides.emoji = value.parse().ok(),
+                _ => {}
+            }
+        }
+        overrides
+    }
+
+    /// 在 `speed` 的基础上应用覆盖
+    pub fn apply(&self, speed: &ReadSpeed) -> ReadSpeed {
+        let mut speed = *speed;
+        if let Some(wpm) = self.wpm {
+            speed.words_per_minute = wpm;
+        }
+        if let Some(seconds) = self.image_time {
+            speed.seconds_per_image = seconds;
+        }
+        if let Some(seconds) = self.code_block_time {
+            speed.seconds_per_code_block = seconds;
+        }
+        if let Some(chinese) = self.chinese {
+            speed.chinese = chinese;
+        }
+        if let Some(emoji) = self.emoji {
+            speed.count_emoji = emoji;
+        }
+        speed
+    }
+}
+
+impl Estimator<'_> {
+    /// 应用文档 front matter 中的覆盖后估算阅读时间
+    ///
+    /// front matter 中设置了 `readtime: false` 时返回 `None`。
+    pub fn estimate_with_overrides(&mut self, markdown: &str) -> Option<ReadTime> {
+        let overrides = Overrides::from_front_matter(markdown);
+        if overrides.skip {
+            return None;
+        }
+        let speed = self.speed;
+        self.speed = overrides.apply(&speed);
+        let read_time = self.estimate(markdown);
+        self.speed = speed;
+        Some(read_time)
+    }
+}
+
+/// 应用文档 front matter 中的覆盖后估算阅读时间
+///
+/// 使用默认的阅读速度配置作为基础。单篇文章的调整与文章放在一起，
+/// 不需要修改全局配置，可用的键参见 [`Overrides`]。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，front matter 中设置了 `readtime: false` 时返回 `None`。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_with_overrides;
+///
+/// let markdown = "---\nreadtime_wpm: 100\n---\n这是一段示例内容";
+/// assert_eq!(estimate_with_overrides(markdown).unwrap().total_seconds, 5);
+/// assert_eq!(estimate_with_overrides("---\nreadtime: false\n---\n正文"), None);
+/// ```
+pub fn estimate_with_overrides(markdown: &str) -> Option<ReadTime> {
+    estimate_with_overrides_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 以自定义速度配置为基础，应用文档 front matter 中的覆盖后估算阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置，front matter 中未覆盖的项使用该配置
+///
+/// # Returns
+///
+/// 返回 [`ReadTime`]，front matter 中设置了 `readtime: false` 时返回 `None`。
+pub fn estimate_with_overrides_with_speed(markdown: &str, speed: &ReadSpeed) -> Option<ReadTime> {
+    Estimator::new(*speed).estimate_with_overrides(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate_with_speed;
+
+    #[test]
+    fn test_overrides() {
+        let markdown = "{\"readtime_chinese\": false, \"readtime_image_time\": \"5\"}\nsome english words\n\n![a](a.png)";
+        let overrides = Overrides::from_front_matter(markdown);
+        assert_eq!(overrides.chinese, Some(false));
+        assert_eq!(overrides.image_time, Some(5.0));
+        assert_eq!(overrides.wpm, None);
+
+        let speed = ReadSpeed::default();
+        let read_time = estimate_with_overrides_with_speed(markdown, &speed).unwrap();
+        assert_eq!(read_time.word_count, 3);
+        assert_eq!(
+            read_time,
+            estimate_with_speed(markdown, &overrides.apply(&speed))
+        );
+
+        let ignored = Overrides::from_front_matter("---\nreadtime_wpm: fast\nreadtime: yes\n---\n");
+        assert_eq!(ignored, Overrides::default());
+        assert_eq!(Overrides::from_front_matter("正文"), Overrides::default());
+    }
+
+    #[test]
+    fn test_overrides_out_of_range() {
+        for wpm in ["0", "-100", "1e-300", "NaN", "inf"] {
+            let markdown = format!("---\nreadtime_wpm: {wpm}\nreadtime_image_time: -5\n---\n正文");
+            assert_eq!(
+                Overrides::from_front_matter(&markdown),
+                Overrides::default()
+            );
+            assert_eq!(
+                estimate_with_overrides(&markdown).unwrap(),
+                estimate_with_speed(&markdown, &ReadSpeed::default())
+            );
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::front_matter::front_matter_len;
+use crate::{ReadSpeed, ReadTime, estimate_with_speed};
+use rayon::prelude::*;
+use std::borrow::Cow;
+
+/// 单篇文档并行估算时每块的最小字节数，更小的文档直接在当前线程中估算
+const MIN_CHUNK_BYTES: usize = 256 * 1024;
+
+/// 可能跨越空行的 HTML 块：开始标记和结束标记（小写）
+const HTML_BLOCKS: &[(&str, &str)] = &[
+    ("<pre", "</pre>"),
+    ("<script", "</script>"),
+    ("<style", "</style>"),
+    ("<textarea", "</textarea>"),
+    ("<!--", "-->"),
+    ("<?", "?>"),
+    ("<![cdata[", "]]>"),
+];
+
+/// 并行估算多篇文档的阅读时间
+///
+/// 使用 rayon 全局线程池并行处理，结果顺序与输入顺序一致。需要启用 `rayon` 特性。
+///
+/// # Arguments
+///
+/// * `docs` - 需要估算的 Markdown 文档
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 与输入顺序一致的 [`ReadTime`] 列表。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate_many_parallel, ReadSpeed};
+///
+/// let docs = vec!["# 第一篇\n\n内容", "# 第二篇\n\n更多内容"];
+/// let results = estimate_many_parallel(&docs, &ReadSpeed::default());
+/// assert_eq!(results.len(), 2);
+/// ```
+pub fn estimate_many_parallel<S>(docs: &[S], speed: &ReadSpeed) -> Vec<ReadTime>
+where
+    S: AsRef<str> + Sync,
+{
+    docs.par_iter()
+        .map(|doc| estimate_with_speed(doc.as_ref(), speed))
+        .collect()
+}
+
+/// 把单篇大文档拆分为多块并行估算
+///
+/// 只在顶层块之间拆分：拆分点位于空行之后、不在围栏代码块和跨行的 HTML 块中，
+/// 链接引用定义会复制到每一块，各块的计数合并后按 `speed` 重新计算总时间，
+/// 结果与 [`estimate_with_speed`] 相同。小于 256 KiB 的文档不拆分；
+/// 启用 [`ReadSpeed::dedupe_links`] 时链接需要在整篇文档中去重，同样不拆分。需要启用 `rayon` 特性。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadSpeed, estimate_parallel, estimate_with_speed};
+///
+/// let book = "# 第一章\n\n这是内容。\n\n```rust\nfn main() {}\n```\n\n".repeat(10_000);
+/// let speed = ReadSpeed::default();
+/// assert_eq!(estimate_parallel(&book, &speed), estimate_with_speed(&book, &speed));
+/// ```
+pub fn estimate_parallel(markdown: &str, speed: &ReadSpeed) -> ReadTime {
+    let target = (markdown.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_BYTES);
+    estimate_chunked(markdown, speed, target)
+}
+
+fn estimate_chunked(markdown: &str, speed: &ReadSpeed, target: usize) -> ReadTime {
+    if speed.dedupe_links && speed.seconds_per_link > 0.0 {
+        return estimate_with_speed(markdown, speed);
+    }
+    let chunks = split_blocks(markdown, target);
+    if chunks.len() == 1 {
+        return estimate_with_speed(markdown, speed);
+    }
+    let definitions = reference_definitions(markdown);
+    let results: Vec<ReadTime> = chunks
+        .par_iter()
+        .enumerate()
+        .map(|(index, &chunk)| {
+            let source = match (index, definitions.is_empty()) {
+                (_, true) => Cow::Borrowed(chunk),
+                // 第一块可能以 front matter 开头，定义放在末尾
+                (0, false) => Cow::Owned(format!("{chunk}\n\n{definitions}")),
+                (_, false) => Cow::Owned(format!("{definitions}\n\n{chunk}")),
+            };
+            estimate_with_speed(&source, speed)
+        })
+        .collect();
+    ReadTime::merge_all(&results, speed)
+}
+
+/// 跨越空行、尚未结束的块
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Open {
+    /// 围栏代码块的标记字符和长度
+    Fence(u8, usize),
+
+    /// HTML 块的结束标记
+    Html(&'static str),
+}
+
+/// 行首的围栏标记：标记字符、长度和之后的信息字符串
+fn fence(text: &str) -> Option<(u8, usize, &str)> {
+    let marker = *text
+        .as_bytes()
+        .first()
+        .filter(|b| matches!(b, b'`' | b'~'))?;
+    let len = text.bytes().take_while(|&b| b == marker).count();
+    let info = text[len..].trim();
+    (len >= 3 && !(marker == b'`' && info.contains('`'))).then_some((marker, len, info))
+}
+
+/// 以跨行 HTML 块开头时的结束标记
+fn html_end(text: &str) -> Option<&'static str> {
+    HTML_BLOCKS.iter().find_map(|&(start, end)| {
+        text.get(..start.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(start))
+            .then_some(end)
+    })
+}
+
+fn contains_ignore_case(text: &str, part: &str) -> bool {
+    text.to_ascii_lowercase().contains(part)
+}
+
+/// 在不小于 `target` 字节的位置拆分文档，每块都从顶层块的开头开始
+fn split_blocks(markdown: &str, target: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0;
+    let mut offset = front_matter_len(markdown);
+    let mut open = None;
+    let mut previous_blank = false;
+    for line in markdown[offset..].split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let text = line.trim_end_matches(['\n', '\r']);
+        let body = text.trim_start_matches(' ');
+        let indent = text.len() - body.len();
+        let blank = body.trim().is_empty();
+        match open {
+            Some(Open::Fence(marker, len)) => {
+                if indent <= 3
+                    && fence(body)
+                        .is_some_and(|(m, l, info)| m == marker && l >= len && info.is_empty())
+                {
+                    open = None;
+                }
+            }
+            Some(Open::Html(end)) => {
+                if contains_ignore_case(text, end) {
+                    open = None;
+                }
+            }
+            None => {
+                // 以 `---`、`+++` 或 `{` 开头的块可能被当作新的 front matter
+                if previous_blank
+                    && !blank
+                    && indent == 0
+                    && start - chunk_start >= target
+                    && !body.starts_with(['-', '+', '{', '\u{feff}'])
+                {
+                    chunks.push(&markdown[chunk_start..start]);
+                    chunk_start = start;
+                }
+                if indent <= 3 {
+                    if let Some((marker, len, _)) = fence(body) {
+                        open = Some(Open::Fence(marker, len));
+                    } else if let Some(end) = html_end(body)
+                        && !contains_ignore_case(body, end)
+                    {
+                        open = Some(Open::Html(end));
+                    }
+                }
+            }
+        }
+        previous_blank = blank;
+    }
+    chunks.push(&markdown[chunk_start..]);
+    chunks
+}
+
+/// 顶层的链接引用定义，例如 `[id]: https://example.com`
+fn reference_definitions(markdown: &str) -> String {
+    let mut definitions = String::new();
+    let mut open = None;
+    let mut can_start = true;
+    for line in markdown.lines() {
+        let body = line.trim_start_matches(' ');
+        let indent = line.len() - body.len();
+        if let Some(Open::Fence(marker, len)) = open {
+            if fence(body).is_some_and(|(m, l, info)| m == marker && l >= len && info.is_empty()) {
+                open = None;
+            }
+            continue;
+        }
+        if indent <= 3
+            && let Some((marker, len, _)) = fence(body)
+        {
+            open = Some(Open::Fence(marker, len));
+            continue;
+        }
+        let is_definition = indent <= 3
+            && body.starts_with('[')
+            && body
+                .find("]:")
+                .is_some_and(|end| end > 1 && !body[1..end].contains(']'));
+        if is_definition && can_start {
+            definitions.push_str(body);
+            definitions.push('\n');
+        }
+        // 链接引用定义不能打断段落
+        can_start = line.trim().is_empty() || (is_definition && can_start);
+    }
+    definitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_many_parallel() {
+        let docs: Vec<String> = (1..=50).map(|n| "字".repeat(n)).collect();
+        let speed = ReadSpeed::default();
+        let results = estimate_many_parallel(&docs, &speed);
+        assert_eq!(results.len(), 50);
+        for (n, read_time) in results.iter().enumerate() {
+            assert_eq!(read_time.word_count, n + 1);
+        }
+    }
+
+    const SECTION: &str = r#"## 章节
+
+这是一段正文，参见 [参考资料][ref] 和 ![图片](a.png)。
+
+```rust
+fn main() {
+
+    // 空行之后仍在代码块中
+
+}
+```
+
+<!-- 注释
+
+不计入的内容
+-->
+
+- 列表项
+
+  继续的内容
+
+    缩进代码
+
+---
+
+Setext 标题
+===========
+
+"#;
+
+    #[test]
+    fn test_estimate_chunked_matches_sequential() {
+        let markdown = format!(
+            "---\ntitle: 标题\n\nsummary: 摘要\n---\n{}[ref]: https://example.com\n",
+            SECTION.repeat(40)
+        );
+        let chunks = split_blocks(&markdown, 200);
+        assert!(chunks.len() > 30);
+        assert_eq!(chunks.concat(), markdown);
+        for chunk in &chunks[1..] {
+            assert!(chunk.starts_with("## ") || chunk.starts_with("Setext"));
+        }
+        for speed in [
+            ReadSpeed::default(),
+            ReadSpeed::default().chinese(false),
+            ReadSpeed::default().link_time(2.0).dedupe_links(true),
+        ] {
+            assert_eq!(
+                estimate_chunked(&markdown, &speed, 200),
+                estimate_with_speed(&markdown, &speed)
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_blocks_unclosed() {
+        let markdown = "段落\n\n```\n代码\n\n未关闭\n\n<pre>\n\n正文\n";
+        assert_eq!(
+            split_blocks(markdown, 1),
+            ["段落\n\n", "```\n代码\n\n未关闭\n\n<pre>\n\n正文\n"]
+        );
+        assert_eq!(
+            reference_definitions("文字\n[a]: /x\n\n[b]: /y\n[c]: /z"),
+            "[b]: /y\n[c]: /z\n"
+        );
+    }
+}
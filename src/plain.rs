@@ -0,0 +1,71 @@
+use crate::estimator::Counters;
+use crate::utils::{count_english_words, count_words};
+use crate::{ReadSpeed, ReadTime};
+
+/// 估算纯文本的阅读时间
+///
+/// 使用默认的阅读速度配置。不进行 Markdown 解析，整段文本按配置的语言规则计数，
+/// `#`、`*`、缩进等都按普通字符处理，适合评论、聊天消息等非 Markdown 内容。
+///
+/// # Arguments
+///
+/// * `text` - 需要估算阅读时间的纯文本
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体，图片和代码块数量始终为 0。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate, estimate_plain_text};
+///
+/// let text = "# 不是标题\n\n    也不是代码块";
+/// assert_eq!(estimate_plain_text(text).word_count, 11);
+/// assert_eq!(estimate(text).word_count, 4);
+/// ```
+pub fn estimate_plain_text(text: &str) -> ReadTime {
+    estimate_plain_text_with_speed(text, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算纯文本的阅读时间
+///
+/// # Arguments
+///
+/// * `text` - 需要估算阅读时间的纯文本
+/// * `speed` - 自定义的阅读速度配置，图片和代码块相关的配置不会生效
+///
+/// # Returns
+///
+/// 返回包含阅读时间信息的 [`ReadTime`] 结构体。
+pub fn estimate_plain_text_with_speed(text: &str, speed: &ReadSpeed) -> ReadTime {
+    let word_count = if speed.chinese {
+        count_words(text, speed.count_emoji)
+    } else {
+        count_english_words(text, speed.count_emoji)
+    };
+    let counters = Counters {
+        word_count,
+        ..Counters::default()
+    };
+    ReadTime::from_counters(counters, speed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_plain_text() {
+        let speed = ReadSpeed::default().chinese(false);
+        let read_time = estimate_plain_text_with_speed("**bold** ![img](a.png) `x`", &speed);
+        assert_eq!(read_time.word_count, 3);
+        assert_eq!(read_time.image_count, 0);
+        assert_eq!(read_time.code_block_count, 0);
+
+        let read_time = estimate_plain_text("```\n代码\n```");
+        assert_eq!(read_time.word_count, 8);
+        assert_eq!(read_time.code_block_count, 0);
+        assert_eq!(estimate_plain_text(""), ReadTime::default());
+    }
+}
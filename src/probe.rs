@@ -0,0 +1,318 @@
+//! 探测远程图片和视频的元数据
+use crate::{Counters, EventVisitor, Visit};
+use pulldown_cmark::{Event, Tag};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// 探测结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Asset {
+    /// 按普通图片或链接计时
+    Normal,
+    /// 体积很小的装饰图片，例如徽章和占位图
+    Decorative,
+    /// 视频，时长未知时为 `None`
+    Video(Option<f64>),
+}
+
+/// 通过网络请求远程资源的元数据来调整图片和视频的时间，需要启用 `http` 特性
+///
+/// 作为 [`EventVisitor`] 使用，只有显式注册时才会发出请求：
+///
+/// - 以 `http` 或 `https` 地址引用的图片发送 `HEAD` 请求：`Content-Type` 为 `video/*` 时按视频计时，
+///   `Content-Length` 小于 [`icon_bytes`](Self::icon_bytes) 的图片（徽章、占位图等）视为装饰，不计为图片；
+/// - 指向 YouTube 或 Vimeo 视频的链接请求 oEmbed 接口，响应中带有 `duration` 时按视频时长计时，
+///   否则使用 [`video_time`](Self::video_time)。YouTube 的 oEmbed 目前不提供时长；
+/// - 视频记为 `video` 自定义元素，其他链接不会发出请求。
+///
+/// 请求失败、超时或状态码表示错误时按普通图片或链接计时。每个地址只请求一次，
+/// 结果缓存在访问器中，估算多篇文档时可以复用同一个实例。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{Estimator, ReadSpeed, RemoteAssets};
+///
+/// let read_time = Estimator::new(ReadSpeed::default())
+///     .visitor(RemoteAssets::new().video_time(120.0))
+///     .estimate("![构建状态](https://img.shields.io/badge/build-passing-green)\n\n\
+///                [演示视频](https://vimeo.com/76979871)");
+/// println!("{}", read_time.formatted);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteAssets {
+    video_seconds: f64,
+    icon_bytes: u64,
+    timeout: Duration,
+    cache: BTreeMap<String, Asset>,
+}
+
+impl Default for RemoteAssets {
+    fn default() -> Self {
+        Self {
+            video_seconds: 60.0,
+            icon_bytes: 2048,
+            timeout: Duration::from_secs(5),
+            cache: BTreeMap::new(),
+        }
+    }
+}
+
+impl RemoteAssets {
+    /// 创建访问器
+    ///
+    /// 默认时长未知的视频计 60 秒，小于 2 KiB 的图片视为装饰，每个请求最长等待 5 秒。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置时长未知的视频的时间（秒）
+    pub fn video_time(mut self, seconds: f64) -> Self {
+        self.video_seconds = seconds;
+        self
+    }
+
+    /// 设置装饰图片的字节数上限，为 0 时不识别装饰图片
+    pub fn icon_bytes(mut self, bytes: u64) -> Self {
+        self.icon_bytes = bytes;
+        self
+    }
+
+    /// 设置每个请求的超时时间
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn agent(&self) -> ureq::Agent {
+        ureq::Agent::new_with_config(
+            ureq::Agent::config_builder()
+                .timeout_global(Some(self.timeout))
+                .build(),
+        )
+    }
+
+    /// 探测图片地址，结果会被缓存
+    fn image(&mut self, url: &str) -> Asset {
+        if let Some(&asset) = self.cache.get(url) {
+            return asset;
+        }
+        let asset = match self.agent().head(url).call() {
+            Ok(response) => {
+                let headers = response.headers();
+                let header = |name: &str| {
+                    headers
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::trim)
+                };
+                let length = header("content-length").and_then(|value| value.parse::<u64>().ok());
+                let is_video = header("content-type")
+                    .is_some_and(|value| value.to_ascii_lowercase().starts_with("video/"));
+                if is_video {
+                    Asset::Video(None)
+                } else if length.is_some_and(|length| length < self.icon_bytes) {
+                    Asset::Decorative
+                } else {
+                    Asset::Normal
+                }
+            }
+            Err(_) => Asset::Normal,
+        };
+        self.cache.insert(String::from(url), asset);
+        asset
+    }
+
+    /// 探测视频页面的链接，结果会被缓存
+    fn link(&mut self, url: &str) -> Asset {
+        let Some(endpoint) = oembed_endpoint(url) else {
+            return Asset::Normal;
+        };
+        if let Some(&asset) = self.cache.get(url) {
+            return asset;
+        }
+        let duration = self
+            .agent()
+            .get(&endpoint)
+            .call()
+            .ok()
+            .and_then(|mut response| response.body_mut().read_to_string().ok())
+            .and_then(|body| json_number(&body, "duration"));
+        let asset = Asset::Video(duration);
+        self.cache.insert(String::from(url), asset);
+        asset
+    }
+}
+
+impl EventVisitor for RemoteAssets {
+    fn visit(&mut self, event: &Event<'_>, _: &Counters) -> Visit {
+        let (asset, is_image) = match event {
+            Event::Start(Tag::Image { dest_url, .. }) if is_remote(dest_url) => {
+                (self.image(dest_url), true)
+            }
+            Event::Start(Tag::Link { dest_url, .. }) => (self.link(dest_url), false),
+            _ => return Visit::count(),
+        };
+        match asset {
+            Asset::Normal => Visit::count(),
+            Asset::Decorative => Visit::skip(),
+            Asset::Video(duration) => {
+                let seconds = duration.unwrap_or(self.video_seconds);
+                // 嵌入的视频不再计为图片
+                let visit = if is_image {
+                    Visit::skip()
+                } else {
+                    Visit::count()
+                };
+                visit.extra_seconds(seconds).tag("video")
+            }
+        }
+    }
+}
+
+fn is_remote(url: &str) -> bool {
+    ["http://", "https://"].iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// 视频页面对应的 oEmbed 接口地址，不是 YouTube 或 Vimeo 视频时返回 `None`
+fn oembed_endpoint(url: &str) -> Option<String> {
+    if !is_remote(url) {
+        return None;
+    }
+    let rest = &url[url.find("://")? + 3..];
+    let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let host = host.to_ascii_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(&host);
+    let endpoint = match host {
+        "youtube.com" if path.starts_with("/watch?") || path.starts_with("/shorts/") => {
+            "https://www.youtube.com/oembed?format=json&url="
+        }
+        "youtu.be" if path.len() > 1 => "https://www.youtube.com/oembed?format=json&url=",
+        "vimeo.com"
+            if path
+                .trim_start_matches('/')
+                .starts_with(|c: char| c.is_ascii_digit()) =>
+        {
+            "https://vimeo.com/api/oembed.json?url="
+        }
+        _ => return None,
+    };
+    let mut endpoint = String::from(endpoint);
+    for byte in url.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            endpoint.push(byte as char);
+        } else {
+            let _ = write!(endpoint, "%{:02X}", byte);
+        }
+    }
+    Some(endpoint)
+}
+
+/// JSON 对象中 `key` 对应的数值，只用于读取 oEmbed 响应的顶层字段
+fn json_number(json: &str, key: &str) -> Option<f64> {
+    let quoted = format!("\"{}\"", key);
+    let rest = &json[json.find(&quoted)? + quoted.len()..];
+    let rest = rest.trim_start().strip_prefix(':')?.trim_start();
+    let end = rest
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Estimator, ReadSpeed};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// 在本地端口上依次应答请求，响应头由请求路径决定
+    fn serve(responses: &'static [(&'static str, &'static str)]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let headers = responses
+                    .iter()
+                    .find(|(prefix, _)| path == *prefix)
+                    .map_or("HTTP/1.1 404 Not Found\r\nContent-Length: 0", |(_, h)| h);
+                let _ = write!(stream, "{}\r\nConnection: close\r\n\r\n", headers);
+            }
+        });
+        base
+    }
+
+    #[test]
+    fn test_remote_assets() {
+        let base = serve(&[
+            (
+                "/badge.svg",
+                "HTTP/1.1 200 OK\r\nContent-Type: image/svg+xml\r\nContent-Length: 900",
+            ),
+            (
+                "/photo.jpg",
+                "HTTP/1.1 200 OK\r\nContent-Type: image/jpeg\r\nContent-Length: 250000",
+            ),
+            (
+                "/clip.mp4",
+                "HTTP/1.1 200 OK\r\nContent-Type: video/mp4\r\nContent-Length: 9000000",
+            ),
+        ]);
+        let markdown = format!(
+            "![]({base}/badge.svg) ![]({base}/badge.svg)\n\n![]({base}/photo.jpg)\n\n\
+             ![]({base}/clip.mp4)\n\n![]({base}/missing.png)\n\n![](local.png)\n\n\
+             [文档]({base}/docs)"
+        );
+        let mut assets = RemoteAssets::new().video_time(90.0);
+        let read_time = Estimator::new(ReadSpeed::default())
+            .visitor(|event: &Event<'_>, counters: &Counters| assets.visit(event, counters))
+            .estimate(&markdown);
+        assert_eq!(read_time.image_count, 3);
+        assert_eq!(read_time.custom_elements["video"], 1);
+        assert_eq!(read_time.extra_seconds, 90.0);
+        assert_eq!(assets.cache.len(), 4);
+    }
+
+    #[test]
+    fn test_oembed_endpoint() {
+        assert_eq!(
+            oembed_endpoint("https://www.youtube.com/watch?v=abc").as_deref(),
+            Some(
+                "https://www.youtube.com/oembed?format=json&url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3Dabc"
+            )
+        );
+        assert!(oembed_endpoint("https://youtu.be/abc").is_some());
+        assert_eq!(
+            oembed_endpoint("https://vimeo.com/76979871").as_deref(),
+            Some("https://vimeo.com/api/oembed.json?url=https%3A%2F%2Fvimeo.com%2F76979871")
+        );
+        assert_eq!(oembed_endpoint("https://vimeo.com/about"), None);
+        assert_eq!(oembed_endpoint("https://www.youtube.com/about"), None);
+        assert_eq!(oembed_endpoint("https://example.com/watch?v=abc"), None);
+    }
+
+    #[test]
+    fn test_json_number() {
+        let json = r#"{"type": "video", "title": "演示", "duration" : 1234, "width": 640}"#;
+        assert_eq!(json_number(json, "duration"), Some(1234.0));
+        assert_eq!(json_number(json, "height"), None);
+        assert_eq!(json_number(r#"{"title": "duration"}"#, "duration"), None);
+    }
+}
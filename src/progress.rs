@@ -0,0 +1,137 @@
+use crate::utils::{ceil_seconds, format_time};
+use crate::{ReadSpeed, ReadingCurve, estimate_curve_with_speed};
+
+/// 阅读进度
+///
+/// 可以是源文本中的字节偏移，也可以是在文档中的位置比例（0.0 到 1.0，例如滚动比例）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Progress {
+    /// 源文本中的字节偏移
+    Offset(usize),
+
+    /// 在文档中的位置比例，超出 0.0 到 1.0 的值会被截断
+    Fraction(f64),
+}
+
+impl From<usize> for Progress {
+    fn from(offset: usize) -> Self {
+        Progress::Offset(offset)
+    }
+}
+
+impl From<f64> for Progress {
+    fn from(fraction: f64) -> Self {
+        Progress::Fraction(fraction)
+    }
+}
+
+/// 剩余阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct RemainingTime {
+    /// 剩余秒数，向上取整
+    pub seconds: u64,
+
+    /// 格式化后的剩余时间，例如 "3分钟"
+    pub formatted: String,
+}
+
+impl RemainingTime {
+    pub fn seconds(&self) -> u64 {
+        self.seconds
+    }
+
+    pub fn formatted(&self) -> &str {
+        &self.formatted
+    }
+}
+
+/// 根据阅读进度计算剩余时间
+///
+/// 基于 [`ReadingCurve`]，构建一次后可以在每次滚动时廉价地查询。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::ReadTimeProgress;
+///
+/// let markdown = "# 标题\n\n第一段内容\n\n第二段内容\n";
+/// let progress = ReadTimeProgress::new(markdown);
+///
+/// // 按滚动比例查询
+/// println!("大约还剩 {}", progress.remaining(0.5).formatted);
+/// // 按源文本偏移查询
+/// assert_eq!(progress.remaining(markdown.len()).seconds, 0);
+/// assert_eq!(progress.remaining(0).seconds, 4);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadTimeProgress {
+    curve: ReadingCurve,
+    len: usize,
+}
+
+impl ReadTimeProgress {
+    /// 使用默认的阅读速度配置
+    pub fn new(markdown: &str) -> Self {
+        Self::with_speed(markdown, &ReadSpeed::default())
+    }
+
+    pub fn with_speed(markdown: &str, speed: &ReadSpeed) -> Self {
+        Self::from_curve(estimate_curve_with_speed(markdown, speed), markdown.len())
+    }
+
+    /// 使用已有的曲线，`len` 为源文本的字节长度，用于把位置比例换算为偏移
+    pub fn from_curve(curve: ReadingCurve, len: usize) -> Self {
+        Self { curve, len }
+    }
+
+    pub fn curve(&self) -> &ReadingCurve {
+        &self.curve
+    }
+
+    /// 读到 `progress` 时的剩余阅读时间
+    pub fn remaining(&self, progress: impl Into<Progress>) -> RemainingTime {
+        let offset = match progress.into() {
+            Progress::Offset(offset) => offset,
+            Progress::Fraction(fraction) => {
+                let fraction = if fraction.is_nan() {
+                    0.0
+                } else {
+                    fraction.clamp(0.0, 1.0)
+                };
+                (self.len as f64 * fraction) as usize
+            }
+        };
+        let left = self.curve.total_seconds() - self.curve.seconds_at(offset);
+        let seconds = ceil_seconds(left.max(0.0));
+        RemainingTime {
+            seconds,
+            formatted: format_time(seconds),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_remaining() {
+        let markdown = "# 标题\n\n段落内容\n\n![图片](a.png)\n";
+        let progress = ReadTimeProgress::new(markdown);
+
+        let start = progress.remaining(0);
+        assert_eq!(start.seconds, estimate(markdown).total_seconds);
+        assert_eq!(start.formatted, estimate(markdown).formatted);
+        assert_eq!(progress.remaining(0.0), start);
+
+        let image = markdown.find('!').unwrap();
+        assert_eq!(progress.remaining(image).seconds, 12);
+        assert_eq!(progress.remaining(1.0).seconds, 0);
+        assert_eq!(progress.remaining(2.0).seconds, 0);
+        assert_eq!(progress.remaining(f64::NAN), start);
+    }
+}
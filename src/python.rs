@@ -0,0 +1,157 @@
+//! PyO3 绑定
+//!
+//! 使用 maturin 构建后即可在 Python 中调用：
+//!
+//! ```python
+//! import markdown_readtime
+//!
+//! speed = markdown_readtime.ReadSpeed(words_per_minute=250, chinese=False)
+//! result = markdown_readtime.estimate("# Title\n\nSome content", speed)
+//! print(result.formatted, result.word_count)
+//! ```
+use crate::{ReadSpeed, ReadTime};
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+/// Python 中的阅读速度配置
+#[pyclass(name = "ReadSpeed", module = "markdown_readtime", from_py_object)]
+#[derive(Debug, Clone, Copy)]
+pub struct PyReadSpeed(ReadSpeed);
+
+#[pymethods]
+impl PyReadSpeed {
+    #[new]
+    #[pyo3(signature = (
+        words_per_minute = None,
+        seconds_per_image = None,
+        seconds_per_code_block = None,
+        count_emoji = None,
+        chinese = None,
+    ))]
+    fn new(
+        words_per_minute: Option<f64>,
+        seconds_per_image: Option<f64>,
+        seconds_per_code_block: Option<f64>,
+        count_emoji: Option<bool>,
+        chinese: Option<bool>,
+    ) -> Self {
+        let mut speed = ReadSpeed::default();
+        if let Some(wpm) = words_per_minute {
+            speed = speed.wpm(wpm);
+        }
+        if let Some(seconds) = seconds_per_image {
+            speed = speed.image_time(seconds);
+        }
+        if let Some(seconds) = seconds_per_code_block {
+            speed = speed.code_block_time(seconds);
+        }
+        if let Some(count) = count_emoji {
+            speed = speed.emoji(count);
+        }
+        if let Some(is_chinese) = chinese {
+            speed = speed.chinese(is_chinese);
+        }
+        Self(speed)
+    }
+
+    #[getter]
+    fn words_per_minute(&self) -> f64 {
+        self.0.words_per_minute
+    }
+
+    #[getter]
+    fn seconds_per_image(&self) -> f64 {
+        self.0.seconds_per_image
+    }
+
+    #[getter]
+    fn seconds_per_code_block(&self) -> f64 {
+        self.0.seconds_per_code_block
+    }
+
+    #[getter]
+    fn count_emoji(&self) -> bool {
+        self.0.count_emoji
+    }
+
+    #[getter]
+    fn chinese(&self) -> bool {
+        self.0.chinese
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+/// Python 中的阅读时间估算结果
+#[pyclass(
+    name = "ReadTime",
+    module = "markdown_readtime",
+    frozen,
+    skip_from_py_object
+)]
+#[derive(Debug, Clone)]
+pub struct PyReadTime(ReadTime);
+
+#[pymethods]
+impl PyReadTime {
+    #[getter]
+    fn total_seconds(&self) -> u64 {
+        self.0.total_seconds
+    }
+
+    #[getter]
+    fn formatted(&self) -> &str {
+        &self.0.formatted
+    }
+
+    #[getter]
+    fn word_count(&self) -> usize {
+        self.0.word_count
+    }
+
+    #[getter]
+    fn image_count(&self) -> usize {
+        self.0.image_count
+    }
+
+    #[getter]
+    fn code_block_count(&self) -> usize {
+        self.0.code_block_count
+    }
+
+    #[getter]
+    fn extra_seconds(&self) -> f64 {
+        self.0.extra_seconds
+    }
+
+    #[getter]
+    fn custom_elements(&self) -> BTreeMap<String, usize> {
+        self.0.custom_elements.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ReadTime(total_seconds={}, formatted={:?}, word_count={})",
+            self.0.total_seconds, self.0.formatted, self.0.word_count
+        )
+    }
+}
+
+/// 估算 Markdown 的阅读时间，未传入 `speed` 时使用默认配置
+#[pyfunction]
+#[pyo3(signature = (markdown, speed = None))]
+fn estimate(py: Python<'_>, markdown: &str, speed: Option<PyReadSpeed>) -> PyReadTime {
+    let speed = speed.map(|speed| speed.0).unwrap_or_default();
+    PyReadTime(py.detach(|| crate::estimate_with_speed(markdown, &speed)))
+}
+
+/// Python 模块入口
+#[pymodule]
+fn markdown_readtime(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyReadSpeed>()?;
+    m.add_class::<PyReadTime>()?;
+    m.add_function(wrap_pyfunction!(estimate, m)?)?;
+    Ok(())
+}
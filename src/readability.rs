@@ -0,0 +1,391 @@
+//! 可读性指数
+//!
+//! 在估算阅读时间的同一次遍历中统计计入字数的英文正文：
+//!
+//! - 单词为连续的字母，单词内部的撇号不拆分，中日韩字符、数字和标点不计入；
+//! - 句子以 `.`、`!`、`?` 及对应的全角标点结束（后面紧跟字母或数字时不算，例如 `3.14`），
+//!   段落、标题、列表项和表格单元格的末尾也结束一个句子；
+//! - 音节按元音字母组数估算，词尾不发音的 `e` 不计入，每个单词至少一个音节。
+//!
+//! 行内代码、代码块和图片的替代文本不参与统计。
+use crate::chars::is_cjk;
+use crate::estimator::Counters;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, TagEnd};
+
+/// 可读性指数，结果均为大致对应的美国学年
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ReadabilityIndex {
+    /// Gunning Fog：`0.4 × (单词数 / 句子数 + 100 × 三音节以上的单词数 / 单词数)`
+    GunningFog,
+
+    /// SMOG：`1.043 × √(三音节以上的单词数 × 30 / 句子数) + 3.1291`
+    Smog,
+
+    /// Coleman-Liau：`0.0588 × 每百词字母数 − 0.296 × 每百词句子数 − 15.8`
+    ColemanLiau,
+}
+
+impl ReadabilityIndex {
+    /// 所有支持的指数
+    pub const ALL: [ReadabilityIndex; 3] = [
+        ReadabilityIndex::GunningFog,
+        ReadabilityIndex::Smog,
+        ReadabilityIndex::ColemanLiau,
+    ];
+}
+
+/// 计算可读性指数所需的统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ReadabilityStats {
+    /// 句子数
+    pub sentences: usize,
+
+    /// 单词数
+    pub words: usize,
+
+    /// 字母数
+    pub letters: usize,
+
+    /// 音节数
+    pub syllables: usize,
+
+    /// 三个音节以上的单词数
+    pub polysyllables: usize,
+}
+
+impl ReadabilityStats {
+    pub fn sentences(&self) -> usize {
+        self.sentences
+    }
+
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    pub fn letters(&self) -> usize {
+        self.letters
+    }
+
+    pub fn syllables(&self) -> usize {
+        self.syllables
+    }
+
+    pub fn polysyllables(&self) -> usize {
+        self.polysyllables
+    }
+
+    /// 按 `index` 计算可读性指数，没有英文单词时返回 `None`
+    pub fn score(&self, index: ReadabilityIndex) -> Option<f64> {
+        if self.words == 0 || self.sentences == 0 {
+            return None;
+        }
+        let words = self.words as f64;
+        let sentences = self.sentences as f64;
+        let polysyllables = self.polysyllables as f64;
+        let score = match index {
+            ReadabilityIndex::GunningFog => {
+                0.4 * (words / sentences + 100.0 * polysyllables / words)
+            }
+            ReadabilityIndex::Smog => 1.043 * (polysyllables * 30.0 / sentences).sqrt() + 3.1291,
+            ReadabilityIndex::ColemanLiau => {
+                let letters = self.letters as f64 / words * 100.0;
+                0.0588 * letters - 0.296 * (sentences / words * 100.0) - 15.8
+            }
+        };
+        Some(score)
+    }
+}
+
+/// 一项可读性指数的结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct ReadabilityScore {
+    /// 指数类型
+    pub index: ReadabilityIndex,
+
+    /// 指数值
+    pub score: f64,
+}
+
+/// 带可读性指数的估算结果
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ReadabilityReadTime {
+    /// 与 [`estimate`](crate::estimate) 相同的估算结果
+    pub read_time: ReadTime,
+
+    /// 英文正文的统计
+    pub stats: ReadabilityStats,
+
+    /// 按请求的顺序排列的指数，没有英文单词时为空
+    pub scores: Vec<ReadabilityScore>,
+}
+
+impl ReadabilityReadTime {
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+
+    pub fn stats(&self) -> &ReadabilityStats {
+        &self.stats
+    }
+
+    pub fn scores(&self) -> &[ReadabilityScore] {
+        &self.scores
+    }
+
+    /// 指定指数的值，未请求或没有英文单词时返回 `None`
+    pub fn score(&self, index: ReadabilityIndex) -> Option<f64> {
+        self.scores
+            .iter()
+            .find(|score| score.index == index)
+            .map(|score| score.score)
+    }
+}
+
+/// 按元音字母组数估算单词的音节数
+fn syllables(word: &str) -> usize {
+    let is_vowel = |c: char| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+    let mut count = 0;
+    let mut previous = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous {
+            count += 1;
+        }
+        previous = vowel;
+    }
+    let lower = word.to_ascii_lowercase();
+    // 词尾不发音的 e，例如 make；以辅音加 le 结尾时发音，例如 table
+    if count > 1 && lower.ends_with('e') && !lower.ends_with("le") {
+        count -= 1;
+    }
+    count.max(1)
+}
+
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？')
+}
+
+/// 统计计入字数的正文文本
+#[derive(Default)]
+struct StatsCollector {
+    stats: ReadabilityStats,
+    /// 上一个事件之前的计数
+    last_words: usize,
+    /// 上一个事件的正文文本，下一个事件时根据字数是否增加判断它是否被计数
+    pending: Option<String>,
+    /// 当前的单词
+    word: String,
+    /// 当前句子中是否已有单词
+    in_sentence: bool,
+}
+
+impl StatsCollector {
+    fn observe(&mut self, event: &Event<'_>, counters: &Counters) {
+        self.settle(counters);
+        match event {
+            Event::Text(text) => self.pending = Some(text.to_string()),
+            Event::End(
+                TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell,
+            ) => self.end_sentence(),
+            _ => {}
+        }
+    }
+
+    fn settle(&mut self, counters: &Counters) {
+        let words = std::mem::replace(&mut self.last_words, counters.word_count);
+        if let Some(text) = self.pending.take()
+            && counters.word_count > words
+        {
+            self.push_text(&text);
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c.is_alphabetic() && !is_cjk(c) {
+                self.word.push(c);
+                continue;
+            }
+            if matches!(c, '\'' | '’') && !self.word.is_empty() {
+                continue;
+            }
+            self.push_word();
+            if is_sentence_end(c) && !chars.peek().is_some_and(|next| next.is_alphanumeric()) {
+                self.end_sentence();
+            }
+        }
+        self.push_word();
+    }
+
+    fn push_word(&mut self) {
+        if self.word.is_empty() {
+            return;
+        }
+        let syllables = syllables(&self.word);
+        self.stats.words += 1;
+        self.stats.letters += self.word.chars().count();
+        self.stats.syllables += syllables;
+        if syllables >= 3 {
+            self.stats.polysyllables += 1;
+        }
+        self.in_sentence = true;
+        self.word.clear();
+    }
+
+    fn end_sentence(&mut self) {
+        self.push_word();
+        if std::mem::take(&mut self.in_sentence) {
+            self.stats.sentences += 1;
+        }
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算阅读时间并计算 `indices` 中的可读性指数
+    pub fn estimate_readability(
+        &mut self,
+        markdown: &str,
+        indices: &[ReadabilityIndex],
+    ) -> ReadabilityReadTime {
+        let speed = *self.speed();
+        let mut collector = StatsCollector::default();
+        let counters = self.count(markdown, &mut |event, _, counters| {
+            collector.observe(event, counters)
+        });
+        collector.settle(&counters);
+        collector.end_sentence();
+
+        let stats = collector.stats;
+        let scores = indices
+            .iter()
+            .filter_map(|&index| {
+                let score = stats.score(index)?;
+                Some(ReadabilityScore { index, score })
+            })
+            .collect();
+        ReadabilityReadTime {
+            read_time: ReadTime::from_counters(counters, &speed),
+            stats,
+            scores,
+        }
+    }
+}
+
+/// 估算阅读时间并计算可读性指数
+///
+/// 使用默认的阅读速度配置。各项指数在估算的同一次遍历中统计，教育和无障碍团队
+/// 可以按各自采用的标准选择指数，统计规则参见模块说明。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `indices` - 需要计算的指数，[`ReadabilityIndex::ALL`] 为全部
+///
+/// # Returns
+///
+/// 返回包含估算结果、统计和指数的 [`ReadabilityReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadabilityIndex, estimate_readability};
+///
+/// let result = estimate_readability(
+///     "The cat sat on the mat. It was a sunny day.",
+///     &[ReadabilityIndex::GunningFog],
+/// );
+/// assert_eq!(result.stats.sentences, 2);
+/// assert_eq!(result.stats.words, 11);
+/// let fog = result.score(ReadabilityIndex::GunningFog).unwrap();
+/// assert!((fog - 2.2).abs() < 1e-9);
+/// assert_eq!(result.score(ReadabilityIndex::Smog), None);
+/// ```
+pub fn estimate_readability(markdown: &str, indices: &[ReadabilityIndex]) -> ReadabilityReadTime {
+    estimate_readability_with_speed(markdown, indices, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算阅读时间并计算可读性指数
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `indices` - 需要计算的指数，[`ReadabilityIndex::ALL`] 为全部
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含估算结果、统计和指数的 [`ReadabilityReadTime`]。
+pub fn estimate_readability_with_speed(
+    markdown: &str,
+    indices: &[ReadabilityIndex],
+    speed: &ReadSpeed,
+) -> ReadabilityReadTime {
+    Estimator::new(*speed).estimate_readability(markdown, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    fn assert_close(actual: Option<f64>, expected: f64) {
+        let actual = actual.unwrap();
+        assert!((actual - expected).abs() < 1e-3, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn test_syllables() {
+        for (word, expected) in [
+            ("the", 1),
+            ("make", 1),
+            ("table", 2),
+            ("readability", 5),
+            ("beautiful", 3),
+            ("rhythm", 1),
+        ] {
+            assert_eq!(syllables(word), expected, "{word}");
+        }
+    }
+
+    #[test]
+    fn test_estimate_readability() {
+        let markdown = "# Readability Notes\n\n\
+                        Readability formulas estimate difficulty. Version 3.14 is here!\n\n\
+                        - Simple words help\n\n\
+                        中文不计入。`inline code` is skipped.\n\n\
+                        ```\nignored code block.\n```\n";
+        let result = estimate_readability(markdown, &ReadabilityIndex::ALL);
+        assert_eq!(result.read_time, estimate(markdown));
+        // Readability Notes | Readability formulas estimate difficulty | Version is here
+        // | Simple words help | is skipped
+        assert_eq!(result.stats.sentences, 5);
+        assert_eq!(result.stats.words, 14);
+        assert_eq!(result.stats.letters, 90);
+        assert_eq!(result.stats.polysyllables, 5);
+
+        let scores: Vec<ReadabilityIndex> = result.scores.iter().map(|s| s.index).collect();
+        assert_eq!(scores, ReadabilityIndex::ALL);
+        assert_close(result.score(ReadabilityIndex::GunningFog), 15.405714);
+        assert_close(
+            result.score(ReadabilityIndex::Smog),
+            1.043 * 30.0_f64.sqrt() + 3.1291,
+        );
+        assert_close(result.score(ReadabilityIndex::ColemanLiau), 11.428571);
+
+        let chinese = estimate_readability("只有中文。", &ReadabilityIndex::ALL);
+        assert_eq!(chinese.stats, ReadabilityStats::default());
+        assert!(chinese.scores.is_empty());
+    }
+}
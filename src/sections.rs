@@ -0,0 +1,190 @@
+use crate::estimator::Counters;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Tag, TagEnd};
+use std::mem;
+use std::ops::Range;
+
+/// 单个章节的阅读时间
+///
+/// 章节从一个标题开始，到下一个任意级别的标题之前结束，标题本身计入所在章节。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct SectionReadTime {
+    /// 标题文本，第一个标题之前的内容为空字符串
+    pub heading: String,
+
+    /// 标题级别（1-6），第一个标题之前的内容为 0
+    pub level: u8,
+
+    /// 本章节的阅读时间
+    pub read_time: ReadTime,
+}
+
+impl SectionReadTime {
+    pub fn heading(&self) -> &str {
+        &self.heading
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.read_time.word_count
+    }
+
+    pub fn total_seconds(&self) -> u64 {
+        self.read_time.total_seconds
+    }
+}
+
+/// 章节的起点
+#[derive(Debug)]
+pub(crate) struct Boundary {
+    /// 标题文本在 [`Scratch::text`](crate::estimator::Scratch) 中的范围
+    heading: Range<usize>,
+    level: u8,
+    start: Counters,
+}
+
+impl Estimator<'_> {
+    /// 按标题拆分并估算每个章节的阅读时间
+    ///
+    /// 第一个标题之前有内容时，会返回一个标题为空、级别为 0 的章节。
+    /// 每个章节的总秒数单独向上取整，因此各章节之和可能略大于整篇文档的估算结果。
+    pub fn estimate_sections(&mut self, markdown: &str) -> Vec<SectionReadTime> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("sections", sections = tracing::field::Empty).entered();
+        let mut headings = mem::take(&mut self.scratch.text);
+        let mut boundaries = mem::take(&mut self.scratch.sections);
+        headings.clear();
+        boundaries.clear();
+        boundaries.push(Boundary {
+            heading: 0..0,
+            level: 0,
+            start: Counters::default(),
+        });
+        let mut in_heading = false;
+
+        let total = self.count(markdown, &mut |event, _, counters| match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                boundaries.push(Boundary {
+                    heading: headings.len()..headings.len(),
+                    level: *level as u8,
+                    start: counters.clone(),
+                });
+                in_heading = true;
+            }
+            Event::End(TagEnd::Heading(_)) => in_heading = false,
+            Event::Text(text) | Event::Code(text) if in_heading => {
+                headings.push_str(text);
+                if let Some(boundary) = boundaries.last_mut() {
+                    boundary.heading.end = headings.len();
+                }
+            }
+            _ => {}
+        });
+
+        let speed = *self.speed();
+        let ends = boundaries
+            .iter()
+            .skip(1)
+            .map(|boundary| &boundary.start)
+            .chain([&total]);
+        let sections = boundaries
+            .iter()
+            .zip(ends)
+            .filter_map(|(boundary, end)| {
+                let counters = end.since(&boundary.start);
+                if boundary.level == 0 && counters.is_empty() {
+                    return None;
+                }
+                Some(SectionReadTime {
+                    heading: String::from(headings[boundary.heading.clone()].trim()),
+                    level: boundary.level,
+                    read_time: ReadTime::from_counters(counters, &speed),
+                })
+            })
+            .collect::<Vec<_>>();
+        #[cfg(feature = "tracing")]
+        span.record("sections", sections.len());
+        self.scratch.text = headings;
+        self.scratch.sections = boundaries;
+        sections
+    }
+}
+
+/// 按标题拆分并估算每个章节的阅读时间
+///
+/// 使用默认的阅读速度配置。适合在文档站点的侧边栏中显示每个章节的阅读时间。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`SectionReadTime`] 列表。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_sections;
+///
+/// let sections = estimate_sections("# 简介\n\n一些内容\n\n## 安装\n\n更多内容");
+/// assert_eq!(sections.len(), 2);
+/// assert_eq!(sections[1].heading, "安装");
+/// assert_eq!(sections[1].level, 2);
+/// ```
+pub fn estimate_sections(markdown: &str) -> Vec<SectionReadTime> {
+    estimate_sections_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置按标题拆分并估算每个章节的阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`SectionReadTime`] 列表。
+pub fn estimate_sections_with_speed(markdown: &str, speed: &ReadSpeed) -> Vec<SectionReadTime> {
+    Estimator::new(*speed).estimate_sections(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_sections() {
+        let markdown = "前言内容\n\n# 第一章\n\n正文内容\n\n![图片](a.png)\n\n## `代码`小节\n\n```rust\nfn main() {}\n```\n";
+        let sections = estimate_sections(markdown);
+        assert_eq!(sections.len(), 3);
+
+        assert_eq!(sections[0].heading, "");
+        assert_eq!(sections[0].level, 0);
+        assert_eq!(sections[0].word_count(), 4);
+
+        assert_eq!(sections[1].heading, "第一章");
+        assert_eq!(sections[1].level, 1);
+        assert_eq!(sections[1].word_count(), 7);
+        assert_eq!(sections[1].read_time.image_count, 1);
+
+        assert_eq!(sections[2].heading, "代码小节");
+        assert_eq!(sections[2].level, 2);
+        assert_eq!(sections[2].read_time.code_block_count, 1);
+
+        let total: usize = sections.iter().map(SectionReadTime::word_count).sum();
+        assert_eq!(total, crate::words(markdown));
+        assert_eq!(estimate_sections("# 标题").len(), 1);
+    }
+}
@@ -0,0 +1,193 @@
+//! HTTP 服务
+//!
+//! 基于 axum 提供 `POST /estimate` 接口，可以作为独立的 sidecar 部署：
+//!
+//! ```sh
+//! cargo run --release --example serve --features serve -- 127.0.0.1:3000
+//! curl -X POST localhost:3000/estimate -H 'Content-Type: text/markdown' --data-binary @README.md
+//! curl -X POST localhost:3000/estimate -H 'Content-Type: application/json' \
+//!     -d '{"markdown": "# Title", "speed": {"words_per_minute": 250}}'
+//! ```
+//!
+//! 请求体为 JSON 时按 [`EstimateRequest`] 解析，否则整个请求体作为 Markdown 内容并使用默认配置。
+//! 成功时返回 [`ReadTime`] 的 JSON，请求体无效时返回 400 和 `{"error": "..."}`，
+//! 解码后的 Markdown 内容超出默认的 [`Limits`](crate::Limits) 时返回 413。
+//! 估算在阻塞线程池中进行，耗时较长的请求不会阻塞同一工作线程上的其他连接。
+use crate::{Limits, ReadSpeed, ReadTime, estimate_with_speed, try_estimate_with_speed};
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::{IntoResponse, Json, Response};
+use axum::routing::post;
+use serde::Deserialize;
+use std::io;
+use tokio::net::TcpListener;
+use tokio::task;
+
+/// `POST /estimate` 的 JSON 请求体
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+#[non_exhaustive]
+pub struct EstimateRequest {
+    /// Markdown 内容
+    pub markdown: String,
+    /// 阅读速度配置，缺失的字段使用默认值
+    pub speed: ReadSpeed,
+}
+
+impl EstimateRequest {
+    /// 解析请求体，`Content-Type` 为 JSON 时按 JSON 解析，否则作为 Markdown 内容
+    fn parse(content_type: Option<&str>, body: String) -> Result<Self, serde_json::Error> {
+        let is_json = content_type
+            .and_then(|value| value.split(';').next())
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+        if is_json {
+            serde_json::from_str(&body)
+        } else {
+            Ok(Self {
+                markdown: body,
+                ..Self::default()
+            })
+        }
+    }
+
+    /// 估算阅读时间
+    pub fn estimate(&self) -> ReadTime {
+        estimate_with_speed(&self.markdown, &self.speed)
+    }
+}
+
+/// JSON 请求体中 Markdown 内容以外的部分（键名、速度配置等）允许的字节数
+const JSON_OVERHEAD: usize = 64 * 1024;
+
+/// 创建包含 `POST /estimate` 路由的 [`Router`]，可以合并到已有的 axum 应用中
+///
+/// [`Limits::default`] 的 `max_input_bytes` 作用于解码后的 Markdown 内容。
+/// JSON 字符串中的一个字节转义后最多占 6 个字节（`\u001f`），
+/// 因此请求体的上限为它的 6 倍再加上 64 KiB，内容刚好不超出上限的 JSON 请求不会被拒绝。
+pub fn router() -> Router {
+    let body_limit = Limits::default()
+        .max_input_bytes
+        .map_or(DefaultBodyLimit::disable(), |max| {
+            DefaultBodyLimit::max(max.saturating_mul(6).saturating_add(JSON_OVERHEAD))
+        });
+    Router::new()
+        .route("/estimate", post(estimate_handler))
+        .layer(body_limit)
+}
+
+/// 在 `listener` 上启动 HTTP 服务，直到出错才返回
+///
+/// # Examples
+///
+/// ```no_run
+/// # tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(async {
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await.unwrap();
+/// markdown_readtime::serve::serve(listener).await.unwrap();
+/// # });
+/// ```
+pub async fn serve(listener: TcpListener) -> io::Result<()> {
+    axum::serve(listener, router()).await
+}
+
+async fn estimate_handler(headers: HeaderMap, body: String) -> Response {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let request = match EstimateRequest::parse(content_type, body) {
+        Ok(request) => request,
+        Err(err) => return error_response(StatusCode::BAD_REQUEST, &err),
+    };
+    let estimated = task::spawn_blocking(move || {
+        try_estimate_with_speed(&request.markdown, &request.speed, &Limits::default())
+    })
+    .await;
+    match estimated {
+        Ok(Ok(read_time)) => Json(read_time).into_response(),
+        Ok(Err(err)) => error_response(StatusCode::PAYLOAD_TOO_LARGE, &err),
+        Err(err) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &err),
+    }
+}
+
+fn error_response(status: StatusCode, err: &dyn std::fmt::Display) -> Response {
+    (
+        status,
+        Json(serde_json::json!({ "error": err.to_string() })),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+    use axum::http::HeaderValue;
+
+    fn headers(content_type: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+        headers
+    }
+
+    #[test]
+    fn test_estimate_handler() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let request = EstimateRequest::parse(Some("text/markdown"), "# 标题".into()).unwrap();
+            assert_eq!(request.estimate(), estimate("# 标题"));
+
+            let body = r#"{"markdown": "hello world", "speed": {"chinese": false}}"#;
+            let request =
+                EstimateRequest::parse(Some("application/json; charset=utf-8"), body.into())
+                    .unwrap();
+            assert_eq!(request.estimate().word_count, 2);
+
+            let response = estimate_handler(headers("application/json"), body.into()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let response = estimate_handler(headers("application/json"), "{".into()).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+            let nested = "> ".repeat(1000) + "内容";
+            let response = estimate_handler(headers("text/markdown"), nested).await;
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        });
+    }
+
+    #[test]
+    fn test_body_limit() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let max = Limits::default().max_input_bytes.unwrap();
+            let request = |content_type: &str, body: String| {
+                Request::post("/estimate")
+                    .header(header::CONTENT_TYPE, content_type)
+                    .body(Body::from(body))
+                    .unwrap()
+            };
+            let markdown = |len: usize| request("text/markdown", "a".repeat(len));
+            let response = router().oneshot(markdown(max)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let response = router().oneshot(markdown(max + 1)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+            // 转义后的请求体超过上限，但解码后的内容没有超出
+            let json = |len: usize| {
+                let body = serde_json::json!({ "markdown": "\u{1}".repeat(len) });
+                request("application/json", body.to_string())
+            };
+            let response = router().oneshot(json(max)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let response = router().oneshot(json(max + 1)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        });
+    }
+}
@@ -0,0 +1,148 @@
+use crate::blocks::merge_blocks;
+use crate::{BlockReadTime, Estimator, ReadSpeed, ReadTime};
+use std::ops::Range;
+
+/// 一次阅读的内容
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ReadingSession {
+    /// 在源文本中的字节范围，相邻的范围首尾相接并覆盖整篇文档
+    pub range: Range<usize>,
+
+    /// 本次阅读的时间
+    pub read_time: ReadTime,
+}
+
+impl ReadingSession {
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+}
+
+/// 按目标时长把块列表分组，`len` 为源文本的字节长度
+fn split_blocks(
+    blocks: &[BlockReadTime],
+    target_seconds: u64,
+    len: usize,
+    speed: &ReadSpeed,
+) -> Vec<ReadingSession> {
+    let target = target_seconds as f64;
+    let mut sessions = Vec::new();
+    let mut start = 0;
+    let mut current: Vec<&BlockReadTime> = Vec::new();
+    let mut seconds = 0.0;
+
+    for block in blocks {
+        if !current.is_empty() && seconds + block.seconds > target {
+            let end = block.range.start.max(start);
+            sessions.push(session(start..end, &current, speed));
+            start = end;
+            current.clear();
+            seconds = 0.0;
+        }
+        current.push(block);
+        seconds += block.seconds;
+    }
+    if !current.is_empty() {
+        sessions.push(session(start..len.max(start), &current, speed));
+    }
+    sessions
+}
+
+fn session(range: Range<usize>, blocks: &[&BlockReadTime], speed: &ReadSpeed) -> ReadingSession {
+    ReadingSession {
+        range,
+        read_time: merge_blocks(blocks.iter().copied(), speed),
+    }
+}
+
+impl Estimator<'_> {
+    /// 按目标时长在块边界处把文档拆分为多次阅读
+    pub fn split_sessions(&mut self, markdown: &str, target_seconds: u64) -> Vec<ReadingSession> {
+        let blocks = self.estimate_blocks(markdown);
+        split_blocks(&blocks, target_seconds, markdown.len(), self.speed())
+    }
+}
+
+/// 按目标时长在块边界处把文档拆分为多次阅读
+///
+/// 使用默认的阅读速度配置。依次累加块，加入下一块会超过目标时长时开始新的一次阅读，
+/// 因此单个超过目标时长的块会独占一次阅读。适合连载阅读和邮件摘要等需要分段的场景。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要拆分的 Markdown 文本
+/// * `target_seconds` - 每次阅读的目标时长（秒）
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`ReadingSession`] 列表。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::split_sessions;
+///
+/// let chapter = "这是一段大约需要一分钟读完的内容。".repeat(12);
+/// let markdown = format!("{chapter}\n\n{chapter}\n\n{chapter}\n");
+/// let sessions = split_sessions(&markdown, 10 * 60);
+/// assert_eq!(sessions.len(), 1);
+///
+/// let sessions = split_sessions(&markdown, 90);
+/// assert_eq!(sessions.len(), 3);
+/// assert_eq!(&markdown[sessions[0].range.clone()], format!("{chapter}\n\n"));
+/// ```
+pub fn split_sessions(markdown: &str, target_seconds: u64) -> Vec<ReadingSession> {
+    split_sessions_with_speed(markdown, target_seconds, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置按目标时长在块边界处把文档拆分为多次阅读
+///
+/// # Arguments
+///
+/// * `markdown` - 需要拆分的 Markdown 文本
+/// * `target_seconds` - 每次阅读的目标时长（秒）
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 按文档顺序排列的 [`ReadingSession`] 列表。
+pub fn split_sessions_with_speed(
+    markdown: &str,
+    target_seconds: u64,
+    speed: &ReadSpeed,
+) -> Vec<ReadingSession> {
+    Estimator::new(*speed).split_sessions(markdown, target_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_split_sessions() {
+        let markdown = "# 第一章\n\n段落内容\n\n![图片](a.png)\n\n![图片](b.png)\n\n结尾";
+        let sessions = split_sessions(markdown, 13);
+        assert_eq!(sessions.len(), 3);
+        assert_eq!(sessions[0].range.start, 0);
+        assert_eq!(sessions.last().unwrap().range.end, markdown.len());
+        assert!(
+            sessions
+                .windows(2)
+                .all(|w| w[0].range.end == w[1].range.start)
+        );
+
+        assert_eq!(&markdown[sessions[1].range()], "![图片](a.png)\n\n");
+        let words: usize = sessions.iter().map(|s| s.read_time.word_count).sum();
+        assert_eq!(words, estimate(markdown).word_count);
+
+        assert!(split_sessions("", 60).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use crate::estimator::Counters;
+use crate::utils::{ceil_seconds, count_english_words, count_words, format_time};
+use crate::{Breakdown, Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::{Event, Tag, TagEnd};
+
+/// 句末标点
+const SENTENCE_ENDS: &[char] = &['。', '！', '？', '.', '!', '?'];
+
+/// 区分需要细读和可以略读的文字
+#[derive(Default)]
+struct SkimCollector {
+    heading: bool,
+    strong: usize,
+    /// 当前段落是否包含图片，包含图片的段落中的文字视为图注
+    caption: bool,
+    /// 当前段落的首句是否尚未结束
+    first_sentence: bool,
+    /// 上一个事件中需要细读的单词数，下一次观察时按实际计数截断
+    pending: usize,
+    words: usize,
+    focused: usize,
+}
+
+impl SkimCollector {
+    fn observe(&mut self, speed: &ReadSpeed, event: &Event<'_>, counters: &Counters) {
+        self.settle(counters);
+        match event {
+            Event::Start(Tag::Heading { .. }) => self.heading = true,
+            Event::End(TagEnd::Heading(_)) => self.heading = false,
+            Event::Start(Tag::Strong) => self.strong += 1,
+            Event::End(TagEnd::Strong) => self.strong = self.strong.saturating_sub(1),
+            Event::Start(Tag::Paragraph | Tag::Item) => {
+                self.caption = false;
+                self.first_sentence = true;
+            }
+            Event::Start(Tag::Image { .. }) => self.caption = true,
+            Event::Text(text) | Event::Code(text) => {
+                let count = |text: &str| {
+                    if speed.chinese {
+                        count_words(text, speed.count_emoji)
+                    } else {
+                        count_english_words(text, speed.count_emoji)
+                    }
+                };
+                if self.heading || self.strong > 0 || self.caption {
+                    self.pending = count(text);
+                } else if self.first_sentence {
+                    match text.find(SENTENCE_ENDS) {
+                        Some(end) => {
+                            self.first_sentence = false;
+                            self.pending = count(&text[..end]);
+                        }
+                        None => self.pending = count(text),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 把上一个事件实际计入的单词归类
+    fn settle(&mut self, counters: &Counters) {
+        let delta = counters.word_count - self.words;
+        self.focused += self.pending.min(delta);
+        self.words = counters.word_count;
+        self.pending = 0;
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算略读时间
+    ///
+    /// 标题、段落（或列表项）首句、加粗文本和图注按正常速度计算，
+    /// 其余内容的时间乘以 [`ReadSpeed::skim_rate`]。
+    pub fn estimate_skim(&mut self, markdown: &str) -> ReadTime {
+        let speed = *self.speed();
+        let mut collector = SkimCollector::default();
+        let counters = self.count(markdown, &mut |event, _, counters| {
+            collector.observe(&speed, event, counters)
+        });
+        collector.settle(&counters);
+
+        let full = Breakdown::from_counters(&counters, &speed);
+        let rate = speed.skim_rate;
+        let focused = collector.focused as f64 / speed.words_per_minute * 60.0;
+        let breakdown = Breakdown {
+            prose: focused + (full.prose - focused) * rate,
+            images: full.images * rate,
+            code: full.code * rate,
+            tables: full.tables * rate,
+            links: full.links * rate,
+            elements: full
+                .elements
+                .into_iter()
+                .map(|(kind, seconds)| (kind, seconds * rate))
+                .collect(),
+            other: full.other * rate,
+        };
+        let total_seconds = ceil_seconds(breakdown.total());
+        ReadTime {
+            total_seconds,
+            formatted: format_time(total_seconds),
+            word_count: counters.word_count,
+            word_count_breakdown: counters.word_count_breakdown,
+            image_count: counters.image_count,
+            image_alt_chars: counters.image_alt_chars,
+            code_block_count: counters.code_block_count,
+            code_line_count: counters.code_line_count,
+            table_count: counters.table_count,
+            table_cell_count: counters.table_cell_count,
+            link_count: counters.link_count,
+            extra_seconds: counters.extra_seconds,
+            pages: speed.pages(counters.word_count),
+            front_matter_bytes: counters.front_matter_bytes,
+            custom_elements: counters.custom_elements,
+            breakdown,
+        }
+    }
+}
+
+/// 估算略读时间
+///
+/// 使用默认的阅读速度配置。略读时读者只仔细阅读标题、每段首句、加粗文本和图注，
+/// 其余内容快速扫过，适合与 [`estimate`](crate::estimate) 一起显示“阅读 14分钟 / 略读 4分钟”。
+/// 返回结果中的计数与完整估算相同，只有总时间和 [`Breakdown`] 按略读计算。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算略读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回略读的 [`ReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{estimate, estimate_skim};
+///
+/// let markdown = "# 标题\n\n第一句很重要。后面的内容可以快速扫过，不需要逐字阅读。\n";
+/// let skim = estimate_skim(markdown);
+/// assert_eq!(skim.word_count, estimate(markdown).word_count);
+/// assert!(skim.total_seconds < estimate(markdown).total_seconds);
+/// ```
+pub fn estimate_skim(markdown: &str) -> ReadTime {
+    estimate_skim_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算略读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算略读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置，略读系数见 [`ReadSpeed::skim_rate`]
+///
+/// # Returns
+///
+/// 返回略读的 [`ReadTime`]。
+pub fn estimate_skim_with_speed(markdown: &str, speed: &ReadSpeed) -> ReadTime {
+    Estimator::new(*speed).estimate_skim(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_skim() {
+        let speed = ReadSpeed::default().wpm(60.0).skim(0.5);
+        let markdown =
+            "# 标题\n\n首句。其余内容**重点**其余\n\n![图片](a.png)图注\n\n```\ncode\n```\n";
+        let skim = estimate_skim_with_speed(markdown, &speed);
+        // 标题 2、首句 2、重点 2、图注 2 按正常速度，其余 7 个字（含句号）减半
+        assert_eq!(skim.breakdown.prose, 8.0 + 7.0 * 0.5);
+        assert_eq!(skim.breakdown.images, 6.0);
+        assert_eq!(skim.breakdown.code, 10.0);
+        assert_eq!(skim.total_seconds, 28);
+
+        let full = estimate_skim_with_speed(markdown, &speed.skim(1.0));
+        assert_eq!(full, crate::estimate_with_speed(markdown, &speed));
+    }
+}
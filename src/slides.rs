@@ -0,0 +1,204 @@
+use crate::{Estimator, ReadSpeed, ReadTime};
+use std::ops::Range;
+
+/// 单张幻灯片的阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct SlideReadTime {
+    /// 在源文本中的字节范围，不包含分隔线
+    pub range: Range<usize>,
+
+    /// 本张幻灯片的阅读时间
+    pub read_time: ReadTime,
+}
+
+impl SlideReadTime {
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+}
+
+/// 幻灯片演示文稿的阅读时间
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Slides {
+    /// 按顺序排列的各张幻灯片
+    pub slides: Vec<SlideReadTime>,
+
+    /// 所有幻灯片的合计
+    pub total: ReadTime,
+}
+
+impl Slides {
+    pub fn slides(&self) -> &[SlideReadTime] {
+        &self.slides
+    }
+
+    pub fn total(&self) -> &ReadTime {
+        &self.total
+    }
+}
+
+/// 单张幻灯片的范围及其中演讲者备注的起点
+struct SlideSpan {
+    range: Range<usize>,
+    notes: Option<usize>,
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// reveal.js 的备注分隔：以 `Note:` 或 `Notes:` 开头的行
+fn is_notes(line: &str) -> bool {
+    let line = line.trim_start().as_bytes();
+    [&b"note:"[..], &b"notes:"[..]].iter().any(|prefix| {
+        line.len() >= prefix.len() && line[..prefix.len()].eq_ignore_ascii_case(prefix)
+    })
+}
+
+/// 按 `---` 分隔线拆分幻灯片，跳过代码块内的分隔线和开头的 Marp 指令块
+fn split_slides(markdown: &str) -> Vec<SlideSpan> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut notes = None;
+    let mut in_fence = false;
+    let mut front_matter = false;
+    let mut offset = 0;
+
+    for (index, line) in markdown.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        offset += line.len();
+        if is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            continue;
+        }
+        if line.trim_end() == "---" {
+            if index == 0 {
+                front_matter = true;
+            } else if front_matter {
+                front_matter = false;
+            } else {
+                spans.push(SlideSpan {
+                    range: start..line_start,
+                    notes: notes.take(),
+                });
+            }
+            start = offset;
+        } else if !front_matter && notes.is_none() && is_notes(line) {
+            notes = Some(line_start);
+        }
+    }
+    spans.push(SlideSpan {
+        range: start..markdown.len().max(start),
+        notes,
+    });
+    spans
+}
+
+impl Estimator<'_> {
+    /// 按 `---` 分隔线拆分并估算每张幻灯片的阅读时间
+    ///
+    /// `include_notes` 为假时，不统计每张幻灯片中 `Note:` 之后的演讲者备注。
+    pub fn estimate_slides(&mut self, markdown: &str, include_notes: bool) -> Slides {
+        let slides: Vec<SlideReadTime> = split_slides(markdown)
+            .into_iter()
+            .map(|span| {
+                let end = match span.notes {
+                    Some(notes) if !include_notes => notes,
+                    _ => span.range.end,
+                };
+                SlideReadTime {
+                    read_time: self.estimate(&markdown[span.range.start..end]),
+                    range: span.range,
+                }
+            })
+            .collect();
+        let total = ReadTime::merge_all(slides.iter().map(|slide| &slide.read_time), self.speed());
+        Slides { slides, total }
+    }
+}
+
+/// 按 `---` 分隔线拆分并估算每张幻灯片的阅读时间
+///
+/// 使用默认的阅读速度配置。适用于 Marp、reveal.js 等以 Markdown 编写的演示文稿，
+/// 开头的 Marp 指令块和代码块中的分隔线会被忽略。演讲者备注为 reveal.js 的 `Note:` 写法，
+/// Marp 使用的 HTML 注释本身不计入阅读时间。
+///
+/// # Arguments
+///
+/// * `markdown` - 演示文稿的 Markdown 文本
+/// * `include_notes` - 是否统计演讲者备注
+///
+/// # Returns
+///
+/// 返回包含每张幻灯片和合计阅读时间的 [`Slides`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_slides;
+///
+/// let deck = "# 封面\n\n---\n\n## 第二页\n\n要点\n\nNote: 这里讲一个小故事\n";
+/// let slides = estimate_slides(deck, false);
+/// assert_eq!(slides.slides.len(), 2);
+/// assert_eq!(slides.slides[1].read_time.word_count, 5);
+/// assert_eq!(estimate_slides(deck, true).slides[1].read_time.word_count, 18);
+/// ```
+pub fn estimate_slides(markdown: &str, include_notes: bool) -> Slides {
+    estimate_slides_with_speed(markdown, include_notes, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置按 `---` 分隔线拆分并估算每张幻灯片的阅读时间
+///
+/// # Arguments
+///
+/// * `markdown` - 演示文稿的 Markdown 文本
+/// * `include_notes` - 是否统计演讲者备注
+/// * `speed` - 自定义的阅读速度配置，排练演讲时可以设置为语速
+///
+/// # Returns
+///
+/// 返回包含每张幻灯片和合计阅读时间的 [`Slides`]。
+pub fn estimate_slides_with_speed(
+    markdown: &str,
+    include_notes: bool,
+    speed: &ReadSpeed,
+) -> Slides {
+    Estimator::new(*speed).estimate_slides(markdown, include_notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_slides() {
+        let deck =
+            "---\nmarp: true\n---\n\n# 封面\n\n---\n\n```yaml\n---\n```\n\n---\nnotes: 备注\n";
+        let slides = estimate_slides(deck, false);
+        let texts: Vec<_> = slides.slides.iter().map(|s| &deck[s.range()]).collect();
+        assert_eq!(
+            texts,
+            ["\n# 封面\n\n", "\n```yaml\n---\n```\n\n", "notes: 备注\n"]
+        );
+        assert_eq!(slides.slides[1].read_time.code_block_count, 1);
+        assert_eq!(slides.slides[2].read_time.word_count, 0);
+        assert_eq!(slides.total.word_count, 2);
+
+        let slides = estimate_slides(deck, true);
+        assert_eq!(slides.slides[2].read_time.word_count, 8);
+        assert_eq!(estimate_slides("", true).slides.len(), 1);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{ReadSpeed, ReadTime, estimate_with_speed};
+use std::borrow::Cow;
+
+/// 可估算阅读时间的内容来源
+///
+/// 为自己的文章、页面等类型实现该 trait 后，即可直接调用 `.read_time()`。
+/// 字符串类型默认按 Markdown 处理。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{EstimateSource, ReadSpeed, ReadTime};
+///
+/// struct Post {
+///     title: String,
+///     body: String,
+/// }
+///
+/// impl EstimateSource for Post {
+///     fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+///         self.body.estimate(speed)
+///     }
+/// }
+///
+/// let post = Post {
+///     title: "标题".to_string(),
+///     body: "# 标题\n\n这是内容".to_string(),
+/// };
+/// println!("{}: {}", post.title, post.read_time().formatted);
+/// ```
+pub trait EstimateSource {
+    /// 使用指定的阅读速度配置估算阅读时间
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime;
+
+    /// 使用默认的阅读速度配置估算阅读时间
+    fn read_time(&self) -> ReadTime {
+        self.estimate(&ReadSpeed::default())
+    }
+}
+
+impl EstimateSource for str {
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+        estimate_with_speed(self, speed)
+    }
+}
+
+impl EstimateSource for String {
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+        self.as_str().estimate(speed)
+    }
+}
+
+impl EstimateSource for Cow<'_, str> {
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+        self.as_ref().estimate(speed)
+    }
+}
+
+impl<T: EstimateSource + ?Sized> EstimateSource for &T {
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+        (**self).estimate(speed)
+    }
+}
+
+impl<T: EstimateSource + ?Sized> EstimateSource for Box<T> {
+    fn estimate(&self, speed: &ReadSpeed) -> ReadTime {
+        (**self).estimate(speed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate;
+
+    #[test]
+    fn test_string_sources() {
+        let markdown = "# 标题\n\n这是内容";
+        let expected = estimate(markdown);
+        assert_eq!(markdown.read_time(), expected);
+        assert_eq!(markdown.to_string().read_time(), expected);
+        assert_eq!(Cow::Borrowed(markdown).read_time(), expected);
+
+        let speed = ReadSpeed::default().wpm(100.0);
+        assert_eq!(
+            (&markdown).estimate(&speed),
+            estimate_with_speed(markdown, &speed)
+        );
+    }
+}
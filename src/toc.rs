@@ -0,0 +1,226 @@
+use crate::{Estimator, ReadSpeed, ReadTime, SectionReadTime};
+use std::fmt::Write;
+
+/// 带阅读时间的目录
+///
+/// 按标题层级组织的 [`TocEntry`] 树，第一个标题之前的内容不会出现在目录中。
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct Toc {
+    /// 顶层条目
+    pub entries: Vec<TocEntry>,
+}
+
+/// 目录条目
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct TocEntry {
+    /// 标题文本
+    pub heading: String,
+
+    /// 标题级别（1-6）
+    pub level: u8,
+
+    /// 本标题下直到下一个标题之前的阅读时间
+    pub read_time: ReadTime,
+
+    /// 包含所有子标题在内的累计阅读时间
+    pub total: ReadTime,
+
+    /// 子标题
+    pub children: Vec<TocEntry>,
+}
+
+impl TocEntry {
+    fn new(section: &SectionReadTime) -> Self {
+        Self {
+            heading: section.heading.clone(),
+            level: section.level,
+            read_time: section.read_time.clone(),
+            total: section.read_time.clone(),
+            children: Vec::new(),
+        }
+    }
+
+    /// 按子标题重新计算累计阅读时间
+    fn finish(&mut self, speed: &ReadSpeed) {
+        for child in &mut self.children {
+            child.finish(speed);
+        }
+        let totals = self.children.iter().map(|child| &child.total);
+        self.total = ReadTime::merge_all(std::iter::once(&self.read_time).chain(totals), speed);
+    }
+
+    fn write_markdown(&self, out: &mut String, depth: usize) {
+        let _ = writeln!(
+            out,
+            "{:indent$}- {}（{}）",
+            "",
+            self.heading,
+            self.total.formatted,
+            indent = depth * 2
+        );
+        for child in &self.children {
+            child.write_markdown(out, depth + 1);
+        }
+    }
+
+    fn write_html(&self, out: &mut String) {
+        out.push_str("<li>");
+        push_escaped(out, &self.heading);
+        let _ = write!(
+            out,
+            " <span class=\"readtime\">{}</span>",
+            self.total.formatted
+        );
+        write_html_list(out, &self.children);
+        out.push_str("</li>");
+    }
+}
+
+impl Toc {
+    /// 根据章节列表构建目录
+    ///
+    /// 级别更高的标题成为前一个较低级别标题的子条目，累计时间按 `speed` 合并计算。
+    pub fn from_sections(sections: &[SectionReadTime], speed: &ReadSpeed) -> Self {
+        let mut entries: Vec<TocEntry> = Vec::new();
+        for section in sections.iter().filter(|section| section.level > 0) {
+            let mut siblings = &mut entries;
+            while siblings
+                .last()
+                .is_some_and(|parent| parent.level < section.level)
+            {
+                siblings = &mut siblings.last_mut().unwrap().children;
+            }
+            siblings.push(TocEntry::new(section));
+        }
+        for entry in &mut entries {
+            entry.finish(speed);
+        }
+        Self { entries }
+    }
+
+    /// 渲染为嵌套的 Markdown 列表
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::estimate_toc;
+    ///
+    /// let toc = estimate_toc("# 第一章\n\n内容\n\n## 第一节\n\n更多内容");
+    /// assert_eq!(toc.to_markdown(), "- 第一章（4秒）\n  - 第一节（3秒）\n");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            entry.write_markdown(&mut out, 0);
+        }
+        out
+    }
+
+    /// 渲染为嵌套的 HTML 列表，每个条目的阅读时间位于 `<span class="readtime">` 中
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        write_html_list(&mut out, &self.entries);
+        out
+    }
+}
+
+fn write_html_list(out: &mut String, entries: &[TocEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    out.push_str("<ul>");
+    for entry in entries {
+        entry.write_html(out);
+    }
+    out.push_str("</ul>");
+}
+
+pub(crate) fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算带阅读时间的目录
+    pub fn estimate_toc(&mut self, markdown: &str) -> Toc {
+        let sections = self.estimate_sections(markdown);
+        Toc::from_sections(&sections, self.speed())
+    }
+}
+
+/// 估算带阅读时间的目录
+///
+/// 使用默认的阅读速度配置。每个条目包含本标题及其所有子标题的累计阅读时间，
+/// 适合在长文档的导航中显示“本章：12分钟”。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 按标题层级组织的 [`Toc`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::estimate_toc;
+///
+/// let toc = estimate_toc("# 第一章\n\n内容\n\n## 第一节\n\n内容\n\n# 第二章\n\n内容");
+/// assert_eq!(toc.entries.len(), 2);
+/// assert_eq!(toc.entries[0].children[0].heading, "第一节");
+/// ```
+pub fn estimate_toc(markdown: &str) -> Toc {
+    estimate_toc_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算带阅读时间的目录
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 按标题层级组织的 [`Toc`]。
+pub fn estimate_toc_with_speed(markdown: &str, speed: &ReadSpeed) -> Toc {
+    Estimator::new(*speed).estimate_toc(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toc_nesting_and_totals() {
+        let markdown = "前言\n\n## 概述\n\n内容\n\n# 第一章\n\n内容\n\n### 细节\n\n内容\n\n## 第一节\n\n内容\n\n# 第二章 <测试>\n";
+        let toc = estimate_toc(markdown);
+        let headings: Vec<_> = toc.entries.iter().map(|e| e.heading.as_str()).collect();
+        assert_eq!(headings, ["概述", "第一章", "第二章 <测试>"]);
+
+        let chapter = &toc.entries[1];
+        assert_eq!(chapter.children.len(), 2);
+        assert_eq!(chapter.children[0].heading, "细节");
+        assert_eq!(chapter.read_time.word_count, 5);
+        assert_eq!(chapter.total.word_count, 5 + 4 + 5);
+
+        let html = toc.to_html();
+        assert!(html.starts_with("<ul><li>概述 <span class=\"readtime\">"));
+        assert!(html.contains("第二章 &lt;测试&gt;"));
+        assert!(toc.to_markdown().contains("\n  - 细节（"));
+    }
+}
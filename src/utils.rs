@@ -1,67 +1,225 @@
-/// emoji支持扩展
-trait CharExt {
-    fn is_emoji(&self) -> bool;
-}
-
-impl CharExt for char {
-    fn is_emoji(&self) -> bool {
-        // 简单的emoji范围检测
-        matches!(*self as u32,
-            0x1F600..=0x1F64F |  // Emoticons
-            0x1F300..=0x1F5FF |  // Miscellaneous Symbols and Pictographs
-            0x1F680..=0x1F6FF |  // Transport and Map Symbols
-            0x1F700..=0x1F77F |  // Alchemical Symbols
-            0x1F780..=0x1F7FF |  // Geometric Shapes Extended
-            0x1F800..=0x1F8FF |  // Supplemental Arrows-C
-            0x1F900..=0x1F9FF |  // Supplemental Symbols and Pictographs
-            0x1FA00..=0x1FA6F |  // Chess Symbols
-            0x1FA70..=0x1FAFF |  // Symbols and Pictographs Extended-A
-            0x2600..=0x26FF   |  // Miscellaneous Symbols
-            0x2700..=0x27BF   |  // Dingbats
-            0x2B50           |  // star
-            0x2B55              // heavy large circle
-        )
-    }
-}
+use crate::WordCountBreakdown;
+use crate::chars::{CONTROL, EMOJI, SPACE, TEXT_EMOJI, VARIATION, class, count_chars, is_cjk};
+use std::borrow::Cow;
+use std::fmt::Write;
 
 /// 计算文本中的中文字数
+///
+/// 纯 ASCII 文本每次比较 8 个字节，结果与逐字符统计相同。
 pub fn count_words(text: &str, count_emoji: bool) -> usize {
-    if count_emoji {
-        // 对于包含emoji的文本，计算非空白字符数
-        text.chars()
-            .filter(|c| !c.is_whitespace() && (!c.is_control() || c.is_emoji()))
-            .count()
+    if text.is_ascii() {
+        // ASCII 中没有 emoji，计数模式下只需要额外排除控制字符
+        count_ascii_chars(text.as_bytes(), count_emoji)
     } else {
-        // 直接计算非空白字符数，适用于中文等无空格分隔的语言
-        text.chars().filter(|c| !c.is_whitespace()).count()
+        // 计数模式下排除控制字符，emoji 不是控制字符，仍然计入
+        let skip = if count_emoji { SPACE | CONTROL } else { SPACE };
+        count_chars(text, skip)
     }
 }
 
 /// 计算文本中的英文字数
+///
+/// 纯 ASCII 文本每次比较 8 个字节，结果与 [`str::split_whitespace`] 相同。
 pub fn count_english_words(text: &str, count_emoji: bool) -> usize {
-    if count_emoji {
-        // 计算空格分隔的单词数，并考虑emoji作为独立单位
-        text.split_whitespace()
-            .map(|word| {
-                // 对于每个单词，如果包含emoji，则每个emoji算作一个单位
-                let emoji_count = word.chars().filter(|c| c.is_emoji()).count();
-                if emoji_count > 0 {
-                    // 如果有emoji，将单词拆分为普通字符和emoji
-                    let non_emoji_chars: usize = word
-                        .chars()
-                        .filter(|c| !c.is_emoji() && !c.is_whitespace())
-                        .count();
-                    // 每个非emoji字符算一个单位，每个emoji也算一个单位
-                    non_emoji_chars + emoji_count
-                } else {
-                    // 没有emoji则整个单词算一个单位
-                    1
-                }
-            })
-            .sum()
+    if text.is_ascii() {
+        count_ascii_words(text.as_bytes())
     } else {
-        text.split_whitespace().count()
+        count_unicode_words(text, count_emoji)
+    }
+}
+
+/// 空白分隔的单词数，`count_emoji` 为真时含有 emoji 的单词按字符计数
+///
+/// 默认以文本样式显示的字符只有后跟 VS16 时才算作 emoji，变体选择符本身不计为字符。
+fn count_unicode_words(text: &str, count_emoji: bool) -> usize {
+    // 单词的计数单位：没有 emoji 时为 1，否则每个字符（包括 emoji）各算一个单位
+    let units = |chars: usize, emoji: usize| match (chars, emoji) {
+        (0, _) => 0,
+        (_, 0) => 1,
+        _ => chars,
+    };
+    let mut count = 0;
+    let mut chars = 0;
+    let mut emoji = 0;
+    let mut previous = 0;
+    for c in text.chars() {
+        let class = class(c);
+        if class & SPACE != 0 {
+            count += units(chars, emoji);
+            chars = 0;
+            emoji = 0;
+        } else if class & VARIATION != 0 {
+            if count_emoji && c == '\u{FE0F}' && previous & TEXT_EMOJI != 0 {
+                emoji += 1;
+            }
+        } else {
+            chars += 1;
+            if count_emoji && class & EMOJI != 0 {
+                emoji += 1;
+            }
+        }
+        previous = class;
+    }
+    count + units(chars, emoji)
+}
+
+/// 与 [`char::is_whitespace`] 在 ASCII 范围内一致
+fn is_space(b: u8) -> bool {
+    b == b' ' || (b'\t'..=b'\r').contains(&b)
+}
+
+/// 每个字节都为 `b` 的 64 位整数
+const fn splat(b: u8) -> u64 {
+    u64::from_le_bytes([b; 8])
+}
+
+/// 每个字节的最高位
+const HIGH: u64 = splat(0x80);
+
+/// 小于 `n` 的字节最高位置 1，其余位为 0
+///
+/// 只适用于 ASCII 字节且 `1 <= n <= 0x80`，此时各字节相加不会向相邻字节进位。
+fn less_than(block: u64, n: u8) -> u64 {
+    !(block + splat(0x80 - n)) & HIGH
+}
+
+/// 等于 `b` 的字节最高位置 1
+fn equal(block: u64, b: u8) -> u64 {
+    less_than(block ^ splat(b), 1)
+}
+
+/// 空白字节最高位置 1
+fn spaces(block: u64) -> u64 {
+    equal(block, b' ') | (less_than(block, b'\r' + 1) & !less_than(block, b'\t'))
+}
+
+/// 把 ASCII 文本按 8 字节一组读成整数，末尾不足 8 字节的部分用空格补齐
+///
+/// 补齐的空格既不算字符，也不会产生新的单词，两种计数都不受影响。
+fn ascii_blocks(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    let chunks = bytes.chunks_exact(8);
+    let rest = chunks.remainder();
+    let last = (!rest.is_empty()).then(|| {
+        let mut block = [b' '; 8];
+        block[..rest.len()].copy_from_slice(rest);
+        u64::from_le_bytes(block)
+    });
+    chunks
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+        .chain(last)
+}
+
+/// 统计非空白字节数，`skip_control` 为真时同时排除控制字符
+///
+/// 每次比较 8 个字节（SWAR），不需要逐字节分支。
+fn count_ascii_chars(bytes: &[u8], skip_control: bool) -> usize {
+    ascii_blocks(bytes)
+        .map(|block| {
+            let skip = if skip_control {
+                less_than(block, 0x20) | equal(block, 0x7f) | equal(block, b' ')
+            } else {
+                spaces(block)
+            };
+            8 - skip.count_ones() as usize
+        })
+        .sum()
+}
+
+/// 统计空白分隔的单词数，即前一个字节为空白（或位于开头）的非空白字节数
+fn count_ascii_words(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    // 上一组最后一个字节是否为空白，放在最低字节的最高位
+    let mut carry = 0x80;
+    for block in ascii_blocks(bytes) {
+        let space = spaces(block);
+        let before = (space << 8) | carry;
+        count += (!space & before).count_ones() as usize;
+        carry = space >> 56;
+    }
+    count
+}
+
+/// 按文字累加文本中的计数单位，参见 [`WordCountBreakdown`]
+pub fn count_scripts(text: &str, words: &mut WordCountBreakdown) {
+    // 当前单词中是否已有字母或数字
+    let mut alphanumeric = false;
+    if text.is_ascii() {
+        for &b in text.as_bytes() {
+            if is_space(b) {
+                words.latin += usize::from(alphanumeric);
+                alphanumeric = false;
+            } else if b.is_ascii_alphanumeric() {
+                alphanumeric = true;
+            }
+        }
+        words.latin += usize::from(alphanumeric);
+        return;
     }
+    let mut previous = 0;
+    for c in text.chars() {
+        let class = class(c);
+        let cjk = class & EMOJI == 0 && is_cjk(c);
+        if cjk || class & (SPACE | EMOJI | TEXT_EMOJI | VARIATION) != 0 {
+            words.latin += usize::from(alphanumeric);
+            alphanumeric = false;
+            if cjk {
+                words.cjk += 1;
+            } else if class & EMOJI != 0 || (c == '\u{FE0F}' && previous & TEXT_EMOJI != 0) {
+                words.emoji += 1;
+            }
+        } else if c.is_alphanumeric() {
+            alphanumeric = true;
+        }
+        previous = class;
+    }
+    words.latin += usize::from(alphanumeric);
+}
+
+/// 按 UTF-8 解码字节内容，丢弃无效的字节序列
+pub fn decode_utf8_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(bytes.utf8_chunks().map(|chunk| chunk.valid()).collect())
+}
+
+/// 解码文本文件内容
+///
+/// 去除 UTF-8 BOM，带有 UTF-16 BOM 的内容按 UTF-16 解码，其余按 UTF-8 解码。
+pub fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => decode_utf8_lossy(rest),
+        [0xFF, 0xFE, rest @ ..] => Cow::Owned(decode_utf16(rest, u16::from_le_bytes)),
+        [0xFE, 0xFF, rest @ ..] => Cow::Owned(decode_utf16(rest, u16::from_be_bytes)),
+        _ => decode_utf8_lossy(bytes),
+    }
+}
+
+/// 解码 UTF-16 内容，丢弃无效的代码单元
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units).filter_map(Result::ok).collect()
+}
+
+/// FNV-1a 64 位哈希
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    fnv1a_extend(0xcbf2_9ce4_8422_2325, bytes)
+}
+
+/// 在已有的 FNV-1a 哈希值上继续处理 `bytes`
+pub fn fnv1a_extend(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// 将秒数向上取整
+///
+/// 负数和 NaN 视为 0，超出范围时为 `u64::MAX`。
+pub fn ceil_seconds(seconds: f64) -> u64 {
+    seconds.ceil() as u64
 }
 
 /// 格式化时间显示
@@ -78,6 +236,25 @@ pub fn format_time(seconds: u64) -> String {
     }
 }
 
+/// 追加转义后带引号的 JSON 字符串
+pub fn push_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,6 +273,121 @@ mod tests {
         assert_eq!(word_count, 6);
     }
 
+    #[test]
+    fn test_count_scripts() {
+        let count = |text: &str| {
+            let mut words = WordCountBreakdown::default();
+            count_scripts(text, &mut words);
+            (words.cjk, words.latin, words.emoji)
+        };
+        assert_eq!(count(""), (0, 0, 0));
+        assert_eq!(count("Hello, world - again!"), (0, 3, 0));
+        assert_eq!(count("中文，English混排。"), (4, 1, 0));
+        assert_eq!(count("2024年 ひらがな 한국어"), (8, 1, 0));
+        assert_eq!(count("café😀😀 ok"), (0, 2, 2));
+        // 文本样式的字符只有后跟 VS16 时才算作 emoji
+        assert_eq!(count("I ❤\u{FE0F} Rust ❤"), (0, 2, 1));
+        assert_eq!(count("\u{20000}\u{3000}x"), (1, 1, 0));
+    }
+
+    #[test]
+    fn test_count_unicode_words() {
+        let is_emoji = |c: &char| class(*c) & EMOJI != 0;
+        for text in [
+            "你好 world 😀",
+            "hi😀😀 there\u{3000}全角\u{a0}空格",
+            "\u{85}控制\u{1}字符\t😀",
+            "",
+        ] {
+            let expected: usize = text
+                .split_whitespace()
+                .map(|word| match word.chars().filter(is_emoji).count() {
+                    0 => 1,
+                    _ => word.chars().count(),
+                })
+                .sum();
+            assert_eq!(count_english_words(text, true), expected);
+            assert_eq!(
+                count_english_words(text, false),
+                text.split_whitespace().count()
+            );
+            assert_eq!(
+                count_words(text, true),
+                text.chars()
+                    .filter(|c| !c.is_whitespace() && !c.is_control())
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_variation_selector() {
+        // ❤ 默认以文本样式显示，只有后跟 VS16 时才算作 emoji
+        assert_eq!(count_english_words("love ❤", true), 2);
+        assert_eq!(count_english_words("love ❤\u{fe0f}", true), 2);
+        assert_eq!(count_english_words("love❤\u{fe0f}", true), 5);
+        assert_eq!(count_english_words("love❤", true), 1);
+        assert_eq!(count_english_words("love❤\u{fe0e}", true), 1);
+        assert_eq!(count_english_words("love😀\u{fe0f}", true), 5);
+        assert_eq!(count_english_words("love❤\u{fe0f}", false), 1);
+    }
+
+    #[test]
+    fn test_ascii_fast_path() {
+        // 所有 ASCII 字符反复排列，跨越多个字节块
+        let text: String = (0u8..128)
+            .cycle()
+            .take(1000)
+            .map(char::from)
+            .chain(" end  of\ttext ".chars())
+            .collect();
+        for len in [0, 1, 7, 8, 9, 16, 31, 33, 64, 95, 200, text.len()] {
+            let text = &text[..len];
+            assert_eq!(
+                count_words(text, true),
+                text.chars()
+                    .filter(|c| !c.is_whitespace() && !c.is_control())
+                    .count()
+            );
+            assert_eq!(
+                count_words(text, false),
+                text.chars().filter(|c| !c.is_whitespace()).count()
+            );
+            assert_eq!(
+                count_english_words(text, true),
+                text.split_whitespace().count()
+            );
+        }
+        assert_eq!(count_english_words(&"word ".repeat(100), false), 100);
+    }
+
+    #[test]
+    fn test_decode_utf8_lossy() {
+        assert!(matches!(decode_utf8_lossy(b"abc"), Cow::Borrowed("abc")));
+        assert_eq!(decode_utf8_lossy(b"a\xFFb\xC3"), "ab");
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(decode_text(b"\xEF\xBB\xBFabc"), "abc");
+        assert_eq!(decode_text(b"\xFF\xFEa\x00b\x00"), "ab");
+        assert_eq!(decode_text(b"\xFE\xFF\x00a\x00b"), "ab");
+    }
+
+    #[test]
+    fn test_ceil_seconds() {
+        assert_eq!(ceil_seconds(0.0), 0);
+        assert_eq!(ceil_seconds(4.5), 5);
+        assert_eq!(ceil_seconds(5.0), 5);
+        assert_eq!(ceil_seconds(-1.5), 0);
+        assert_eq!(ceil_seconds(f64::NAN), 0);
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
     #[test]
     fn test_format_time() {
         assert_eq!(format_time(45), "45秒");
@@ -0,0 +1,492 @@
+use crate::estimator::Counters;
+use crate::utils::push_json_string;
+use crate::{Estimator, ReadSpeed, ReadTime};
+use pulldown_cmark::Event;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::ops::Range;
+
+/// 示例文本的最大字符数
+const SAMPLE_CHARS: usize = 40;
+
+/// [`VerboseReadTime::to_snapshot`] 的格式版本，输出的结构变化时递增
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// 被计数的元素类型
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ElementKind {
+    /// 正文文本
+    #[default]
+    Text,
+
+    /// 行内代码
+    InlineCode,
+
+    /// 图片
+    Image,
+
+    /// 代码块
+    CodeBlock,
+
+    /// 表格或表格单元格，仅在设置了表格的额外时间时出现
+    Table,
+
+    /// 链接，仅在设置了每个链接的额外时间时出现
+    Link,
+
+    /// 访问器标记的自定义元素
+    Custom(String),
+
+    /// 访问器添加的、未标记类型的额外时间
+    Extra,
+}
+
+/// 一个被计数的元素
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct CountedElement {
+    /// 元素类型
+    pub kind: ElementKind,
+
+    /// 源文本片段，超过 40 个字符时截断
+    pub sample: String,
+
+    /// 在源文本中的字节范围
+    pub range: Range<usize>,
+
+    /// 贡献的单位数：文本为单词数，图片、代码块、表格、单元格和链接为 1，
+    /// 图片的替代文本为字符数，代码块的内容为行数
+    pub units: usize,
+
+    /// 贡献的阅读时间（秒），未取整
+    pub seconds: f64,
+}
+
+/// 带逐项明细的估算结果
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct VerboseReadTime {
+    /// 与 [`estimate`](crate::estimate) 相同的估算结果
+    pub read_time: ReadTime,
+
+    /// 按文档顺序排列的被计数元素
+    pub elements: Vec<CountedElement>,
+}
+
+impl VerboseReadTime {
+    pub fn read_time(&self) -> &ReadTime {
+        &self.read_time
+    }
+
+    pub fn elements(&self) -> &[CountedElement] {
+        &self.elements
+    }
+
+    /// 序列化为适合快照测试的 JSON
+    ///
+    /// 输出只由估算结果决定，可以直接与保存的快照文件比较：
+    ///
+    /// - 字段按固定顺序排列，自定义元素按名称排序，第一个字段 `version` 为 [`SNAPSHOT_VERSION`]；
+    /// - 秒数四舍五入到毫秒并去掉末尾的 0，浮点运算顺序的细微差别不会改变输出；
+    /// - 每个被计数元素占一行，估算行为变化时差异集中在受影响的元素上。
+    ///
+    /// 与启用 `serde` 特性后的序列化结果不同，该格式不随字段的增加而变化，只在版本号递增时变化。
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use markdown_readtime::estimate_verbose;
+    ///
+    /// let snapshot = estimate_verbose("正文\n\n![图片](a.png)").to_snapshot();
+    /// assert!(snapshot.starts_with("{\n  \"version\": 1,\n  \"total_seconds\": 13,\n"));
+    /// assert!(snapshot.contains(
+    ///     "    {\"kind\": \"image\", \"range\": [8, 24], \"units\": 1, \"seconds\": 12, \"sample\": \"![图片](a.png)\"}\n"
+    /// ));
+    /// ```
+    pub fn to_snapshot(&self) -> String {
+        let read_time = &self.read_time;
+        let mut out = String::from("{\n");
+        let _ = writeln!(out, "  \"version\": {},", SNAPSHOT_VERSION);
+        let _ = writeln!(out, "  \"total_seconds\": {},", read_time.total_seconds);
+        out.push_str("  \"formatted\": ");
+        push_json_string(&mut out, &read_time.formatted);
+        out.push_str(",\n");
+        let _ = writeln!(out, "  \"word_count\": {},", read_time.word_count);
+        let _ = writeln!(out, "  \"image_count\": {},", read_time.image_count);
+        let _ = writeln!(
+            out,
+            "  \"code_block_count\": {},",
+            read_time.code_block_count
+        );
+        let _ = writeln!(
+            out,
+            "  \"extra_seconds\": {},",
+            seconds(read_time.extra_seconds)
+        );
+        let _ = writeln!(out, "  \"pages\": {},", read_time.pages);
+        let _ = writeln!(
+            out,
+            "  \"front_matter_bytes\": {},",
+            read_time.front_matter_bytes
+        );
+        out.push_str("  \"custom_elements\": ");
+        push_object(&mut out, &read_time.custom_elements, |count| {
+            format!("{}", count)
+        });
+        out.push_str(",\n");
+
+        let breakdown = &read_time.breakdown;
+        out.push_str("  \"breakdown\": {");
+        let _ = write!(
+            out,
+            "\"prose\": {}, \"images\": {}, \"code\": {}, \"other\": {}, \"elements\": ",
+            seconds(breakdown.prose),
+            seconds(breakdown.images),
+            seconds(breakdown.code),
+            seconds(breakdown.other)
+        );
+        push_object(&mut out, &breakdown.elements, |value| seconds(*value));
+        out.push_str("},\n");
+
+        out.push_str("  \"elements\": [");
+        for (index, element) in self.elements.iter().enumerate() {
+            out.push_str(if index == 0 { "\n" } else { ",\n" });
+            out.push_str("    {\"kind\": ");
+            let (kind, tag) = match &element.kind {
+                ElementKind::Text => ("text", None),
+                ElementKind::InlineCode => ("inline_code", None),
+                ElementKind::Image => ("image", None),
+                ElementKind::CodeBlock => ("code_block", None),
+                ElementKind::Table => ("table", None),
+                ElementKind::Link => ("link", None),
+                ElementKind::Custom(tag) => ("custom", Some(tag)),
+                ElementKind::Extra => ("extra", None),
+            };
+            push_json_string(&mut out, kind);
+            if let Some(tag) = tag {
+                out.push_str(", \"tag\": ");
+                push_json_string(&mut out, tag);
+            }
+            let _ = write!(
+                out,
+                ", \"range\": [{}, {}], \"units\": {}, \"seconds\": {}, \"sample\": ",
+                element.range.start,
+                element.range.end,
+                element.units,
+                seconds(element.seconds)
+            );
+            push_json_string(&mut out, &element.sample);
+            out.push('}');
+        }
+        if !self.elements.is_empty() {
+            out.push_str("\n  ");
+        }
+        out.push_str("]\n}\n");
+        out
+    }
+}
+
+/// 按键排序的单行 JSON 对象
+fn push_object<V>(out: &mut String, map: &BTreeMap<String, V>, value: impl Fn(&V) -> String) {
+    out.push('{');
+    for (index, (key, item)) in map.iter().enumerate() {
+        if index > 0 {
+            out.push_str(", ");
+        }
+        push_json_string(out, key);
+        out.push_str(": ");
+        out.push_str(&value(item));
+    }
+    out.push('}');
+}
+
+/// 四舍五入到毫秒的秒数，去掉末尾的 0，非有限值为 `null`
+fn seconds(value: f64) -> String {
+    if !value.is_finite() {
+        return String::from("null");
+    }
+    // 先换算为毫秒再四舍五入，结果只取决于毫秒数
+    let millis = value.abs() * 1000.0 + 0.5;
+    let millis = millis as u64;
+    let sign = if value < 0.0 && millis > 0 { "-" } else { "" };
+    let fraction = millis % 1000;
+    if fraction == 0 {
+        format!("{}{}", sign, millis / 1000)
+    } else {
+        let fraction = format!("{:03}", fraction);
+        format!(
+            "{}{}.{}",
+            sign,
+            millis / 1000,
+            fraction.trim_end_matches('0')
+        )
+    }
+}
+
+fn sample(text: &str) -> String {
+    let mut chars = text.trim().chars();
+    let mut sample: String = chars.by_ref().take(SAMPLE_CHARS).collect();
+    if chars.next().is_some() {
+        sample.push('…');
+    }
+    sample
+}
+
+/// 记录每个事件带来的计数变化
+struct ElementCollector<'m, 's> {
+    markdown: &'m str,
+    speed: &'s ReadSpeed,
+    elements: Vec<CountedElement>,
+    /// 上一个事件之前的计数
+    last: Counters,
+    /// 上一个事件是否为行内代码，以及它的字节范围
+    pending: Option<(bool, Range<usize>)>,
+}
+
+impl ElementCollector<'_, '_> {
+    fn observe(&mut self, event: &Event<'_>, range: Range<usize>, counters: &Counters) {
+        self.settle(counters);
+        self.pending = Some((matches!(event, Event::Code(_)), range));
+    }
+
+    fn settle(&mut self, counters: &Counters) {
+        let Some((inline_code, range)) = self.pending.take() else {
+            return;
+        };
+        let delta = counters.since(&self.last);
+        let seconds = counters.seconds_since(&self.last, self.speed);
+        self.last = counters.clone();
+        if delta.is_empty() {
+            return;
+        }
+
+        let (kind, units) = if delta.image_count > 0 {
+            (ElementKind::Image, delta.image_count)
+        } else if delta.code_block_count > 0 {
+            (ElementKind::CodeBlock, delta.code_block_count)
+        } else if delta.image_alt_chars > 0 {
+            (ElementKind::Image, delta.image_alt_chars)
+        } else if delta.code_line_count > 0 {
+            (ElementKind::CodeBlock, delta.code_line_count)
+        } else if delta.table_count + delta.table_cell_count > 0 {
+            (
+                ElementKind::Table,
+                delta.table_count + delta.table_cell_count,
+            )
+        } else if delta.link_count > 0 {
+            (ElementKind::Link, delta.link_count)
+        } else if delta.word_count > 0 && inline_code {
+            (ElementKind::InlineCode, delta.word_count)
+        } else if delta.word_count > 0 {
+            (ElementKind::Text, delta.word_count)
+        } else if let Some((tag, &count)) = delta.custom_elements.iter().next() {
+            (ElementKind::Custom(tag.clone()), count)
+        } else {
+            (ElementKind::Extra, 0)
+        };
+        let text = self.markdown.get(range.clone()).unwrap_or_default();
+        self.elements.push(CountedElement {
+            kind,
+            sample: sample(text),
+            range,
+            units,
+            seconds,
+        });
+    }
+}
+
+impl Estimator<'_> {
+    /// 估算阅读时间并返回每个被计数元素的明细
+    pub fn estimate_verbose(&mut self, markdown: &str) -> VerboseReadTime {
+        let speed = *self.speed();
+        let mut collector = ElementCollector {
+            markdown,
+            speed: &speed,
+            elements: Vec::new(),
+            last: Counters::default(),
+            pending: None,
+        };
+        let counters = self.count(markdown, &mut |event, range, counters| {
+            collector.observe(event, range, counters)
+        });
+        collector.settle(&counters);
+        VerboseReadTime {
+            read_time: ReadTime::from_counters(counters, &speed),
+            elements: collector.elements,
+        }
+    }
+}
+
+/// 估算阅读时间并返回每个被计数元素的明细
+///
+/// 使用默认的阅读速度配置。估算结果与预期不符时，可以据此查看解析器实际看到了哪些内容、
+/// 每项贡献了多少单词和秒数，便于排查和提交问题。
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+///
+/// # Returns
+///
+/// 返回包含估算结果和元素明细的 [`VerboseReadTime`]。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ElementKind, estimate_verbose};
+///
+/// let verbose = estimate_verbose("# 标题\n\n正文 `code`\n\n![图片](a.png)");
+/// let kinds: Vec<_> = verbose.elements.iter().map(|element| &element.kind).collect();
+/// assert_eq!(
+///     kinds,
+///     [&ElementKind::Text, &ElementKind::Text, &ElementKind::InlineCode, &ElementKind::Image]
+/// );
+/// assert_eq!(verbose.elements[3].sample, "![图片](a.png)");
+/// ```
+pub fn estimate_verbose(markdown: &str) -> VerboseReadTime {
+    estimate_verbose_with_speed(markdown, &ReadSpeed::default())
+}
+
+/// 使用自定义速度配置估算阅读时间并返回每个被计数元素的明细
+///
+/// # Arguments
+///
+/// * `markdown` - 需要估算阅读时间的 Markdown 文本
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回包含估算结果和元素明细的 [`VerboseReadTime`]。
+pub fn estimate_verbose_with_speed(markdown: &str, speed: &ReadSpeed) -> VerboseReadTime {
+    Estimator::new(*speed).estimate_verbose(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Visit, estimate};
+    use pulldown_cmark::Tag;
+
+    #[test]
+    fn test_estimate_verbose() {
+        let long = "很长的段落".repeat(10);
+        let markdown = format!("{long}\n\n> 引用\n\n```rust\nfn main() {{}}\n```\n");
+        let mut estimator =
+            Estimator::new(ReadSpeed::default()).visitor(|event: &Event<'_>, _: &Counters| {
+                match event {
+                    Event::Start(Tag::BlockQuote) => Visit::count().extra_seconds(5.0).tag("quote"),
+                    _ => Visit::count(),
+                }
+            });
+        let verbose = estimator.estimate_verbose(&markdown);
+        let elements = verbose.elements();
+        assert_eq!(elements.len(), 4);
+
+        assert_eq!(elements[0].units, 50);
+        assert_eq!(elements[0].sample.chars().count(), SAMPLE_CHARS + 1);
+        assert_eq!(elements[1].kind, ElementKind::Custom("quote".into()));
+        assert_eq!(elements[1].seconds, 5.0);
+        assert_eq!(elements[2].units, 2);
+        assert_eq!(elements[3].kind, ElementKind::CodeBlock);
+        assert_eq!(
+            &markdown[elements[3].range.clone()],
+            "```rust\nfn main() {}\n```"
+        );
+
+        let seconds: f64 = elements.iter().map(|element| element.seconds).sum();
+        assert_eq!(seconds.ceil() as u64, verbose.read_time.total_seconds);
+        assert_eq!(estimate_verbose("").elements, []);
+        assert_eq!(estimate_verbose(&long).read_time, estimate(&long));
+    }
+
+    #[test]
+    fn test_estimate_verbose_image_decay() {
+        let speed = ReadSpeed::default().decay_images(2.0, 5.0);
+        let markdown = "![a](a.png)\n\n正文\n\n![b](b.png) ![c](c.png)";
+        let verbose = estimate_verbose_with_speed(markdown, &speed);
+        let seconds: Vec<f64> = verbose
+            .elements
+            .iter()
+            .filter(|element| element.kind == ElementKind::Image)
+            .map(|element| element.seconds)
+            .collect();
+        assert_eq!(seconds, [12.0, 10.0, 8.0]);
+        assert_eq!(verbose.read_time.breakdown.images, 30.0);
+    }
+
+    #[test]
+    fn test_estimate_verbose_links() {
+        let markdown = "[a](a.md) [b](a.md)";
+        assert!(
+            estimate_verbose(markdown)
+                .elements
+                .iter()
+                .all(|element| element.kind == ElementKind::Text)
+        );
+
+        let speed = ReadSpeed::default().link_time(3.0).dedupe_links(true);
+        let links: Vec<f64> = estimate_verbose_with_speed(markdown, &speed)
+            .elements
+            .iter()
+            .filter(|element| element.kind == ElementKind::Link)
+            .map(|element| element.seconds)
+            .collect();
+        assert_eq!(links, [3.0]);
+    }
+
+    #[test]
+    fn test_to_snapshot() {
+        let mut estimator =
+            Estimator::new(ReadSpeed::default()).visitor(|event: &Event<'_>, _: &Counters| {
+                match event {
+                    Event::Start(Tag::BlockQuote) => Visit::count().extra_seconds(5.0).tag("quote"),
+                    _ => Visit::count(),
+                }
+            });
+        let snapshot = estimator
+            .estimate_verbose("# 标题\n\n> 引用\n\n正文 `a\\b`")
+            .to_snapshot();
+        assert_eq!(
+            snapshot,
+            r#"{
+  "version": 1,
+  "total_seconds": 8,
+  "formatted": "8秒",
+  "word_count": 9,
+  "image_count": 0,
+  "code_block_count": 0,
+  "extra_seconds": 5,
+  "pages": 1,
+  "front_matter_bytes": 0,
+  "custom_elements": {"quote": 1},
+  "breakdown": {"prose": 2.7, "images": 0, "code": 0, "other": 0, "elements": {"quote": 5}},
+  "elements": [
+    {"kind": "text", "range": [2, 8], "units": 2, "seconds": 0.6, "sample": "标题"},
+    {"kind": "custom", "tag": "quote", "range": [10, 19], "units": 1, "seconds": 5, "sample": "> 引用"},
+    {"kind": "text", "range": [12, 18], "units": 2, "seconds": 0.6, "sample": "引用"},
+    {"kind": "text", "range": [20, 27], "units": 2, "seconds": 0.6, "sample": "正文"},
+    {"kind": "inline_code", "range": [27, 32], "units": 3, "seconds": 0.9, "sample": "`a\\b`"}
+  ]
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_snapshot_seconds() {
+        assert_eq!(seconds(0.0), "0");
+        assert_eq!(seconds(12.0), "12");
+        assert_eq!(seconds(0.1 + 0.2), "0.3");
+        assert_eq!(seconds(1.2345), "1.235");
+        assert_eq!(seconds(1.0004), "1");
+        assert_eq!(seconds(-2.5), "-2.5");
+        assert_eq!(seconds(-0.0001), "0");
+        assert_eq!(seconds(f64::NAN), "null");
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{BatchReadTime, DirOptions, Error, ReadTime, estimate_file_with_speed};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkParallel, WalkState};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// 并行遍历目录，每估算完一个文件就通过通道发送结果
+///
+/// 遍历和估算在后台线程中进行，多个线程以工作窃取的方式同时发现、读取和估算文件，
+/// 结果按完成顺序到达。某个文件读取失败时只发送该文件的错误，遍历继续进行；
+/// 丢弃返回的 [`Receiver`] 后遍历会尽快停止。需要启用 `walk` 特性。
+///
+/// 除了 [`DirOptions`] 的扩展名过滤，还会按 [`DirOptions::include`] 和 [`DirOptions::exclude`]
+/// 中的模式筛选文件，启用 [`DirOptions::ignore_files`] 时遵循 `.gitignore` 和 `.ignore`。
+/// 模式无效时只发送一个以 `path` 为路径的 [`Error::Pattern`]。
+///
+/// # Arguments
+///
+/// * `path` - 目录路径
+/// * `options` - 批量估算选项
+///
+/// # Returns
+///
+/// 接收 `(文件路径, 估算结果)` 的 [`Receiver`]，所有文件处理完后通道关闭。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{DirOptions, estimate_dir_stream};
+///
+/// for (path, result) in estimate_dir_stream("content", &DirOptions::default()) {
+///     match result {
+///         Ok(read_time) => println!("{}: {}", path.display(), read_time.formatted),
+///         Err(err) => eprintln!("{}: {}", path.display(), err),
+///     }
+/// }
+/// ```
+pub fn estimate_dir_stream(
+    path: impl AsRef<Path>,
+    options: &DirOptions,
+) -> Receiver<(PathBuf, Result<ReadTime, Error>)> {
+    let root = path.as_ref().to_path_buf();
+    let options = options.clone();
+    let (sender, receiver) = mpsc::channel();
+    let (walker, include) = match build_walker(&root, &options) {
+        Ok(walker) => walker,
+        Err(err) => {
+            let _ = sender.send((root, Err(err)));
+            return receiver;
+        }
+    };
+
+    thread::spawn(move || {
+        walker.run(|| {
+            let sender = sender.clone();
+            let options = &options;
+            let include = &include;
+            let root = &root;
+            Box::new(move |entry| {
+                let result = match entry {
+                    Ok(entry) => {
+                        // 与 estimate_dir 相同：估算指向文件的符号链接，但不进入指向目录的符号链接
+                        let is_file = entry.file_type().is_some_and(|kind| {
+                            kind.is_file() || (kind.is_symlink() && entry.path().is_file())
+                        });
+                        let matches = match include {
+                            Some(include) => {
+                                let relative =
+                                    entry.path().strip_prefix(root).unwrap_or(entry.path());
+                                include.is_match(relative)
+                            }
+                            None => options.matches(entry.path()),
+                        };
+                        if !is_file || !matches {
+                            return WalkState::Continue;
+                        }
+                        let path = entry.into_path();
+                        let read_time = estimate_file_with_speed(&path, &options.speed);
+                        (path, read_time)
+                    }
+                    Err(err) => {
+                        let path = error_path(&err).unwrap_or(root).to_path_buf();
+                        let err = err
+                            .into_io_error()
+                            .unwrap_or_else(|| io::Error::other("遍历目录失败"));
+                        (path, Err(Error::Io(err)))
+                    }
+                };
+                match sender.send(result) {
+                    Ok(()) => WalkState::Continue,
+                    Err(_) => WalkState::Quit,
+                }
+            })
+        });
+    });
+    receiver
+}
+
+/// 并行批量估算目录中的文件
+///
+/// 文件的发现、读取和估算在多个线程中同时进行，适合包含大量文件的内容仓库。需要启用 `walk` 特性。
+///
+/// [`DirOptions::include`] 和 [`DirOptions::exclude`] 为空、[`DirOptions::ignore_files`] 为 false 时
+/// 结果与 [`estimate_dir`](crate::estimate_dir) 相同；这三个选项只用于并行遍历，
+/// 详见 [`estimate_dir_stream`]。
+///
+/// # Arguments
+///
+/// * `path` - 目录路径
+/// * `options` - 批量估算选项
+///
+/// # Returns
+///
+/// 返回每个文件的阅读时间以及合计，任意文件读取失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{DirOptions, estimate_dir_parallel};
+///
+/// let batch = estimate_dir_parallel("content", &DirOptions::default()).unwrap();
+/// println!("共 {} 篇，合计: {}", batch.files.len(), batch.total.formatted);
+/// ```
+pub fn estimate_dir_parallel(
+    path: impl AsRef<Path>,
+    options: &DirOptions,
+) -> Result<BatchReadTime, Error> {
+    let mut files = BTreeMap::new();
+    for (path, result) in estimate_dir_stream(path, options) {
+        files.insert(path, result?);
+    }
+    Ok(BatchReadTime::new(files, &options.speed))
+}
+
+/// 按选项创建并行遍历器，以及 [`DirOptions::include`] 非空时用于匹配相对路径的模式集合
+fn build_walker(
+    root: &Path,
+    options: &DirOptions,
+) -> Result<(WalkParallel, Option<GlobSet>), Error> {
+    let pattern_error = |err: &dyn std::error::Error| Error::Pattern(err.to_string());
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .standard_filters(options.ignore_files)
+        .require_git(false);
+    if !options.recursive {
+        builder.max_depth(Some(1));
+    }
+    if !options.exclude.is_empty() {
+        // 排除模式作为取反的覆盖规则，优先于 .gitignore 中的规则
+        let mut overrides = OverrideBuilder::new(root);
+        for pattern in &options.exclude {
+            overrides
+                .add(&format!("!{}", pattern))
+                .map_err(|err| pattern_error(&err))?;
+        }
+        builder.overrides(overrides.build().map_err(|err| pattern_error(&err))?);
+    }
+
+    let include = if options.include.is_empty() {
+        None
+    } else {
+        let mut set = GlobSetBuilder::new();
+        for pattern in &options.include {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(|err| pattern_error(&err))?;
+            set.add(glob);
+        }
+        Some(set.build().map_err(|err| pattern_error(&err))?)
+    };
+    Ok((builder.build_parallel(), include))
+}
+
+/// 遍历错误对应的路径
+fn error_path(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        ignore::Error::Loop { child, .. } => Some(child),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::estimate_dir;
+    use std::fs;
+
+    #[test]
+    fn test_estimate_dir_parallel() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-estimate-dir-parallel");
+        let _ = fs::remove_dir_all(&dir);
+        for i in 0..20 {
+            let nested = dir.join(format!("section-{}", i % 4));
+            fs::create_dir_all(&nested).unwrap();
+            fs::write(
+                nested.join(format!("{}.md", i)),
+                "# 标题\n\n内容".repeat(i + 1),
+            )
+            .unwrap();
+        }
+        fs::write(dir.join("index.md"), "首页").unwrap();
+        fs::write(dir.join("notes.txt"), "不是 Markdown").unwrap();
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("index.md", dir.join("link.md")).unwrap();
+            std::os::unix::fs::symlink(".", dir.join("loop")).unwrap();
+        }
+        let expected = if cfg!(unix) { 22 } else { 21 };
+
+        let options = DirOptions::default();
+        let batch = estimate_dir_parallel(&dir, &options).unwrap();
+        assert_eq!(batch.files.len(), expected);
+        assert_eq!(batch, estimate_dir(&dir, &options).unwrap());
+
+        let options = DirOptions::default().recursive(false);
+        let streamed: Vec<_> = estimate_dir_stream(&dir, &options).into_iter().collect();
+        assert_eq!(streamed.len(), expected - 20);
+        assert!(
+            streamed
+                .iter()
+                .any(|(path, _)| *path == dir.join("index.md"))
+        );
+
+        let missing = estimate_dir_parallel(dir.join("missing"), &options);
+        assert!(matches!(missing, Err(Error::Io(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_estimate_dir_patterns() {
+        let dir = std::env::temp_dir().join("markdown-readtime-test-estimate-dir-patterns");
+        let _ = fs::remove_dir_all(&dir);
+        for path in [
+            "posts/a.md",
+            "posts/2024/b.md",
+            "posts/drafts/c.md",
+            "posts/notes.txt",
+            "node_modules/pkg/README.md",
+            "vendor/d.md",
+            ".hidden/e.md",
+        ] {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "内容").unwrap();
+        }
+        fs::write(dir.join(".gitignore"), "vendor/\n").unwrap();
+        let files = |options: &DirOptions| -> Vec<String> {
+            let batch = estimate_dir_parallel(&dir, options).unwrap();
+            batch
+                .files
+                .keys()
+                .map(|path| path.strip_prefix(&dir).unwrap().display().to_string())
+                .collect()
+        };
+
+        let options = DirOptions::default().exclude(["node_modules", "drafts/"]);
+        assert_eq!(
+            files(&options),
+            [
+                ".hidden/e.md",
+                "posts/2024/b.md",
+                "posts/a.md",
+                "vendor/d.md"
+            ]
+        );
+        let options = options.ignore_files(true);
+        assert_eq!(files(&options), ["posts/2024/b.md", "posts/a.md"]);
+
+        let options = DirOptions::default().include(["posts/*.*"]);
+        assert_eq!(files(&options), ["posts/a.md", "posts/notes.txt"]);
+        let options = DirOptions::default()
+            .include(["posts/**/*.md"])
+            .exclude(["2024"]);
+        assert_eq!(files(&options), ["posts/a.md", "posts/drafts/c.md"]);
+
+        let options = DirOptions::default().include(["posts/[a"]);
+        let invalid = estimate_dir_parallel(&dir, &options);
+        assert!(matches!(invalid, Err(Error::Pattern(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
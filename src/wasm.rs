@@ -0,0 +1,42 @@
+//! wasm-bindgen 绑定
+//!
+//! 在浏览器中使用与服务端完全相同的算法估算阅读时间。配置对象和返回结果的字段名
+//! 与启用 `serde` 特性时的 JSON 格式一致，配置对象中缺失的字段使用默认值。
+//!
+//! ```js
+//! import { estimate, estimateWithSpeed } from "markdown-readtime";
+//!
+//! const result = estimateWithSpeed("# Title\n\nSome content", {
+//!   words_per_minute: 250,
+//!   chinese: false,
+//! });
+//! console.log(result.formatted, result.word_count);
+//! ```
+use crate::{ReadSpeed, ReadTime};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// 使用默认配置估算阅读时间，返回普通 JS 对象
+#[wasm_bindgen(js_name = estimate)]
+pub fn estimate_js(markdown: &str) -> Result<JsValue, JsValue> {
+    to_js(&crate::estimate(markdown))
+}
+
+/// 使用自定义配置估算阅读时间，返回普通 JS 对象
+///
+/// `options` 为 `undefined` 或 `null` 时使用默认配置。
+#[wasm_bindgen(js_name = estimateWithSpeed)]
+pub fn estimate_with_speed_js(markdown: &str, options: JsValue) -> Result<JsValue, JsValue> {
+    let speed: ReadSpeed = if options.is_undefined() || options.is_null() {
+        ReadSpeed::default()
+    } else {
+        serde_wasm_bindgen::from_value(options)?
+    };
+    to_js(&crate::estimate_with_speed(markdown, &speed))
+}
+
+/// 将结果转换为普通 JS 对象
+fn to_js(read_time: &ReadTime) -> Result<JsValue, JsValue> {
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_maps_as_objects(true);
+    Ok(read_time.serialize(&serializer)?)
+}
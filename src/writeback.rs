@@ -0,0 +1,446 @@
+use crate::ReadTime;
+use crate::front_matter::{FrontMatter, FrontMatterKind};
+use crate::{Error, ReadSpeed, estimate_with_speed};
+use std::{fs, io::Write, path::Path};
+
+/// 默认写入 front matter 的键名
+const KEY: &str = "reading_time";
+
+/// 把阅读时间写入文档的 front matter
+///
+/// 写入的值为向上取整的分钟数，与 [`minutes`](crate::minutes) 相同。
+/// 已有 `reading_time` 键时只更新其值，之后的行内注释保持不变，否则在 front matter 末尾添加；
+/// 文档没有 front matter 时在开头添加一个 YAML 块。其余内容保持不变，
+/// 新增的行沿用文档第一行的换行符（`\n` 或 `\r\n`）。
+///
+/// # Arguments
+///
+/// * `markdown` - 原始的 Markdown 文本
+/// * `read_time` - 需要写入的阅读时间
+///
+/// # Returns
+///
+/// 返回修改后的文本。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadTime, write_reading_time};
+///
+/// let read_time = ReadTime::builder().total_seconds(150).build();
+/// let markdown = "---\ntitle: 你好\n---\n正文\n";
+/// assert_eq!(
+///     write_reading_time(markdown, &read_time),
+///     "---\ntitle: 你好\nreading_time: 3\n---\n正文\n"
+/// );
+/// assert_eq!(write_reading_time("正文", &read_time), "---\nreading_time: 3\n---\n正文");
+/// ```
+pub fn write_reading_time(markdown: &str, read_time: &ReadTime) -> String {
+    write_reading_time_with_key(markdown, read_time, KEY)
+}
+
+/// 把阅读时间写入文档 front matter 中的指定键
+///
+/// 与 [`write_reading_time`] 相同，但使用 `key` 代替 `reading_time`，
+/// 适合站点生成器约定了其他字段名的场景。值未变化时返回的文本与输入相同。
+/// 包含字母、数字、`_` 和 `-` 以外字符的键会写为带转义的双引号字符串。
+///
+/// # Examples
+///
+/// ```
+/// use markdown_readtime::{ReadTime, write_reading_time_with_key};
+///
+/// let read_time = ReadTime::builder().total_seconds(150).build();
+/// assert_eq!(
+///     write_reading_time_with_key("+++\ntitle = \"你好\"\n+++\n正文", &read_time, "minutes"),
+///     "+++\ntitle = \"你好\"\nminutes = 3\n+++\n正文"
+/// );
+/// ```
+pub fn write_reading_time_with_key(markdown: &str, read_time: &ReadTime, key: &str) -> String {
+    let minutes = read_time.total_seconds.div_ceil(60);
+    let eol = line_ending(markdown);
+    let Some(front_matter) = FrontMatter::detect(markdown) else {
+        let bom = if markdown.starts_with('\u{FEFF}') {
+            '\u{FEFF}'.len_utf8()
+        } else {
+            0
+        };
+        let (bom, body) = markdown.split_at(bom);
+        let key = quote_key(key, FrontMatterKind::Yaml);
+        return format!("{bom}---{eol}{key}: {minutes}{eol}---{eol}{body}");
+    };
+
+    let existing = front_matter
+        .entries(markdown)
+        .into_iter()
+        .find(|(name, _)| *name == key);
+    if let Some((_, value)) = existing {
+        // 只替换值本身，保留之后的行内注释
+        let start = value.as_ptr() as usize - markdown.as_ptr() as usize;
+        let scalar = scalar(value);
+        // 值为空时 `:` 之后可能没有空白，YAML 要求两者之间至少有一个空格
+        let before = if scalar.is_empty() && !markdown[..start].ends_with([' ', '\t']) {
+            " "
+        } else {
+            ""
+        };
+        let after = if scalar.is_empty() && !value.is_empty() {
+            " "
+        } else {
+            ""
+        };
+        let replacement = format!("{before}{minutes}{after}");
+        return splice(markdown, start, start + scalar.len(), &replacement);
+    }
+
+    let key = quote_key(key, front_matter.kind);
+    let content = &markdown[front_matter.content.clone()];
+    match front_matter.kind {
+        FrontMatterKind::Yaml | FrontMatterKind::Toml => {
+            let separator = if front_matter.kind == FrontMatterKind::Yaml {
+                ":"
+            } else {
+                " ="
+            };
+            // TOML 的顶层键必须位于第一个表之前
+            let mut at = front_matter.content.end;
+            if front_matter.kind == FrontMatterKind::Toml {
+                let mut offset = front_matter.content.start;
+                for line in content.split_inclusive('\n') {
+                    if line.starts_with('[') {
+                        at = offset;
+                        break;
+                    }
+                    offset += line.len();
+                }
+            }
+            let newline = if at > 0 && !markdown[..at].ends_with('\n') {
+                eol
+            } else {
+                ""
+            };
+            splice(
+                markdown,
+                at,
+                at,
+                &format!("{newline}{key}{separator} {minutes}{eol}"),
+            )
+        }
+        FrontMatterKind::Json => {
+            let close = front_matter.content.start + content.len() - 1;
+            let inner = content[1..content.len() - 1].trim_end();
+            let at = front_matter.content.start + 1 + inner.len();
+            let entry = match (inner.trim_start().is_empty(), inner.contains('\n')) {
+                (true, _) => format!("{key}: {minutes}"),
+                (false, true) => format!(",{eol}  {key}: {minutes}"),
+                (false, false) => format!(", {key}: {minutes}"),
+            };
+            let at = if inner.trim_start().is_empty() {
+                close
+            } else {
+                at
+            };
+            splice(markdown, at, at, &entry)
+        }
+    }
+}
+
+/// YAML 或 TOML 值中的标量部分，不包括之后的 `# 注释`
+fn scalar(value: &str) -> &str {
+    let end = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].find(quote).map_or(value.len(), |end| end + 2),
+        Some('#') => 0,
+        _ => value
+            .char_indices()
+            .find(|&(at, c)| c == '#' && value[..at].ends_with([' ', '\t']))
+            .map_or(value.len(), |(at, _)| at),
+    };
+    value[..end].trim_end()
+}
+
+/// 按 front matter 的格式写出键名
+///
+/// 只包含字母、数字、`_` 和 `-` 的键在 YAML 和 TOML 中原样写出，
+/// 其余的键以及 JSON 中的键写为带转义的双引号字符串。
+fn quote_key(key: &str, kind: FrontMatterKind) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare && kind != FrontMatterKind::Json {
+        return String::from(key);
+    }
+    let mut quoted = String::with_capacity(key.len() + 2);
+    quoted.push('"');
+    for c in key.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// 文档第一行的换行符，没有换行时为 `\n`
+fn line_ending(text: &str) -> &'static str {
+    match text.find('\n') {
+        Some(end) if text[..end].ends_with('\r') => "\r\n",
+        _ => "\n",
+    }
+}
+
+fn splice(text: &str, start: usize, end: usize, replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len() + replacement.len());
+    out.push_str(&text[..start]);
+    out.push_str(replacement);
+    out.push_str(&text[end..]);
+    out
+}
+
+/// 估算文件的阅读时间并写入其 front matter
+///
+/// 先写入同一目录下的临时文件并同步到磁盘，再替换原文件，写入过程中失败或断电不会留下不完整的内容。
+/// 替换后的文件保留原文件的权限；`path` 为符号链接时更新链接指向的文件，链接本身保持不变。
+/// 文件必须是有效的 UTF-8 文本，估算方式与 [`estimate_with_speed`] 相同。
+///
+/// # Arguments
+///
+/// * `path` - Markdown 文件路径
+/// * `speed` - 自定义的阅读速度配置
+///
+/// # Returns
+///
+/// 返回写入的 [`ReadTime`]，读写失败时返回 [`Error`]。
+///
+/// # Examples
+///
+/// ```no_run
+/// use markdown_readtime::{ReadSpeed, write_reading_time_file};
+///
+/// let read_time = write_reading_time_file("content/post.md", &ReadSpeed::default()).unwrap();
+/// println!("已写入: {}", read_time.formatted);
+/// ```
+pub fn write_reading_time_file(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+) -> Result<ReadTime, Error> {
+    write_reading_time_file_with_key(path, speed, KEY)
+}
+
+/// 估算文件的阅读时间并写入其 front matter 中的指定键
+///
+/// 与 [`write_reading_time_file`] 相同，但使用 `key` 代替 `reading_time`。
+/// 写入的值未变化时不会修改文件，可以在每次构建前重复运行。
+pub fn write_reading_time_file_with_key(
+    path: impl AsRef<Path>,
+    speed: &ReadSpeed,
+    key: &str,
+) -> Result<ReadTime, Error> {
+    let path = path.as_ref();
+    let markdown = fs::read_to_string(path)?;
+    let read_time = estimate_with_speed(&markdown, speed);
+    let updated = write_reading_time_with_key(&markdown, &read_time, key);
+    if updated != markdown {
+        let path = fs::canonicalize(path)?;
+        let mut name = std::ffi::OsString::from(".");
+        name.push(path.file_name().unwrap_or_default());
+        name.push(".readtime.tmp");
+        let temp = path.with_file_name(name);
+        if let Err(err) = replace_file(&path, &temp, updated.as_bytes()) {
+            let _ = fs::remove_file(&temp);
+            return Err(err.into());
+        }
+    }
+    Ok(read_time)
+}
+
+/// 把内容写入临时文件，复制原文件的权限并同步到磁盘后替换原文件
+fn replace_file(path: &Path, temp: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(temp)?;
+    file.write_all(contents)?;
+    file.set_permissions(fs::metadata(path)?.permissions())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_reading_time() {
+        let read_time = ReadTime::builder().total_seconds(61).build();
+        let write = |markdown: &str| write_reading_time(markdown, &read_time);
+
+        assert_eq!(
+            write("---\nreading_time: 10 \ntitle: a\n---\n正文"),
+            "---\nreading_time: 2 \ntitle: a\n---\n正文"
+        );
+        assert_eq!(
+            write("\u{FEFF}+++\ntitle = \"a\"\n[extra]\nx = 1\n+++\n正文"),
+            "\u{FEFF}+++\ntitle = \"a\"\nreading_time = 2\n[extra]\nx = 1\n+++\n正文"
+        );
+        assert_eq!(write("{}\n正文"), "{\"reading_time\": 2}\n正文");
+        assert_eq!(
+            write("{\"title\": \"a\"}\n正文"),
+            "{\"title\": \"a\", \"reading_time\": 2}\n正文"
+        );
+        assert_eq!(
+            write("{\n  \"title\": \"a\"\n}\n正文"),
+            "{\n  \"title\": \"a\",\n  \"reading_time\": 2\n}\n正文"
+        );
+        assert_eq!(
+            write("{\"reading_time\": 1, \"a\": 2}\n正文"),
+            "{\"reading_time\": 2, \"a\": 2}\n正文"
+        );
+        assert_eq!(
+            write("\u{FEFF}正文"),
+            "\u{FEFF}---\nreading_time: 2\n---\n正文"
+        );
+
+        let updated = write("---\ntitle: a\n---\n正文");
+        assert_eq!(write(&updated), updated);
+
+        // 新增的行沿用文档的换行符
+        assert_eq!(
+            write("---\r\ntitle: a\r\n---\r\n正文\r\n"),
+            "---\r\ntitle: a\r\nreading_time: 2\r\n---\r\n正文\r\n"
+        );
+        assert_eq!(
+            write("---\r\nreading_time: 10\r\n---\r\n正文"),
+            "---\r\nreading_time: 2\r\n---\r\n正文"
+        );
+        assert_eq!(
+            write("+++\r\ntitle = \"a\"\r\n[extra]\r\n+++\r\n正文"),
+            "+++\r\ntitle = \"a\"\r\nreading_time = 2\r\n[extra]\r\n+++\r\n正文"
+        );
+        assert_eq!(
+            write("{\r\n  \"title\": \"a\"\r\n}\r\n正文"),
+            "{\r\n  \"title\": \"a\",\r\n  \"reading_time\": 2\r\n}\r\n正文"
+        );
+        assert_eq!(
+            write("正文\r\n第二行"),
+            "---\r\nreading_time: 2\r\n---\r\n正文\r\n第二行"
+        );
+
+        let write = |markdown: &str| write_reading_time_with_key(markdown, &read_time, "minutes");
+        assert_eq!(
+            write("---\nminutes: 5\nreading_time: 9\n---\n正文"),
+            "---\nminutes: 2\nreading_time: 9\n---\n正文"
+        );
+        assert_eq!(
+            write("{\"title\": \"a\"}\n正文"),
+            "{\"title\": \"a\", \"minutes\": 2}\n正文"
+        );
+    }
+
+    #[test]
+    fn test_write_reading_time_keeps_comments() {
+        let read_time = ReadTime::builder().total_seconds(200).build();
+        let write = |markdown: &str| write_reading_time(markdown, &read_time);
+
+        assert_eq!(
+            write("---\nreading_time: 3 # auto\ntitle: a\n---\n正文"),
+            "---\nreading_time: 4 # auto\ntitle: a\n---\n正文"
+        );
+        assert_eq!(
+            write("+++\nreading_time = \"3\"  # 自动生成\n+++\n正文"),
+            "+++\nreading_time = 4  # 自动生成\n+++\n正文"
+        );
+        assert_eq!(
+            write("---\nreading_time: # 待填\n---\n正文"),
+            "---\nreading_time: 4 # 待填\n---\n正文"
+        );
+        assert_eq!(
+            write("---\nreading_time: a#b\n---\n正文"),
+            "---\nreading_time: 4\n---\n正文"
+        );
+    }
+
+    #[test]
+    fn test_write_reading_time_empty_value() {
+        let read_time = ReadTime::builder().total_seconds(61).build();
+        let markdown = "---\nreading_time:\ntitle: a\n---\n正文";
+        let once = write_reading_time(markdown, &read_time);
+        assert_eq!(once, "---\nreading_time: 2\ntitle: a\n---\n正文");
+        assert_eq!(write_reading_time(&once, &read_time), once);
+        assert_eq!(
+            write_reading_time("+++\nreading_time =\n+++\n正文", &read_time),
+            "+++\nreading_time = 2\n+++\n正文"
+        );
+    }
+
+    #[test]
+    fn test_write_reading_time_quotes_keys() {
+        let read_time = ReadTime::builder().total_seconds(61).build();
+        let write =
+            |markdown: &str, key: &str| write_reading_time_with_key(markdown, &read_time, key);
+
+        assert_eq!(
+            write("正文", "read: time"),
+            "---\n\"read: time\": 2\n---\n正文"
+        );
+        assert_eq!(
+            write("+++\ntitle = \"a\"\n+++\n正文", "extra.minutes"),
+            "+++\ntitle = \"a\"\n\"extra.minutes\" = 2\n+++\n正文"
+        );
+        assert_eq!(
+            write("{\"title\": \"a\"}\n正文", "say \"hi\"\\"),
+            "{\"title\": \"a\", \"say \\\"hi\\\"\\\\\": 2}\n正文"
+        );
+
+        // 写入后可以按同一个键名找到并更新
+        let updated = write("---\ntitle: a\n---\n正文", "read: time");
+        assert_eq!(updated, "---\ntitle: a\n\"read: time\": 2\n---\n正文");
+        assert_eq!(write(&updated, "read: time"), updated);
+    }
+
+    #[test]
+    fn test_write_reading_time_file() {
+        let path = std::env::temp_dir().join("markdown-readtime-writeback.md");
+        fs::write(&path, "---\ntitle: 测试\n---\n正文内容\n").unwrap();
+        let read_time = write_reading_time_file(&path, &ReadSpeed::default()).unwrap();
+        assert_eq!(read_time.word_count, 4);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "---\ntitle: 测试\nreading_time: 1\n---\n正文内容\n"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_reading_time_file_through_symlink() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+
+        let dir = std::env::temp_dir().join("markdown-readtime-writeback-link");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("post.md");
+        let link = dir.join("link.md");
+        fs::write(&target, "正文内容\n").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o640)).unwrap();
+        symlink(&target, &link).unwrap();
+
+        write_reading_time_file(&link, &ReadSpeed::default()).unwrap();
+        assert!(
+            fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "---\nreading_time: 1\n---\n正文内容\n"
+        );
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,32 @@
+// 用 npm 包 reading-time 校验 reading-time.tsv 中的期望值：
+//
+//     npm install reading-time@1.5.0
+//     node tests/fixtures/reading-time.mjs
+//
+// 与 src/npm_compat.rs 的测试共用同一份数据，两边都通过即说明结果一致。
+import { readFileSync } from 'node:fs'
+import readingTime from 'reading-time'
+
+const unescape = (s) =>
+  s.replace(/\\(.)/g, (_, c) => ({ n: '\n', t: '\t', r: '\r' })[c] ?? c)
+
+const lines = readFileSync(new URL('reading-time.tsv', import.meta.url), 'utf8')
+  .split('\n')
+  .filter((line) => line && !line.startsWith('#'))
+
+let failed = 0
+for (const line of lines) {
+  const [wpm, words, time, text, ...input] = line.split('\t')
+  const actual = readingTime(unescape(input.join('\t')), { wordsPerMinute: Number(wpm) })
+  const expected = { words: Number(words), time: Number(time), text }
+  if (
+    actual.words.total !== expected.words ||
+    actual.time !== expected.time ||
+    actual.text !== expected.text
+  ) {
+    failed++
+    console.error('不一致:', JSON.stringify(line), actual)
+  }
+}
+console.log(`${lines.length - failed}/${lines.length} 通过`)
+process.exit(failed ? 1 : 0)
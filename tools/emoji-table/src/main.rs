@@ -0,0 +1,62 @@
+//! 生成 `src/chars/emoji.rs` 中的 emoji 字符范围
+//!
+//! 升级 `emojis` 以跟进新的 Unicode 版本后，在仓库根目录运行：
+//!
+//! ```sh
+//! cargo run --manifest-path tools/emoji-table/Cargo.toml > src/chars/emoji.rs
+//! ```
+use std::collections::BTreeMap;
+
+/// 默认以 emoji 样式显示的字符
+const EMOJI: u8 = 1;
+
+/// 默认以文本样式显示，后跟 VS16 (U+FE0F) 时才显示为 emoji 的字符
+const TEXT: u8 = 2;
+
+fn main() {
+    let mut kinds = BTreeMap::new();
+    for emoji in emojis::iter() {
+        let mut chars = emoji.as_str().chars().peekable();
+        while let Some(c) = chars.next() {
+            let vs16 = chars.next_if_eq(&'\u{FE0F}').is_some();
+            if c.is_ascii() || is_joiner(c) {
+                continue;
+            }
+            // 同一字符在任何序列中不带 VS16 出现过，就说明它默认以 emoji 样式显示
+            let kind = if vs16 { TEXT } else { EMOJI };
+            let entry = kinds.entry(c as u32).or_insert(kind);
+            *entry = (*entry).min(kind);
+        }
+    }
+
+    println!("// 此文件由 tools/emoji-table 根据 Unicode emoji 数据生成，请勿手动修改");
+    for (name, kind) in [("EMOJI_RANGES", EMOJI), ("TEXT_EMOJI_RANGES", TEXT)] {
+        let codes = kinds.iter().filter(|&(_, &k)| k == kind).map(|(&c, _)| c);
+        println!();
+        println!("pub(super) const {}: &[(u32, u32)] = &[", name);
+        for (start, end) in merge(codes) {
+            println!("    (0x{:04X}, 0x{:04X}),", start, end);
+        }
+        println!("];");
+    }
+}
+
+/// 只用于组合 emoji 序列、本身不显示的字符
+fn is_joiner(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200D}' | '\u{20E3}' | '\u{FE0E}' | '\u{FE0F}' | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// 把升序的码位合并为连续的闭区间
+fn merge(codes: impl Iterator<Item = u32>) -> Vec<(u32, u32)> {
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for code in codes {
+        match ranges.last_mut() {
+            Some(last) if last.1 + 1 == code => last.1 = code,
+            _ => ranges.push((code, code)),
+        }
+    }
+    ranges
+}